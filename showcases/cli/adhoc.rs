@@ -0,0 +1,121 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Throwaway examples composed from `--app` flags (`score-cli adhoc`).
+//!
+//! An `--app` is a comma separated list of `key=value` pairs, the keys being
+//! the fields of an app in a `*.score.json` file. Lists like `args` are split
+//! into words like a shell does, `env=KEY=VALUE` can be repeated, numbers and
+//! booleans are taken as such and values starting with `{` or `[` as JSON.
+//! Relative paths are resolved against the current directory.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Map, Value};
+use std::env;
+
+use crate::{builtin, condition, config_dir, instrument, interaction, readiness, ScoreConfig};
+
+/// Fields holding a list of strings, given as words.
+const LIST_KEYS: &[&str] = &["args", "env_remove", "preload", "library_path"];
+
+/// The example `name` running the apps described by `apps`.
+pub fn config(name: &str, apps: &[String]) -> Result<ScoreConfig> {
+    let apps = apps
+        .iter()
+        .map(|spec| app(spec).with_context(|| format!("Invalid --app \"{}\"", spec)))
+        .collect::<Result<Vec<Value>>>()?;
+    let mut example = json!({
+        "name": name,
+        "description": "Ad-hoc example from --app flags",
+        "apps": apps,
+    });
+    config_dir::anchor(&mut example, &env::current_dir()?)?;
+    let mut configs = vec![serde_json::from_value(example).context("Invalid --app")?];
+    condition::filter_apps(&mut configs)?;
+    readiness::validate(&configs)?;
+    interaction::validate(&configs)?;
+    instrument::validate(&configs)?;
+    builtin::expand(&mut configs)?;
+    Ok(configs.remove(0))
+}
+
+fn app(spec: &str) -> Result<Value> {
+    let mut app = Map::new();
+    let mut env = Map::new();
+    for (key, value) in pairs(spec)? {
+        let value = match key.as_str() {
+            "env" => {
+                let Some((name, value)) = value.split_once('=') else {
+                    bail!("expected env=KEY=VALUE, got env={}", value);
+                };
+                env.insert(name.to_string(), Value::String(value.to_string()));
+                continue;
+            }
+            key if LIST_KEYS.contains(&key) => json!(split_words(&value)?),
+            _ => match serde_json::from_str::<Value>(&value) {
+                Ok(parsed) if !parsed.is_string() && !parsed.is_null() => parsed,
+                _ => Value::String(value),
+            },
+        };
+        if app.insert(key.clone(), value).is_some() {
+            bail!("{} is given twice", key);
+        }
+    }
+    if !env.is_empty() {
+        app.insert("env".to_string(), Value::Object(env));
+    }
+    if !app.contains_key("path") && !app.contains_key("type") {
+        bail!("missing path=<program>");
+    }
+    Ok(Value::Object(app))
+}
+
+/// The `key=value` pairs of `spec`. A comma only separates pairs if a key follows, so values may contain commas.
+fn pairs(spec: &str) -> Result<Vec<(String, String)>> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    for part in spec.split(',') {
+        let is_key = |key: &str| !key.is_empty() && key.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+        match (part.split_once('='), pairs.last_mut()) {
+            (Some((key, value)), _) if is_key(key.trim()) => pairs.push((key.trim().to_string(), value.to_string())),
+            (_, Some((_, value))) => {
+                value.push(',');
+                value.push_str(part);
+            }
+            (_, None) => bail!("expected key=value, got \"{}\"", part),
+        }
+    }
+    Ok(pairs)
+}
+
+/// Splits `text` into words at whitespace, keeping quoted parts together like a shell.
+fn split_words(text: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in text.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        bail!("unterminated quote in \"{}\"", text);
+    }
+    words.extend(word);
+    Ok(words)
+}
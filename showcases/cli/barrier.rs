@@ -0,0 +1,141 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Start barrier releasing apps of an example at the same moment.
+//!
+//! Apps joining the barrier get the path of a unix socket in `SCORE_SYNC_SOCKET`
+//! and their app index in `SCORE_SYNC_APP`. Once ready, an app connects, sends
+//! its index followed by a newline and blocks reading. When all apps joined,
+//! the runner writes `go\n` to all of them at once. Apps connecting after the
+//! release, e.g. restarted ones, get `go` right away.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::private_dir;
+
+/// How long a connected app may take to send its index.
+const HELLO_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct State {
+    waiting: BTreeMap<usize, UnixStream>,
+    released: Option<Instant>,
+}
+
+/// A barrier of one example. The socket is removed when this is dropped.
+pub struct Barrier {
+    path: PathBuf,
+    participants: BTreeSet<usize>,
+    state: Arc<Mutex<State>>,
+    closing: Arc<AtomicBool>,
+}
+
+impl Barrier {
+    /// Creates the socket of a barrier joined by the apps `participants`.
+    pub fn start(participants: BTreeSet<usize>) -> Result<Barrier> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        // Socket paths are limited to about 100 bytes, too short for artifacts directories.
+        // The private runtime directory keeps other users from taking the path or connecting.
+        let path = private_dir::runtime("sync")?.join(format!(
+            "{}-{}.sock",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_file(&path);
+        let listener =
+            UnixListener::bind(&path).with_context(|| format!("Failed to create start barrier socket {:?}", path))?;
+        let barrier = Barrier {
+            path,
+            participants,
+            state: Arc::default(),
+            closing: Arc::default(),
+        };
+        let participants = barrier.participants.clone();
+        let state = barrier.state.clone();
+        let closing = barrier.closing.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if closing.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let Some(app) = hello(&stream) else { continue };
+                let mut state = state.lock().unwrap();
+                if state.released.is_some() {
+                    let _ = (&stream).write_all(b"go\n");
+                    continue;
+                }
+                state.waiting.insert(app, stream);
+                if participants.iter().all(|app| state.waiting.contains_key(app)) {
+                    // Sent back to back, so all apps start within microseconds.
+                    for stream in state.waiting.values() {
+                        let _ = (&*stream).write_all(b"go\n");
+                    }
+                    state.waiting.clear();
+                    state.released = Some(Instant::now());
+                }
+            }
+        });
+        Ok(barrier)
+    }
+
+    /// Environment variables telling app `index` how to join.
+    pub fn env(&self, index: usize) -> [(&'static str, String); 2] {
+        [
+            ("SCORE_SYNC_SOCKET", self.path.display().to_string()),
+            ("SCORE_SYNC_APP", index.to_string()),
+        ]
+    }
+
+    /// When all apps joined and were released, if they did.
+    pub fn released(&self) -> Option<Instant> {
+        self.state.lock().unwrap().released
+    }
+
+    /// Apps that have not joined yet.
+    pub fn missing(&self) -> Vec<usize> {
+        let state = self.state.lock().unwrap();
+        if state.released.is_some() {
+            return Vec::new();
+        }
+        self.participants
+            .iter()
+            .filter(|app| !state.waiting.contains_key(app))
+            .copied()
+            .collect()
+    }
+}
+
+impl Drop for Barrier {
+    fn drop(&mut self) {
+        // Wakes the accepting thread so it ends; waiting apps see the connection close.
+        self.closing.store(true, Ordering::Relaxed);
+        let _ = UnixStream::connect(&self.path);
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Reads the app index an app sends after connecting.
+fn hello(stream: &UnixStream) -> Option<usize> {
+    stream.set_read_timeout(Some(HELLO_TIMEOUT)).ok()?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line).ok()?;
+    stream.set_read_timeout(None).ok()?;
+    line.trim().parse().ok()
+}
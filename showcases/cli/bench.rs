@@ -0,0 +1,353 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Benchmark mode: repeat an example, measure it and compare with saved baselines.
+//!
+//! Metrics are the duration of the example and the values apps print as
+//! `SCORE_METRIC <name>=<value>` lines, lower being better unless the line ends
+//! with `higher`. The median over all iterations counts. Baselines are stored
+//! per host fingerprint (CPU model, CPU count and isolated CPUs) below
+//! `$XDG_STATE_HOME/score-cli/baselines`, so results are only ever compared with
+//! results of the same kind of host.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::report::Status;
+use crate::{
+    gc, history, host, new_report, new_run_id, random_seed, run_score, slug, timeline, Run, RunOptions, ScoreConfig,
+};
+
+const METRIC_PREFIX: &str = "SCORE_METRIC ";
+const DURATION_METRIC: &str = "duration_ms";
+/// Smaller increases of the duration are scheduling noise, whatever their percentage.
+const MIN_DURATION_REGRESSION_MS: f64 = 100.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Metric {
+    value: f64,
+    #[serde(default)]
+    higher_is_better: bool,
+}
+
+/// The host a baseline was measured on.
+#[derive(Debug, Serialize, Deserialize)]
+struct Fingerprint {
+    cpu_model: String,
+    cpus: usize,
+    isolated_cpus: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Baseline {
+    host: Fingerprint,
+    examples: BTreeMap<String, BTreeMap<String, Metric>>,
+}
+
+impl Fingerprint {
+    fn current() -> Fingerprint {
+        Fingerprint {
+            cpu_model: host::cpu_model().unwrap_or_else(|| "unknown".to_string()),
+            cpus: host::cpu_count(),
+            isolated_cpus: host::isolated_cpus(),
+        }
+    }
+
+    /// Directory name of the baselines of this host.
+    fn id(&self) -> String {
+        let mut id = format!("{}-{}cpus", slug(&self.cpu_model), self.cpus);
+        if !self.isolated_cpus.is_empty() {
+            id.push_str(&format!("-isolcpus-{}", slug(&self.isolated_cpus)));
+        }
+        id
+    }
+}
+
+fn baselines_dir() -> PathBuf {
+    history::state_dir().join("baselines")
+}
+
+fn baseline_path(host: &Fingerprint, name: &str) -> PathBuf {
+    baselines_dir().join(host.id()).join(format!("{}.json", slug(name)))
+}
+
+/// What to do with the measured metrics.
+pub struct BenchOptions {
+    pub iterations: u32,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
+    pub max_regression: f64, // percent
+    pub force: bool,         // save the baseline even if metrics regressed
+}
+
+/// Benchmarks `config` and compares it with the baseline in `bench`, if any.
+///
+/// Returns whether no metric regressed by more than the allowed percentage.
+pub fn run(config: &ScoreConfig, bench: &BenchOptions, options: &RunOptions) -> Result<bool> {
+    if !config.steps.is_empty() {
+        bail!(
+            "Benchmark mode measures an example with apps, '{}' is a composite example",
+            config.name
+        );
+    }
+    if let Some(reason) = config.skip_reason()? {
+        bail!("Example '{}' cannot run on this host: {}", config.name, reason);
+    }
+    let host = Fingerprint::current();
+    // Fail early instead of after all iterations.
+    let baseline = match bench.baseline {
+        Some(ref name) => Some(load_baseline(&host, name)?),
+        None => None,
+    };
+    // One id for all iterations, so the run directory and SCORE_RUN_ID of the apps match.
+    let run_id = new_run_id();
+    let run_dir = crate::run_dir(options, &run_id)?;
+
+    let mut run_report = new_report(&run_id, 0, &run_dir, options);
+    let mut samples: BTreeMap<String, (Vec<f64>, bool)> = BTreeMap::new();
+    for iteration in 1..=bench.iterations {
+        if options.stop_requested() {
+            bail!("Stop file found, benchmark of '{}' stopped", config.name);
+        }
+        let run = Run::new(run_id.clone(), options.seed.unwrap_or_else(random_seed));
+        let iteration_dir = run_dir.join(format!("iteration-{}", iteration));
+        let example_dir = iteration_dir.join(slug(&config.name));
+        fs::create_dir_all(&example_dir)
+            .with_context(|| format!("Failed to create run directory {:?}", example_dir))?;
+        println!("━━ Iteration {}/{} (run {})", iteration, bench.iterations, run.id);
+        run_report.seed = run.seed;
+        run.checkpoint.start_example(&run_report, config, &example_dir);
+        let report = run_score(config, options, &run, &example_dir);
+        run.checkpoint.remove();
+        let report = report?;
+        if report.status != Status::Passed {
+            bail!(
+                "Example '{}' {} in iteration {}, artifacts kept in {}",
+                config.name,
+                report.status.name(),
+                iteration,
+                iteration_dir.display()
+            );
+        }
+        let mut metrics = app_metrics(&example_dir)?;
+        metrics.insert(
+            DURATION_METRIC.to_string(),
+            Metric {
+                value: report.duration_ms as f64,
+                higher_is_better: false,
+            },
+        );
+        for (name, metric) in metrics {
+            let entry = samples
+                .entry(name)
+                .or_insert_with(|| (Vec::new(), metric.higher_is_better));
+            entry.0.push(metric.value);
+        }
+        let _ = fs::remove_dir_all(&iteration_dir);
+    }
+    let measured: BTreeMap<String, Metric> = samples
+        .into_iter()
+        .map(|(name, (values, higher_is_better))| {
+            let value = median(values);
+            (
+                name,
+                Metric {
+                    value,
+                    higher_is_better,
+                },
+            )
+        })
+        .collect();
+
+    let passed = match baseline {
+        Some(ref baseline) => compare(config, &measured, baseline, bench.max_regression),
+        None => {
+            println!(
+                "━━ Metrics of '{}' (median of {} iterations)",
+                config.name, bench.iterations
+            );
+            for (name, metric) in &measured {
+                println!("  {:<24} {:>14.3}", name, metric.value);
+            }
+            true
+        }
+    };
+    if let Some(ref name) = bench.save_baseline {
+        // A regressed baseline would make the regression the new normal.
+        if !passed && !bench.force {
+            println!(
+                "❌ Not saving baseline '{}': metrics regressed against '{}' (pass --force to save anyway)",
+                name,
+                bench.baseline.as_deref().unwrap_or_default()
+            );
+        } else {
+            let path = save_baseline(host, name, &config.name, measured)?;
+            println!("Baseline '{}' saved to {}", name, path.display());
+        }
+    }
+    Ok(passed)
+}
+
+/// Values of the metrics printed by the apps in the logs below `example_dir`, for checking KPIs.
+pub(crate) fn app_metric_values(example_dir: &Path) -> Result<BTreeMap<String, f64>> {
+    Ok(app_metrics(example_dir)?
+        .into_iter()
+        .map(|(name, metric)| (name, metric.value))
+        .collect())
+}
+
+/// Metrics printed by the apps as `SCORE_METRIC <name>=<value> [higher]` in the logs below `example_dir`.
+fn app_metrics(example_dir: &Path) -> Result<BTreeMap<String, Metric>> {
+    let mut metrics = BTreeMap::new();
+    for entry in fs::read_dir(example_dir)? {
+        let path = entry?.path();
+        // Logs compressed by `gc` as well.
+        let is_app_log = timeline::log_source(&path).is_some_and(|source| source.starts_with("app"));
+        if !is_app_log {
+            continue;
+        }
+        let content = gc::read_log(&path)?;
+        // "<elapsed> <wall clock> <stream> <text>"
+        for text in content.lines().filter_map(|line| line.splitn(4, ' ').nth(3)) {
+            let Some(metric) = text.trim().strip_prefix(METRIC_PREFIX) else {
+                continue;
+            };
+            let mut words = metric.split_whitespace();
+            let Some((name, value)) = words.next().and_then(|pair| pair.split_once('=')) else {
+                continue;
+            };
+            let Ok(value) = value.parse() else {
+                eprintln!("⚠️  Ignoring metric with invalid value: {}", text);
+                continue;
+            };
+            let higher_is_better = words.next() == Some("higher");
+            metrics.insert(
+                name.to_string(),
+                Metric {
+                    value,
+                    higher_is_better,
+                },
+            );
+        }
+    }
+    Ok(metrics)
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(f64::total_cmp);
+    // The middle value, or the mean of both middle values for an even count.
+    let count = values.len();
+    (values[(count - 1) / 2] + values[count / 2]) / 2.0
+}
+
+fn load_baseline(host: &Fingerprint, name: &str) -> Result<Baseline> {
+    let path = baseline_path(host, name);
+    if !path.is_file() {
+        let hosts: Vec<String> = fs::read_dir(baselines_dir())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().join(format!("{}.json", slug(name))).is_file())
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if hosts.is_empty() {
+            bail!("No baseline '{}' saved, create it with --save-baseline {}", name, name);
+        }
+        bail!(
+            "No baseline '{}' for this host ({}), only for: {}",
+            name,
+            host.id(),
+            hosts.join(", ")
+        );
+    }
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed reading {:?}", path))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid baseline {:?}", path))
+}
+
+/// Adds the metrics of `example` to the baseline `name` of `host`.
+fn save_baseline(host: Fingerprint, name: &str, example: &str, metrics: BTreeMap<String, Metric>) -> Result<PathBuf> {
+    let path = baseline_path(&host, name);
+    let mut baseline = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Baseline>(&content).ok())
+        .unwrap_or(Baseline {
+            host,
+            examples: BTreeMap::new(),
+        });
+    baseline.examples.insert(example.to_string(), metrics);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create baseline directory {:?}", dir))?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&baseline)?)
+        .with_context(|| format!("Failed to write baseline {:?}", path))?;
+    Ok(path)
+}
+
+/// Prints the metrics next to the baseline and returns whether none regressed by more than `max_regression` percent.
+fn compare(
+    config: &ScoreConfig,
+    measured: &BTreeMap<String, Metric>,
+    baseline: &Baseline,
+    max_regression: f64,
+) -> bool {
+    let Some(expected) = baseline.examples.get(&config.name) else {
+        println!(
+            "⚠️  The baseline has no metrics of '{}', nothing to compare",
+            config.name
+        );
+        return true;
+    };
+    println!(
+        "━━ Metrics of '{}' against the baseline (max regression {}%)",
+        config.name, max_regression
+    );
+    let mut passed = true;
+    for (name, metric) in measured {
+        let Some(base) = expected.get(name) else {
+            println!("  {:<24} {:>14.3}  (not in baseline)", name, metric.value);
+            continue;
+        };
+        let change = if base.value == 0.0 {
+            0.0
+        } else {
+            (metric.value - base.value) / base.value.abs() * 100.0
+        };
+        let regression = if metric.higher_is_better { -change } else { change };
+        let noise = name == DURATION_METRIC && metric.value - base.value < MIN_DURATION_REGRESSION_MS;
+        let verdict = if regression > max_regression && !noise {
+            passed = false;
+            "❌ regression"
+        } else {
+            "✅"
+        };
+        println!(
+            "  {:<24} {:>14.3}  baseline {:>14.3}  {:+7.1}%  {}",
+            name, metric.value, base.value, change, verdict
+        );
+    }
+    passed
+}
+
+/// Parses a percentage like `5%` or `2.5`.
+pub fn parse_percent(value: &str) -> Result<f64, String> {
+    let number = value.trim().trim_end_matches('%');
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|percent| *percent >= 0.0)
+        .ok_or_else(|| format!("invalid percentage \"{}\"", value))
+}
@@ -0,0 +1,148 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Apps built into the CLI, declared with a `type` instead of a `path`.
+//!
+//! A built-in app runs as a child process like any other app: the CLI starts
+//! itself with the hidden `builtin` subcommand. Output capture, stop signals,
+//! network nodes and reports therefore work the same for built-in apps.
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+use crate::{data_feed, mqtt_broker, sockets, static_server, ScoreConfig};
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppType {
+    /// The executable at `path`
+    #[default]
+    Process,
+    /// Serves the directory `root` over HTTP on `port`
+    StaticServer,
+    /// Runs an MQTT broker on `port` (default 1883)
+    MqttBroker,
+    /// Replays the records of `recording` to `target`
+    DataFeed,
+}
+
+/// Entry points of the built-in apps, started by the CLI itself.
+#[derive(Subcommand, Debug)]
+pub enum Builtin {
+    /// Serve a directory over HTTP
+    StaticServer {
+        // Not `--root`, which is the global option giving the examples directories.
+        #[arg(long = "dir")]
+        root: PathBuf,
+
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+
+        #[arg(long)]
+        port: u16,
+    },
+    /// Run an MQTT broker
+    MqttBroker {
+        #[arg(long, default_value_t = 1883)]
+        port: u16,
+    },
+    /// Replay a recording of sensor data
+    DataFeed {
+        #[arg(long)]
+        recording: PathBuf,
+
+        #[arg(long)]
+        target: String,
+
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+    },
+    /// Set LISTEN_PID to the own pid and exec the command, for apps getting sockets passed
+    ListenPid {
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+}
+
+/// Turns the built-in apps of all examples into invocations of this executable.
+pub fn expand(configs: &mut [ScoreConfig]) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to find the path of the CLI executable")?;
+    for config in configs.iter_mut() {
+        for (i, app) in config.apps.iter_mut().enumerate() {
+            let context = || format!("Example '{}', app {}", config.name, i + 1);
+            let builtin_args = match app.app_type {
+                AppType::Process => {
+                    if app.path.is_empty() {
+                        bail!("{}: needs a \"path\" or a built-in \"type\"", context());
+                    }
+                    continue;
+                }
+                AppType::StaticServer => {
+                    let (Some(root), Some(port)) = (&app.serve_dir, app.port) else {
+                        bail!("{}: a static-server needs \"serve_dir\" and \"port\"", context());
+                    };
+                    vec![
+                        "static-server".to_string(),
+                        "--dir".to_string(),
+                        root.clone(),
+                        "--bind".to_string(),
+                        app.bind.clone().unwrap_or_else(|| "127.0.0.1".to_string()),
+                        "--port".to_string(),
+                        port.to_string(),
+                    ]
+                }
+                AppType::MqttBroker => vec![
+                    "mqtt-broker".to_string(),
+                    "--port".to_string(),
+                    app.port.unwrap_or(1883).to_string(),
+                ],
+                AppType::DataFeed => {
+                    let (Some(recording), Some(target)) = (&app.recording, &app.target) else {
+                        bail!("{}: a data-feed needs \"recording\" and \"target\"", context());
+                    };
+                    let speed = app.speed.unwrap_or(1.0);
+                    data_feed::check_speed(speed).with_context(context)?;
+                    vec![
+                        "data-feed".to_string(),
+                        "--recording".to_string(),
+                        recording.clone(),
+                        "--target".to_string(),
+                        target.clone(),
+                        "--speed".to_string(),
+                        speed.to_string(),
+                    ]
+                }
+            };
+            if !app.path.is_empty() || !app.args.is_empty() {
+                bail!("{}: built-in apps take no \"path\" or \"args\"", context());
+            }
+            app.path = exe.display().to_string();
+            app.args = ["builtin".to_string()].into_iter().chain(builtin_args).collect();
+        }
+    }
+    Ok(())
+}
+
+pub fn run(builtin: Builtin) -> Result<()> {
+    match builtin {
+        Builtin::StaticServer { root, bind, port } => static_server::serve(&root, &bind, port),
+        Builtin::MqttBroker { port } => mqtt_broker::serve(port),
+        Builtin::DataFeed {
+            recording,
+            target,
+            speed,
+        } => data_feed::replay(&recording, &target, speed),
+        Builtin::ListenPid { command } => sockets::exec_with_listen_pid(&command),
+    }
+}
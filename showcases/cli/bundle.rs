@@ -0,0 +1,416 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Offline bundles of examples (`score-cli bundle`), handed to field engineers as one file.
+//!
+//! A bundle is a `tar` archive compressed with `zstd` holding the selected
+//! examples, and the examples they run as steps, in `examples.score.json`. Every
+//! absolute path of their apps naming an existing file or directory, like the
+//! binary, working directory, env file, stdin file, data recording or an
+//! argument, is copied below `files/` and rewritten to `${CONFIG_DIR}/files/...`,
+//! so the bundle runs wherever it is unpacked. The shared libraries of ELF
+//! binaries, as `ldd` lists them, go to `lib/`, which is added to the
+//! `library_path` of the app. The C library and the dynamic loader are left to
+//! the target, they only work together with its kernel and loader. Presets used
+//! by the examples are kept in `bundle.presets.json`, and `bundle.json` lists
+//! where every bundled file came from.
+//!
+//! Bundles are staged and unpacked in fresh directories with mode 0700 below the
+//! private `$TMPDIR/score-cli-<uid>/bundles`. `--from-bundle <file>` unpacks a
+//! bundle once into a directory named after the SHA-256 sum of the file and
+//! uses it as the examples directory. The sums of the unpacked files are
+//! recorded, and a later run reuses the directory only if they still match.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{load_examples, manifest, private_dir, read_examples, select_examples};
+
+const CONFIG_DIR: &str = "${CONFIG_DIR}";
+/// Sums of the files of an unpacked bundle, in the format of `sha256sum`.
+const CONTENTS: &str = ".contents.sha256";
+
+/// Libraries provided by the target, copies would not match its kernel and dynamic loader.
+const SYSTEM_LIBRARIES: &[&str] = &[
+    "ld-linux",
+    "ld64.so",
+    "libc.so",
+    "libm.so",
+    "libdl.so",
+    "libpthread.so",
+    "librt.so",
+    "libresolv.so",
+    "libutil.so",
+];
+
+struct Bundler {
+    staging: PathBuf,
+    copied: BTreeMap<PathBuf, String>, // original path -> path in the bundle
+    libraries: HashSet<String>,        // file names in lib/
+    ldd_missing: bool,
+}
+
+/// Writes the examples selected by `requested` from `roots`, with everything they use, to `output`.
+pub fn create(roots: &[PathBuf], requested: &[String], output: &Path) -> Result<()> {
+    let configs = load_examples(roots, None)?;
+    let selected = select_examples(&configs, requested, false, false)?;
+    let mut names: Vec<String> = Vec::new();
+    let mut pending: Vec<String> = selected.iter().map(|&i| configs[i].name.clone()).collect();
+    while let Some(name) = pending.pop() {
+        if names.contains(&name) {
+            continue;
+        }
+        if let Some(config) = configs.iter().find(|c| c.name == name) {
+            pending.extend(config.steps.iter().cloned());
+        }
+        names.push(name);
+    }
+
+    let (sources, presets) = read_examples(roots)?;
+    let staging = private_dir::fresh(&private_dir::temp("bundles")?, ".staging")?;
+    let mut bundler = Bundler {
+        staging: staging.clone(),
+        copied: BTreeMap::new(),
+        libraries: HashSet::new(),
+        ldd_missing: false,
+    };
+    let result = (|| {
+        let mut examples = Vec::new();
+        let mut used_presets = BTreeMap::new();
+        for source in sources {
+            let name = source.value["name"].as_str().unwrap_or_default().to_string();
+            if !names.contains(&name) {
+                continue;
+            }
+            let mut example = source.value;
+            if let Some(preset) = example["preset"].as_str().and_then(|p| presets.get_key_value(p)) {
+                used_presets.insert(preset.0.clone(), preset.1.clone());
+            }
+            let apps = example
+                .get_mut("apps")
+                .and_then(Value::as_array_mut)
+                .into_iter()
+                .flatten();
+            for app in apps.filter_map(Value::as_object_mut) {
+                bundler
+                    .app(app)
+                    .with_context(|| format!("Failed to bundle an app of example '{}'", name))?;
+            }
+            examples.push(example);
+        }
+        write_json(&staging.join("examples.score.json"), &Value::Array(examples))?;
+        if !used_presets.is_empty() {
+            write_json(
+                &staging.join("bundle.presets.json"),
+                &serde_json::to_value(&used_presets)?,
+            )?;
+        }
+        let files: Vec<Value> = bundler
+            .copied
+            .iter()
+            .map(|(source, bundled)| json!({"source": source, "bundled": bundled}))
+            .collect();
+        write_json(
+            &staging.join("bundle.json"),
+            &json!({
+                "examples": names,
+                "files": files,
+            }),
+        )?;
+        pack(&staging, output)
+    })();
+    let _ = fs::remove_dir_all(&staging);
+    result?;
+    println!(
+        "✅ Bundled {} examples with {} files and {} libraries into {}",
+        names.len(),
+        bundler.copied.len(),
+        bundler.libraries.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Unpacks the bundle `file`, unless it was unpacked before, and returns the examples directory in it.
+pub fn unpack(file: &Path) -> Result<PathBuf> {
+    if !file.is_file() {
+        bail!("No bundle {:?}", file);
+    }
+    let sums = manifest::sha256(&[file.to_path_buf()])?;
+    let Some(sum) = sums.values().next() else {
+        bail!("Failed to compute the checksum of {:?}", file);
+    };
+    let bundles = private_dir::temp("bundles")?;
+    let dir = bundles.join(sum);
+    if dir.exists() {
+        match check_unpacked(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) => {
+                println!("⚠️  Unpacking the bundle again: {:#}", e);
+                fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {:?}", dir))?;
+            }
+        }
+    }
+    // Unpacked next to its final place and renamed, so a run never sees half a bundle.
+    let partial = private_dir::fresh(&bundles, ".unpack")?;
+    let result = unpack_into(file, &partial);
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&partial);
+        return Err(e);
+    }
+    if let Err(e) = fs::rename(&partial, &dir) {
+        // Another run unpacked the same bundle meanwhile.
+        let _ = fs::remove_dir_all(&partial);
+        check_unpacked(&dir)
+            .map_err(|_| e)
+            .with_context(|| format!("Failed to unpack the bundle into {:?}", dir))?;
+    }
+    Ok(dir)
+}
+
+/// Unpacks the bundle `file` into the empty directory `dir` and records the sums of its files.
+fn unpack_into(file: &Path, dir: &Path) -> Result<()> {
+    let status = Command::new("tar")
+        .arg("--zstd")
+        .arg("--no-same-owner")
+        .arg("-xf")
+        .arg(file)
+        .arg("-C")
+        .arg(dir)
+        .status()
+        .context("Failed to run tar to unpack the bundle")?;
+    if !status.success() || !dir.join("bundle.json").exists() {
+        bail!("{:?} is no example bundle (tar {})", file, status);
+    }
+    let contents = sums_of(dir).with_context(|| format!("{:?} is no example bundle", file))?;
+    let listing: String = contents
+        .iter()
+        .map(|(path, sum)| format!("{}  {}\n", sum, path))
+        .collect();
+    fs::write(dir.join(CONTENTS), listing).with_context(|| format!("Failed to write {:?}", dir.join(CONTENTS)))
+}
+
+/// Checks that the files of the bundle unpacked in `dir` still have the sums recorded when it was unpacked.
+fn check_unpacked(dir: &Path) -> Result<()> {
+    private_dir::ensure(dir)?;
+    let listing =
+        fs::read_to_string(dir.join(CONTENTS)).with_context(|| format!("Failed reading {:?}", dir.join(CONTENTS)))?;
+    let recorded: BTreeMap<String, String> = listing
+        .lines()
+        .filter_map(|line| line.split_once("  "))
+        .map(|(sum, path)| (path.to_string(), sum.to_string()))
+        .collect();
+    if sums_of(dir)? != recorded {
+        bail!("the files unpacked in {:?} were modified", dir);
+    }
+    Ok(())
+}
+
+/// SHA-256 sums of the files below `dir` by their relative path, refusing symbolic links.
+fn sums_of(dir: &Path) -> Result<BTreeMap<String, String>> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in fs::read_dir(dir).with_context(|| format!("Failed to read directory {:?}", dir))? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                bail!("{:?} is a symbolic link, bundles hold none", entry.path());
+            } else if file_type.is_dir() {
+                walk(&entry.path(), files)?;
+            } else if entry.file_name() != CONTENTS {
+                files.push(entry.path());
+            }
+        }
+        Ok(())
+    }
+    let mut files = Vec::new();
+    walk(dir, &mut files)?;
+    Ok(manifest::sha256(&files)?
+        .into_iter()
+        .map(|(path, sum)| (path.strip_prefix(dir).unwrap_or(&path).display().to_string(), sum))
+        .collect())
+}
+
+impl Bundler {
+    /// Copies the files used by `app` into the bundle and points the app at the copies.
+    fn app(&mut self, app: &mut serde_json::Map<String, Value>) -> Result<()> {
+        let mut binaries = Vec::new();
+        for key in ["path", "dir", "env_file", "serve_dir", "root", "recording", "asan_path"] {
+            if let Some(Value::String(text)) = app.get_mut(key) {
+                if let Some(original) = self.file(text)? {
+                    if key == "path" || key == "asan_path" {
+                        binaries.push(original);
+                    }
+                }
+            }
+        }
+        if let Some(Value::String(text)) = app.get_mut("stdin").and_then(|stdin| stdin.get_mut("file")) {
+            self.file(text)?;
+        }
+        for key in ["args", "preload", "library_path"] {
+            let values = app.get_mut(key).and_then(Value::as_array_mut).into_iter().flatten();
+            for value in values {
+                if let Value::String(text) = value {
+                    if let Some(original) = self.file(text)? {
+                        if key == "preload" {
+                            binaries.push(original);
+                        }
+                    }
+                }
+            }
+        }
+        let env = app
+            .get_mut("env")
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flat_map(|env| env.values_mut());
+        for value in env {
+            if let Value::String(text) = value {
+                self.file(text)?;
+            }
+        }
+
+        let mut uses_libraries = false;
+        for binary in binaries {
+            uses_libraries |= self.libraries(&binary)?;
+        }
+        if uses_libraries {
+            let library_path = app.entry("library_path").or_insert_with(|| Value::Array(Vec::new()));
+            if let Some(dirs) = library_path.as_array_mut() {
+                // First, so the bundled libraries win over those of the target.
+                dirs.insert(0, Value::String(format!("{}/lib", CONFIG_DIR)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Bundles the file or directory named by `text` if it is an absolute path, rewriting `text` to the copy.
+    /// Returns the original path of a bundled file.
+    fn file(&mut self, text: &mut String) -> Result<Option<PathBuf>> {
+        let path = PathBuf::from(text.as_str());
+        if !path.is_absolute() || !(path.is_file() || path.is_dir()) {
+            return Ok(None);
+        }
+        if path.is_dir() && path.components().count() < 3 {
+            println!("⚠️  Not bundling the directory {}, it is too general", path.display());
+            return Ok(None);
+        }
+        let bundled = format!("files{}", path.display());
+        if !self.copied.contains_key(&path) {
+            copy(&path, &self.staging.join(&bundled))?;
+            self.copied.insert(path.clone(), bundled.clone());
+        }
+        *text = format!("{}/{}", CONFIG_DIR, bundled);
+        Ok(path.is_file().then_some(path))
+    }
+
+    /// Copies the shared libraries of `binary` into `lib/`, returning whether it has any.
+    fn libraries(&mut self, binary: &Path) -> Result<bool> {
+        if !is_elf(binary) || self.ldd_missing {
+            return Ok(false);
+        }
+        let output = match Command::new("ldd").arg(binary).output() {
+            Ok(output) => output,
+            Err(_) => {
+                println!("⚠️  `ldd` is not available, the bundle contains no shared libraries");
+                self.ldd_missing = true;
+                return Ok(false);
+            }
+        };
+        // Statically linked binaries make ldd fail.
+        if !output.status.success() {
+            return Ok(false);
+        }
+        let mut found = false;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let (name, target) = match line.split_once("=>") {
+                Some((name, target)) => (name.trim(), target.trim()),
+                None => (line.trim(), line.trim()),
+            };
+            if target.starts_with("not found") {
+                println!(
+                    "⚠️  {} needs {}, which is not found on this host",
+                    binary.display(),
+                    name
+                );
+                continue;
+            }
+            let Some(library) = target.split_whitespace().next().filter(|t| t.starts_with('/')) else {
+                continue; // the vDSO
+            };
+            let file_name = Path::new(name).file_name().and_then(|n| n.to_str()).unwrap_or(name);
+            if SYSTEM_LIBRARIES.iter().any(|system| file_name.starts_with(system)) {
+                continue;
+            }
+            found = true;
+            if self.libraries.insert(file_name.to_string()) {
+                let library = Path::new(library);
+                fs::create_dir_all(self.staging.join("lib"))?;
+                fs::copy(library, self.staging.join("lib").join(file_name))
+                    .with_context(|| format!("Failed to copy {:?}", library))?;
+            }
+        }
+        Ok(found)
+    }
+}
+
+fn is_elf(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| magic == *b"\x7fELF")
+}
+
+/// Copies a file, or a directory recursively, following symbolic links.
+fn copy(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from).with_context(|| format!("Failed to read directory {:?}", from))? {
+            let path = entry?.path();
+            if path.is_file() || path.is_dir() {
+                copy(&path, &to.join(path.file_name().unwrap_or_default()))?;
+            }
+        }
+        return Ok(());
+    }
+    fs::copy(from, to).with_context(|| format!("Failed to copy {:?}", from))?;
+    Ok(())
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)? + "\n").with_context(|| format!("Failed to write {:?}", path))
+}
+
+fn pack(staging: &Path, output: &Path) -> Result<()> {
+    let output = env::current_dir()?.join(output);
+    let status = Command::new("tar")
+        .arg("--zstd")
+        .arg("-cf")
+        .arg(&output)
+        .arg("-C")
+        .arg(staging)
+        .arg(".")
+        .status()
+        .context("Failed to run tar to pack the bundle")?;
+    if !status.success() {
+        bail!("Failed to pack the bundle {:?}: tar {}", output, status);
+    }
+    Ok(())
+}
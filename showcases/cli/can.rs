@@ -0,0 +1,115 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! CAN interface setup and teardown around an example run.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::iproute::{interface_exists, ip};
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CanKind {
+    #[default]
+    Virtual,
+    Physical,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CanConfig {
+    pub interface: String,
+    #[serde(default)]
+    pub kind: CanKind,
+    pub bitrate: Option<u32>, // only applied to physical interfaces
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+/// Interfaces prepared for an example. They are torn down when this is dropped.
+pub struct CanSetup {
+    prepared: Vec<(String, bool)>, // (interface, created by us)
+}
+
+impl Drop for CanSetup {
+    fn drop(&mut self) {
+        for (interface, created) in self.prepared.iter().rev() {
+            let result = if *created {
+                ip(&["link", "delete", "dev", interface])
+            } else {
+                ip(&["link", "set", "dev", interface, "down"])
+            };
+            match result {
+                Ok(()) => println!("CAN {}: torn down", interface),
+                Err(e) => eprintln!("CAN {}: teardown failed: {:#}", interface, e),
+            }
+        }
+    }
+}
+
+/// Creates and brings up all configured CAN interfaces.
+///
+/// Interfaces already prepared are torn down again if a later one fails.
+pub fn setup(configs: &[CanConfig]) -> Result<CanSetup> {
+    if !configs.is_empty() && !cfg!(target_os = "linux") {
+        bail!("CAN interfaces need Linux (SocketCAN)");
+    }
+    let mut setup = CanSetup { prepared: Vec::new() };
+    for config in configs {
+        let created = setup_interface(config)?;
+        setup.prepared.push((config.interface.clone(), created));
+        println!("CAN {}: up", config.interface);
+    }
+    Ok(setup)
+}
+
+fn setup_interface(config: &CanConfig) -> Result<bool> {
+    let interface = config.interface.as_str();
+    let exists = interface_exists(interface);
+
+    let created = match config.kind {
+        CanKind::Virtual => {
+            if config.bitrate.is_some() {
+                println!("CAN {}: bitrate is ignored for virtual interfaces", interface);
+            }
+            if !exists {
+                ip(&["link", "add", "dev", interface, "type", "vcan"])
+                    .with_context(|| format!("Failed to create virtual CAN interface {}", interface))?;
+            }
+            !exists
+        }
+        CanKind::Physical => {
+            if !exists {
+                bail!(
+                    "CAN interface {} not found. Check that the CAN hardware is present and its driver is loaded",
+                    interface
+                );
+            }
+            if let Some(bitrate) = config.bitrate {
+                let value = bitrate.to_string();
+                ip(&["link", "set", "dev", interface, "down"])?;
+                ip(&["link", "set", "dev", interface, "type", "can", "bitrate", &value])
+                    .with_context(|| format!("Failed to set bitrate {} on {}", bitrate, interface))?;
+            }
+            false
+        }
+    };
+
+    if let Err(e) = ip(&["link", "set", "dev", interface, "up"]) {
+        if created {
+            let _ = ip(&["link", "delete", "dev", interface]);
+        }
+        return Err(e).with_context(|| format!("Failed to bring up CAN interface {}", interface));
+    }
+    Ok(created)
+}
@@ -0,0 +1,238 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Catalog of all discovered examples, rendered for the documentation site.
+
+use clap::ValueEnum;
+use std::fmt::Write;
+
+use crate::builtin::AppType;
+use crate::{kpi, ScoreConfig};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+/// Renders the catalog of `configs`, sorted by example name.
+pub fn render(configs: &[ScoreConfig], format: Format) -> String {
+    let mut examples: Vec<&ScoreConfig> = configs.iter().collect();
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    match format {
+        Format::Markdown => markdown(&examples),
+        Format::Html => html(&examples),
+    }
+}
+
+/// Command line of an app as shown in the catalog.
+fn command_line(app: &crate::AppConfig) -> String {
+    let command: Vec<&str> = match app.app_type {
+        AppType::Process => std::iter::once(app.path.as_str())
+            .chain(app.args.iter().map(String::as_str))
+            .collect(),
+        // Built-in apps run as `<cli> builtin <type> ...`, the CLI's path says nothing about the example.
+        _ => app.args.iter().skip(1).map(String::as_str).collect(),
+    };
+    command.join(" ")
+}
+
+fn parameter_line(parameter: &crate::parameters::Parameter) -> String {
+    let mut line = format!("{}: {}", parameter.name, parameter.kind.describe());
+    if let Some(default) = parameter.default_value() {
+        let _ = write!(line, ", default {}", default);
+    }
+    if !parameter.description.is_empty() {
+        let _ = write!(line, " — {}", parameter.description);
+    }
+    line
+}
+
+/// The KPIs of an example, like `startup_ms ≤ 500`.
+fn kpis(config: &ScoreConfig) -> Vec<String> {
+    config
+        .kpis
+        .iter()
+        .map(|(name, limit)| kpi::describe_limit(name, *limit))
+        .collect()
+}
+
+/// What an example sets up besides its apps.
+fn setup(config: &ScoreConfig) -> Vec<String> {
+    let mut setup = Vec::new();
+    if !config.can.is_empty() {
+        setup.push(format!("{} CAN interface(s)", config.can.len()));
+    }
+    if config.network.is_some() {
+        setup.push("network topology".to_string());
+    }
+    if config.someip_sd.is_some() {
+        setup.push("SOME/IP SD verification".to_string());
+    }
+    if config.health_monitor.is_some() {
+        setup.push("health monitor registration".to_string());
+    }
+    setup
+}
+
+fn markdown(examples: &[&ScoreConfig]) -> String {
+    let mut out = String::from("# Examples\n\n| Example | Description | Tags |\n|---|---|---|\n");
+    for config in examples {
+        let _ = writeln!(
+            out,
+            "| [{}](#{}) | {} | {} |",
+            md_cell(&config.name),
+            anchor(&config.name),
+            md_cell(&config.description),
+            config
+                .tags
+                .iter()
+                .map(|t| format!("`{}`", t))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+    for config in examples {
+        let _ = write!(out, "\n## {}\n\n{}\n\n", config.name, config.description);
+        if !config.tags.is_empty() {
+            let _ = writeln!(out, "**Tags:** {}\n", config.tags.join(", "));
+        }
+        if !config.requirements.is_empty() {
+            let ids: Vec<String> = config.requirements.iter().map(|r| format!("`{}`", r)).collect();
+            let _ = writeln!(out, "**Requirements:** {}\n", ids.join(", "));
+        }
+        let setup = setup(config);
+        if !setup.is_empty() {
+            let _ = writeln!(out, "**Setup:** {}\n", setup.join(", "));
+        }
+        if !config.kpis.is_empty() {
+            let _ = writeln!(out, "**KPIs:** {}\n", md_cell(&kpis(config).join(", ")));
+        }
+        if !config.parameters.is_empty() {
+            let _ = writeln!(out, "**Parameters:**\n");
+            for parameter in &config.parameters {
+                let _ = writeln!(out, "- {}", md_cell(&parameter_line(parameter)));
+            }
+            out.push('\n');
+        }
+        if !config.steps.is_empty() {
+            let _ = writeln!(out, "**Steps:**\n");
+            for (i, step) in config.steps.iter().enumerate() {
+                let _ = writeln!(out, "{}. [{}](#{})", i + 1, step, anchor(step));
+            }
+            continue;
+        }
+        let _ = writeln!(out, "**Apps:**\n");
+        for (i, app) in config.apps.iter().enumerate() {
+            let _ = writeln!(out, "{}. `{}`", i + 1, command_line(app).replace('`', "'"));
+        }
+    }
+    out
+}
+
+fn html(examples: &[&ScoreConfig]) -> String {
+    let mut out = String::from(
+        "<section class=\"score-examples\">\n<h1>Examples</h1>\n<table>\n\
+         <thead><tr><th>Example</th><th>Description</th><th>Tags</th></tr></thead>\n<tbody>\n",
+    );
+    for config in examples {
+        let _ = writeln!(
+            out,
+            "<tr><td><a href=\"#{}\">{}</a></td><td>{}</td><td>{}</td></tr>",
+            anchor(&config.name),
+            escape(&config.name),
+            escape(&config.description),
+            config
+                .tags
+                .iter()
+                .map(|t| format!("<code>{}</code>", escape(t)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+    out.push_str("</tbody>\n</table>\n");
+    for config in examples {
+        let _ = writeln!(
+            out,
+            "<h2 id=\"{}\">{}</h2>\n<p>{}</p>",
+            anchor(&config.name),
+            escape(&config.name),
+            escape(&config.description)
+        );
+        if !config.tags.is_empty() {
+            let _ = writeln!(out, "<p><strong>Tags:</strong> {}</p>", escape(&config.tags.join(", ")));
+        }
+        if !config.requirements.is_empty() {
+            let ids: Vec<String> = config
+                .requirements
+                .iter()
+                .map(|r| format!("<code>{}</code>", escape(r)))
+                .collect();
+            let _ = writeln!(out, "<p><strong>Requirements:</strong> {}</p>", ids.join(", "));
+        }
+        let setup = setup(config);
+        if !setup.is_empty() {
+            let _ = writeln!(out, "<p><strong>Setup:</strong> {}</p>", escape(&setup.join(", ")));
+        }
+        if !config.kpis.is_empty() {
+            let _ = writeln!(
+                out,
+                "<p><strong>KPIs:</strong> {}</p>",
+                escape(&kpis(config).join(", "))
+            );
+        }
+        if !config.parameters.is_empty() {
+            out.push_str("<p><strong>Parameters:</strong></p>\n<ul>\n");
+            for parameter in &config.parameters {
+                let _ = writeln!(out, "<li>{}</li>", escape(&parameter_line(parameter)));
+            }
+            out.push_str("</ul>\n");
+        }
+        if config.steps.is_empty() {
+            out.push_str("<p><strong>Apps:</strong></p>\n<ol>\n");
+            for app in &config.apps {
+                let _ = writeln!(out, "<li><code>{}</code></li>", escape(&command_line(app)));
+            }
+        } else {
+            out.push_str("<p><strong>Steps:</strong></p>\n<ol>\n");
+            for step in &config.steps {
+                let _ = writeln!(out, "<li><a href=\"#{}\">{}</a></li>", anchor(step), escape(step));
+            }
+        }
+        out.push_str("</ol>\n");
+    }
+    out.push_str("</section>\n");
+    out
+}
+
+/// Heading anchor as generated by GitHub and most Markdown renderers.
+fn anchor(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,298 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! A cgroup (v2) per example, holding every process of its apps.
+//!
+//! Apps join the cgroup of their example right before exec, so whatever they
+//! fork, including daemons that double-fork away from the runner, stays in it.
+//! The cgroup gives the CPU time and peak memory of the example as a whole, and
+//! once its apps were stopped, processes still left in it are killed before it
+//! is removed.
+//!
+//! The cgroups are created below the runner's own cgroup, which needs cgroup v2
+//! and write access to it: as root, or in a cgroup delegated to the user, like
+//! `systemd-run --user --scope -p Delegate=yes`. Cgroups of systemd units
+//! (scopes, services and slices) are left alone unless systemd delegated them,
+//! as systemd manages their subtree. Before the first example, the runner moves
+//! itself into a leaf cgroup `score-cli-<pid>-runner` next to the ones of the
+//! examples, since the kernel only enables controllers for the cgroups below a
+//! cgroup without processes of its own. Elsewhere examples run as before,
+//! without the accounting.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::ffi::CString;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::{host, sys};
+
+const ROOT: &str = "/sys/fs/cgroup";
+/// Time processes left in a cgroup have to vanish once killed.
+const KILL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Numbers the cgroups of the examples of this runner.
+static NEXT: AtomicU64 = AtomicU64::new(1);
+/// Set once it was told why apps are not placed in cgroups.
+static EXPLAINED: AtomicBool = AtomicBool::new(false);
+/// The cgroup the cgroups of the examples are created in, or why there is none, set up once.
+static PARENT: OnceLock<Result<PathBuf, String>> = OnceLock::new();
+/// Suffixes of the cgroups of systemd units.
+const SYSTEMD_UNITS: &[&str] = &[".scope", ".service", ".slice"];
+
+/// Resources used by all processes of an example, from its cgroup.
+#[derive(Debug, Clone, Serialize)]
+pub struct CgroupUsage {
+    pub cpu_ms: u64,
+    pub user_ms: u64,
+    pub system_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_peak_kb: Option<u64>, // needs the memory controller in the runner's cgroup
+    #[serde(skip_serializing_if = "is_zero")]
+    pub leftover_processes: usize, // still running after the apps were stopped, killed by the runner
+}
+
+/// The cgroup of an example, emptied and removed when dropped.
+pub(crate) struct ExampleCgroup {
+    path: PathBuf,
+    procs: CString,         // cgroup.procs, for joining between fork and exec
+    memory_peak: AtomicU64, // in bytes, sampled where the kernel has no memory.peak
+}
+
+impl ExampleCgroup {
+    /// Creates a cgroup for an example, or returns `None` if apps cannot be placed in one.
+    ///
+    /// Tells why the first time creating one fails on a host with cgroup v2.
+    pub(crate) fn create() -> Option<ExampleCgroup> {
+        if !host::has_cgroup_v2() {
+            return None;
+        }
+        match Self::try_create() {
+            Ok(cgroup) => Some(cgroup),
+            Err(e) => {
+                if !EXPLAINED.swap(true, Ordering::SeqCst) {
+                    println!("⚠️  Not placing apps in a cgroup per example: {:#}", e);
+                }
+                None
+            }
+        }
+    }
+
+    fn try_create() -> Result<ExampleCgroup> {
+        let parent = match PARENT.get_or_init(|| set_up().map_err(|e| format!("{:#}", e))) {
+            Ok(parent) => parent,
+            Err(e) => bail!("{}", e),
+        };
+        let path = parent.join(format!(
+            "score-cli-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::SeqCst)
+        ));
+        fs::create_dir(&path).with_context(|| format!("Failed to create cgroup {}", path.display()))?;
+        let procs = CString::new(path.join("cgroup.procs").as_os_str().as_bytes())?;
+        Ok(ExampleCgroup {
+            path,
+            procs,
+            memory_peak: AtomicU64::new(0),
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Makes the process started by `cmd` join the cgroup before exec.
+    pub(crate) fn place(&self, cmd: &mut Command) {
+        let procs = self.procs.clone();
+        // SAFETY: the closure only issues open, write and close, which are async-signal-safe.
+        unsafe {
+            cmd.pre_exec(move || {
+                // An app that cannot join runs outside the cgroup rather than not at all.
+                let _ = sys::join_cgroup(&procs);
+                Ok(())
+            });
+        }
+    }
+
+    /// Samples the memory of the cgroup, for kernels without `memory.peak` (before 5.19).
+    pub(crate) fn poll(&self) {
+        if let Some(current) = read_number(&self.path.join("memory.current")) {
+            self.memory_peak.fetch_max(current, Ordering::Relaxed);
+        }
+    }
+
+    /// Kills the processes left in the cgroup and returns what the example used.
+    pub(crate) fn finish(&self) -> CgroupUsage {
+        let leftover_processes = kill_all(&self.path);
+        let stat = fs::read_to_string(self.path.join("cpu.stat")).unwrap_or_default();
+        let usec = |key: &str| {
+            stat.lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix(' '))
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .unwrap_or(0)
+        };
+        let memory_peak = read_number(&self.path.join("memory.peak"))
+            .or_else(|| Some(self.memory_peak.load(Ordering::Relaxed)).filter(|peak| *peak > 0));
+        CgroupUsage {
+            cpu_ms: usec("usage_usec") / 1000,
+            user_ms: usec("user_usec") / 1000,
+            system_ms: usec("system_usec") / 1000,
+            memory_peak_kb: memory_peak.map(|bytes| bytes / 1024),
+            leftover_processes,
+        }
+    }
+}
+
+impl Drop for ExampleCgroup {
+    fn drop(&mut self) {
+        let _ = remove(&self.path);
+    }
+}
+
+/// Kills the processes in the cgroup at `path` and removes it, returning how many were killed.
+///
+/// Also cleans up after runners that died before removing their cgroups.
+pub(crate) fn remove(path: &Path) -> Result<usize> {
+    let killed = kill_all(path);
+    // The kernel may report the cgroup busy for a moment after its last process exited.
+    let deadline = Instant::now() + KILL_TIMEOUT;
+    loop {
+        match fs::remove_dir(path) {
+            Ok(()) => return Ok(killed),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(killed),
+            Err(e) if Instant::now() >= deadline => {
+                return Err(e).with_context(|| format!("Failed to remove cgroup {}", path.display()))
+            }
+            Err(_) => std::thread::sleep(Duration::from_millis(20)),
+        }
+    }
+}
+
+/// Whether `path` is a cgroup the runner with `pid` created for an example, as recorded in its checkpoint.
+pub(crate) fn created_by(path: &Path, pid: u32) -> bool {
+    let plain = path
+        .components()
+        .all(|component| matches!(component, Component::RootDir | Component::Normal(_)));
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    plain
+        && path.starts_with(ROOT)
+        && name
+            .strip_prefix(&format!("score-cli-{}-", pid))
+            .is_some_and(|number| number.parse::<u64>().is_ok())
+}
+
+/// Kills all processes in the cgroup at `path` and waits until they are gone. Returns how many there were.
+fn kill_all(path: &Path) -> usize {
+    let pids = processes(path);
+    if pids.is_empty() {
+        return 0;
+    }
+    if fs::write(path.join("cgroup.kill"), "1").is_err() {
+        // Before Linux 5.14: freezing keeps processes from forking while they are killed one by one.
+        let freeze = path.join("cgroup.freeze");
+        let _ = fs::write(&freeze, "1");
+        for pid in &pids {
+            let _ = sys::send(*pid, sys::Signal::KILL);
+        }
+        let _ = fs::write(&freeze, "0");
+    }
+    let deadline = Instant::now() + KILL_TIMEOUT;
+    while !processes(path).is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    pids.len()
+}
+
+fn processes(path: &Path) -> Vec<u32> {
+    fs::read_to_string(path.join("cgroup.procs"))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.trim().parse().ok())
+        .collect()
+}
+
+/// Moves the runner into a leaf cgroup below its own and enables the memory controller for the
+/// cgroups next to it, returning the runner's former cgroup, where the examples get theirs.
+fn set_up() -> Result<PathBuf> {
+    let own = own_cgroup()?;
+    let name = own.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    if SYSTEMD_UNITS.iter().any(|suffix| name.ends_with(suffix)) && !delegated(&own) {
+        bail!(
+            "the runner's cgroup {} belongs to a systemd unit that was not delegated, \
+             run the CLI with `systemd-run --user --scope -p Delegate=yes`",
+            own.display()
+        );
+    }
+    remove_stale_leaves(&own);
+    let leaf = own.join(format!("score-cli-{}-runner", std::process::id()));
+    fs::create_dir(&leaf).with_context(|| format!("Failed to create cgroup {}", leaf.display()))?;
+    // Writing 0 moves the runner with all its threads.
+    if let Err(e) = fs::write(leaf.join("cgroup.procs"), "0") {
+        let _ = fs::remove_dir(&leaf);
+        return Err(e).with_context(|| format!("Failed to move the runner into cgroup {}", leaf.display()));
+    }
+    // Lets the cgroups of the examples account memory.
+    if let Err(e) = fs::write(own.join("cgroup.subtree_control"), "+memory") {
+        println!(
+            "⚠️  Not measuring the memory of examples: the memory controller cannot be enabled in {}: {} \
+             (other processes in the cgroup, or the controller is not delegated)",
+            own.display(),
+            e
+        );
+    }
+    Ok(own)
+}
+
+/// Whether systemd delegated the cgroup at `path`, which it marks with an extended attribute.
+fn delegated(path: &Path) -> bool {
+    ["trusted.delegate", "user.delegate"]
+        .iter()
+        .any(|name| sys::xattr(path, name).is_ok_and(|value| value == b"1"))
+}
+
+/// Removes the empty leaf cgroups of runners that ended, which cannot remove their own.
+fn remove_stale_leaves(own: &Path) {
+    let Ok(entries) = fs::read_dir(own) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with("score-cli-") && name.ends_with("-runner") {
+            // Fails for cgroups with processes, like the leaves of running runners.
+            let _ = fs::remove_dir(entry.path());
+        }
+    }
+}
+
+/// Directory of the runner's cgroup in the unified hierarchy.
+fn own_cgroup() -> Result<PathBuf> {
+    let content = fs::read_to_string("/proc/self/cgroup").context("Failed to read /proc/self/cgroup")?;
+    let Some(path) = content.lines().find_map(|line| line.strip_prefix("0::")) else {
+        bail!("the runner is in no cgroup v2");
+    };
+    Ok(Path::new(ROOT).join(path.trim_start_matches('/')))
+}
+
+fn read_number(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
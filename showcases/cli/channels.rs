@@ -0,0 +1,188 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! IPC channels provisioned by the runner: named pipes and unix socket paths.
+//!
+//! The `channels` of an example are set up before its first app starts and
+//! removed once it ended, so apps need no `mkfifo` wrappers and never trip over
+//! files left behind by a crashed run. Every app gets the path of every channel
+//! in `SCORE_CHANNEL_<NAME>`, or the variable given as `env`.
+//!
+//! A FIFO is created with its `mode`. A unix socket is bound by one of the
+//! apps, the runner creates its directory, removes a stale socket file and
+//! applies the `mode` once the socket appeared. Until then, the umask of the
+//! apps keeps the socket from being more open than any unix channel's `mode`.
+//! Modes are never set through a symlink put at the path.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::sys;
+
+/// Longest unix socket path, `sun_path` less the terminating NUL.
+const MAX_SOCKET_PATH: usize = 107;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    /// Named pipe created by the runner
+    Fifo,
+    /// Unix socket bound by an app
+    Unix,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ChannelConfig {
+    pub name: String,
+    pub kind: ChannelKind,
+    pub path: Option<String>, // default: channels/<name> in the example's artifacts directory
+    pub mode: Option<String>, // octal permissions, e.g. "0660" (default: "0600")
+    pub env: Option<String>,  // variable holding the path (default: SCORE_CHANNEL_<NAME>)
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+/// Channels set up for an example, removed when this is dropped.
+#[derive(Default)]
+pub struct Channels {
+    env: Vec<(String, String)>,
+    paths: Vec<PathBuf>,
+    pending: Vec<(PathBuf, u32)>, // unix sockets whose mode is applied once they appear
+    umask: u32,                   // added to the umask of the apps, the permissions no unix channel grants
+}
+
+impl Channels {
+    /// Sets up `configs`, placing channels without a `path` below `example_dir`.
+    pub fn create(configs: &[ChannelConfig], example_dir: &Path) -> Result<Channels> {
+        let mut channels = Channels::default();
+        for config in configs {
+            channels
+                .add(config, example_dir)
+                .with_context(|| format!("Channel \"{}\"", config.name))?;
+        }
+        Ok(channels)
+    }
+
+    fn add(&mut self, config: &ChannelConfig, example_dir: &Path) -> Result<()> {
+        if config.name.is_empty()
+            || !config
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            bail!("invalid name, use letters, digits, '_' and '-'");
+        }
+        let mode = match config.mode {
+            Some(ref mode) => u32::from_str_radix(mode, 8)
+                .ok()
+                .filter(|mode| *mode <= 0o777)
+                .with_context(|| format!("invalid mode \"{}\", use octal permissions like \"0660\"", mode))?,
+            None => 0o600,
+        };
+        let path = match config.path {
+            Some(ref path) => PathBuf::from(path),
+            None => example_dir.join("channels").join(&config.name),
+        };
+        if config.kind == ChannelKind::Unix && path.as_os_str().len() > MAX_SOCKET_PATH {
+            bail!(
+                "socket path {} is longer than {} bytes, set a shorter `path`",
+                path.display(),
+                MAX_SOCKET_PATH
+            );
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        // Left behind by a crashed run. Anything else at the path is not ours to remove.
+        if let Ok(metadata) = fs::symlink_metadata(&path) {
+            let file_type = metadata.file_type();
+            if !file_type.is_fifo() && !file_type.is_socket() {
+                bail!("{} exists and is neither a FIFO nor a socket", path.display());
+            }
+            fs::remove_file(&path).with_context(|| format!("Failed to remove stale {}", path.display()))?;
+        }
+        match config.kind {
+            ChannelKind::Fifo => {
+                sys::make_fifo(&path, mode).with_context(|| format!("Failed to create FIFO {}", path.display()))?;
+            }
+            ChannelKind::Unix => {
+                self.umask |= !mode & 0o777;
+                self.pending.push((path.clone(), mode));
+            }
+        }
+        let variable = config
+            .env
+            .clone()
+            .unwrap_or_else(|| format!("SCORE_CHANNEL_{}", config.name.to_ascii_uppercase().replace('-', "_")));
+        self.env.push((variable, path.display().to_string()));
+        self.paths.push(path);
+        Ok(())
+    }
+
+    /// What the apps get of the channels.
+    pub fn for_apps(&self) -> ForApps {
+        ForApps {
+            env: self.env.clone(),
+            umask: self.umask,
+        }
+    }
+
+    /// Applies the mode of unix sockets that appeared since the last call.
+    pub fn poll(&mut self) {
+        self.pending.retain(|(path, mode)| {
+            let bound = fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket());
+            if bound {
+                if let Err(e) = sys::chmod_nofollow(path, *mode) {
+                    println!("⚠️  Failed to set the mode of {}: {}", path.display(), e);
+                }
+            }
+            !bound
+        });
+    }
+}
+
+/// The variables with the paths of the channels and the umask keeping unix sockets closed until their mode is set.
+pub struct ForApps {
+    env: Vec<(String, String)>,
+    umask: u32,
+}
+
+impl ForApps {
+    /// Gives an app the variables and, with unix channels, the umask.
+    pub fn prepare(&self, cmd: &mut Command) {
+        cmd.envs(self.env.iter().cloned());
+        if self.umask != 0 {
+            let umask = self.umask;
+            // SAFETY: the closure only calls umask, which is async-signal-safe.
+            unsafe {
+                cmd.pre_exec(move || {
+                    sys::restrict_umask(umask);
+                    Ok(())
+                });
+            }
+        }
+    }
+}
+
+impl Drop for Channels {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
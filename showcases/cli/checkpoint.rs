@@ -0,0 +1,404 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Run state kept on disk while a run goes on, for when the CLI itself dies.
+//!
+//! `checkpoint.json` in the run directory is rewritten whenever an example
+//! starts or ends and whenever an app is started: the examples that finished
+//! with their reports, the ones running and the pids of their apps. It is
+//! removed once `report.json` is written. `stress`, `bench`, `soak` and `kiosk`
+//! checkpoint the example they are running like a run of it alone. If the CLI
+//! crashed or was OOM-killed, `score-cli recover` stops the apps it left
+//! behind, with everything in the cgroups of their examples, and writes
+//! `report.json` from the checkpoint, so the results of the examples that
+//! finished are kept.
+//!
+//! Recovery only reads checkpoints of the user, and only touches the
+//! `score-cli-<pid>-<n>` cgroups the CLI with the recorded pid creates. Where
+//! process start times are unknown (QNX), a CLI whose pid still exists is
+//! taken to be running, and apps are not stopped, as their pids may belong to
+//! other processes by now.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::report::{ExampleReport, RunReport, Status};
+use crate::sys::{self, Signal};
+use crate::{cgroup, private_dir, procfs, ScoreConfig};
+
+pub const FILE_NAME: &str = "checkpoint.json";
+/// Time apps left behind have to exit on SIGTERM before they are killed.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+const INTERRUPTED: &str = "interrupted: the CLI ended while the example ran";
+const NOT_RUN: &str = "not run: the CLI ended before the example started";
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    cli_pid: u32,
+    cli_started: Option<u64>, // start time of the CLI in clock ticks since boot, telling it from a later process with its pid
+    report: Value,            // the run report without examples
+    examples: Vec<ExampleState>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExampleState {
+    index: usize, // of the example among the examples of the run
+    name: String,
+    requirements: Vec<String>,
+    selected: bool,
+    dir: Option<PathBuf>, // set once the example started
+    apps: Vec<AppState>,
+    #[serde(default)]
+    cgroups: Vec<PathBuf>, // of the attempts of the example
+    report: Option<Value>, // set once the example finished
+}
+
+#[derive(Serialize, Deserialize)]
+struct AppState {
+    index: usize,
+    path: String,
+    pid: u32,
+    started: Option<u64>, // start time in clock ticks since boot, unknown without /proc
+}
+
+/// Checkpoint of a run, doing nothing until [`Checkpoint::start`] is called.
+#[derive(Default)]
+pub(crate) struct Checkpoint {
+    state: Mutex<Option<(PathBuf, State)>>,
+}
+
+impl Checkpoint {
+    /// Starts checkpointing the run of `report`, with the `selected` examples of `configs`.
+    pub(crate) fn start(&self, report: &RunReport, configs: &[ScoreConfig], selected: &[usize]) {
+        let example = |index, config: &ScoreConfig, selected| ExampleState {
+            index,
+            name: config.name.clone(),
+            requirements: config.requirements.clone(),
+            selected,
+            dir: None,
+            apps: Vec::new(),
+            cgroups: Vec::new(),
+            report: None,
+        };
+        // In the order of the run report: the selected examples first, then the ones filtered out.
+        let mut examples: Vec<ExampleState> = selected
+            .iter()
+            .map(|&index| example(index, &configs[index], true))
+            .collect();
+        examples.extend(
+            configs
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !selected.contains(index))
+                .map(|(index, config)| example(index, config, false)),
+        );
+        let cli_pid = std::process::id();
+        let state = State {
+            cli_pid,
+            cli_started: procfs::start_time(cli_pid),
+            report: serde_json::to_value(report).unwrap_or(Value::Null),
+            examples,
+        };
+        let mut guard = self.state.lock().unwrap();
+        *guard = Some((report.run_dir.join(FILE_NAME), state));
+        flush(&guard);
+    }
+
+    /// Starts checkpointing a mode running `config` alone in `example_dir`, like a run of just that example.
+    pub(crate) fn start_example(&self, report: &RunReport, config: &ScoreConfig, example_dir: &Path) {
+        self.start(report, std::slice::from_ref(config), &[0]);
+        self.example_started(0, example_dir);
+    }
+
+    /// Records that example `index` started, with its artifacts in `dir`.
+    pub(crate) fn example_started(&self, index: usize, dir: &Path) {
+        self.change(|state| {
+            if let Some(example) = state.examples.iter_mut().find(|example| example.index == index) {
+                example.dir = Some(dir.to_path_buf());
+            }
+        });
+    }
+
+    /// Records that app `index` of the example with its artifacts in `example_dir` was (re)started as `pid`.
+    pub(crate) fn app_started(&self, example_dir: &Path, index: usize, path: &str, pid: u32) {
+        self.change(|state| {
+            if let Some(example) = running(state, example_dir) {
+                let app = AppState {
+                    index,
+                    path: path.to_string(),
+                    pid,
+                    started: procfs::start_time(pid),
+                };
+                match example.apps.iter_mut().find(|app| app.index == index) {
+                    Some(entry) => *entry = app,
+                    None => example.apps.push(app),
+                }
+            }
+        });
+    }
+
+    /// Records the cgroup created for the example with its artifacts in `example_dir`.
+    pub(crate) fn cgroup_created(&self, example_dir: &Path, cgroup: &Path) {
+        self.change(|state| {
+            if let Some(example) = running(state, example_dir) {
+                example.cgroups.push(cgroup.to_path_buf());
+            }
+        });
+    }
+
+    /// Records the report of the finished example `index`.
+    pub(crate) fn example_finished(&self, index: usize, report: &ExampleReport) {
+        self.change(|state| {
+            if let Some(example) = state.examples.iter_mut().find(|example| example.index == index) {
+                example.report = serde_json::to_value(report).ok();
+                example.apps.clear();
+                example.cgroups.clear();
+            }
+        });
+    }
+
+    /// Removes the checkpoint once the run report is written.
+    pub(crate) fn remove(&self) {
+        let mut guard = self.state.lock().unwrap();
+        if let Some((path, _)) = guard.take() {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    fn change(&self, change: impl FnOnce(&mut State)) {
+        let mut guard = self.state.lock().unwrap();
+        if let Some((_, ref mut state)) = *guard {
+            change(state);
+            flush(&guard);
+        }
+    }
+}
+
+/// The running example with its artifacts in `example_dir`, or in a directory above for attempts and steps.
+fn running<'a>(state: &'a mut State, example_dir: &Path) -> Option<&'a mut ExampleState> {
+    state.examples.iter_mut().find(|example| {
+        example.report.is_none() && example.dir.as_deref().is_some_and(|dir| example_dir.starts_with(dir))
+    })
+}
+
+fn flush(checkpoint: &Option<(PathBuf, State)>) {
+    if let Some((ref path, ref state)) = *checkpoint {
+        if let Err(e) = write(path, state) {
+            eprintln!("⚠️  Failed to write the checkpoint {}: {:#}", path.display(), e);
+        }
+    }
+}
+
+/// Replaces the checkpoint at once, so a crash while writing leaves the previous one.
+fn write(path: &Path, state: &State) -> Result<()> {
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, serde_json::to_vec_pretty(state)?)
+        .with_context(|| format!("Failed to write {}", temporary.display()))?;
+    fs::rename(&temporary, path).with_context(|| format!("Failed to rename {}", temporary.display()))
+}
+
+/// Recovers the runs in `run_dirs`, or all runs under `runs_root` left with a checkpoint.
+pub fn recover(run_dirs: &[PathBuf], runs_root: &Path) -> Result<()> {
+    let run_dirs = if run_dirs.is_empty() {
+        private_dir::ensure(runs_root)?;
+        let mut found: Vec<PathBuf> = fs::read_dir(runs_root)
+            .with_context(|| format!("No recorded runs in {:?}", runs_root))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.join(FILE_NAME).is_file())
+            .collect();
+        found.sort();
+        if found.is_empty() {
+            println!("No runs to recover in {}", runs_root.display());
+        }
+        found
+    } else {
+        run_dirs.to_vec()
+    };
+    for run_dir in &run_dirs {
+        recover_run(run_dir).with_context(|| format!("Failed to recover the run in {}", run_dir.display()))?;
+    }
+    Ok(())
+}
+
+fn recover_run(run_dir: &Path) -> Result<()> {
+    let path = run_dir.join(FILE_NAME);
+    // A checkpoint of another user could make the user stop processes or remove cgroups of theirs.
+    private_dir::check_owned(&path)?;
+    let content = fs::read(&path).with_context(|| format!("No checkpoint {} to recover from", path.display()))?;
+    let state: State =
+        serde_json::from_slice(&content).with_context(|| format!("Invalid checkpoint {}", path.display()))?;
+    let cli_running = match state.cli_started {
+        Some(_) => alive(state.cli_pid, state.cli_started),
+        None => sys::exists(state.cli_pid),
+    };
+    if cli_running {
+        println!(
+            "Run in {} is still going on (CLI pid {}), leaving it alone",
+            run_dir.display(),
+            state.cli_pid
+        );
+        return Ok(());
+    }
+    println!("Recovering the run in {}", run_dir.display());
+
+    let mut leftovers: Vec<Leftover> = state
+        .examples
+        .iter()
+        .filter(|example| example.report.is_none())
+        .flat_map(|example| example.apps.iter().map(move |app| (example, app)))
+        // Opened before the start time is checked, so a pidfd cannot refer to a later process with the pid.
+        .map(|(example, app)| (example, app, sys::Pidfd::open(app.pid).ok()))
+        .filter(|(_, app, _)| alive(app.pid, app.started))
+        .collect();
+    for (example, app) in state
+        .examples
+        .iter()
+        .filter(|example| example.report.is_none())
+        .flat_map(|example| example.apps.iter().map(move |app| (example, app)))
+        .filter(|(_, app)| app.started.is_none() && sys::exists(app.pid))
+    {
+        println!(
+            "⚠️  Not stopping app {} of '{}' (pid {}): without its start time, the pid may belong to another process by now",
+            app.index, example.name, app.pid
+        );
+    }
+    for (example, app, pidfd) in &leftovers {
+        println!(
+            "Stopping app {} of '{}' left running: {} (pid {})",
+            app.index, example.name, app.path, app.pid
+        );
+        let _ = signal(app, pidfd.as_ref(), Signal::TERM);
+    }
+    let deadline = Instant::now() + STOP_GRACE;
+    while !leftovers.is_empty() && Instant::now() < deadline {
+        let pidfds: Option<Vec<&sys::Pidfd>> = leftovers.iter().map(|(_, _, pidfd)| pidfd.as_ref()).collect();
+        match pidfds {
+            Some(pidfds) => {
+                let exited = sys::wait_any(&pidfds, deadline.saturating_duration_since(Instant::now()))?;
+                let mut position = 0;
+                leftovers.retain(|_| {
+                    position += 1;
+                    !exited.contains(&(position - 1))
+                });
+            }
+            None => {
+                std::thread::sleep(Duration::from_millis(100));
+                leftovers.retain(|(_, app, _)| alive(app.pid, app.started));
+            }
+        }
+    }
+    for (_, app, pidfd) in &leftovers {
+        println!(
+            "Killing app {} (pid {}), still running after {:?}",
+            app.index, app.pid, STOP_GRACE
+        );
+        let _ = signal(app, pidfd.as_ref(), Signal::KILL);
+    }
+    // Also takes the processes the apps forked, which the checkpoint does not know.
+    for (example, path) in state
+        .examples
+        .iter()
+        .filter(|example| example.report.is_none())
+        .flat_map(|example| example.cgroups.iter().map(move |path| (example, path)))
+    {
+        if !cgroup::created_by(path, state.cli_pid) {
+            println!(
+                "⚠️  Not removing {}: no cgroup the CLI (pid {}) created",
+                path.display(),
+                state.cli_pid
+            );
+            continue;
+        }
+        match cgroup::remove(path) {
+            Ok(0) => {}
+            Ok(killed) => println!("Killed {} processes left in the cgroup of '{}'", killed, example.name),
+            Err(e) => println!("⚠️  {:#}", e),
+        }
+    }
+
+    let examples: Vec<Value> = state
+        .examples
+        .iter()
+        .map(|example| match (&example.report, &example.dir) {
+            (Some(report), _) => report.clone(),
+            (None, dir) => {
+                let reason = match (dir, example.selected) {
+                    (Some(_), _) => INTERRUPTED,
+                    (None, true) => NOT_RUN,
+                    (None, false) => "filtered out",
+                };
+                let mut report = ExampleReport::skipped(&example.name, &example.requirements, reason.to_string());
+                if dir.is_some() {
+                    report.status = Status::Failed;
+                }
+                serde_json::to_value(&report).unwrap_or(Value::Null)
+            }
+        })
+        .collect();
+    let count = |status: Status| {
+        examples
+            .iter()
+            .filter(|example| example["status"] == status.name())
+            .count()
+    };
+    println!(
+        "Results: {} passed, {} flaky, {} failed, {} skipped, {} xfail, {} xpass",
+        count(Status::Passed),
+        count(Status::Flaky),
+        count(Status::Failed),
+        count(Status::Skipped),
+        count(Status::Xfail),
+        count(Status::Xpass)
+    );
+
+    let mut report = state.report;
+    report["recovered"] = Value::Bool(true);
+    let warning = format!(
+        "recovered from the checkpoint after the CLI (pid {}) ended without writing this report",
+        state.cli_pid
+    );
+    match report["warnings"].as_array_mut() {
+        Some(warnings) => warnings.push(Value::from(warning)),
+        None => report["warnings"] = Value::from(vec![warning]),
+    }
+    report["examples"] = Value::Array(examples);
+    let report_path = run_dir.join("report.json");
+    fs::write(&report_path, serde_json::to_string_pretty(&report)?)
+        .with_context(|| format!("Failed to write run report {:?}", report_path))?;
+    fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    println!("Run report written to {}", report_path.display());
+    Ok(())
+}
+
+/// An app left running by the CLI, with a pidfd where there are pidfds.
+type Leftover<'a> = (&'a ExampleState, &'a AppState, Option<sys::Pidfd>);
+
+fn signal(app: &AppState, pidfd: Option<&sys::Pidfd>, signal: Signal) -> std::io::Result<()> {
+    match pidfd {
+        Some(pidfd) => pidfd.send(signal),
+        None if alive(app.pid, app.started) => sys::send(app.pid, signal),
+        None => Ok(()),
+    }
+}
+
+/// Whether `pid` is still the process that started at `started`, and not a later one reusing the pid.
+///
+/// Without a start time, this cannot be told, and the process is taken to be gone.
+fn alive(pid: u32, started: Option<u64>) -> bool {
+    started.is_some() && procfs::start_time(pid) == started
+}
@@ -0,0 +1,1005 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! The `score-cli` command line: argument parsing and the commands built on the runner.
+
+use crate::completion::Ended;
+use crate::{
+    adhoc, bench, builtin, bundle, catalog, checkpoint, completion, diff, doctor, examples_root, examples_roots,
+    exit_codes, explain, find_example, fmt, gc, graph, helper, history, init, kiosk, load_examples, logs, lsp,
+    manifest, parameters, parse_duration, pattern, run_examples, select_examples, self_update, selftest, serve,
+    session, settings, signing, soak, start_helper, stop_on_interrupt, stress, timeline, traceability, tree_rev,
+    RunOptions,
+};
+use anyhow::{Context, Result};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use cliclack::{clear_screen, confirm, intro, multiselect, outro};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "SCORE CLI")]
+#[command(about = "SCORE CLI showcase entrypoint", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    /// Examples to run (comma-separated names, or "all" to run all examples, skips interactive selection)
+    #[arg(long)]
+    examples: Option<String>,
+
+    #[command(flatten)]
+    run: RunOptions,
+
+    /// Environment preset applied to all examples, overriding the presets they reference
+    #[arg(long, global = true)]
+    preset: Option<String>,
+
+    /// Examples directory, repeat it to overlay the examples of later directories on earlier ones
+    #[arg(long = "root", global = true, value_name = "DIR")]
+    roots: Vec<PathBuf>,
+
+    /// Take the examples from a bundle made with `score-cli bundle` instead of the examples directory
+    #[arg(long, global = true, value_name = "FILE", conflicts_with = "roots")]
+    from_bundle: Option<PathBuf>,
+
+    /// Only run examples from this git revision of the examples directory (see --fetch)
+    #[arg(long, global = true, value_name = "REV")]
+    tree_rev: Option<String>,
+
+    /// With --tree-rev, fetch the revision if needed and run the examples from a worktree checked out at it
+    #[arg(long, global = true, requires = "tree_rev")]
+    fetch: bool,
+
+    /// End another session holding the session lock of this host instead of asking what to do
+    #[arg(long, global = true)]
+    force: bool,
+
+    /// Serve privileged setup requests of a runner on stdin (started via --privileged-helper)
+    #[arg(long, hide = true)]
+    helper: bool,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Run examples non-interactively
+    Run {
+        /// Examples to run (names, comma-separated or repeated, or "all")
+        #[arg(required = true, value_delimiter = ',')]
+        examples: Vec<String>,
+
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Compose a throwaway example from --app flags and run it
+    Adhoc {
+        /// App as comma separated fields, e.g. "name=gen,path=./generator,args=--rate 100" (repeatable)
+        #[arg(long = "app", required = true, value_name = "KEY=VALUE,...")]
+        apps: Vec<String>,
+
+        /// Name of the example
+        #[arg(long, default_value = "adhoc")]
+        name: String,
+
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Repeat an example to find flaky behavior
+    Stress {
+        /// Example to repeat
+        example: String,
+
+        /// Number of iterations
+        #[arg(long, default_value_t = 100)]
+        iterations: u32,
+
+        /// Stop at the first failing iteration
+        #[arg(long)]
+        until_failure: bool,
+
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Measure an example and compare its metrics with a saved baseline
+    Bench {
+        /// Example to measure
+        example: String,
+
+        /// Number of iterations, the median of each metric counts
+        #[arg(long, default_value_t = 5)]
+        iterations: u32,
+
+        /// Save the metrics as this baseline for this host
+        #[arg(long, value_name = "NAME")]
+        save_baseline: Option<String>,
+
+        /// Compare the metrics with this baseline and fail on regressions
+        #[arg(long, value_name = "NAME")]
+        baseline: Option<String>,
+
+        /// Largest allowed regression of a metric against the baseline (e.g. 5%)
+        #[arg(long, value_parser = bench::parse_percent, default_value = "5%")]
+        max_regression: f64,
+
+        /// Save the baseline of --save-baseline even if metrics regressed against --baseline
+        #[arg(long, requires = "save_baseline")]
+        force: bool,
+
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Cycle through a playlist of examples for unattended exhibitions, until the stop file appears
+    Kiosk {
+        /// JSON file listing the examples and how long each one is shown
+        #[arg(long)]
+        playlist: PathBuf,
+
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Keep an example running for a long time and watch its resource usage
+    Soak {
+        /// Example to soak
+        example: String,
+
+        /// How long to keep the example running (e.g. 90s, 30m, 12h, 2d)
+        #[arg(long, value_parser = parse_duration)]
+        duration: Duration,
+
+        /// Time between resource snapshots
+        #[arg(long, value_parser = parse_duration, default_value = "5m")]
+        interval: Duration,
+
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Generate a catalog of all examples for the documentation
+    Catalog {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = catalog::Format::Markdown)]
+        format: catalog::Format,
+
+        /// Write the catalog to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Render the start order of an example's apps as a graph
+    Graph {
+        /// Example to render
+        example: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = graph::Format::Dot)]
+        format: graph::Format,
+    },
+    /// Map requirements to the examples verifying them and their results in recorded runs
+    CoverageMatrix {
+        /// Run directories or run ids under $TMPDIR/score-cli-<uid>/runs, or "last"
+        #[arg(default_value = "last")]
+        runs: Vec<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = traceability::Format::Markdown)]
+        format: traceability::Format,
+
+        /// Write the matrix to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Inspect the configs of the examples as loaded
+    Config {
+        #[command(subcommand)]
+        action: explain::Action,
+    },
+    /// Create or verify the checksums of the configs and binaries of the installation
+    Manifest {
+        #[command(subcommand)]
+        action: manifest::Action,
+    },
+    /// Smoke-check the installed SCORE stack without the showcases
+    Selftest {
+        #[command(subcommand)]
+        test: selftest::Test,
+    },
+    /// Serve a run queue executing the examples that clients enqueue, by priority
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = serve::DEFAULT_ADDRESS)]
+        listen: String,
+
+        /// File holding the token clients authenticate with (default: $SCORE_CLI_SERVE_TOKEN, or a new one)
+        #[arg(long, value_name = "FILE")]
+        token_file: Option<PathBuf>,
+
+        #[command(flatten)]
+        options: RunOptions,
+    },
+    /// Queue examples on a run queue started with `serve` and follow their results
+    Enqueue {
+        /// Examples to queue (names, comma-separated or repeated, or "all")
+        #[arg(required_unless_present = "list", value_delimiter = ',')]
+        examples: Vec<String>,
+
+        /// Address of the run queue
+        #[arg(long, default_value = serve::DEFAULT_ADDRESS)]
+        server: String,
+
+        /// Examples with a higher priority run first
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        priority: i32,
+
+        /// Name shown for the examples in the queue
+        #[arg(long, default_value = "cli")]
+        client: String,
+
+        /// Only print the queue
+        #[arg(long)]
+        list: bool,
+
+        /// Add the app in this JSON file to the running job, given by its id or example, instead of queueing it
+        #[arg(long, value_name = "FILE", conflicts_with = "list")]
+        add_app: Option<PathBuf>,
+
+        /// File holding the token of the run queue (default: $SCORE_CLI_SERVE_TOKEN, or that of a local server)
+        #[arg(long, value_name = "FILE")]
+        token_file: Option<PathBuf>,
+    },
+    /// Pack examples with their binaries, libraries and data into one file for offline use
+    Bundle {
+        /// Examples to bundle (names, comma-separated or repeated, or "all")
+        #[arg(required = true, value_delimiter = ',')]
+        examples: Vec<String>,
+
+        /// Bundle file to write, a zstd compressed tar archive
+        #[arg(long, short, default_value = "bundle.tar.zst")]
+        output: PathBuf,
+    },
+    /// Check the signature of a run report
+    VerifyReport {
+        /// Report file, run directory, run id under $TMPDIR/score-cli-<uid>/runs, or "last"
+        #[arg(default_value = "last")]
+        report: String,
+
+        /// Public key (PEM) matching the key the report was signed with
+        #[arg(long)]
+        key: PathBuf,
+
+        /// Detached signature (default: the report path with ".sig" appended)
+        #[arg(long)]
+        signature: Option<PathBuf>,
+    },
+    /// Update the CLI from a release channel after checking its signature
+    SelfUpdate {
+        /// Release channel, a directory below the release URL
+        #[arg(long, default_value = "stable")]
+        channel: String,
+
+        /// Release URL (default: $SCORE_CLI_UPDATE_URL)
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Public key (PEM) the releases are signed with (default: $SCORE_CLI_UPDATE_KEY)
+        #[arg(long)]
+        key: Option<PathBuf>,
+
+        /// Also replace the examples by the showcases.tar.gz of the channel
+        #[arg(long)]
+        sync_configs: bool,
+
+        /// Only tell whether an update is available
+        #[arg(long)]
+        check: bool,
+    },
+    /// Check the host prerequisites of the examples and print remediation hints
+    Doctor,
+    /// Choose the examples directory, preset, log directory, UI and backend all invocations use
+    Init,
+    /// Show durations, outcomes and flake rates of examples in past runs
+    History {
+        /// Only list the N examples with the longest median duration
+        #[arg(long, value_name = "N")]
+        slowest: Option<usize>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = history::Format::Text)]
+        format: history::Format,
+    },
+    /// Format *.score.json files canonically
+    Fmt {
+        /// Files or directories to format (default: the examples directory)
+        paths: Vec<PathBuf>,
+
+        /// Only list files that are not formatted and fail if there are any
+        #[arg(long)]
+        check: bool,
+    },
+    /// Serve the language server protocol on stdin / stdout for editing *.score.json files
+    Lsp,
+    /// Run a built-in app (started by the runner for apps with a built-in `type`)
+    #[command(hide = true)]
+    Builtin {
+        #[command(subcommand)]
+        app: builtin::Builtin,
+    },
+    /// Show the logs of a recorded run as one merged, chronologically sorted timeline
+    Timeline {
+        /// Run directory, run id under $TMPDIR/score-cli-<uid>/runs, or "last"
+        #[arg(default_value = "last")]
+        run: String,
+
+        /// Only show these sources (app index like "2" or "app2", or "runner"; repeatable)
+        #[arg(long)]
+        app: Vec<String>,
+
+        /// Only show lines matching this regular expression (prefix with "(?i)" to ignore case)
+        #[arg(long)]
+        grep: Option<String>,
+
+        /// Only show lines from this time into the example on (HH:MM:SS, MM:SS or e.g. 90s)
+        #[arg(long, value_parser = timeline::parse_offset, value_name = "TIME")]
+        since: Option<Duration>,
+
+        /// Only show lines up to this time into the example
+        #[arg(long, value_parser = timeline::parse_offset, value_name = "TIME")]
+        until: Option<Duration>,
+
+        /// Do not color lines by app
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Remove and compress old runs under $TMPDIR/score-cli-<uid>/runs to free disk space
+    Gc {
+        /// Keep only this many of the newest runs
+        #[arg(long, value_name = "N")]
+        keep_runs: Option<usize>,
+
+        /// Remove runs older than this many days
+        #[arg(long, value_name = "DAYS")]
+        keep_days: Option<u64>,
+
+        /// Compress the logs of runs older than this with zstd, or "never"
+        #[arg(long, value_name = "DURATION", default_value = "1d")]
+        compress_after: String,
+
+        /// Directory holding the runs
+        #[arg(long, value_name = "DIR")]
+        runs_dir: Option<PathBuf>,
+
+        /// Only print what would be removed and compressed
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print the app logs of a recorded or running run, as the runner showed them
+    Logs {
+        /// Run directory, run id under $TMPDIR/score-cli-<uid>/runs, or "last"
+        #[arg(default_value = "last")]
+        run: String,
+
+        /// Only show these apps (index like "2", "app2", program name, or "runner" for runner events; repeatable)
+        #[arg(long)]
+        app: Vec<String>,
+
+        /// Keep printing lines as the run goes on, until it wrote its report
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Stop the apps of runs whose CLI crashed and write their reports from the checkpoint
+    Recover {
+        /// Run directories or run ids under $TMPDIR/score-cli-<uid>/runs (default: all runs left with a checkpoint)
+        runs: Vec<String>,
+    },
+    /// Compare two recorded runs: results, exit codes, durations, memory and new error lines
+    Diff {
+        /// First run: run directory, run id under $TMPDIR/score-cli-<uid>/runs, or "last"
+        run_a: String,
+
+        /// Second run: run directory, run id under $TMPDIR/score-cli-<uid>/runs, or "last"
+        #[arg(default_value = "last")]
+        run_b: String,
+
+        /// Report durations and peak memory changed by more than this many percent
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = diff::Format::Text)]
+        format: diff::Format,
+    },
+}
+
+fn print_banner() {
+    let (color_code, reset_code) = if std::env::var_os("NO_COLOR").is_some() {
+        ("", "")
+    } else {
+        ("\x1b[38;5;99m", "\x1b[0m")
+    };
+
+    let banner = r#"
+   ███████╗       ██████╗ ██████╗ ██████╗ ███████╗
+   ██╔════╝      ██╔════╝██╔═══██╗██╔══██╗██╔════╝
+   ███████╗█████╗██║     ██║   ██║██████╔╝█████╗  
+   ╚════██║╚════╝██║     ██║   ██║██╔══██╗██╔══╝  
+   ███████║      ╚██████╗╚██████╔╝██║  ██║███████╗
+   ╚══════╝       ╚═════╝ ╚═════╝ ╚═╝  ╚═╝╚══════╝
+"#;
+
+    println!("{}{}{}", color_code, banner, reset_code);
+}
+
+fn pause_for_enter() -> Result<()> {
+    let result = confirm("Do you want to select examples to run?")
+        .initial_value(true)
+        .interact()?;
+    if !result {
+        outro("Falling back to the console. Goodbye!")?;
+        std::process::exit(0);
+    }
+    Ok(())
+}
+
+/// Entry point of `score-cli`.
+pub fn main() {
+    // Settings that could not be loaded leave the codes of a CLI without settings.
+    let mut codes = exit_codes::ExitCodes::default();
+    if let Err(e) = run(&mut codes) {
+        eprintln!("Error: {:?}", e);
+        std::process::exit(codes.infra_error);
+    }
+}
+
+fn run(codes: &mut exit_codes::ExitCodes) -> Result<()> {
+    // Settings files are the defaults, the command line and environment override them.
+    let loaded = settings::Settings::load().and_then(|mut settings| {
+        settings.apply_env()?;
+        let command = settings.apply_defaults(Args::command())?;
+        Ok((settings, command))
+    });
+    // Broken settings only fail commands that use them, help and init still work.
+    let (settings, command, settings_error) = match loaded {
+        Ok((settings, command)) => {
+            *codes = exit_codes::ExitCodes::from_settings(&settings);
+            (settings, command, None)
+        }
+        Err(e) => (settings::Settings::default(), Args::command(), Some(e)),
+    };
+    let matches = command.try_get_matches().unwrap_or_else(|e| {
+        if let Some(ref error) = settings_error {
+            eprintln!("⚠️  Settings not applied: {:#}", error);
+        }
+        e.exit()
+    });
+    let args = Args::from_arg_matches(&matches)?;
+    if let Some(error) = settings_error {
+        if !matches!(args.command, Some(Commands::Init)) {
+            return Err(error);
+        }
+        eprintln!("⚠️  Settings not applied: {:#}", error);
+    }
+    let codes = *codes;
+
+    if args.helper {
+        return helper::serve();
+    }
+
+    if !args.roots.is_empty() {
+        // Everything resolving the examples directories, like the language server, sees the roots given.
+        std::env::set_var("SCORE_CLI_INIT_DIR", std::env::join_paths(&args.roots)?);
+    }
+
+    if let Some(ref bundle) = args.from_bundle {
+        std::env::set_var("SCORE_CLI_INIT_DIR", bundle::unpack(bundle)?);
+    }
+
+    if let Some(Commands::Timeline {
+        run,
+        app,
+        grep,
+        since,
+        until,
+        no_color,
+    }) = args.command
+    {
+        let filter = timeline::Filter {
+            apps: app,
+            grep: grep.as_deref().map(pattern::Pattern::new).transpose()?,
+            since,
+            until,
+        };
+        return timeline::show(&timeline::resolve_run(&run)?, &filter, !no_color);
+    }
+
+    if let Some(Commands::Gc {
+        keep_runs,
+        keep_days,
+        compress_after,
+        runs_dir,
+        dry_run,
+    }) = args.command
+    {
+        let policy = gc::Policy {
+            keep_runs,
+            keep_days,
+            compress_after: gc::parse_compress_after(&compress_after)
+                .map_err(|e| anyhow::anyhow!("Invalid --compress-after: {}", e))?,
+        };
+        return gc::run(&runs_dir.unwrap_or_else(timeline::runs_root), &policy, dry_run);
+    }
+
+    if let Some(Commands::Logs { run, app, follow }) = args.command {
+        return logs::show(&timeline::resolve_run(&run)?, app, follow);
+    }
+
+    if let Some(Commands::Recover { runs }) = args.command {
+        let run_dirs = runs
+            .iter()
+            .map(|run| timeline::resolve_run(run))
+            .collect::<Result<Vec<_>>>()?;
+        return checkpoint::recover(&run_dirs, &timeline::runs_root());
+    }
+
+    if let Some(Commands::Diff {
+        run_a,
+        run_b,
+        threshold,
+        format,
+    }) = args.command
+    {
+        let diff = diff::compare(
+            &timeline::resolve_run(&run_a)?,
+            &timeline::resolve_run(&run_b)?,
+            threshold,
+        )?;
+        print!("{}", diff::render(&diff, format)?);
+        return Ok(());
+    }
+
+    if let Some(Commands::Builtin { app }) = args.command {
+        return builtin::run(app);
+    }
+
+    if let Some(Commands::History { slowest, format }) = args.command {
+        print!("{}", history::render(&history::stats(slowest), format)?);
+        return Ok(());
+    }
+
+    if let Some(Commands::Lsp) = args.command {
+        return lsp::serve();
+    }
+
+    if let Some(Commands::Fmt { paths, check }) = args.command {
+        let paths = if paths.is_empty() { examples_roots() } else { paths };
+        if !fmt::run(&paths, check)? && check {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Commands::Init) = args.command {
+        return init::run();
+    }
+
+    if let Some(Commands::Doctor) = args.command {
+        if !doctor::run() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Commands::VerifyReport { report, key, signature }) = args.command {
+        let report = if Path::new(&report).is_file() {
+            PathBuf::from(report)
+        } else {
+            timeline::resolve_run(&report)?.join("report.json")
+        };
+        let signature = signature.unwrap_or_else(|| signing::signature_path(&report));
+        signing::verify(&report, &signature, &key)?;
+        println!("Signature of {} is valid", report.display());
+        return Ok(());
+    }
+
+    if let Some(Commands::SelfUpdate {
+        channel,
+        url,
+        key,
+        sync_configs,
+        check,
+    }) = args.command
+    {
+        let Some(url) = url.or_else(|| std::env::var("SCORE_CLI_UPDATE_URL").ok()) else {
+            anyhow::bail!("No release URL, pass --url or set SCORE_CLI_UPDATE_URL");
+        };
+        let Some(key) = key.or_else(|| std::env::var_os("SCORE_CLI_UPDATE_KEY").map(PathBuf::from)) else {
+            anyhow::bail!("No release key, pass --key or set SCORE_CLI_UPDATE_KEY");
+        };
+        return self_update::run(&self_update::Options {
+            url,
+            channel,
+            key,
+            sync_configs,
+            check,
+        });
+    }
+
+    if let Some(ref rev) = args.tree_rev {
+        // Only the first examples directory is pinned, later ones are local overlays.
+        let mut roots = examples_roots();
+        roots[0] = tree_rev::pin(&roots[0], rev, args.fetch)?;
+        // Everything resolving the examples directory, like the host info in reports, sees the pinned tree.
+        std::env::set_var("SCORE_CLI_INIT_DIR", std::env::join_paths(&roots)?);
+    }
+
+    if let Some(Commands::Enqueue {
+        examples,
+        server,
+        priority,
+        client,
+        list,
+        add_app,
+        token_file,
+    }) = args.command
+    {
+        let token_file = token_file.as_deref();
+        if list {
+            return serve::list(&server, token_file);
+        }
+        if let Some(file) = add_app {
+            let [job] = examples.as_slice() else {
+                anyhow::bail!("--add-app needs exactly one job or example to add the app to");
+            };
+            return serve::add_app(&server, job, &file, token_file);
+        }
+        if !serve::enqueue(&server, &examples, priority, &client, token_file)? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Commands::Selftest { test }) = args.command {
+        if !selftest::run(test, &examples_root())? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Commands::Manifest { action }) = args.command {
+        let roots = examples_roots();
+        let default = || roots[0].join(manifest::DEFAULT_NAME);
+        match action {
+            manifest::Action::Create { output, sign_key } => {
+                return manifest::create(&roots, &output.unwrap_or_else(default), sign_key.as_deref())
+            }
+            manifest::Action::Verify { manifest, key } => {
+                if !manifest::verify(&roots, &manifest.unwrap_or_else(default), key.as_deref())? {
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    if let Some(Commands::Config { action }) = args.command {
+        // Flags without a value on the command line have the default of a settings file, if any.
+        let from_settings = |matches: Option<&clap::ArgMatches>, id: &str| {
+            matches.and_then(|matches| matches.value_source(id)) == Some(clap::parser::ValueSource::DefaultValue)
+        };
+        let explain_matches = matches
+            .subcommand_matches("config")
+            .and_then(|config| config.subcommand_matches("explain"));
+        let given = explain::Given {
+            preset: args.preset.as_deref(),
+            preset_from_settings: from_settings(Some(&matches), "preset"),
+            params_from_settings: from_settings(explain_matches, "params"),
+            settings: &settings,
+        };
+        return explain::run(action, &examples_roots(), &given);
+    }
+
+    if let Some(Commands::Bundle { examples, output }) = args.command {
+        return bundle::create(&examples_roots(), &examples, &output);
+    }
+
+    // Commands running examples end on a shared path, taking the --on-complete actions however they ended.
+    let on_complete = on_complete(&args).to_vec();
+    match run_examples_command(args) {
+        Ok(None) => Ok(()),
+        Ok(Some(ended)) => {
+            completion::notify(&on_complete, Ok(&ended));
+            exit_with(ended.exit_code(&codes));
+            Ok(())
+        }
+        Err(e) => {
+            completion::notify(&on_complete, Err(&e));
+            Err(e)
+        }
+    }
+}
+
+/// Runs the examples of the command, returning how it ended, or `None` if it ran none.
+fn run_examples_command(args: Args) -> Result<Option<Ended>> {
+    if let Some(Commands::Adhoc { apps, name, options }) = args.command {
+        let config = adhoc::config(&name, &apps)?;
+        if options.dry_run {
+            crate::dry_run(&[config], &[0], &options)?;
+            return Ok(None);
+        }
+        if !session::acquire(args.force)? {
+            return Ok(None);
+        }
+        start_helper(&options)?;
+        stop_on_interrupt()?;
+        let report = run_examples(&[config], &[0], &options)?;
+        return Ok(Some(Ended::Run(Box::new(report))));
+    }
+
+    let mut configs = load_examples(&examples_roots(), args.preset.as_deref())?;
+    if runs_examples(&args.command) && !session::acquire(args.force)? {
+        return Ok(None);
+    }
+
+    let (requested, mut options) = match args.command {
+        Some(Commands::Run { examples, options }) => (Some(examples), options),
+        Some(Commands::Stress {
+            example,
+            iterations,
+            until_failure,
+            options,
+        }) => {
+            let index = select_one(&configs, &example, "Stress", options.prefixes())?;
+            parameters::resolve(
+                &mut configs,
+                &[index],
+                &parameters::parse_given(&options.params)?,
+                false,
+            )?;
+            if options.dry_run {
+                crate::dry_run(&configs, &[index], &options)?;
+                return Ok(None);
+            }
+            start_helper(&options)?;
+            stop_on_interrupt()?;
+            let failures = stress::run(&configs[index], iterations, until_failure, &options)?;
+            return Ok(Some(Ended::Mode(failures == 0)));
+        }
+        Some(Commands::Bench {
+            example,
+            iterations,
+            save_baseline,
+            baseline,
+            max_regression,
+            force,
+            options,
+        }) => {
+            let index = select_one(&configs, &example, "Bench", options.prefixes())?;
+            parameters::resolve(
+                &mut configs,
+                &[index],
+                &parameters::parse_given(&options.params)?,
+                false,
+            )?;
+            if options.dry_run {
+                crate::dry_run(&configs, &[index], &options)?;
+                return Ok(None);
+            }
+            start_helper(&options)?;
+            stop_on_interrupt()?;
+            let bench = bench::BenchOptions {
+                iterations,
+                save_baseline,
+                baseline,
+                max_regression,
+                force,
+            };
+            let passed = bench::run(&configs[index], &bench, &options)?;
+            return Ok(Some(Ended::Mode(passed)));
+        }
+        Some(Commands::Soak {
+            example,
+            duration,
+            interval,
+            options,
+        }) => {
+            let index = select_one(&configs, &example, "Soak", options.prefixes())?;
+            parameters::resolve(
+                &mut configs,
+                &[index],
+                &parameters::parse_given(&options.params)?,
+                false,
+            )?;
+            if options.dry_run {
+                crate::dry_run(&configs, &[index], &options)?;
+                return Ok(None);
+            }
+            start_helper(&options)?;
+            stop_on_interrupt()?;
+            let passed = soak::run(&configs[index], duration, interval, &options)?;
+            return Ok(Some(Ended::Mode(passed)));
+        }
+        Some(Commands::Serve {
+            listen,
+            token_file,
+            options,
+        }) => {
+            if options.dry_run {
+                anyhow::bail!("--dry-run cannot be used with serve, use it with run");
+            }
+            let all: Vec<usize> = (0..configs.len()).collect();
+            parameters::resolve(&mut configs, &all, &parameters::parse_given(&options.params)?, false)?;
+            start_helper(&options)?;
+            serve::run(&configs, &listen, &options, token_file.as_deref())?;
+            return Ok(None);
+        }
+        Some(Commands::Kiosk { playlist, options }) => {
+            if options.dry_run {
+                anyhow::bail!("--dry-run cannot be used with kiosk, use it with run");
+            }
+            start_helper(&options)?;
+            stop_on_interrupt()?;
+            kiosk::run(&mut configs, &playlist, &options)?;
+            return Ok(Some(Ended::Mode(true)));
+        }
+        Some(Commands::Catalog { format, output }) => {
+            let catalog = catalog::render(&configs, format);
+            match output {
+                Some(path) => {
+                    fs::write(&path, catalog).with_context(|| format!("Failed to write catalog {:?}", path))?
+                }
+                None => print!("{}", catalog),
+            }
+            return Ok(None);
+        }
+        Some(Commands::Graph { example, format }) => {
+            let index = find_example(&configs, &example)?;
+            print!("{}", graph::render(&configs[index], format));
+            return Ok(None);
+        }
+        Some(Commands::CoverageMatrix { runs, format, output }) => {
+            let run_dirs = runs
+                .iter()
+                .map(|run| timeline::resolve_run(run))
+                .collect::<Result<Vec<_>>>()?;
+            let run_dirs: Vec<&Path> = run_dirs.iter().map(PathBuf::as_path).collect();
+            let matrix = traceability::render(&traceability::matrix(&configs, &run_dirs)?, format)?;
+            match output {
+                Some(path) => fs::write(&path, matrix).with_context(|| format!("Failed to write matrix {:?}", path))?,
+                None => print!("{}", matrix),
+            }
+            return Ok(None);
+        }
+        Some(
+            Commands::Adhoc { .. }
+            | Commands::Timeline { .. }
+            | Commands::Logs { .. }
+            | Commands::Recover { .. }
+            | Commands::Gc { .. }
+            | Commands::Diff { .. }
+            | Commands::VerifyReport { .. }
+            | Commands::SelfUpdate { .. }
+            | Commands::Bundle { .. }
+            | Commands::Manifest { .. }
+            | Commands::Config { .. }
+            | Commands::Selftest { .. }
+            | Commands::Enqueue { .. }
+            | Commands::Doctor
+            | Commands::Init
+            | Commands::History { .. }
+            | Commands::Fmt { .. }
+            | Commands::Lsp
+            | Commands::Builtin { .. },
+        ) => {
+            unreachable!("handled before loading configs")
+        }
+        None => (
+            args.examples
+                .map(|examples_str| examples_str.split(',').map(|s| s.trim().to_string()).collect()),
+            args.run,
+        ),
+    };
+
+    let given = parameters::parse_given(&options.params)?;
+    let interactive = requested.is_none();
+    let selected = if let Some(requested) = requested {
+        // Non-interactive mode: use provided examples
+        select_examples(&configs, &requested, options.ignore_missing, options.prefixes())?
+    } else {
+        // Interactive mode
+        print_banner();
+        intro("WELCOME TO SHOWCASE ENTRYPOINT")?;
+        pause_for_enter()?;
+
+        clear_screen()?;
+
+        // Create options for multiselect
+        let options: Vec<(usize, String, String)> = configs
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, c.name.clone(), c.description.clone()))
+            .collect();
+
+        let selected: Vec<usize> =
+            multiselect("Select examples to run (use space to select (multiselect supported), enter to run examples):")
+                .items(&options)
+                .interact()?;
+
+        if selected.is_empty() {
+            outro("No examples selected. Goodbye!")?;
+            return Ok(None);
+        }
+
+        selected
+    };
+
+    parameters::resolve(&mut configs, &selected, &given, interactive)?;
+    if options.dry_run {
+        crate::dry_run(&configs, &selected, &options)?;
+        return Ok(None);
+    }
+    options.interactive = interactive;
+    start_helper(&options)?;
+    stop_on_interrupt()?;
+    let report = run_examples(&configs, &selected, &options)?;
+
+    outro("All done!")?;
+    Ok(Some(Ended::Run(Box::new(report))))
+}
+
+/// Ends the CLI with `code`, unless it is 0 and the CLI returns normally.
+fn exit_with(code: i32) {
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
+
+/// The `--on-complete` actions of the command, taken when it ends.
+///
+/// `serve` takes its actions after each job instead.
+fn on_complete(args: &Args) -> &[completion::Action] {
+    match &args.command {
+        None => &args.run.on_complete,
+        Some(
+            Commands::Run { options, .. }
+            | Commands::Adhoc { options, .. }
+            | Commands::Stress { options, .. }
+            | Commands::Bench { options, .. }
+            | Commands::Soak { options, .. }
+            | Commands::Kiosk { options, .. },
+        ) => &options.on_complete,
+        Some(_) => &[],
+    }
+}
+
+/// Whether `command` runs examples, so it takes the session lock.
+fn runs_examples(command: &Option<Commands>) -> bool {
+    matches!(
+        command,
+        None | Some(
+            Commands::Run { .. }
+                | Commands::Stress { .. }
+                | Commands::Bench { .. }
+                | Commands::Soak { .. }
+                | Commands::Kiosk { .. }
+        )
+    )
+}
+
+/// The single example selected by `example`, for modes running one example.
+fn select_one(configs: &[crate::ScoreConfig], example: &str, mode: &str, prefixes: bool) -> Result<usize> {
+    match select_examples(configs, &[example.to_string()], false, prefixes)?[..] {
+        [index] => Ok(index),
+        ref selected => anyhow::bail!(
+            "{} mode runs a single example, \"{}\" selects {}",
+            mode,
+            example,
+            selected.len()
+        ),
+    }
+}
@@ -0,0 +1,162 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Record of the exact command, working directory and environment an app was spawned with.
+//!
+//! Written as `app<N>.command.json` next to the app's log, with sorted keys, so
+//! the records of two machines or runs can be compared with a plain `diff`.
+//! Secrets are masked like everywhere else.
+//!
+//! Variables set by the example are recorded with their values. Of those the
+//! app inherits from the runner, only the ones in [`RECORDED_ENV`] are; the
+//! others, like the tokens of a CI job, are listed with a masked value.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::redact::MASK;
+
+/// Inherited variables recorded with their values, as they explain how an app behaves.
+/// A trailing `*` matches any suffix.
+const RECORDED_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "SHELL",
+    "TERM",
+    "LANG",
+    "LANGUAGE",
+    "LC_*",
+    "TZ",
+    "TMPDIR",
+    "PWD",
+    "LD_LIBRARY_PATH",
+    "LD_PRELOAD",
+    "DISPLAY",
+    "WAYLAND_DISPLAY",
+    "XDG_RUNTIME_DIR",
+    "XDG_SESSION_TYPE",
+    "PROFILE",
+    "NO_COLOR",
+    "RUST_LOG",
+    "RUST_BACKTRACE",
+    "SCORE_*",
+];
+
+#[derive(Debug, Serialize)]
+pub struct CommandRecord {
+    pub program: String,
+    pub resolved_program: Option<PathBuf>, // the file executed, after looking up the program in PATH
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    pub env: BTreeMap<String, String>, // the environment of the app, inherited values outside RECORDED_ENV masked
+}
+
+impl CommandRecord {
+    /// Describes `cmd` as it is spawned. `inherit_env` tells whether it starts from the runner's environment.
+    pub fn of(cmd: &Command, inherit_env: bool, redact: impl Fn(&str) -> String) -> CommandRecord {
+        let mut env: BTreeMap<String, String> = if inherit_env {
+            env::vars()
+                .map(|(key, value)| {
+                    let value = if recorded(&key) { value } else { MASK.to_string() };
+                    (key, value)
+                })
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        for (key, value) in cmd.get_envs() {
+            let key = key.to_string_lossy().into_owned();
+            match value {
+                Some(value) => env.insert(key, value.to_string_lossy().into_owned()),
+                None => env.remove(&key),
+            };
+        }
+        let program = cmd.get_program().to_string_lossy().into_owned();
+        let cwd = cmd
+            .get_current_dir()
+            .map(Path::to_path_buf)
+            .or_else(|| env::current_dir().ok())
+            .unwrap_or_default();
+        CommandRecord {
+            resolved_program: resolve(&program, &cwd, path_of(cmd, inherit_env).as_deref()),
+            program: redact(&program),
+            args: cmd.get_args().map(|arg| redact(&arg.to_string_lossy())).collect(),
+            cwd,
+            env: env.into_iter().map(|(key, value)| (key, redact(&value))).collect(),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)? + "\n";
+        fs::write(path, content).with_context(|| format!("Failed to write command record {:?}", path))
+    }
+}
+
+/// `cmd` as a shell command line, preceded by the variables it sets.
+///
+/// Every value is redacted before it is quoted, so escaping cannot keep a secret from being masked.
+pub fn command_line(cmd: &Command, redact: impl Fn(&str) -> String) -> String {
+    let mut words: Vec<String> = cmd
+        .get_envs()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .map(|(key, value)| format!("{}={}", key.to_string_lossy(), quote(&redact(&value.to_string_lossy()))))
+        .collect();
+    words.push(quote(&redact(&cmd.get_program().to_string_lossy())));
+    words.extend(cmd.get_args().map(|arg| quote(&redact(&arg.to_string_lossy()))));
+    words.join(" ")
+}
+
+/// `word` quoted for a POSIX shell where needed.
+pub fn quote(word: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !word.is_empty() && word.chars().all(plain) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// Whether the inherited variable `key` is recorded with its value.
+fn recorded(key: &str) -> bool {
+    RECORDED_ENV.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => key.starts_with(prefix),
+        None => key == *pattern,
+    })
+}
+
+/// The `PATH` of the app started by `cmd`: set for it, or inherited from the runner.
+fn path_of(cmd: &Command, inherit_env: bool) -> Option<std::ffi::OsString> {
+    match cmd.get_envs().find(|(key, _)| *key == OsStr::new("PATH")) {
+        Some((_, value)) => value.map(OsStr::to_os_string),
+        None if inherit_env => env::var_os("PATH"),
+        None => None,
+    }
+}
+
+/// The file run for `program`: looked up in the app's `PATH` like `Command` does, or relative to `cwd`.
+fn resolve(program: &str, cwd: &Path, path: Option<&OsStr>) -> Option<PathBuf> {
+    let path = if program.contains('/') {
+        cwd.join(program)
+    } else {
+        env::split_paths(path?)
+            .map(|dir| dir.join(program))
+            .find(|candidate| candidate.is_file())?
+    };
+    fs::canonicalize(path).ok()
+}
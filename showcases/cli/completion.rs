@@ -0,0 +1,202 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Cues telling operators that a run finished, for long runs nobody watches.
+//!
+//! `--on-complete` takes these actions, several in the order given:
+//!
+//! - `bell`: rings the terminal bell
+//! - `flash`: flashes the terminal a few times (reverse video)
+//! - `sound` or `sound:<file>`: plays a sound with the first of `paplay`,
+//!   `pw-play` and `aplay` found (default: the freedesktop "complete" sound)
+//! - `exec:<command>`: runs the command with `sh -c`, with the result in its
+//!   environment
+//!
+//! The actions are taken however the command running the examples ended,
+//! also when it failed with an error. Commands running examples once (the
+//! default command, `run` and `adhoc`) give the command `SCORE_RUN_ID`,
+//! `SCORE_RUN_DIR`, the counts of the results (`SCORE_RUN_PASSED`,
+//! `SCORE_RUN_FAILED`, `SCORE_RUN_FLAKY`, `SCORE_RUN_XFAIL`,
+//! `SCORE_RUN_XPASS`, `SCORE_RUN_SKIPPED` and `SCORE_RUN_INFRA_ERRORS`) and
+//! `SCORE_RUN_RESULT`, the category of the run's exit code (`passed`,
+//! `failed`, `flaky`, `xpass`, `skipped_only` or `infra_error`). `stress`,
+//! `bench`, `soak` and `kiosk` give only `SCORE_RUN_RESULT`, `passed` or
+//! `failed`. A command failing with an error gives `infra_error` and the
+//! error in `SCORE_RUN_ERROR`. `serve` takes the actions after each job.
+//!
+//! Failing actions are warnings, the results of the run stay as they are.
+
+use anyhow::{bail, Context, Result};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::exit_codes::{self, ExitCodes};
+use crate::host;
+use crate::report::{RunReport, Status};
+
+const DEFAULT_SOUND: &str = "/usr/share/sounds/freedesktop/stereo/complete.oga";
+pub(crate) const SOUND_PLAYERS: &[&str] = &["paplay", "pw-play", "aplay"];
+const FLASHES: usize = 3;
+const FLASH_DURATION: Duration = Duration::from_millis(200);
+
+/// An action of `--on-complete`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Bell,
+    Flash,
+    Sound(Option<String>),
+    Exec(String),
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Bell => write!(f, "bell"),
+            Action::Flash => write!(f, "flash"),
+            Action::Sound(None) => write!(f, "sound"),
+            Action::Sound(Some(file)) => write!(f, "sound:{}", file),
+            Action::Exec(command) => write!(f, "exec:{}", command),
+        }
+    }
+}
+
+/// How a command running examples ended without an error.
+#[derive(Debug)]
+pub enum Ended {
+    Run(Box<RunReport>), // the default command, `run`, `adhoc` and the jobs of `serve`
+    Mode(bool),          // `stress`, `bench`, `soak` and `kiosk`, whether they passed
+}
+
+impl Ended {
+    /// The exit code of the CLI for this end.
+    pub fn exit_code(&self, codes: &ExitCodes) -> i32 {
+        match self {
+            Ended::Run(report) => codes.of(report),
+            Ended::Mode(true) => 0,
+            Ended::Mode(false) => codes.failed,
+        }
+    }
+}
+
+/// Parses an `--on-complete` action.
+pub fn parse_action(value: &str) -> Result<Action, String> {
+    match value.split_once(':') {
+        None if value == "bell" => Ok(Action::Bell),
+        None if value == "flash" => Ok(Action::Flash),
+        None if value == "sound" => Ok(Action::Sound(None)),
+        Some(("sound", file)) if !file.is_empty() => Ok(Action::Sound(Some(file.to_string()))),
+        Some(("exec", command)) if !command.trim().is_empty() => Ok(Action::Exec(command.to_string())),
+        _ => Err(format!(
+            "invalid action \"{}\", use bell, flash, sound, sound:<file> or exec:<command>",
+            value
+        )),
+    }
+}
+
+/// Takes the `actions` for a command that `ended`, or failed with an error.
+pub fn notify(actions: &[Action], ended: Result<&Ended, &anyhow::Error>) {
+    for action in actions {
+        if let Err(e) = take(action, ended) {
+            eprintln!("⚠️  --on-complete {} failed: {:#}", action, e);
+        }
+    }
+}
+
+/// The environment of `exec` actions.
+fn variables(ended: Result<&Ended, &anyhow::Error>) -> Vec<(&'static str, String)> {
+    match ended {
+        Ok(Ended::Run(report)) => {
+            let count = |status| report.count(status).to_string();
+            let infra_errors = report.examples.iter().filter(|e| e.infra_error).count();
+            vec![
+                ("SCORE_RUN_ID", report.run_id.clone()),
+                ("SCORE_RUN_DIR", report.run_dir.display().to_string()),
+                ("SCORE_RUN_RESULT", exit_codes::category(report).to_string()),
+                ("SCORE_RUN_PASSED", count(Status::Passed)),
+                ("SCORE_RUN_FAILED", count(Status::Failed)),
+                ("SCORE_RUN_FLAKY", count(Status::Flaky)),
+                ("SCORE_RUN_XFAIL", count(Status::Xfail)),
+                ("SCORE_RUN_XPASS", count(Status::Xpass)),
+                ("SCORE_RUN_SKIPPED", count(Status::Skipped)),
+                ("SCORE_RUN_INFRA_ERRORS", infra_errors.to_string()),
+            ]
+        }
+        Ok(Ended::Mode(passed)) => vec![(
+            "SCORE_RUN_RESULT",
+            if *passed { "passed" } else { "failed" }.to_string(),
+        )],
+        Err(e) => vec![
+            ("SCORE_RUN_RESULT", "infra_error".to_string()),
+            ("SCORE_RUN_ERROR", format!("{:#}", e)),
+        ],
+    }
+}
+
+fn take(action: &Action, ended: Result<&Ended, &anyhow::Error>) -> Result<()> {
+    match action {
+        Action::Bell => terminal(|tty| tty.write_all(b"\x07")),
+        Action::Flash => terminal(|tty| {
+            for _ in 0..FLASHES {
+                tty.write_all(b"\x1b[?5h")?;
+                tty.flush()?;
+                std::thread::sleep(FLASH_DURATION);
+                tty.write_all(b"\x1b[?5l")?;
+                tty.flush()?;
+                std::thread::sleep(FLASH_DURATION);
+            }
+            Ok(())
+        }),
+        Action::Sound(file) => {
+            let Some(player) = SOUND_PLAYERS.iter().find(|player| host::find_in_path(player).is_some()) else {
+                bail!("none of {} found in PATH", SOUND_PLAYERS.join(", "));
+            };
+            let file = file.as_deref().unwrap_or(DEFAULT_SOUND);
+            let status = Command::new(player)
+                .arg(file)
+                .stdin(Stdio::null())
+                .status()
+                .with_context(|| format!("Failed to run {}", player))?;
+            if !status.success() {
+                bail!("{} {} exited with {}", player, file, status);
+            }
+            Ok(())
+        }
+        Action::Exec(command) => {
+            let status = Command::new("sh")
+                .args(["-c", command])
+                .envs(variables(ended))
+                .stdin(Stdio::null())
+                .status()
+                .context("Failed to run sh")?;
+            if !status.success() {
+                bail!("exited with {}", status);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Writes to the controlling terminal, which stays reachable when stdout is redirected.
+fn terminal(write: impl FnOnce(&mut dyn Write) -> io::Result<()>) -> Result<()> {
+    match OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(mut tty) => write(&mut tty)?,
+        Err(_) => {
+            let mut stdout = io::stdout();
+            write(&mut stdout)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,254 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! `when` conditions of apps, evaluated against the environment of the CLI.
+//!
+//! Grammar:
+//!
+//! ```text
+//! expr       = and ("||" and)*
+//! and        = unary ("&&" unary)*
+//! unary      = "!" unary | primary
+//! primary    = "(" expr ")" | function | value (("==" | "!=") value)?
+//! function   = "env_set(" NAME ")" | "exists(" value ")"
+//! value      = "${" NAME "}" | 'string' | "string"
+//! ```
+//!
+//! A value on its own is true unless it is empty. Unset variables are empty.
+
+use anyhow::{bail, Result};
+use std::env;
+use std::path::Path;
+
+use crate::ScoreConfig;
+
+/// Removes the apps whose `when` condition is false from all examples.
+pub fn filter_apps(configs: &mut [ScoreConfig]) -> Result<()> {
+    for config in configs.iter_mut() {
+        let mut apps = Vec::with_capacity(config.apps.len());
+        for (i, app) in config.apps.drain(..).enumerate() {
+            let keep = match app.when {
+                Some(ref condition) => evaluate(condition).map_err(|e| {
+                    e.context(format!(
+                        "Example '{}', app {}: invalid condition \"{}\"",
+                        config.name,
+                        i + 1,
+                        condition
+                    ))
+                })?,
+                None => true,
+            };
+            if keep {
+                apps.push(app);
+            }
+        }
+        config.apps = apps;
+    }
+    Ok(())
+}
+
+/// Evaluates a condition with variables taken from the environment.
+pub fn evaluate(condition: &str) -> Result<bool> {
+    let mut parser = Parser {
+        tokens: tokenize(condition)?,
+        pos: 0,
+    };
+    let result = parser.expr()?;
+    if parser.pos != parser.tokens.len() {
+        bail!("unexpected {:?}", parser.tokens[parser.pos]);
+    }
+    Ok(result)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Var(String),
+    Str(String),
+    Ident(String),
+    Eq,
+    Ne,
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '$' if next == Some('{') => {
+                let Some(end) = chars[i..].iter().position(|&c| c == '}') else {
+                    bail!("unterminated ${{...}}");
+                };
+                tokens.push(Token::Var(chars[i + 2..i + end].iter().collect()));
+                i += end + 1;
+            }
+            '\'' | '"' => {
+                let Some(end) = chars[i + 1..].iter().position(|&q| q == c) else {
+                    bail!("unterminated string");
+                };
+                tokens.push(Token::Str(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            '=' if next == Some('=') => {
+                tokens.push(Token::Eq);
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Ne);
+                i += 2;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::Open);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                i += 1;
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                let len = chars[i..]
+                    .iter()
+                    .position(|c| !(c.is_alphanumeric() || *c == '_'))
+                    .unwrap_or(chars.len() - i);
+                tokens.push(Token::Ident(chars[i..i + len].iter().collect()));
+                i += len;
+            }
+            _ => bail!("unexpected character '{}'", c),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            bail!("unexpected end of condition");
+        };
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.next()? {
+            token if token == expected => Ok(()),
+            token => bail!("expected {:?}, found {:?}", expected, token),
+        }
+    }
+
+    fn expr(&mut self) -> Result<bool> {
+        let mut result = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            // Evaluate both sides, so errors on the right are reported regardless of the left.
+            let right = self.and()?;
+            result = result || right;
+        }
+        Ok(result)
+    }
+
+    fn and(&mut self) -> Result<bool> {
+        let mut result = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            let right = self.unary()?;
+            result = result && right;
+        }
+        Ok(result)
+    }
+
+    fn unary(&mut self) -> Result<bool> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(!self.unary()?);
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<bool> {
+        match self.peek() {
+            Some(Token::Open) => {
+                self.pos += 1;
+                let result = self.expr()?;
+                self.expect(Token::Close)?;
+                return Ok(result);
+            }
+            Some(Token::Ident(_)) => return self.function(),
+            _ => {}
+        }
+        let left = self.value()?;
+        match self.peek() {
+            Some(Token::Eq) => {
+                self.pos += 1;
+                Ok(left == self.value()?)
+            }
+            Some(Token::Ne) => {
+                self.pos += 1;
+                Ok(left != self.value()?)
+            }
+            _ => Ok(!left.is_empty()),
+        }
+    }
+
+    fn function(&mut self) -> Result<bool> {
+        let Token::Ident(name) = self.next()? else {
+            unreachable!("checked by the caller");
+        };
+        self.expect(Token::Open)?;
+        let result = match name.as_str() {
+            "env_set" => match self.next()? {
+                Token::Ident(var) | Token::Str(var) => env::var_os(var).is_some_and(|v| !v.is_empty()),
+                token => bail!("env_set expects a variable name, found {:?}", token),
+            },
+            "exists" => Path::new(&self.value()?).exists(),
+            _ => bail!("unknown function {}(), use env_set() or exists()", name),
+        };
+        self.expect(Token::Close)?;
+        Ok(result)
+    }
+
+    fn value(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Var(name) => Ok(env::var(name).unwrap_or_default()),
+            Token::Str(value) => Ok(value),
+            token => bail!("expected ${{VARIABLE}} or a quoted string, found {:?}", token),
+        }
+    }
+}
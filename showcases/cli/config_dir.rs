@@ -0,0 +1,176 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Paths in examples anchored to the directory of their `*.score.json` file.
+//!
+//! `${CONFIG_DIR}` in the path, working directory, arguments, environment,
+//! `env_file`, libraries and data files of an app, in the `health_monitor`
+//! reporter and its arguments, and in the paths of `channels` is replaced by that
+//! directory. Afterwards relative `path`, `dir`, `env_file` and `reporter` values
+//! are resolved against it, so example trees
+//! work wherever they are mounted. A `path` without `/` is still looked up in
+//! `PATH`, and values starting with a `{{parameter}}` are taken as they are once
+//! the parameter is substituted.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_DIR: &str = "${CONFIG_DIR}";
+
+/// Anchors the paths of the apps of `example`, read from a file in `dir`, and merges their `env_file` into their `env`.
+pub fn anchor(example: &mut Value, dir: &Path) -> Result<()> {
+    let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let dir_str = dir.to_string_lossy().into_owned();
+    let expand = |text: &mut String| {
+        if text.contains(CONFIG_DIR) {
+            *text = text.replace(CONFIG_DIR, &dir_str);
+        }
+    };
+    let resolve = |text: &mut String| {
+        if !text.is_empty() && !text.starts_with("{{") && Path::new(text.as_str()).is_relative() {
+            // Collecting the components drops "." entries like in "./bin/app".
+            let path: PathBuf = dir.join(text.as_str()).components().collect();
+            *text = path.to_string_lossy().into_owned();
+        }
+    };
+    let name = example["name"].as_str().unwrap_or_default().to_string();
+    if let Some(health_monitor) = example.get_mut("health_monitor").and_then(Value::as_object_mut) {
+        if let Some(Value::String(reporter)) = health_monitor.get_mut("reporter") {
+            expand(reporter);
+            if reporter.contains('/') {
+                resolve(reporter);
+            }
+        }
+        let args = health_monitor
+            .get_mut("args")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten();
+        for value in args {
+            if let Value::String(text) = value {
+                expand(text);
+            }
+        }
+        let env = health_monitor
+            .get_mut("env")
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flat_map(|env| env.values_mut());
+        for value in env {
+            if let Value::String(text) = value {
+                expand(text);
+            }
+        }
+    }
+    let channels = example
+        .get_mut("channels")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten();
+    for channel in channels {
+        if let Some(Value::String(path)) = channel.get_mut("path") {
+            expand(path);
+            resolve(path);
+        }
+    }
+    let Some(apps) = example.get_mut("apps").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+    // Values of the wrong type are left alone, parsing the example reports them.
+    for app in apps.iter_mut().filter_map(Value::as_object_mut) {
+        if let Some(Value::String(path)) = app.get_mut("path") {
+            expand(path);
+            if path.contains('/') {
+                resolve(path);
+            }
+        }
+        for key in ["dir", "env_file"] {
+            if let Some(Value::String(text)) = app.get_mut(key) {
+                expand(text);
+                resolve(text);
+            }
+        }
+        if let Some(Value::String(path)) = app.get_mut("headless").and_then(|headless| headless.get_mut("path")) {
+            expand(path);
+            if path.contains('/') {
+                resolve(path);
+            }
+        }
+        if let Some(Value::String(file)) = app.get_mut("stdin").and_then(|stdin| stdin.get_mut("file")) {
+            expand(file);
+            resolve(file);
+        }
+        for key in ["serve_dir", "root", "recording", "asan_path"] {
+            if let Some(Value::String(text)) = app.get_mut(key) {
+                expand(text);
+            }
+        }
+        for key in ["args", "preload", "library_path"] {
+            let values = app.get_mut(key).and_then(Value::as_array_mut).into_iter().flatten();
+            for value in values {
+                if let Value::String(text) = value {
+                    expand(text);
+                }
+            }
+        }
+        let env = app
+            .get_mut("env")
+            .and_then(Value::as_object_mut)
+            .into_iter()
+            .flat_map(|env| env.values_mut());
+        for value in env {
+            if let Value::String(text) = value {
+                expand(text);
+            }
+        }
+        if let Some(env_file) = app.get("env_file").and_then(Value::as_str).map(str::to_string) {
+            let variables = load_env_file(Path::new(&env_file))
+                .with_context(|| format!("Example '{}': invalid env_file {}", name, env_file))?;
+            if let Some(env) = app
+                .entry("env")
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+            {
+                for (key, value) in variables {
+                    env.entry(key).or_insert(Value::String(value));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads `KEY=VALUE` lines, skipping empty lines and `#` comments. Values may be quoted.
+pub(crate) fn load_env_file(path: &Path) -> Result<HashMap<String, String>> {
+    let content = fs::read_to_string(path)?;
+    let mut variables = HashMap::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("line {}: expected KEY=VALUE", number + 1);
+        };
+        let value = value.trim();
+        let value = [('"', '"'), ('\'', '\'')]
+            .iter()
+            .find_map(|&(open, close)| value.strip_prefix(open)?.strip_suffix(close))
+            .unwrap_or(value);
+        variables.insert(key.trim().to_string(), value.to_string());
+    }
+    Ok(variables)
+}
@@ -0,0 +1,184 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Key commands controlling a running example in interactive mode.
+//!
+//! Presenters select an app with its number and restart it, pause and resume
+//! it, hide its output or stop the whole example, without a second terminal.
+//! The terminal is switched to unbuffered input (`stty`) while an example runs,
+//! and restored when it ends or the runner is interrupted.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, IsTerminal};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::output::Capture;
+use crate::{supervisor, sys};
+
+pub const HELP: &str = "Keys: 1-9 select app, r restart, s pause/resume, l hide/show output, k stop example, h help";
+
+/// How often the key reader checks whether it should stop.
+const READ_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Requests the runner carries out, as they need its bookkeeping of apps.
+pub enum Action {
+    Restart(usize),
+    StopExample,
+}
+
+pub struct Controls {
+    keys: Receiver<u8>,
+    reader: Option<JoinHandle<()>>, // thread reading keys from stdin
+    stop_reading: Arc<AtomicBool>,
+    selected: usize,
+    paused: BTreeMap<usize, u32>, // app index -> pid
+}
+
+impl Controls {
+    /// Starts reading keys, `None` if stdin is no terminal.
+    pub fn start() -> Option<Controls> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        sys::save_terminal(!supervisor::stops_on_interrupt()).ok()?;
+        if stty(&["-icanon", "-echo", "min", "1"]).is_none() {
+            sys::restore_terminal();
+            return None;
+        }
+        // Keys typed between examples are not meant for this one.
+        sys::discard_terminal_input();
+        let (sender, keys) = mpsc::channel();
+        let stop_reading = Arc::new(AtomicBool::new(false));
+        let stop = stop_reading.clone();
+        let reader = std::thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                match sys::wait_readable(&[0], READ_INTERVAL) {
+                    Ok(ready) if ready.is_empty() => continue,
+                    Ok(_) => {}
+                    Err(_) => return,
+                }
+                let mut stdin = std::io::stdin().lock();
+                let typed = match stdin.fill_buf() {
+                    Ok([]) | Err(_) => return,
+                    Ok(typed) => typed.to_vec(),
+                };
+                stdin.consume(typed.len());
+                if typed.into_iter().any(|key| sender.send(key).is_err()) {
+                    return;
+                }
+            }
+        });
+        println!("{}", HELP);
+        Some(Controls {
+            keys,
+            reader: Some(reader),
+            stop_reading,
+            selected: 1,
+            paused: BTreeMap::new(),
+        })
+    }
+
+    /// Handles the keys typed since the last call. `running` lists the index and pid of running apps.
+    pub fn poll(&mut self, running: &[(usize, u32)], capture: &Capture) -> Vec<Action> {
+        let keys: Vec<u8> = self.keys.try_iter().collect();
+        let mut actions = Vec::new();
+        for key in keys {
+            let pid = running
+                .iter()
+                .find(|(index, _)| *index == self.selected)
+                .map(|&(_, pid)| pid);
+            match key {
+                b'1'..=b'9' => {
+                    let index = (key - b'0') as usize;
+                    if running.iter().any(|(i, _)| *i == index) {
+                        self.selected = index;
+                        println!("App {} selected", index);
+                    } else {
+                        println!("App {} is not running", index);
+                    }
+                }
+                b'r' => {
+                    self.resume(self.selected);
+                    actions.push(Action::Restart(self.selected));
+                }
+                b's' => match (self.paused.contains_key(&self.selected), pid) {
+                    (true, _) => {
+                        self.resume(self.selected);
+                        println!("App {} resumed", self.selected);
+                    }
+                    (false, Some(pid)) => match sys::send(pid, sys::Signal::STOP) {
+                        Ok(()) => {
+                            self.paused.insert(self.selected, pid);
+                            println!("App {} paused, press s again to resume", self.selected);
+                        }
+                        Err(e) => println!("App {}: failed to pause: {}", self.selected, e),
+                    },
+                    (false, None) => println!("App {} is not running", self.selected),
+                },
+                b'l' => {
+                    let hidden = capture.toggle_muted(self.selected);
+                    println!(
+                        "App {}: output {}",
+                        self.selected,
+                        if hidden { "hidden (still logged)" } else { "shown" }
+                    );
+                }
+                b'k' => {
+                    self.resume_all();
+                    actions.push(Action::StopExample);
+                }
+                b'h' | b'?' => println!("{}", HELP),
+                _ => {}
+            }
+        }
+        actions
+    }
+
+    fn resume(&mut self, index: usize) {
+        if let Some(pid) = self.paused.remove(&index) {
+            let _ = sys::send(pid, sys::Signal::CONT);
+        }
+    }
+
+    /// Resumes all paused apps, e.g. before they are stopped.
+    pub fn resume_all(&mut self) {
+        for index in self.paused.keys().copied().collect::<Vec<_>>() {
+            self.resume(index);
+        }
+    }
+}
+
+impl Drop for Controls {
+    fn drop(&mut self) {
+        self.resume_all();
+        self.stop_reading.store(true, Ordering::Relaxed);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+        sys::restore_terminal();
+    }
+}
+
+fn stty(args: &[&str]) -> Option<()> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output.status.success().then_some(())
+}
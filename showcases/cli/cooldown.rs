@@ -0,0 +1,153 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Cooldown between examples, so one example's aftermath does not fail the next.
+//!
+//! With `--cooldown <max wait>` the runner checks before every example but the
+//! first that the 1-minute load average is at most `--settle-load`, that no
+//! process matches a `--settle-process` pattern and that `/dev/shm` holds at
+//! most `--settle-shm` MiB. It continues once all checks pass, or with a warning
+//! naming what did not settle once the cooldown elapsed.
+
+use anyhow::{bail, Result};
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::pattern::Pattern;
+use crate::{host, procfs, RunOptions};
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+/// Leftover processes named in a message, the rest is counted.
+const MAX_NAMED_PROCESSES: usize = 3;
+const MAX_COMMAND_LINE_CHARS: usize = 60;
+
+/// Waits until the system settled, at most for the `--cooldown`.
+pub fn settle(options: &RunOptions) -> Result<()> {
+    let Some(cooldown) = options.cooldown else {
+        return Ok(());
+    };
+    if !procfs::SUPPORTED {
+        if !options.settle_process.is_empty() {
+            bail!("--settle-process needs Linux /proc to find processes");
+        }
+        if options.settle_load.is_some() {
+            bail!("--settle-load needs Linux /proc to read the load average");
+        }
+    }
+    let patterns = options
+        .settle_process
+        .iter()
+        .map(|pattern| Pattern::new(pattern))
+        .collect::<Result<Vec<_>>>()?;
+    let max_load = options.settle_load.unwrap_or(host::cpu_count() as f64);
+    let start = Instant::now();
+    let mut waiting = false;
+    loop {
+        let unsettled = unsettled(max_load, &patterns, options.settle_shm);
+        if unsettled.is_empty() {
+            if waiting {
+                println!("System settled after {:.1}s", start.elapsed().as_secs_f64());
+            }
+            return Ok(());
+        }
+        let left = cooldown.saturating_sub(start.elapsed());
+        if left.is_zero() {
+            println!(
+                "⚠️  System not settled after {:?}, continuing: {}",
+                cooldown,
+                unsettled.join(", ")
+            );
+            return Ok(());
+        }
+        if !waiting {
+            println!("Cooling down: {}", unsettled.join(", "));
+            waiting = true;
+        }
+        std::thread::sleep(left.min(CHECK_INTERVAL));
+    }
+}
+
+/// Descriptions of the checks not passing.
+fn unsettled(max_load: f64, patterns: &[Pattern], max_shm_mib: Option<u64>) -> Vec<String> {
+    let mut unsettled = Vec::new();
+    if let Some(load) = load_average().filter(|load| *load > max_load) {
+        unsettled.push(format!("load average {:.2} above {:.2}", load, max_load));
+    }
+    let leftovers = matching_processes(patterns);
+    if !leftovers.is_empty() {
+        let mut named = leftovers[..leftovers.len().min(MAX_NAMED_PROCESSES)].join(", ");
+        if leftovers.len() > MAX_NAMED_PROCESSES {
+            named.push_str(&format!(" and {} more", leftovers.len() - MAX_NAMED_PROCESSES));
+        }
+        unsettled.push(format!("processes left: {}", named));
+    }
+    if let Some(max) = max_shm_mib {
+        if let Some((size, available)) = host::filesystem_kb("/dev/shm") {
+            let used = size.saturating_sub(available) / 1024;
+            if used > max {
+                unsettled.push(format!("/dev/shm holds {} MiB, above {} MiB", used, max));
+            }
+        }
+    }
+    unsettled
+}
+
+/// The 1-minute load average.
+fn load_average() -> Option<f64> {
+    fs::read_to_string("/proc/loadavg")
+        .ok()?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// `<pid> (<command line>)` of the processes whose command line matches one of `patterns`.
+fn matching_processes(patterns: &[Pattern]) -> Vec<String> {
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    // The runner and the shells starting it have the patterns in their command lines.
+    let mut ancestors = vec![std::process::id()];
+    while let Some(parent) = ancestors
+        .last()
+        .and_then(|&pid| procfs::parent(pid))
+        .filter(|&pid| pid > 1)
+    {
+        ancestors.push(parent);
+    }
+    let mut found = Vec::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        if ancestors.contains(&pid) {
+            continue;
+        }
+        let Ok(cmdline) = fs::read(entry.path().join("cmdline")) else {
+            continue;
+        };
+        // Kernel threads have no command line.
+        let cmdline = String::from_utf8_lossy(&cmdline).replace('\0', " ").trim().to_string();
+        if !cmdline.is_empty() && patterns.iter().any(|pattern| pattern.is_match(&cmdline)) {
+            let mut shown: String = cmdline.chars().take(MAX_COMMAND_LINE_CHARS).collect();
+            if shown.len() < cmdline.len() {
+                shown.push('…');
+            }
+            found.push(format!("{} ({})", pid, shown));
+        }
+    }
+    found
+}
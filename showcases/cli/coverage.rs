@@ -0,0 +1,77 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Coverage profile collection for apps built with LLVM or gcov instrumentation.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment making app `index` write its coverage profiles below `example_dir/coverage`.
+pub fn env(example_dir: &Path, index: usize) -> Vec<(String, String)> {
+    let dir = example_dir.join("coverage");
+    vec![
+        (
+            "LLVM_PROFILE_FILE".to_string(),
+            dir.join(format!("app{}-%p-%m.profraw", index)).display().to_string(),
+        ),
+        (
+            "GCOV_PREFIX".to_string(),
+            dir.join(format!("app{}", index)).display().to_string(),
+        ),
+    ]
+}
+
+/// Merges all `.profraw` files below `run_dir` into `run_dir/coverage.profdata`.
+///
+/// Returns `None` if no LLVM profiles were written.
+pub fn merge(run_dir: &Path) -> Result<Option<PathBuf>> {
+    let mut profiles = Vec::new();
+    find_profiles(run_dir, &mut profiles)?;
+    if profiles.is_empty() {
+        println!("Coverage: no LLVM profiles found in {}", run_dir.display());
+        return Ok(None);
+    }
+
+    let output = run_dir.join("coverage.profdata");
+    let result = Command::new("llvm-profdata")
+        .args(["merge", "-sparse", "-o"])
+        .arg(&output)
+        .args(&profiles)
+        .status();
+    let status = match result {
+        Ok(status) => status,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            bail!("`llvm-profdata` is required to merge coverage profiles but was not found in PATH")
+        }
+        Err(e) => return Err(e).context("Failed to run `llvm-profdata`"),
+    };
+    if !status.success() {
+        bail!("`llvm-profdata merge` failed with {}", status);
+    }
+    println!("Coverage: merged {} profiles into {}", profiles.len(), output.display());
+    Ok(Some(output))
+}
+
+fn find_profiles(dir: &Path, profiles: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read directory {:?}", dir))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_profiles(&path, profiles)?;
+        } else if path.extension().is_some_and(|ext| ext == "profraw") {
+            profiles.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,269 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Replay of recorded sensor data, the `data-feed` built-in app.
+//!
+//! Recordings are CSV files (timestamp in seconds, then the payload) or MCAP
+//! files with uncompressed chunks. Each record is sent as one message to a UDP
+//! socket, a FIFO or as SOME/IP notification, keeping the recorded intervals
+//! scaled by the replay speed.
+
+use anyhow::{bail, Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::net::{SocketAddr, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const MCAP_MAGIC: &[u8] = b"\x89MCAP0\r\n";
+const MCAP_OP_MESSAGE: u8 = 0x05;
+const MCAP_OP_CHUNK: u8 = 0x06;
+const SOMEIP_MESSAGE_TYPE_NOTIFICATION: u8 = 0x02;
+
+struct Record {
+    at: Duration, // since the start of the recording
+    payload: Vec<u8>,
+}
+
+/// Where the records are sent to, given as `udp:<host>:<port>`, `fifo:<path>`
+/// or `someip:<host>:<port>:<service id>:<event id>`.
+enum Target {
+    Udp(UdpSocket, SocketAddr),
+    Fifo(File),
+    SomeIp {
+        socket: UdpSocket,
+        addr: SocketAddr,
+        service: u16,
+        event: u16,
+        session: u16,
+    },
+}
+
+pub fn replay(recording: &Path, target: &str, speed: f64) -> Result<()> {
+    check_speed(speed)?;
+    let records = load(recording)?;
+    let mut target = Target::open(target)?;
+    println!(
+        "Replaying {} records of {} at {}x",
+        records.len(),
+        recording.display(),
+        speed
+    );
+    let start = Instant::now();
+    for record in &records {
+        let due = record.at.div_f64(speed);
+        if let Some(wait) = due.checked_sub(start.elapsed()) {
+            std::thread::sleep(wait);
+        }
+        target.send(&record.payload)?;
+    }
+    println!("Replay finished after {:.3}s", start.elapsed().as_secs_f64());
+    Ok(())
+}
+
+fn load(recording: &Path) -> Result<Vec<Record>> {
+    let content = fs::read(recording).with_context(|| format!("Failed to read recording {:?}", recording))?;
+    let mut records = if content.starts_with(MCAP_MAGIC) {
+        parse_mcap(&content).with_context(|| format!("Invalid MCAP recording {:?}", recording))?
+    } else {
+        parse_csv(&String::from_utf8_lossy(&content))
+            .with_context(|| format!("Invalid CSV recording {:?}", recording))?
+    };
+    records.sort_by_key(|r| r.at);
+    // Replay starts with the first record, not at the time the recording started.
+    if let Some(first) = records.first().map(|r| r.at) {
+        for record in &mut records {
+            record.at -= first;
+        }
+    }
+    Ok(records)
+}
+
+/// Lines of `<seconds>,<payload>`; a header line and empty lines are skipped.
+fn parse_csv(content: &str) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (time, payload) = line.split_once(',').unwrap_or((line, ""));
+        let at = match time.trim().parse::<f64>() {
+            Ok(seconds) if seconds >= 0.0 => Duration::from_secs_f64(seconds),
+            _ if i == 0 => continue,
+            _ => bail!("Line {}: expected a timestamp in seconds, got {:?}", i + 1, time),
+        };
+        records.push(Record {
+            at,
+            payload: payload.as_bytes().to_vec(),
+        });
+    }
+    Ok(records)
+}
+
+/// Rejects replay speeds that are not a positive, finite factor.
+pub fn check_speed(speed: f64) -> Result<()> {
+    if !(speed.is_finite() && speed > 0.0) {
+        bail!("The replay speed must be a positive number, got {}", speed);
+    }
+    Ok(())
+}
+
+fn parse_mcap(content: &[u8]) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    parse_mcap_records(&content[MCAP_MAGIC.len()..], &mut records)?;
+    Ok(records)
+}
+
+/// Collects the messages of a sequence of MCAP records, descending into chunks.
+fn parse_mcap_records(mut data: &[u8], records: &mut Vec<Record>) -> Result<()> {
+    // The file ends with the footer's magic, too short for a record.
+    while data.len() >= 9 {
+        let opcode = data[0];
+        let end = usize::try_from(u64::from_le_bytes(data[1..9].try_into()?))
+            .ok()
+            .and_then(|len| len.checked_add(9));
+        let Some(body) = end.and_then(|end| data.get(9..end)) else {
+            bail!("Truncated record (opcode {:#04x})", opcode);
+        };
+        match opcode {
+            MCAP_OP_MESSAGE => {
+                // channel id (2), sequence (4), log time (8), publish time (8), data
+                if body.len() < 22 {
+                    bail!("Truncated message record");
+                }
+                let log_time = u64::from_le_bytes(body[6..14].try_into()?);
+                records.push(Record {
+                    at: Duration::from_nanos(log_time),
+                    payload: body[22..].to_vec(),
+                });
+            }
+            MCAP_OP_CHUNK => {
+                // start (8), end (8), uncompressed size (8), crc (4), compression, records
+                let mut rest = body.get(28..).context("Truncated chunk record")?;
+                let compression = take_mcap_string(&mut rest)?;
+                if !compression.is_empty() {
+                    bail!(
+                        "Chunks compressed with {:?} are not supported, re-record without compression",
+                        compression
+                    );
+                }
+                let len = u64::from_le_bytes(rest.get(..8).context("Truncated chunk record")?.try_into()?);
+                let records_end = usize::try_from(len).ok().and_then(|len| len.checked_add(8));
+                let chunk = records_end.and_then(|end| rest.get(8..end));
+                parse_mcap_records(chunk.context("Truncated chunk record")?, records)?;
+            }
+            _ => {}
+        }
+        data = &data[9 + body.len()..];
+    }
+    Ok(())
+}
+
+fn take_mcap_string(data: &mut &[u8]) -> Result<String> {
+    let len = u32::from_le_bytes(data.get(..4).context("Truncated string")?.try_into()?);
+    let end = usize::try_from(len).ok().and_then(|len| len.checked_add(4));
+    let text = end.and_then(|end| data.get(4..end)).context("Truncated string")?;
+    *data = &data[4 + text.len()..];
+    Ok(String::from_utf8_lossy(text).into_owned())
+}
+
+impl Target {
+    fn open(spec: &str) -> Result<Self> {
+        let invalid = || format!("Invalid target {:?}", spec);
+        let udp = |addr: &str| -> Result<(UdpSocket, SocketAddr)> {
+            let addr: SocketAddr = addr.parse().with_context(invalid)?;
+            let bind = if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+            let socket = UdpSocket::bind(bind).context("Failed to create UDP socket")?;
+            socket.set_broadcast(true)?;
+            Ok((socket, addr))
+        };
+        match spec.split_once(':') {
+            Some(("udp", addr)) => {
+                let (socket, addr) = udp(addr)?;
+                Ok(Target::Udp(socket, addr))
+            }
+            Some(("fifo", path)) => {
+                let path = PathBuf::from(path);
+                if !path.exists() {
+                    crate::sys::make_fifo(&path, 0o600).with_context(|| format!("Failed to create FIFO {:?}", path))?;
+                }
+                println!("Waiting for a reader of {}", path.display());
+                // Blocks until the consumer opened the FIFO for reading.
+                let fifo = OpenOptions::new()
+                    .write(true)
+                    .open(&path)
+                    .with_context(|| format!("Failed to open FIFO {:?}", path))?;
+                Ok(Target::Fifo(fifo))
+            }
+            Some(("someip", rest)) => {
+                let mut parts = rest.rsplitn(3, ':');
+                let (Some(event), Some(service), Some(addr)) = (parts.next(), parts.next(), parts.next()) else {
+                    bail!("{}: expected someip:<host>:<port>:<service id>:<event id>", invalid());
+                };
+                let (socket, addr) = udp(addr)?;
+                Ok(Target::SomeIp {
+                    socket,
+                    addr,
+                    service: parse_id(service).with_context(invalid)?,
+                    event: parse_id(event).with_context(invalid)?,
+                    session: 0,
+                })
+            }
+            _ => bail!("{}: expected udp:<host>:<port>, fifo:<path> or someip:...", invalid()),
+        }
+    }
+
+    fn send(&mut self, payload: &[u8]) -> Result<()> {
+        match self {
+            Target::Udp(socket, addr) => {
+                socket.send_to(payload, *addr)?;
+            }
+            Target::Fifo(fifo) => {
+                fifo.write_all(payload)?;
+                fifo.write_all(b"\n")?;
+            }
+            Target::SomeIp {
+                socket,
+                addr,
+                service,
+                event,
+                session,
+            } => {
+                // Session ids wrap from 0xFFFF to 1, 0 means "not used".
+                *session = session.checked_add(1).unwrap_or(1);
+                let length = u32::try_from(payload.len())
+                    .ok()
+                    .and_then(|len| len.checked_add(8))
+                    .context("Record too large for a SOME/IP message")?;
+                let mut message = Vec::with_capacity(16 + payload.len());
+                message.extend(service.to_be_bytes());
+                message.extend(event.to_be_bytes());
+                message.extend(length.to_be_bytes());
+                message.extend(0u16.to_be_bytes()); // client id
+                message.extend(session.to_be_bytes());
+                message.extend([1, 1, SOMEIP_MESSAGE_TYPE_NOTIFICATION, 0]); // protocol, interface, type, return code
+                message.extend(payload);
+                socket.send_to(&message, *addr)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a SOME/IP id given as decimal or hex ("0x8001") number.
+fn parse_id(text: &str) -> Result<u16> {
+    let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u16::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    parsed.with_context(|| format!("Invalid id {:?}", text))
+}
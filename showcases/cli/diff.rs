@@ -0,0 +1,364 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Comparison of two recorded runs (`score-cli diff`).
+//!
+//! Reports per example what changed from the first run to the second: results,
+//! exit codes of apps, durations and peak memory beyond a threshold, sanitizer
+//! findings, and error lines in the logs that the first run did not have.
+//! Numbers in log lines are ignored when comparing them, so pids and timestamps
+//! do not make every line new.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::report::Status;
+use crate::{gc, slug, timeline};
+
+/// New error lines listed per example at most.
+const MAX_NEW_ERRORS: usize = 10;
+/// Smaller changes of durations are noise, whatever their percentage.
+const MIN_DURATION_CHANGE_MS: u128 = 100;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// The parts of a `report.json` compared.
+#[derive(Deserialize)]
+struct RecordedRun {
+    run_id: String,
+    #[serde(default)]
+    host: serde_json::Map<String, serde_json::Value>, // missing in reports of older versions
+    examples: Vec<RecordedExample>,
+}
+
+#[derive(Deserialize)]
+struct RecordedExample {
+    name: String,
+    status: Status,
+    #[serde(default)]
+    duration_ms: u128,
+    #[serde(default)]
+    apps: Vec<RecordedApp>,
+}
+
+#[derive(Deserialize)]
+struct RecordedApp {
+    index: usize,
+    path: String,
+    exit_code: Option<i32>,
+    #[serde(default)]
+    stopped: bool,
+    #[serde(default)]
+    oom_killed: bool,
+    #[serde(default)]
+    oom_unknown: bool,
+    #[serde(default)]
+    samples: Vec<RecordedSample>,
+    instrumentation: Option<RecordedInstrumentation>,
+}
+
+#[derive(Deserialize)]
+struct RecordedSample {
+    rss_kb: u64,
+}
+
+#[derive(Deserialize)]
+struct RecordedInstrumentation {
+    errors: u64,
+    leaked_bytes: u64,
+}
+
+/// Differences between two runs.
+#[derive(Debug, Serialize)]
+pub struct RunDiff {
+    pub run_a: String,
+    pub run_b: String,
+    pub examples: Vec<ExampleDiff>, // examples with changes
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub unchanged: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub host: Vec<String>, // differences of the hosts, e.g. "kernel: 6.8.0 -> 6.11.0"
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExampleDiff {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<(Status, Status)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<(u128, u128)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub apps: Vec<AppDiff>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub new_errors: Vec<String>, // "<source>: <line>"
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppDiff {
+    pub index: usize,
+    pub path: String,
+    pub change: String, // e.g. "exit 0 -> exit 1", "peak RSS 12.0 MiB -> 20.5 MiB (+71%)"
+}
+
+impl ExampleDiff {
+    fn is_empty(&self) -> bool {
+        self.status.is_none() && self.duration_ms.is_none() && self.apps.is_empty() && self.new_errors.is_empty()
+    }
+}
+
+fn load(run_dir: &Path) -> Result<RecordedRun> {
+    let path = run_dir.join("report.json");
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed reading {:?}", path))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid run report {:?}", path))
+}
+
+/// Whether `after` differs from `before` by more than `threshold` percent.
+fn changed(before: f64, after: f64, threshold: f64) -> bool {
+    before > 0.0 && ((after - before) / before * 100.0).abs() > threshold
+}
+
+fn percent(before: f64, after: f64) -> String {
+    format!("{:+.0}%", (after - before) / before * 100.0)
+}
+
+fn exit(app: &RecordedApp) -> String {
+    match (app.stopped, app.exit_code) {
+        (true, _) => "stopped".to_string(),
+        (false, Some(code)) => format!("exit {}", code),
+        (false, None) if app.oom_killed => "OOM-killed".to_string(),
+        (false, None) if app.oom_unknown => "SIGKILL, OOM unknown".to_string(),
+        (false, None) => "killed by signal".to_string(),
+    }
+}
+
+fn peak_rss_kb(app: &RecordedApp) -> Option<u64> {
+    app.samples.iter().map(|s| s.rss_kb).max()
+}
+
+/// Compares the runs in `dir_a` and `dir_b`, reporting durations and memory changed by more than `threshold` percent.
+pub fn compare(dir_a: &Path, dir_b: &Path, threshold: f64) -> Result<RunDiff> {
+    let (a, b) = (load(dir_a)?, load(dir_b)?);
+    // Examples filtered out of a run are compared as if they were not in it.
+    let ran = |run: &RecordedRun| -> BTreeMap<String, usize> {
+        run.examples
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.status != Status::Skipped)
+            .map(|(i, e)| (e.name.clone(), i))
+            .collect()
+    };
+    let (in_a, in_b) = (ran(&a), ran(&b));
+    let mut diff = RunDiff {
+        run_a: a.run_id.clone(),
+        run_b: b.run_id.clone(),
+        examples: Vec::new(),
+        only_in_a: in_a.keys().filter(|name| !in_b.contains_key(*name)).cloned().collect(),
+        only_in_b: in_b.keys().filter(|name| !in_a.contains_key(*name)).cloned().collect(),
+        unchanged: 0,
+        host: a
+            .host
+            .iter()
+            .filter(|&(key, value)| !b.host.is_empty() && b.host.get(key).unwrap_or(&serde_json::Value::Null) != value)
+            .map(|(key, value)| {
+                let after = b.host.get(key).unwrap_or(&serde_json::Value::Null);
+                format!("{}: {} -> {}", key, value, after)
+            })
+            .collect(),
+    };
+    for (name, &index_b) in &in_b {
+        let Some(&index_a) = in_a.get(name) else {
+            continue;
+        };
+        let (ea, eb) = (&a.examples[index_a], &b.examples[index_b]);
+        let mut example = ExampleDiff {
+            name: name.clone(),
+            status: (ea.status != eb.status).then_some((ea.status, eb.status)),
+            duration_ms: (ea.duration_ms.abs_diff(eb.duration_ms) >= MIN_DURATION_CHANGE_MS
+                && changed(ea.duration_ms as f64, eb.duration_ms as f64, threshold))
+            .then_some((ea.duration_ms, eb.duration_ms)),
+            apps: Vec::new(),
+            new_errors: new_errors(&dir_a.join(slug(name)), &dir_b.join(slug(name)))?,
+        };
+        for app_b in &eb.apps {
+            let Some(app_a) = ea.apps.iter().find(|app| app.index == app_b.index) else {
+                continue;
+            };
+            let mut changes = Vec::new();
+            if exit(app_a) != exit(app_b) {
+                changes.push(format!("{} -> {}", exit(app_a), exit(app_b)));
+            }
+            if let (Some(before), Some(after)) = (peak_rss_kb(app_a), peak_rss_kb(app_b)) {
+                if changed(before as f64, after as f64, threshold) {
+                    changes.push(format!(
+                        "peak RSS {:.1} MiB -> {:.1} MiB ({})",
+                        before as f64 / 1024.0,
+                        after as f64 / 1024.0,
+                        percent(before as f64, after as f64)
+                    ));
+                }
+            }
+            if let (Some(before), Some(after)) = (&app_a.instrumentation, &app_b.instrumentation) {
+                if (before.errors, before.leaked_bytes) != (after.errors, after.leaked_bytes) {
+                    changes.push(format!(
+                        "sanitizer errors {} -> {}, leaked {} -> {} bytes",
+                        before.errors, after.errors, before.leaked_bytes, after.leaked_bytes
+                    ));
+                }
+            }
+            example.apps.extend(changes.into_iter().map(|change| AppDiff {
+                index: app_b.index,
+                path: app_b.path.clone(),
+                change,
+            }));
+        }
+        if example.is_empty() {
+            diff.unchanged += 1;
+        } else {
+            diff.examples.push(example);
+        }
+    }
+    Ok(diff)
+}
+
+/// Lines of `text` with every number replaced, for comparing lines across runs.
+fn normalize(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_number = false;
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            if !in_number {
+                out.push('#');
+            }
+            in_number = true;
+        } else {
+            out.push(c);
+            in_number = false;
+        }
+    }
+    out
+}
+
+fn is_error(stream: &str, text: &str) -> bool {
+    let lower = text.to_lowercase();
+    stream == "err"
+        || ["error", "panic", "fatal", "abort"]
+            .iter()
+            .any(|word| lower.contains(word))
+}
+
+/// Error lines of the app logs below `dir`, as `(source, text)`.
+fn error_lines(dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut lines = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(lines);
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            lines.extend(error_lines(&path)?);
+            continue;
+        }
+        // Logs compressed by `gc` as well.
+        let Some(source) = timeline::log_source(&path).filter(|source| source.starts_with("app")) else {
+            continue;
+        };
+        let content = gc::read_log(&path)?;
+        for line in content.lines() {
+            // "<elapsed> <wall clock> <stream> <text>"
+            let mut parts = line.splitn(4, ' ').skip(2);
+            let (Some(stream), Some(text)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if is_error(stream, text) {
+                lines.push((source.clone(), text.to_string()));
+            }
+        }
+    }
+    Ok(lines)
+}
+
+/// Error lines in the logs below `dir_b` without an equal line, ignoring numbers, below `dir_a`.
+fn new_errors(dir_a: &Path, dir_b: &Path) -> Result<Vec<String>> {
+    let known: BTreeSet<String> = error_lines(dir_a)?
+        .into_iter()
+        .map(|(source, text)| format!("{}: {}", source, normalize(&text)))
+        .collect();
+    let mut seen = BTreeSet::new();
+    let mut new = Vec::new();
+    for (source, text) in error_lines(dir_b)? {
+        let key = format!("{}: {}", source, normalize(&text));
+        if !known.contains(&key) && seen.insert(key) {
+            new.push(format!("{}: {}", source, text));
+        }
+    }
+    new.truncate(MAX_NEW_ERRORS);
+    Ok(new)
+}
+
+/// Renders `diff` as text or JSON.
+pub fn render(diff: &RunDiff, format: Format) -> Result<String> {
+    if let Format::Json = format {
+        return Ok(serde_json::to_string_pretty(diff)? + "\n");
+    }
+    let mut out = format!("Comparing run {} with run {}\n", diff.run_a, diff.run_b);
+    if !diff.host.is_empty() {
+        let _ = writeln!(out, "\nHost changed:");
+        for change in &diff.host {
+            let _ = writeln!(out, "  {}", change);
+        }
+    }
+    for example in &diff.examples {
+        let _ = writeln!(out, "\n{}", example.name);
+        if let Some((before, after)) = example.status {
+            let _ = writeln!(out, "  result: {} -> {}", before.name(), after.name());
+        }
+        if let Some((before, after)) = example.duration_ms {
+            let _ = writeln!(
+                out,
+                "  duration: {:.2}s -> {:.2}s ({})",
+                before as f64 / 1000.0,
+                after as f64 / 1000.0,
+                percent(before as f64, after as f64)
+            );
+        }
+        for app in &example.apps {
+            let _ = writeln!(out, "  app {} ({}): {}", app.index, app.path, app.change);
+        }
+        if !example.new_errors.is_empty() {
+            let _ = writeln!(out, "  new error lines:");
+            for line in &example.new_errors {
+                let _ = writeln!(out, "    {}", line);
+            }
+        }
+    }
+    for (label, names) in [
+        ("Only in the first run", &diff.only_in_a),
+        ("Only in the second run", &diff.only_in_b),
+    ] {
+        if !names.is_empty() {
+            let _ = writeln!(out, "\n{}: {}", label, names.join(", "));
+        }
+    }
+    let _ = writeln!(out, "\n{} changed, {} unchanged", diff.examples.len(), diff.unchanged);
+    Ok(out)
+}
@@ -0,0 +1,141 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Guard against runs filling the disk with logs.
+//!
+//! While a run lasts, the free space of the file system holding the run
+//! directory is checked every few seconds. Once it falls below `--min-free-mb`,
+//! app output stops being written to the log files: it is still shown on the
+//! terminal and forwarded, and runner events are still logged. Log capture
+//! resumes once twice that space is free again. Every pause is printed and
+//! recorded as a warning in `report.json`, with the number of lines that were
+//! not logged. The captures of a run share its `Pause`, and each counts the
+//! lines it dropped itself.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::sys;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Whether app output of a run is logged, set by its guard.
+#[derive(Clone, Default)]
+pub(crate) struct Pause(Arc<PauseState>);
+
+#[derive(Default)]
+struct PauseState {
+    paused: AtomicBool,
+    dropped: AtomicU64, // log lines dropped during the current pause
+}
+
+impl Pause {
+    /// Whether app output is not logged for lack of disk space, counting the line if so.
+    pub(crate) fn drop_line(&self) -> bool {
+        let paused = self.0.paused.load(Ordering::Relaxed);
+        if paused {
+            self.0.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        paused
+    }
+}
+
+/// Checks the free space of a run directory until the guard is finished.
+pub(crate) struct Guard {
+    stop: mpsc::Sender<()>,
+    thread: JoinHandle<()>,
+    warnings: Arc<Mutex<Vec<String>>>,
+}
+
+/// Starts guarding the file system of `dir` for the run logging with `pause`, unless `min_free_mb` is 0.
+pub(crate) fn start(dir: &Path, min_free_mb: u64, pause: &Pause) -> Option<Guard> {
+    if min_free_mb == 0 {
+        return None;
+    }
+    let (stop, stopped) = mpsc::channel();
+    let warnings = Arc::new(Mutex::new(Vec::new()));
+    let thread = {
+        let dir = dir.to_path_buf();
+        let warnings = warnings.clone();
+        let pause = pause.clone();
+        std::thread::spawn(move || watch(dir, min_free_mb, &pause, stopped, warnings))
+    };
+    Some(Guard { stop, thread, warnings })
+}
+
+impl Guard {
+    /// Stops guarding, resuming log capture, and returns the warnings for the report.
+    pub(crate) fn finish(self) -> Vec<String> {
+        let _ = self.stop.send(());
+        let _ = self.thread.join();
+        std::mem::take(&mut *self.warnings.lock().unwrap())
+    }
+}
+
+fn watch(
+    dir: PathBuf,
+    min_free_mb: u64,
+    pause: &Pause,
+    stopped: mpsc::Receiver<()>,
+    warnings: Arc<Mutex<Vec<String>>>,
+) {
+    let state = &pause.0;
+    let mut paused_at = None;
+    loop {
+        if let Ok(available) = sys::available_space(&dir) {
+            let free_mb = available / (1024 * 1024);
+            if paused_at.is_none() && free_mb < min_free_mb {
+                let message = format!(
+                    "only {} MB free on the file system of {}, app output is no longer logged",
+                    free_mb,
+                    dir.display()
+                );
+                println!("🛑 Disk almost full: {}", message);
+                state.dropped.store(0, Ordering::Relaxed);
+                state.paused.store(true, Ordering::Relaxed);
+                paused_at = Some(message);
+            } else if paused_at.is_some() && free_mb >= 2 * min_free_mb {
+                state.paused.store(false, Ordering::Relaxed);
+                let message = paused_at.take().expect("paused");
+                record_pause(state, &warnings, &message, Some(free_mb));
+            }
+        }
+        if stopped.recv_timeout(CHECK_INTERVAL) != Err(mpsc::RecvTimeoutError::Timeout) {
+            break;
+        }
+    }
+    state.paused.store(false, Ordering::Relaxed);
+    if let Some(message) = paused_at {
+        record_pause(state, &warnings, &message, None);
+    }
+}
+
+fn record_pause(state: &PauseState, warnings: &Mutex<Vec<String>>, message: &str, resumed_free_mb: Option<u64>) {
+    let dropped = state.dropped.swap(0, Ordering::Relaxed);
+    let warning = match resumed_free_mb {
+        Some(free_mb) => format!(
+            "{}: {} lines not logged until {} MB were free again",
+            message, dropped, free_mb
+        ),
+        None => format!("{}: {} lines not logged until the end of the run", message, dropped),
+    };
+    if resumed_free_mb.is_some() {
+        println!(
+            "✅ Disk space recovered, logging app output again ({} lines not logged)",
+            dropped
+        );
+    }
+    warnings.lock().unwrap().push(warning);
+}
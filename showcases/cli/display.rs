@@ -0,0 +1,169 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Apps needing a display, on hosts without one.
+//!
+//! An app with `requires_display` needs `DISPLAY` or `WAYLAND_DISPLAY` set. In
+//! sessions without either, like SSH logins and CI jobs, its `headless`
+//! fallback applies instead of letting the GUI crash:
+//!
+//! - `"skip"` (default): the example is skipped, with the app as the reason
+//! - `"xvfb"`: the app runs on a virtual framebuffer, one `Xvfb` per example,
+//!   which picks a free display number itself and reports it through `-displayfd`
+//! - `{"path": ..., "args": [...], "env": {...}}`: a headless variant of the
+//!   app runs instead, with its own path and arguments and additional variables
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::{host, sys, AppConfig, ScoreConfig};
+
+/// Time Xvfb has to accept connections.
+const XVFB_START_TIMEOUT: Duration = Duration::from_secs(5);
+const XVFB_SCREEN: &str = "1280x1024x24";
+
+/// What runs instead of an app needing a display when there is none.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Headless {
+    Mode(HeadlessMode),
+    Variant(HeadlessVariant),
+}
+
+impl Default for Headless {
+    fn default() -> Self {
+        Headless::Mode(HeadlessMode::Skip)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadlessMode {
+    Skip,
+    Xvfb,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct HeadlessVariant {
+    pub path: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>, // added to the app's variables
+}
+
+/// Whether apps can open windows in the session of the runner.
+pub fn available() -> bool {
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|name| env::var_os(name).is_some_and(|value| !value.is_empty()))
+}
+
+/// Runs the headless variants of the apps needing a display, if there is none.
+///
+/// Apps to run on the virtual framebuffer, or skipping the example, keep `requires_display`.
+pub fn resolve(configs: &mut [ScoreConfig]) {
+    if available() {
+        return;
+    }
+    for app in configs.iter_mut().flat_map(|config| config.apps.iter_mut()) {
+        if let (true, Headless::Variant(ref variant)) = (app.requires_display, &app.headless) {
+            app.path = variant.path.clone();
+            app.args = variant.args.clone();
+            app.env.extend(variant.env.clone());
+            app.requires_display = false;
+        }
+    }
+}
+
+/// Why the example is skipped without a display: an app needs one and has the `"skip"` fallback.
+pub fn skip_reason(config: &ScoreConfig) -> Option<String> {
+    if available() {
+        return None;
+    }
+    let (i, app) = config
+        .apps
+        .iter()
+        .enumerate()
+        .find(|(_, app)| app.requires_display && matches!(app.headless, Headless::Mode(HeadlessMode::Skip)))?;
+    Some(format!(
+        "app {} ({}) needs a display, neither DISPLAY nor WAYLAND_DISPLAY is set",
+        i + 1,
+        app.path
+    ))
+}
+
+/// An `Xvfb` server for the apps of an example, stopped when this is dropped.
+pub struct VirtualDisplay {
+    child: Child,
+    display: String,
+}
+
+impl VirtualDisplay {
+    /// Starts a virtual framebuffer if apps of `config` need a display and there is none.
+    pub fn start_if_needed(config: &ScoreConfig) -> Result<Option<VirtualDisplay>> {
+        let xvfb = |app: &AppConfig| app.requires_display && matches!(app.headless, Headless::Mode(HeadlessMode::Xvfb));
+        if available() || !config.apps.iter().any(xvfb) {
+            return Ok(None);
+        }
+        if host::find_in_path("Xvfb").is_none() {
+            bail!("Xvfb not found in PATH, needed for apps with \"headless\": \"xvfb\", install the xvfb package");
+        }
+        // Xvfb takes the first free display number and writes it to stdout once it accepts connections.
+        let mut child = Command::new("Xvfb")
+            .args(["-displayfd", "1", "-screen", "0", XVFB_SCREEN, "-nolisten", "tcp"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start Xvfb")?;
+        let stdout = child.stdout.take().expect("piped");
+        let (sender, number) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            let _ = BufReader::new(stdout).read_line(&mut line);
+            let _ = sender.send(line);
+        });
+        let display = match number.recv_timeout(XVFB_START_TIMEOUT) {
+            Ok(line) if line.trim().parse::<u32>().is_ok() => format!(":{}", line.trim()),
+            Ok(_) => {
+                let status = child.wait()?;
+                bail!("Xvfb exited with {} before reporting its display", status);
+            }
+            Err(_) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!("Xvfb did not start within {:?}", XVFB_START_TIMEOUT);
+            }
+        };
+        println!("Virtual display {} started for the apps needing a display", display);
+        Ok(Some(VirtualDisplay { child, display }))
+    }
+
+    /// The `DISPLAY` of the apps needing a display.
+    pub fn display(&self) -> &str {
+        &self.display
+    }
+}
+
+impl Drop for VirtualDisplay {
+    fn drop(&mut self) {
+        let _ = sys::stop(&mut self.child, sys::Signal::TERM, Duration::from_secs(2));
+    }
+}
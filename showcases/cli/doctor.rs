@@ -0,0 +1,249 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Pre-flight check of the host prerequisites of the examples.
+
+use crate::host;
+
+/// Oldest kernel the examples are tested with.
+const MIN_KERNEL: (u32, u32) = (5, 10);
+/// Shared memory below this size makes the IPC examples fail on larger payloads.
+const MIN_SHM_MB: u64 = 256;
+const MIN_OPEN_FILES: u64 = 4096;
+const MIN_MEMORY_MB: u64 = 2048;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Check {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn problem(name: &'static str, status: Status, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check {
+            name,
+            status,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Runs all checks and prints their results with remediation hints.
+///
+/// Returns false if a check failed; warnings only affect some examples or features.
+pub fn run() -> bool {
+    let checks = [
+        kernel(),
+        cgroups(),
+        memory(),
+        shared_memory(),
+        module("vcan", "virtual CAN interfaces (`can` blocks of kind \"virtual\")"),
+        module("can_raw", "CAN sockets of the apps"),
+        open_files(),
+        core_dumps(),
+        privileges(),
+        binary("ip", "CAN interfaces and network topologies", "iproute2"),
+        binary("tc", "network impairments (netem)", "iproute2"),
+        binary("valgrind", "--instrument valgrind", "valgrind"),
+        binary("llvm-profdata", "--merge-coverage", "llvm"),
+        binary("openssl", "--sign-key and verify-report", "openssl"),
+        binary("ffmpeg", "--capture-screen on X11 displays", "ffmpeg"),
+        binary(
+            "Xvfb",
+            "apps with \"headless\": \"xvfb\" on hosts without a display",
+            "xvfb",
+        ),
+    ];
+
+    for check in &checks {
+        let icon = match check.status {
+            Status::Ok => "✅",
+            Status::Warning => "⚠️ ",
+            Status::Error => "❌",
+        };
+        println!("{} {:<16} {}", icon, check.name, check.detail);
+        if let Some(ref hint) = check.hint {
+            println!("   {:<16} → {}", "", hint);
+        }
+    }
+
+    let count = |status| checks.iter().filter(|c| c.status == status).count();
+    println!(
+        "\n{} ok, {} warnings, {} errors",
+        count(Status::Ok),
+        count(Status::Warning),
+        count(Status::Error)
+    );
+    count(Status::Error) == 0
+}
+
+fn kernel() -> Check {
+    let name = "kernel";
+    match (host::kernel_release(), host::kernel_version()) {
+        (Some(release), Some(version)) if version >= MIN_KERNEL => Check::ok(name, release),
+        (Some(release), Some(_)) => Check::problem(
+            name,
+            Status::Error,
+            format!("{} is older than {}.{}", release, MIN_KERNEL.0, MIN_KERNEL.1),
+            "Use a host or VM with a newer kernel, e.g. a current Ubuntu LTS",
+        ),
+        _ => Check::problem(
+            name,
+            Status::Error,
+            "unknown (cannot read /proc/sys/kernel/osrelease)",
+            "Run on Linux with /proc mounted",
+        ),
+    }
+}
+
+fn cgroups() -> Check {
+    if host::has_cgroup_v2() {
+        Check::ok("cgroup v2", "unified hierarchy mounted at /sys/fs/cgroup")
+    } else {
+        Check::problem(
+            "cgroup v2",
+            Status::Warning,
+            "not available",
+            "Boot with systemd.unified_cgroup_hierarchy=1, or run containers with --cgroupns=host and cgroup v2 (needed for a cgroup per example)",
+        )
+    }
+}
+
+fn memory() -> Check {
+    match host::total_memory_mb() {
+        Some(total) if total < MIN_MEMORY_MB => Check::problem(
+            "memory",
+            Status::Warning,
+            format!("{} MiB", total),
+            format!(
+                "Give the host or VM at least {} MiB, larger examples may run out of memory",
+                MIN_MEMORY_MB
+            ),
+        ),
+        Some(total) => Check::ok("memory", format!("{} MiB", total)),
+        None => Check::ok("memory", "unknown"),
+    }
+}
+
+fn shared_memory() -> Check {
+    let name = "/dev/shm";
+    match host::filesystem_kb("/dev/shm") {
+        Some((size_kb, available_kb)) if size_kb / 1024 >= MIN_SHM_MB => Check::ok(
+            name,
+            format!("{} MiB ({} MiB free)", size_kb / 1024, available_kb / 1024),
+        ),
+        Some((size_kb, _)) => Check::problem(
+            name,
+            Status::Warning,
+            format!("only {} MiB", size_kb / 1024),
+            format!(
+                "Enlarge it: `mount -o remount,size=1G /dev/shm`, or `docker run --shm-size=1g` (at least {} MiB)",
+                MIN_SHM_MB
+            ),
+        ),
+        None => Check::problem(
+            name,
+            Status::Error,
+            "not mounted",
+            "Mount a tmpfs at /dev/shm, the IPC examples need shared memory",
+        ),
+    }
+}
+
+fn module(name: &'static str, needed_for: &str) -> Check {
+    if host::module_available(name) {
+        Check::ok(name, "kernel module available")
+    } else {
+        Check::problem(
+            name,
+            Status::Warning,
+            format!("kernel module not available, needed for {}", needed_for),
+            format!(
+                "`sudo modprobe {}`; on WSL2 and some cloud kernels it is missing and needs a custom kernel or the linux-modules-extra package",
+                name
+            ),
+        )
+    }
+}
+
+fn open_files() -> Check {
+    let name = "open files";
+    match host::soft_limit("Max open files") {
+        Some(Some(limit)) if limit < MIN_OPEN_FILES => Check::problem(
+            name,
+            Status::Warning,
+            format!("limit {} is low", limit),
+            format!(
+                "`ulimit -n {}`, or raise nofile in /etc/security/limits.conf",
+                MIN_OPEN_FILES
+            ),
+        ),
+        Some(Some(limit)) => Check::ok(name, format!("limit {}", limit)),
+        Some(None) => Check::ok(name, "unlimited"),
+        None => Check::ok(name, "limit unknown"),
+    }
+}
+
+fn core_dumps() -> Check {
+    match host::soft_limit("Max core file size") {
+        Some(Some(0)) => Check::problem(
+            "core dumps",
+            Status::Warning,
+            "disabled",
+            "`ulimit -c unlimited` to get core dumps of crashing apps",
+        ),
+        _ => Check::ok("core dumps", "enabled"),
+    }
+}
+
+fn privileges() -> Check {
+    if host::is_root() {
+        Check::ok("privileges", "running as root")
+    } else {
+        Check::problem(
+            "privileges",
+            Status::Warning,
+            "not root",
+            "CAN interfaces, network topologies and --observe need root (or CAP_NET_ADMIN / CAP_BPF)",
+        )
+    }
+}
+
+fn binary(program: &'static str, needed_for: &str, package: &str) -> Check {
+    match host::find_in_path(program) {
+        Some(path) => Check::ok(program, path.display().to_string()),
+        None => Check::problem(
+            program,
+            Status::Warning,
+            format!("not found in PATH, needed for {}", needed_for),
+            format!("Install the {} package", package),
+        ),
+    }
+}
@@ -0,0 +1,109 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Exit codes of the CLI per result category, for CI systems attaching their
+//! own meaning to exit codes.
+//!
+//! The codes are configured in the `[exit_codes]` table of the settings files:
+//!
+//! ```toml
+//! [exit_codes]
+//! failed = 1
+//! infra_error = 70
+//! skipped_only = 0
+//! ```
+//!
+//! A run exits with the code of its most severe category: an infrastructure
+//! error, i.e. the runner failing to run an example or erroring out, then
+//! failed, xpass and flaky examples, then a run that skipped every example.
+//! Categories not configured keep the codes of a CLI without the table.
+
+use crate::report::{RunReport, Status};
+use crate::settings::{Settings, Value};
+
+/// Table of the exit codes in the settings files.
+pub const TABLE: &str = "exit_codes";
+/// The categories of the table.
+pub const CATEGORIES: &[&str] = &["passed", "failed", "flaky", "xpass", "skipped_only", "infra_error"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExitCodes {
+    pub passed: i32,       // all examples passed, or failed as expected
+    pub failed: i32,       // an example failed
+    pub flaky: i32,        // an example passed only when retried
+    pub xpass: i32,        // an example marked as known to fail passed
+    pub skipped_only: i32, // no example ran, e.g. as the host met no requirements
+    pub infra_error: i32,  // the runner failed, e.g. an app binary is missing
+}
+
+impl Default for ExitCodes {
+    fn default() -> Self {
+        ExitCodes {
+            passed: 0,
+            failed: 1,
+            flaky: 0,
+            xpass: 0,
+            skipped_only: 0,
+            infra_error: 1,
+        }
+    }
+}
+
+impl ExitCodes {
+    /// The exit codes configured in `settings`, checked to be in 0..=255 when read.
+    pub fn from_settings(settings: &Settings) -> ExitCodes {
+        let mut codes = ExitCodes::default();
+        for (category, code) in [
+            ("passed", &mut codes.passed),
+            ("failed", &mut codes.failed),
+            ("flaky", &mut codes.flaky),
+            ("xpass", &mut codes.xpass),
+            ("skipped_only", &mut codes.skipped_only),
+            ("infra_error", &mut codes.infra_error),
+        ] {
+            if let Some(&Value::Integer(value)) = settings.get(&format!("{}.{}", TABLE, category)) {
+                *code = value as i32;
+            }
+        }
+        codes
+    }
+
+    /// The exit code of a run with `report`.
+    pub fn of(&self, report: &RunReport) -> i32 {
+        match category(report) {
+            "infra_error" => self.infra_error,
+            "failed" => self.failed,
+            "xpass" => self.xpass,
+            "flaky" => self.flaky,
+            "skipped_only" => self.skipped_only,
+            _ => self.passed,
+        }
+    }
+}
+
+/// The category of a run with `report`, one of `CATEGORIES`.
+pub fn category(report: &RunReport) -> &'static str {
+    let any = |status| report.examples.iter().any(|e| e.status == status);
+    if report.examples.iter().any(|e| e.infra_error) {
+        "infra_error"
+    } else if any(Status::Failed) {
+        "failed"
+    } else if any(Status::Xpass) {
+        "xpass"
+    } else if any(Status::Flaky) {
+        "flaky"
+    } else if report.examples.iter().all(|e| e.status == Status::Skipped) {
+        "skipped_only"
+    } else {
+        "passed"
+    }
+}
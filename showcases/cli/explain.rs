@@ -0,0 +1,339 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! `score-cli config explain <example>.<app>.<field>`: the final value of a
+//! field of an app and every layer that contributed to it.
+//!
+//! The layers are, in the order they apply:
+//!
+//! 1. the `*.score.json` files defining the example, then those of later
+//!    examples directories replacing or patching it (`--root`), as recorded
+//!    while loading the examples
+//! 2. the app's `env_file`, for variables the config does not set
+//! 3. the environment preset of the example, or of `--preset` or the settings
+//!    files, for variables not set before
+//! 4. `{{parameter}}` values, from `--param`, the settings files or the
+//!    parameter's default
+//! 5. at run time, `secrets` replacing variables, and for variables nothing
+//!    else sets the CLI's environment, which the settings files add to
+//!
+//! The app is given by its position, starting at 1, or its name. The values of
+//! the files are shown as loaded, with `${CONFIG_DIR}` expanded.
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::parameters::{self, Parameter};
+use crate::settings::Settings;
+use crate::{condition, config_dir, overlay};
+
+/// Variables the runner sets for every app.
+const RUNNER_VARIABLES: &[&str] = &["SCORE_RUN_ID", "SCORE_RUN_SEED"];
+
+#[derive(Subcommand, Debug)]
+pub enum Action {
+    /// Show the final value of a field of an app and every layer that contributed to it
+    Explain {
+        /// Field like `<example>.<app>.env.FOO`, the app given by position (from 1) or name
+        field: String,
+
+        /// Parameter value, as given to `run` (repeatable)
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+    },
+}
+
+/// What the CLI was given besides the action, and whether from the command line or a settings file.
+pub struct Given<'a> {
+    pub preset: Option<&'a str>, // the environment preset applied to all examples
+    pub preset_from_settings: bool,
+    pub params_from_settings: bool, // the `--param` values
+    pub settings: &'a Settings,
+}
+
+/// Runs the config `action` on the examples in `roots`.
+pub fn run(action: Action, roots: &[PathBuf], given: &Given) -> Result<()> {
+    match action {
+        Action::Explain { field, params } => {
+            let values = parameters::parse_given(&params)?;
+            print!("{}", explain(roots, &field, given, &values)?);
+            Ok(())
+        }
+    }
+}
+
+impl Given<'_> {
+    /// Where the flag `--flag` got its value, the settings file of `key` or the command line.
+    fn origin(&self, from_settings: bool, key: &str, flag: &str) -> String {
+        let file = self
+            .settings
+            .file(&format!("config.{}", key))
+            .or(self.settings.file(key));
+        match file.filter(|_| from_settings) {
+            Some(file) => format!("setting \"{}\" in {}", key, file.display()),
+            None => flag.to_string(),
+        }
+    }
+}
+
+/// A layer contributing to the value.
+struct Layer {
+    kind: &'static str,
+    source: String,
+    value: Option<Value>, // None: the layer removes the value
+    note: Option<String>,
+}
+
+fn explain(roots: &[PathBuf], field: &str, cli: &Given, given: &BTreeMap<String, String>) -> Result<String> {
+    let (merged, env_presets) = crate::read_examples(roots)?;
+    let Some(example) = merged
+        .iter()
+        .filter(|example| field.starts_with(&format!("{}.", name_of(&example.value))))
+        .max_by_key(|example| name_of(&example.value).len())
+    else {
+        bail!("\"{}\" names no example, use <example>.<app>.<field>", field);
+    };
+    let name = name_of(&example.value).to_string();
+    let rest = &field[name.len() + 1..];
+    let Some((app_ref, path)) = rest.split_once('.') else {
+        bail!("\"{}\" names no field, use <example>.<app>.<field>", field);
+    };
+    let apps = example.value["apps"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let Some(index) = find_app(apps, app_ref) else {
+        bail!(
+            "Example '{}' has no app \"{}\", give its position from 1 or its name",
+            name,
+            app_ref
+        );
+    };
+    let app = &apps[index];
+    // Keys of objects like `env` may hold dots, so everything after the object name is the key.
+    let path: Vec<&str> = match path.split_once('.') {
+        Some((object, key)) => vec![object, key],
+        None => vec![path],
+    };
+
+    let mut layers = Vec::new();
+    let mut current: Option<Value> = None;
+    for origin in &example.origins {
+        let patch = origin.patch;
+        let raw_app = origin.apps.iter().find(|(i, _)| *i == index).map(|(_, app)| app);
+        let value = raw_app.and_then(|app| lookup(app, &path)).cloned();
+        let (kind, value) = match (patch, value) {
+            (false, value) => ("config", value),
+            (true, None) => continue,
+            (true, Some(Value::Null)) => ("patch", None),
+            (true, Some(value)) => ("patch", Some(value)),
+        };
+        current = match (current.take(), &value) {
+            (Some(mut earlier), Some(value)) if patch => {
+                overlay::merge(&mut earlier, value.clone());
+                Some(earlier)
+            }
+            (_, value) => value.clone(),
+        };
+        layers.push(Layer {
+            kind,
+            source: origin.file.display().to_string(),
+            value,
+            note: None,
+        });
+    }
+
+    let variable = match path.as_slice() {
+        ["env", variable] => Some(*variable),
+        _ => None,
+    };
+    if let (Some(variable), Some(env_file)) = (variable, app.get("env_file").and_then(Value::as_str)) {
+        let variables = config_dir::load_env_file(Path::new(env_file))
+            .with_context(|| format!("Example '{}': invalid env_file {}", name, env_file))?;
+        if let Some(value) = variables.get(variable) {
+            let applied = current.is_none();
+            layers.push(Layer {
+                kind: "env file",
+                source: env_file.to_string(),
+                value: Some(Value::String(value.clone())),
+                note: (!applied).then(|| "not applied, set before".to_string()),
+            });
+            if applied {
+                current = Some(Value::String(value.clone()));
+            }
+        }
+    }
+
+    let preset_name = cli
+        .preset
+        .map(|preset| (preset, cli.origin(cli.preset_from_settings, "preset", "--preset")))
+        .or_else(|| {
+            example.value["preset"]
+                .as_str()
+                .map(|preset| (preset, "preset of the example".to_string()))
+        });
+    if let (Some(variable), Some((preset_name, origin))) = (variable, preset_name) {
+        let Some(env_preset) = env_presets.get(preset_name) else {
+            bail!("Example '{}': unknown preset \"{}\"", name, preset_name);
+        };
+        let value = match env_preset.env().get(variable) {
+            Some(value) => Some(value.clone()),
+            None if variable == "PATH" && !env_preset.path().is_empty() => {
+                Some(format!("{}:<PATH of the CLI>", env_preset.path().join(":")))
+            }
+            None => None,
+        };
+        if let Some(value) = value {
+            let applied = current.is_none();
+            layers.push(Layer {
+                kind: "preset",
+                source: format!("{} ({})", preset_name, origin),
+                value: Some(Value::String(value.clone())),
+                note: (!applied).then(|| "not applied, set before".to_string()),
+            });
+            if applied {
+                current = Some(Value::String(value));
+            }
+        }
+    }
+
+    if let Some(Value::String(text)) = &current {
+        let declared: Vec<Parameter> = match example.value.get("parameters") {
+            Some(value) => serde_json::from_value(value.clone())
+                .with_context(|| format!("Example '{}': invalid parameters", name))?,
+            None => Vec::new(),
+        };
+        let mut substituted = text.clone();
+        for parameter in &declared {
+            let placeholder = format!("{{{{{}}}}}", parameter.name);
+            if !text.contains(&placeholder) {
+                continue;
+            }
+            let (value, origin) = match (given.get(&parameter.name), parameter.default_value()) {
+                (Some(value), _) => (
+                    Some(value.clone()),
+                    cli.origin(cli.params_from_settings, "param", "--param"),
+                ),
+                (None, Some(value)) => (Some(value), "default".to_string()),
+                (None, None) => (None, "asked for when run interactively".to_string()),
+            };
+            if let Some(ref value) = value {
+                substituted = substituted.replace(&placeholder, value);
+            }
+            layers.push(Layer {
+                kind: "parameter",
+                source: format!("{} ({})", placeholder, origin),
+                value: value.map(Value::String),
+                note: None,
+            });
+        }
+        current = Some(Value::String(substituted));
+    }
+
+    if let Some(variable) = variable {
+        if let Some(secret) = app.get("secrets").and_then(|secrets| secrets.get(variable)) {
+            layers.push(Layer {
+                kind: "secret",
+                source: secret.to_string(),
+                value: None,
+                note: Some("replaces the value at run time, masked in all output".to_string()),
+            });
+        } else if RUNNER_VARIABLES.contains(&variable) {
+            layers.push(Layer {
+                kind: "runner",
+                source: "set for every run".to_string(),
+                value: None,
+                note: Some("replaces the value at run time".to_string()),
+            });
+        } else if current.is_none() && inherits(app, variable) {
+            if let Ok(value) = env::var(variable) {
+                let (kind, source) = match cli.settings.env_origin(variable) {
+                    Some((key, file)) => ("settings", format!("setting \"{}\" in {}", key, file.display())),
+                    None => ("inherited", "environment of the CLI".to_string()),
+                };
+                layers.push(Layer {
+                    kind,
+                    source,
+                    value: Some(Value::String(value.clone())),
+                    note: None,
+                });
+                current = Some(Value::String(value));
+            }
+        }
+    }
+
+    let mut out = format!(
+        "{} = {}\n",
+        field,
+        current.as_ref().map_or("(not set)".to_string(), Value::to_string)
+    );
+    if let Some(condition) = app.get("when").and_then(Value::as_str) {
+        let holds = condition::evaluate(condition).unwrap_or(false);
+        out.push_str(&format!(
+            "  the app only runs if {}, which is {} now\n",
+            condition, holds
+        ));
+    }
+    let width = layers.iter().map(|layer| layer.source.len()).max().unwrap_or(0);
+    for layer in &layers {
+        let value = match (&layer.value, layer.kind) {
+            (Some(value), _) => value.to_string(),
+            (None, "config" | "parameter") => "(not set)".to_string(),
+            (None, "patch") => "(removed)".to_string(),
+            (None, _) => String::new(),
+        };
+        let note = layer
+            .note
+            .as_ref()
+            .map(|note| format!(" ({})", note))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "  {:<10} {:<width$}  {}{}\n",
+            layer.kind,
+            layer.source,
+            value,
+            note,
+            width = width
+        ));
+    }
+    Ok(out)
+}
+
+fn name_of(example: &Value) -> &str {
+    example["name"].as_str().unwrap_or_default()
+}
+
+/// The app given by its position from 1, its name, or the file name of its path.
+fn find_app(apps: &[Value], app_ref: &str) -> Option<usize> {
+    if let Ok(position) = app_ref.parse::<usize>() {
+        return (1..=apps.len()).contains(&position).then(|| position - 1);
+    }
+    apps.iter().position(|app| {
+        let label = app["name"].as_str().or_else(|| {
+            let path = app["path"].as_str()?;
+            Path::new(path).file_name()?.to_str()
+        });
+        label == Some(app_ref)
+    })
+}
+
+fn lookup<'a>(app: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter().try_fold(app, |value, key| value.get(*key))
+}
+
+fn inherits(app: &Value, variable: &str) -> bool {
+    let removed = app["env_remove"]
+        .as_array()
+        .is_some_and(|removed| removed.iter().any(|name| name.as_str() == Some(variable)));
+    app["inherit_env"].as_bool().unwrap_or(true) && !removed
+}
@@ -0,0 +1,59 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Fake clock support for apps through libfaketime.
+
+use anyhow::{bail, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+
+const LIB_CANDIDATES: &[&str] = &[
+    "/usr/lib/x86_64-linux-gnu/faketime/libfaketime.so.1",
+    "/usr/lib/aarch64-linux-gnu/faketime/libfaketime.so.1",
+    "/usr/lib/faketime/libfaketime.so.1",
+    "/usr/lib64/faketime/libfaketime.so.1",
+    "/usr/local/lib/faketime/libfaketime.so.1",
+];
+
+/// Location of libfaketime, overridable with `SCORE_FAKETIME_LIB`.
+fn library() -> Result<PathBuf> {
+    if let Ok(path) = env::var("SCORE_FAKETIME_LIB") {
+        let path = PathBuf::from(path);
+        if !path.exists() {
+            bail!("SCORE_FAKETIME_LIB points to {:?}, which does not exist", path);
+        }
+        return Ok(path);
+    }
+    LIB_CANDIDATES
+        .iter()
+        .map(Path::new)
+        .find(|p| p.exists())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow::anyhow!("libfaketime not found, install it or set SCORE_FAKETIME_LIB"))
+}
+
+/// Adds the `FAKETIME` variable making the app run with the clock described by `spec` to `env`
+/// and returns the library to preload.
+///
+/// `spec` uses libfaketime's `FAKETIME` syntax, e.g. `"@2030-01-01 12:00:00"`
+/// to start ticking from a fixed point in time or `"+2d"` / `"-1h"` for an offset.
+pub fn apply(env: &mut Vec<(String, String)>, spec: &str) -> Result<PathBuf> {
+    if spec.trim().is_empty() {
+        bail!("faketime must not be empty");
+    }
+    if !cfg!(target_os = "linux") {
+        bail!("faketime needs Linux (libfaketime)");
+    }
+    let lib = library()?;
+    env.push(("FAKETIME".to_string(), spec.to_string()));
+    Ok(lib)
+}
@@ -0,0 +1,139 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Canonical formatting of `*.score.json` files (`score-cli fmt`).
+//!
+//! Objects are indented by four spaces with one key per line, arrays of plain
+//! values stay on one line. Keys are ordered `name`, `description`, `tags`,
+//! `path`, `args`, `env`, then alphabetically, with `apps` and `steps` last, so
+//! the fields describing an example or app come first and `env` keys are sorted.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::is_score_file;
+
+const INDENT: &str = "    ";
+/// Keys placed first, in this order.
+const FIRST_KEYS: &[&str] = &["name", "description", "tags", "path", "args", "env"];
+/// Keys placed last, in this order.
+const LAST_KEYS: &[&str] = &["apps", "steps"];
+
+/// Formats the content of a config file canonically.
+pub fn format(content: &str) -> Result<String> {
+    let value: Value = serde_json::from_str(content)?;
+    let mut out = String::new();
+    write_value(&mut out, &value, 0);
+    out.push('\n');
+    Ok(out)
+}
+
+fn key_rank(key: &str) -> (usize, &str) {
+    if let Some(position) = FIRST_KEYS.iter().position(|k| *k == key) {
+        (position, "")
+    } else if let Some(position) = LAST_KEYS.iter().position(|k| *k == key) {
+        (FIRST_KEYS.len() + 1 + position, "")
+    } else {
+        (FIRST_KEYS.len(), key)
+    }
+}
+
+fn write_value(out: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_by_key(|key| key_rank(key));
+            out.push_str("{\n");
+            for (i, key) in keys.iter().enumerate() {
+                out.push_str(&INDENT.repeat(depth + 1));
+                out.push_str(&Value::String(key.to_string()).to_string());
+                out.push_str(": ");
+                write_value(out, &map[key.as_str()], depth + 1);
+                out.push_str(if i + 1 < keys.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&INDENT.repeat(depth));
+            out.push('}');
+        }
+        Value::Array(items) if items.iter().any(|item| item.is_object() || item.is_array()) => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(&INDENT.repeat(depth + 1));
+                write_value(out, item, depth + 1);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            out.push_str(&INDENT.repeat(depth));
+            out.push(']');
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(Value::to_string).collect();
+            out.push_str(&format!("[{}]", items.join(", ")));
+        }
+        // Empty objects and plain values.
+        value => out.push_str(&value.to_string()),
+    }
+}
+
+/// All config files in `paths`, searching directories recursively.
+fn files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            collect(path, &mut files)?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {:?}", dir))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_symlink() {
+            continue;
+        }
+        if path.is_dir() {
+            collect(&path, files)?;
+        } else if is_score_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Formats the config files in `paths`, or with `check` only lists the files that are not formatted.
+///
+/// Returns whether all files were formatted already.
+pub fn run(paths: &[PathBuf], check: bool) -> Result<bool> {
+    let mut formatted = true;
+    for path in files(paths)? {
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed reading {:?}", path))?;
+        let canonical = format(&content).with_context(|| format!("Invalid JSON in {:?}", path))?;
+        if canonical == content {
+            continue;
+        }
+        formatted = false;
+        if check {
+            println!("Not formatted: {}", path.display());
+        } else {
+            fs::write(&path, canonical).with_context(|| format!("Failed to write {:?}", path))?;
+            println!("Formatted {}", path.display());
+        }
+    }
+    Ok(formatted)
+}
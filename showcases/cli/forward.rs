@@ -0,0 +1,186 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Live forwarding of log lines to external viewers.
+//!
+//! Every line is sent as one JSON object per line to `tcp://host:port` or
+//! `unix:/path`. A viewer that goes away does not disturb the run: lines are
+//! dropped while it is unreachable and the connection is retried at most once
+//! per second.
+//!
+//! Every viewer has a writer thread sending the lines of its bounded queue, so
+//! apps printing never wait for a slow viewer or a reconnect. Lines for a
+//! viewer whose queue is full are dropped.
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::os::unix::net::UnixStream;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Minimum time between attempts to reconnect to a viewer.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+/// How long connecting to a viewer may take.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+/// How long a stalled viewer may block a write before it is considered gone, so it never holds up apps.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(500);
+/// Lines waiting to be sent to a viewer.
+const QUEUE: usize = 4096;
+/// How long the end of a run waits for queued lines to be sent.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Connections shared by all examples of a run, by address.
+static VIEWERS: Mutex<Vec<(String, Arc<Viewer>)>> = Mutex::new(Vec::new());
+
+/// A log line as sent to viewers.
+#[derive(Serialize)]
+pub struct Event<'a> {
+    pub example: &'a str,
+    pub app: usize, // 0 for events of the runner
+    pub label: &'a str,
+    pub stream: &'a str, // "out", "err" or "evt"
+    pub elapsed_s: f64,  // since the example started
+    pub time: String,    // RFC 3339 UTC
+    pub text: &'a str,
+}
+
+/// An external viewer receiving log lines.
+pub struct Viewer {
+    address: String,
+    lines: SyncSender<Vec<u8>>, // encoded lines for the viewer's writer thread
+    queued: Arc<AtomicUsize>,   // lines not yet written by the writer thread
+    dropping: AtomicBool,       // set while lines are dropped because the queue is full
+}
+
+/// Where apps' lines go: viewers getting all lines and viewers of single apps.
+#[derive(Clone, Default)]
+pub struct Forwarding {
+    pub example: String,
+    pub all: Option<Arc<Viewer>>,
+    pub apps: HashMap<usize, Arc<Viewer>>,
+}
+
+impl Forwarding {
+    /// Sends `event` to the viewers of its app.
+    pub fn send(&self, event: &Event) {
+        if let Some(ref viewer) = self.all {
+            viewer.send(event);
+        }
+        if let Some(viewer) = self.apps.get(&event.app) {
+            if !self.all.as_ref().is_some_and(|all| Arc::ptr_eq(all, viewer)) {
+                viewer.send(event);
+            }
+        }
+    }
+}
+
+fn connect(address: &str) -> Result<Box<dyn Write + Send>> {
+    if let Some(host) = address.strip_prefix("tcp://") {
+        let address = host
+            .to_socket_addrs()?
+            .next()
+            .with_context(|| format!("{} did not resolve to an address", host))?;
+        let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+        let _ = stream.set_nodelay(true);
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        Ok(Box::new(stream))
+    } else if let Some(path) = address.strip_prefix("unix:") {
+        let stream = UnixStream::connect(path)?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        Ok(Box::new(stream))
+    } else {
+        bail!("unsupported address, use tcp://<host>:<port> or unix:<path>")
+    }
+}
+
+/// Connects to the viewer at `address`, reusing an existing connection.
+pub fn viewer(address: &str) -> Result<Arc<Viewer>> {
+    let mut viewers = VIEWERS.lock().unwrap();
+    if let Some((_, viewer)) = viewers.iter().find(|(a, _)| a == address) {
+        return Ok(viewer.clone());
+    }
+    let stream = connect(address).with_context(|| format!("Failed to connect to log viewer {}", address))?;
+    let (lines, receiver) = mpsc::sync_channel(QUEUE);
+    let queued = Arc::new(AtomicUsize::new(0));
+    let (writer, written) = (address.to_string(), queued.clone());
+    std::thread::Builder::new()
+        .name("log-viewer".to_string())
+        .spawn(move || write_lines(&writer, stream, receiver, &written))
+        .context("Failed to start the log viewer thread")?;
+    let viewer = Arc::new(Viewer {
+        address: address.to_string(),
+        lines,
+        queued,
+        dropping: AtomicBool::new(false),
+    });
+    viewers.push((address.to_string(), viewer.clone()));
+    Ok(viewer)
+}
+
+/// Writes the queued lines to the viewer at `address`, reconnecting while it is unreachable.
+fn write_lines(address: &str, stream: Box<dyn Write + Send>, lines: Receiver<Vec<u8>>, queued: &AtomicUsize) {
+    let mut stream = Some(stream);
+    let mut last_attempt = Instant::now();
+    for line in lines {
+        if stream.is_none() && last_attempt.elapsed() >= RECONNECT_INTERVAL {
+            last_attempt = Instant::now();
+            stream = connect(address).ok();
+        }
+        if let Some(ref mut connected) = stream {
+            if connected.write_all(&line).is_err() {
+                stream = None;
+            }
+        }
+        queued.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Waits a little for the lines still queued for the viewers, so the end of a run reaches them.
+pub fn flush() {
+    let viewers: Vec<Arc<Viewer>> = VIEWERS.lock().unwrap().iter().map(|(_, v)| v.clone()).collect();
+    let until = Instant::now() + FLUSH_TIMEOUT;
+    while viewers.iter().any(|v| v.queued.load(Ordering::SeqCst) > 0) && Instant::now() < until {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+impl Viewer {
+    /// Queues `event` for the writer thread without ever blocking.
+    fn send(&self, event: &Event) {
+        let Ok(mut line) = serde_json::to_vec(event) else {
+            return;
+        };
+        line.push(b'\n');
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        match self.lines.try_send(line) {
+            Ok(()) => {
+                if self.dropping.swap(false, Ordering::Relaxed) {
+                    eprintln!("Log viewer {} keeps up again", self.address);
+                }
+            }
+            Err(TrySendError::Full(_)) => {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
+                if !self.dropping.swap(true, Ordering::Relaxed) {
+                    eprintln!("Log viewer {} does not keep up, dropping lines", self.address);
+                }
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                self.queued.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Retention of run artifacts (`score-cli gc`), so CI agents and booth machines
+//! do not fill their disks with old showcase logs.
+//!
+//! Runs beyond the newest `--keep-runs` or older than `--keep-days` are removed.
+//! The logs of the runs kept are compressed with the `zstd` tool once they are
+//! older than `--compress-after`, their line index is removed with them.
+//! Everything reading logs, like `timeline`, `logs`, `diff` and `bench`, goes
+//! through `read_log` and reads compressed logs as well. A run without report
+//! that was written to within the last hour is still going on and left alone.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+use crate::log_index;
+
+/// A run without report modified more recently is taken as still running.
+const RUNNING_GRACE: Duration = Duration::from_secs(60 * 60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub struct Policy {
+    pub keep_runs: Option<usize>,
+    pub keep_days: Option<u64>,
+    pub compress_after: Option<Duration>, // None: never compress
+}
+
+struct RunDir {
+    path: PathBuf,
+    modified: SystemTime, // newest modification of a file in the run
+}
+
+/// Applies `policy` to the runs in `runs_root`, only printing what it would do with `dry_run`.
+pub fn run(runs_root: &Path, policy: &Policy, dry_run: bool) -> Result<()> {
+    let now = SystemTime::now();
+    let mut runs: Vec<RunDir> = fs::read_dir(runs_root)
+        .with_context(|| format!("No recorded runs in {:?}", runs_root))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .map(|path| RunDir {
+            modified: newest_modification(&path),
+            path,
+        })
+        .collect();
+    runs.sort_by_key(|run| std::cmp::Reverse(run.modified));
+
+    let (mut removed, mut freed) = (0, 0);
+    let (mut compressed, mut saved) = (0, 0);
+    for (position, run) in runs.iter().enumerate() {
+        let age = now.duration_since(run.modified).unwrap_or_default();
+        if !run.path.join("report.json").exists() && age < RUNNING_GRACE {
+            continue;
+        }
+        let too_many = policy.keep_runs.is_some_and(|keep| position >= keep);
+        let too_old = policy.keep_days.is_some_and(|days| age > DAY * days as u32);
+        if too_many || too_old {
+            let size = disk_usage(&run.path);
+            println!(
+                "{} {} ({} MiB, {} days old)",
+                if dry_run { "Would remove" } else { "Removing" },
+                run.path.display(),
+                size / (1024 * 1024),
+                age.as_secs() / DAY.as_secs()
+            );
+            if !dry_run {
+                fs::remove_dir_all(&run.path).with_context(|| format!("Failed to remove {:?}", run.path))?;
+            }
+            removed += 1;
+            freed += size;
+            continue;
+        }
+        if policy.compress_after.is_some_and(|after| age > after) {
+            for log in logs(&run.path) {
+                let size = fs::metadata(&log).map(|m| m.len()).unwrap_or(0);
+                if dry_run {
+                    println!("Would compress {}", log.display());
+                    compressed += 1;
+                    continue;
+                }
+                compress(&log)?;
+                let packed = fs::metadata(compressed_path(&log)).map(|m| m.len()).unwrap_or(size);
+                compressed += 1;
+                saved += size.saturating_sub(packed);
+            }
+        }
+    }
+    let would = if dry_run { "would be " } else { "" };
+    println!(
+        "{} runs {}removed ({} MiB), {} logs {}compressed ({} MiB saved), {} runs kept",
+        removed,
+        would,
+        freed / (1024 * 1024),
+        compressed,
+        would,
+        saved / (1024 * 1024),
+        runs.len() - removed
+    );
+    Ok(())
+}
+
+/// Parses `--compress-after`: a duration like `1d`, or `never`.
+pub fn parse_compress_after(value: &str) -> Result<Option<Duration>, String> {
+    match value {
+        "never" => Ok(None),
+        _ => crate::parse_duration(value).map(Some),
+    }
+}
+
+/// Path of the compressed version of the log `path`.
+fn compressed_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".zst");
+    PathBuf::from(name)
+}
+
+/// Reads a log file, decompressing it if `gc` compressed it.
+pub fn read_log(path: &Path) -> Result<String> {
+    if path.extension().is_none_or(|ext| ext != "zst") {
+        return fs::read_to_string(path).with_context(|| format!("Failed reading {:?}", path));
+    }
+    let output = Command::new("zstd")
+        .args(["-d", "-c", "-q"])
+        .arg(path)
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("Failed to run zstd to read {:?}", path))?;
+    if !output.status.success() {
+        bail!("zstd failed to decompress {:?}: {}", path, output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn compress(log: &Path) -> Result<()> {
+    let status = Command::new("zstd")
+        .args(["-q", "--rm", "-f"])
+        .arg(log)
+        .status()
+        .context("Failed to run zstd, install it or use --compress-after never")?;
+    if !status.success() {
+        bail!("zstd failed to compress {:?}: {}", log, status);
+    }
+    let _ = fs::remove_file(log_index::index_path(log));
+    Ok(())
+}
+
+/// The uncompressed `*.log` files below `dir`.
+fn logs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut logs = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            logs.extend(self::logs(&path));
+        } else if path.extension().is_some_and(|ext| ext == "log") {
+            logs.push(path);
+        }
+    }
+    logs
+}
+
+fn newest_modification(dir: &Path) -> SystemTime {
+    let mut newest = fs::metadata(dir)
+        .and_then(|m| m.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    for path in fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+    {
+        let modified = if path.is_dir() {
+            newest_modification(&path)
+        } else {
+            fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH)
+        };
+        newest = newest.max(modified);
+    }
+    newest
+}
+
+fn disk_usage(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                disk_usage(&path)
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            }
+        })
+        .sum()
+}
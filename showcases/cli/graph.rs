@@ -0,0 +1,193 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Start-order graph of an example, rendered as Graphviz dot or Mermaid.
+//!
+//! Apps start one after another, so the graph is a chain labelled with the
+//! delays. Nodes show what else shapes the start: readiness conditions and
+//! deadlines, `when` conditions, network nodes and the lifecycle. Apps joining
+//! the start barrier point to it. Composite examples are a chain of steps.
+
+use clap::ValueEnum;
+use std::fmt::Write;
+
+use crate::builtin::AppType;
+use crate::{AppConfig, Lifecycle, ScoreConfig};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Dot,
+    Mermaid,
+}
+
+struct Node {
+    id: String,
+    lines: Vec<String>,
+}
+
+struct Edge {
+    from: String,
+    to: String,
+    label: Option<String>,
+    dashed: bool,
+}
+
+/// Renders the start order of `config`.
+pub fn render(config: &ScoreConfig, format: Format) -> String {
+    let (nodes, edges) = if config.steps.is_empty() {
+        apps(config)
+    } else {
+        steps(config)
+    };
+    match format {
+        Format::Dot => dot(&config.name, &nodes, &edges),
+        Format::Mermaid => mermaid(&nodes, &edges),
+    }
+}
+
+fn apps(config: &ScoreConfig) -> (Vec<Node>, Vec<Edge>) {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    for (i, app) in config.apps.iter().enumerate() {
+        let id = format!("app{}", i + 1);
+        if i > 0 {
+            edges.push(Edge {
+                from: format!("app{}", i),
+                to: id.clone(),
+                label: app
+                    .delay
+                    .filter(|&delay| delay > 0)
+                    .map(|delay| format!("after {}s", delay)),
+                dashed: false,
+            });
+        }
+        if app.barrier {
+            edges.push(Edge {
+                from: id.clone(),
+                to: "barrier".to_string(),
+                label: Some("joins".to_string()),
+                dashed: true,
+            });
+        }
+        nodes.push(Node {
+            id,
+            lines: app_lines(i + 1, app),
+        });
+    }
+    if config.apps.iter().any(|app| app.barrier) {
+        let timeout = config
+            .barrier_timeout
+            .map(|secs| format!(" ({}s)", secs))
+            .unwrap_or_default();
+        nodes.push(Node {
+            id: "barrier".to_string(),
+            lines: vec![format!("start barrier{}", timeout)],
+        });
+    }
+    (nodes, edges)
+}
+
+fn app_lines(index: usize, app: &AppConfig) -> Vec<String> {
+    let name = match (&app.name, app.app_type) {
+        (Some(name), _) => name.clone(),
+        (None, AppType::Process) => crate::app_label(index, &app.path),
+        // Built-in apps run as `<cli> builtin <type> ...`.
+        (None, _) => format!("{}:{}", index, app.args.get(1).map(String::as_str).unwrap_or_default()),
+    };
+    let mut lines = vec![name];
+    if app.lifecycle == Lifecycle::KeepAlive {
+        lines.push("keep-alive".to_string());
+    }
+    if let Some(ref ready) = app.ready {
+        lines.push(format!("ready: {}", ready.describe()));
+    }
+    if let Some(timeout) = app.start_timeout {
+        lines.push(format!("start timeout {}s", timeout));
+    }
+    if let Some(ref condition) = app.when {
+        lines.push(format!("when {}", condition));
+    }
+    if let Some(ref node) = app.node {
+        lines.push(format!("node {}", node));
+    }
+    lines
+}
+
+fn steps(config: &ScoreConfig) -> (Vec<Node>, Vec<Edge>) {
+    let nodes: Vec<Node> = config
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| Node {
+            id: format!("step{}", i + 1),
+            lines: vec![format!("step {}: {}", i + 1, step)],
+        })
+        .collect();
+    let edges = nodes
+        .windows(2)
+        .map(|pair| Edge {
+            from: pair[0].id.clone(),
+            to: pair[1].id.clone(),
+            label: Some("passed".to_string()),
+            dashed: false,
+        })
+        .collect();
+    (nodes, edges)
+}
+
+fn dot(name: &str, nodes: &[Node], edges: &[Edge]) -> String {
+    let escape = |text: &str| text.replace('\\', "\\\\").replace('"', "\\\"");
+    let quote = |text: &str| format!("\"{}\"", escape(text));
+    let mut out = format!("digraph {} {{\n    rankdir=LR;\n    node [shape=box];\n", quote(name));
+    for node in nodes {
+        // "\n" is a line break in dot labels.
+        let label: Vec<String> = node.lines.iter().map(|line| escape(line)).collect();
+        let _ = writeln!(out, "    {} [label=\"{}\"];", node.id, label.join("\\n"));
+    }
+    for edge in edges {
+        let mut attributes = Vec::new();
+        if let Some(ref label) = edge.label {
+            attributes.push(format!("label={}", quote(label)));
+        }
+        if edge.dashed {
+            attributes.push("style=dashed".to_string());
+        }
+        let _ = write!(out, "    {} -> {}", edge.from, edge.to);
+        if !attributes.is_empty() {
+            let _ = write!(out, " [{}]", attributes.join(", "));
+        }
+        out.push_str(";\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid(nodes: &[Node], edges: &[Edge]) -> String {
+    let escape = |text: &str| text.replace('"', "#quot;");
+    let mut out = String::from("flowchart LR\n");
+    for node in nodes {
+        let label: Vec<String> = node.lines.iter().map(|line| escape(line)).collect();
+        let _ = writeln!(out, "    {}[\"{}\"]", node.id, label.join("<br/>"));
+    }
+    for edge in edges {
+        let arrow = if edge.dashed { "-.->" } else { "-->" };
+        match edge.label {
+            Some(ref label) => {
+                let _ = writeln!(out, "    {} {}|\"{}\"| {}", edge.from, arrow, escape(label), edge.to);
+            }
+            None => {
+                let _ = writeln!(out, "    {} {} {}", edge.from, arrow, edge.to);
+            }
+        }
+    }
+    out
+}
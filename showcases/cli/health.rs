@@ -0,0 +1,196 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! The runner as a supervised entity of the SCORE health monitor.
+//!
+//! The health-management daemon only talks to its client library, so an
+//! example with a `health_monitor` starts a reporter linked against that
+//! library once all apps started: `health_reporter` next to the CLI, which
+//! reports the runner's alive checkpoint, or another program. It gets the entity
+//! name in `IDENTIFIER` and `PROCESSIDENTIFIER`, like the apps the launch
+//! manager starts, and reads one JSON object per line on stdin:
+//!
+//! - `{"register": "score_runner", "run": "<run id>", "example": "<name>", "apps": ["<label>", ...]}` first
+//! - `{"alive": ["score_runner", "<label>", ...]}` every `cycle_ms`, for the
+//!   runner and the apps still running
+//! - `{"exited": "<label>", "app": 2, "status": "exit status: 1"}` once an app exited
+//!
+//! The reporter turns these into alive indications of the entities, so the
+//! demo shows the supervision chain up to the launcher: a hanging runner
+//! stops reporting like a hanging app. End of input means the example ended.
+//! A reporter that exits early is reported once and does not disturb the run.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::output::Capture;
+use crate::{sys, RunningApp};
+
+/// Reporter installed next to the CLI, linked against the health monitor's client library.
+const DEFAULT_REPORTER: &str = "health_reporter";
+/// Time the reporter has to deregister once its input ends.
+const FINISH_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HealthMonitor {
+    pub reporter: Option<String>, // program linked against the daemon's client library (default: health_reporter next to the CLI)
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>, // e.g. CONFIG_PATH of the reporter's supervision config
+    #[serde(default = "default_entity")]
+    pub entity: String, // supervised entity of the runner
+    #[serde(default = "default_cycle_ms")]
+    pub cycle_ms: u64, // reporting cycle
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+impl HealthMonitor {
+    /// The reporter to start, `health_reporter` next to the CLI unless `reporter` is given.
+    pub(crate) fn program(&self) -> Result<PathBuf> {
+        if let Some(ref reporter) = self.reporter {
+            return Ok(PathBuf::from(reporter));
+        }
+        let exe = std::env::current_exe().context("Failed to find the path of the CLI executable")?;
+        Ok(exe.with_file_name(DEFAULT_REPORTER))
+    }
+}
+
+fn default_entity() -> String {
+    "score_runner".to_string()
+}
+
+fn default_cycle_ms() -> u64 {
+    100
+}
+
+/// A started reporter forwarding the liveness of the runner and the apps.
+pub(crate) struct Reporter {
+    child: Child,
+    stdin: Option<ChildStdin>, // None once the reporter went away
+    gone_reported: bool,
+    entity: String,
+    cycle: Duration,
+    next: Instant,
+    exited: HashSet<usize>, // apps whose exit was reported
+}
+
+impl Reporter {
+    /// Starts the reporter of `config` and registers the runner for the example `example` of run `run_id`.
+    pub(crate) fn start(config: &HealthMonitor, run_id: &str, example: &str, apps: &[RunningApp]) -> Result<Self> {
+        let program = config.program()?;
+        let mut child = Command::new(&program)
+            .args(&config.args)
+            .envs(&config.env)
+            .env("IDENTIFIER", &config.entity)
+            .env("PROCESSIDENTIFIER", &config.entity)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to start the health monitor reporter {}", program.display()))?;
+        let mut reporter = Reporter {
+            stdin: child.stdin.take(),
+            gone_reported: false,
+            child,
+            entity: config.entity.clone(),
+            cycle: Duration::from_millis(config.cycle_ms.max(1)),
+            next: Instant::now(),
+            exited: HashSet::new(),
+        };
+        let labels: Vec<&str> = apps.iter().map(|app| app.label.as_str()).collect();
+        reporter.send(json!({
+            "register": reporter.entity,
+            "run": run_id,
+            "example": example,
+            "apps": labels,
+        }));
+        Ok(reporter)
+    }
+
+    /// Reports apps that exited since the last call and, once per cycle, the ones still running.
+    pub(crate) fn poll(&mut self, apps: &[RunningApp], statuses: &[Option<ExitStatus>], capture: &Capture) {
+        self.report_exits(apps, statuses);
+        if Instant::now() >= self.next {
+            self.next = Instant::now() + self.cycle;
+            let mut alive = vec![self.entity.as_str()];
+            alive.extend(
+                apps.iter()
+                    .zip(statuses)
+                    .filter(|(_, status)| status.is_none())
+                    .map(|(app, _)| app.label.as_str()),
+            );
+            self.send(json!({ "alive": alive }));
+        }
+        if self.stdin.is_none() && !self.gone_reported {
+            self.gone_reported = true;
+            let status = match self.child.try_wait() {
+                Ok(Some(status)) => format!("exited with {}", status),
+                _ => "closed its input".to_string(),
+            };
+            println!("⚠️  Health monitor reporter {}, liveness is no longer reported", status);
+            capture.event(&format!("health monitor reporter {}", status));
+        }
+    }
+
+    /// Reports the remaining exits and lets the reporter deregister the runner.
+    pub(crate) fn finish(mut self, apps: &[RunningApp], statuses: &[Option<ExitStatus>]) {
+        self.report_exits(apps, statuses);
+    }
+
+    fn report_exits(&mut self, apps: &[RunningApp], statuses: &[Option<ExitStatus>]) {
+        for (app, status) in apps.iter().zip(statuses) {
+            let Some(status) = status else {
+                // Restarted apps exit again.
+                self.exited.remove(&app.index);
+                continue;
+            };
+            if self.exited.insert(app.index) {
+                self.send(json!({
+                    "exited": app.label,
+                    "app": app.index,
+                    "status": status.to_string(),
+                }));
+            }
+        }
+    }
+
+    fn send(&mut self, line: serde_json::Value) {
+        let Some(ref mut stdin) = self.stdin else {
+            return;
+        };
+        if writeln!(stdin, "{}", line).and_then(|()| stdin.flush()).is_err() {
+            self.stdin = None;
+        }
+    }
+}
+
+impl Drop for Reporter {
+    fn drop(&mut self) {
+        // End of input tells the reporter to deregister.
+        self.stdin = None;
+        let deadline = Instant::now() + FINISH_GRACE;
+        while Instant::now() < deadline {
+            if !matches!(self.child.try_wait(), Ok(None)) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let _ = sys::stop(&mut self.child, sys::Signal::TERM, FINISH_GRACE);
+    }
+}
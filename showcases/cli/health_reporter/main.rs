@@ -0,0 +1,71 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Default reporter of the runner's `health_monitor`, linked against the
+//! client library of the SCORE health monitor.
+//!
+//! Reads the runner's JSON lines on stdin (see `health.rs` of the CLI) and
+//! reports the alive checkpoint of the runner's supervised entity, named in
+//! `IDENTIFIER`, for every `alive` line listing it. The client library finds
+//! the supervision config of the entity through `CONFIG_PATH`. The apps are
+//! supervised entities of their own and report through the library
+//! themselves, so the other lines are not needed here. End of input ends the
+//! example: the monitor is dropped, which deregisters the entity.
+
+use monitor_rs::Monitor;
+use serde_json::Value;
+use std::io::BufRead;
+use std::process::ExitCode;
+
+/// Checkpoint of the runner's alive supervision, as numbered in its supervision config.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy)]
+enum Checkpoint {
+    Alive = 1,
+}
+
+impl From<Checkpoint> for u32 {
+    fn from(checkpoint: Checkpoint) -> u32 {
+        checkpoint as u32
+    }
+}
+
+fn main() -> ExitCode {
+    let Ok(entity) = std::env::var("IDENTIFIER") else {
+        eprintln!("health_reporter: IDENTIFIER is not set, start it through score-cli");
+        return ExitCode::FAILURE;
+    };
+    let monitor = match Monitor::<Checkpoint>::new(&entity) {
+        Ok(monitor) => monitor,
+        Err(e) => {
+            eprintln!(
+                "health_reporter: cannot register {} with the health monitor: {:?}",
+                entity, e
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                eprintln!("health_reporter: ignoring line {:?}: {}", line, e);
+                continue;
+            }
+        };
+        let mut alive = message["alive"].as_array().into_iter().flatten();
+        if alive.any(|name| name.as_str() == Some(entity.as_str())) {
+            monitor.report_checkpoint(Checkpoint::Alive);
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,274 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Privileged helper performing the setup steps that need root for an unprivileged runner.
+//!
+//! The runner starts `score-cli --helper` once through `sudo` or `pkexec` and
+//! sends it `ip`/`tc` invocations as JSON lines over stdin. The helper only
+//! runs whitelisted operations: creating virtual CAN interfaces, bridges, veth
+//! pairs and `score-*` network namespaces, and configuring or deleting what it
+//! created itself or existing CAN interfaces. Everything else is refused. The
+//! tools are run from the system directories with an empty environment.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Mutex;
+
+/// Directories `ip` and `tc` are taken from, never from the `PATH` of the invoking user.
+const TOOL_DIRS: &[&str] = &["/usr/sbin", "/sbin", "/usr/bin", "/bin"];
+
+/// Linux `ARPHRD_CAN`, the `type` of CAN interfaces in sysfs.
+const ARPHRD_CAN: &str = "280";
+
+static HELPER: Mutex<Option<Client>> = Mutex::new(None);
+
+#[derive(Serialize, Deserialize)]
+struct Request {
+    tool: String,
+    args: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Response {
+    ok: bool,
+    stderr: String,
+}
+
+struct Client {
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+/// Starts the helper through `launcher` (e.g. `sudo` or `pkexec`), which may ask for a password.
+pub fn start(launcher: &str) -> Result<()> {
+    let mut helper = HELPER.lock().unwrap();
+    if helper.is_some() {
+        return Ok(());
+    }
+    let exe = std::env::current_exe().context("Failed to find the path of the CLI executable")?;
+    let mut words = launcher.split_whitespace();
+    let program = words.next().context("Empty privileged helper command")?;
+    let mut child = Command::new(program)
+        .args(words)
+        .arg(&exe)
+        .arg("--helper")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start the privileged helper with `{}`", launcher))?;
+    let mut client = Client {
+        stdin: child.stdin.take().unwrap(),
+        stdout: BufReader::new(child.stdout.take().unwrap()),
+        _child: child,
+    };
+    // The helper greets once it runs, after the launcher authenticated the user.
+    let mut greeting = String::new();
+    client.stdout.read_line(&mut greeting)?;
+    if greeting.trim() != "ready" {
+        bail!(
+            "The privileged helper did not start (`{}` failed or was denied)",
+            launcher
+        );
+    }
+    println!("Privileged helper started with {}", launcher);
+    *helper = Some(client);
+    Ok(())
+}
+
+/// Runs `tool args` through the helper if one was started.
+///
+/// Returns `None` without a helper, otherwise whether the tool succeeded and its stderr.
+pub fn run(tool: &str, args: &[&str]) -> Option<Result<(bool, String)>> {
+    let mut helper = HELPER.lock().unwrap();
+    let client = helper.as_mut()?;
+    let request = Request {
+        tool: tool.to_string(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+    };
+    Some((|| {
+        writeln!(client.stdin, "{}", serde_json::to_string(&request)?)?;
+        client.stdin.flush()?;
+        let mut line = String::new();
+        if client.stdout.read_line(&mut line)? == 0 {
+            bail!("The privileged helper exited");
+        }
+        let response: Response = serde_json::from_str(&line).context("Invalid response of the privileged helper")?;
+        Ok((response.ok, response.stderr))
+    })())
+}
+
+/// Helper mode: serves requests from stdin until the runner closes it.
+pub fn serve() -> Result<()> {
+    let mut created = Created::default();
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    writeln!(out, "ready")?;
+    out.flush()?;
+    for line in std::io::stdin().lock().lines() {
+        let request: Request = serde_json::from_str(&line?).context("Invalid request")?;
+        let args: Vec<&str> = request.args.iter().map(String::as_str).collect();
+        let response = match created.check(&request.tool, &args) {
+            Err(e) => Response {
+                ok: false,
+                stderr: format!("refused by the privileged helper: {}", e),
+            },
+            Ok(()) => match tool_path(&request.tool).and_then(|tool| {
+                Command::new(tool)
+                    .args(&args)
+                    .env_clear()
+                    .output()
+                    .map_err(anyhow::Error::from)
+            }) {
+                Ok(output) => {
+                    if output.status.success() {
+                        created.record(&args);
+                    }
+                    Response {
+                        ok: output.status.success(),
+                        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                    }
+                }
+                Err(e) => Response {
+                    ok: false,
+                    stderr: format!("failed to run {}: {:#}", request.tool, e),
+                },
+            },
+        };
+        writeln!(out, "{}", serde_json::to_string(&response)?)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// The absolute path of the system's `tool`.
+fn tool_path(tool: &str) -> Result<std::path::PathBuf> {
+    TOOL_DIRS
+        .iter()
+        .map(|dir| std::path::Path::new(dir).join(tool))
+        .find(|path| path.is_file())
+        .with_context(|| format!("{} is not installed in {}", tool, TOOL_DIRS.join(", ")))
+}
+
+/// Interfaces and namespaces created through the helper, the only ones it modifies.
+#[derive(Default)]
+struct Created {
+    interfaces: BTreeSet<String>,
+    namespaces: BTreeSet<String>,
+}
+
+impl Created {
+    /// Checks that `tool args` is a whitelisted operation.
+    fn check(&self, tool: &str, args: &[&str]) -> Result<()> {
+        let name = |name: &str| -> Result<()> {
+            if name.is_empty() || name.starts_with('-') || name.len() > 15 || name.contains('/') {
+                bail!("invalid interface name {:?}", name);
+            }
+            Ok(())
+        };
+        let own = |interface: &str| -> Result<()> {
+            if !self.interfaces.contains(interface) {
+                bail!("{} was not created by the helper", interface);
+            }
+            Ok(())
+        };
+        let own_or_can = |interface: &str| -> Result<()> {
+            if !self.interfaces.contains(interface) && !is_can(interface) {
+                bail!("{} is neither a CAN interface nor created by the helper", interface);
+            }
+            Ok(())
+        };
+        let own_ns = |ns: &str| -> Result<()> {
+            if !self.namespaces.contains(ns) {
+                bail!("{} was not created by the helper", ns);
+            }
+            Ok(())
+        };
+        match (tool, args) {
+            // Setting up a network node inside a namespace of its own, see `network::setup`.
+            ("ip", ["-n", ns, "link", "set", "dev", interface, "name", "eth0"]) => own_ns(ns).and(own(interface)),
+            ("ip", ["-n", ns, "addr", "add", address, "dev", "eth0"]) => own_ns(ns).and(check_address(address)),
+            ("ip", ["-n", ns, "link", "set", "dev", "eth0" | "lo", "up"]) => own_ns(ns),
+            ("ip", ["-n", ns, "route", "add", "224.0.0.0/4", "dev", "eth0"]) => own_ns(ns),
+            ("tc", ["-n", ns, "qdisc", "add", "dev", "eth0", "root", "netem", netem @ ..]) => {
+                own_ns(ns).and(check_netem(netem))
+            }
+            ("ip", ["netns", "add", ns]) if ns.starts_with("score-") => name(&ns["score-".len()..]),
+            ("ip", ["netns", "delete", ns]) if self.namespaces.contains(*ns) => Ok(()),
+            ("ip", ["link", "add", "dev", interface, "type", "vcan"]) => name(interface),
+            ("ip", ["link", "add", "name", bridge, "type", "bridge"]) => name(bridge),
+            ("ip", ["link", "add", host, "type", "veth", "peer", "name", peer]) => name(host).and(name(peer)),
+            ("ip", ["link", "delete", "dev", interface]) => own(interface),
+            ("ip", ["link", "set", "dev", interface, "up" | "down"]) => own_or_can(interface),
+            ("ip", ["link", "set", "dev", interface, "type", "can", "bitrate", bitrate]) => {
+                bitrate.parse::<u32>().context("invalid bitrate")?;
+                own_or_can(interface)
+            }
+            ("ip", ["link", "set", "dev", interface, "master", bridge]) => own(interface).and(own(bridge)),
+            ("ip", ["link", "set", "dev", interface, "netns", ns]) if self.namespaces.contains(*ns) => own(interface),
+            _ => bail!("`{} {}` is not a whitelisted operation", tool, args.join(" ")),
+        }
+    }
+
+    /// Remembers what a successful operation created or removed.
+    fn record(&mut self, args: &[&str]) {
+        match args {
+            ["netns", "add", ns] => {
+                self.namespaces.insert(ns.to_string());
+            }
+            ["netns", "delete", ns] => {
+                self.namespaces.remove(*ns);
+            }
+            ["link", "add", "dev" | "name", interface, ..] => {
+                self.interfaces.insert(interface.to_string());
+            }
+            ["link", "add", host, "type", "veth", "peer", "name", peer] => {
+                self.interfaces.insert(host.to_string());
+                self.interfaces.insert(peer.to_string());
+            }
+            ["link", "delete", "dev", interface] => {
+                self.interfaces.remove(*interface);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Checks an address with prefix length like `10.0.0.2/24`.
+fn check_address(address: &str) -> Result<()> {
+    let (ip, prefix) = address.split_once('/').unwrap_or((address, "32"));
+    if ip.parse::<std::net::IpAddr>().is_err() || prefix.parse::<u8>().is_err() {
+        bail!("invalid address {:?}", address);
+    }
+    Ok(())
+}
+
+/// Checks the parameters of a netem qdisc, as built by `network::Impairment`.
+fn check_netem(args: &[&str]) -> Result<()> {
+    for arg in args {
+        let keyword = matches!(*arg, "delay" | "loss" | "rate");
+        // Values like "20ms", "1.5%" or "10mbit".
+        let value = arg.starts_with(|c: char| c.is_ascii_digit())
+            && arg.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '%');
+        if !keyword && !value {
+            bail!("invalid netem parameter {:?}", arg);
+        }
+    }
+    Ok(())
+}
+
+fn is_can(interface: &str) -> bool {
+    fs::read_to_string(format!("/sys/class/net/{}/type", interface)).is_ok_and(|t| t.trim() == ARPHRD_CAN)
+}
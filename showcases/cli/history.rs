@@ -0,0 +1,203 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Local database of past example results, powering ETAs, `history --slowest` and flake rates.
+//!
+//! The database is a JSON file at `$SCORE_CLI_HISTORY`, by default
+//! `$XDG_STATE_HOME/score-cli/history.json` (`~/.local/state/...`). Unlike run
+//! directories under `$TMPDIR` it survives reboots. Only the most recent
+//! results of every example are kept.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::clock;
+use crate::report::{RunReport, Status};
+
+/// Results kept per example.
+const MAX_ENTRIES: usize = 100;
+/// Results an ETA is based on.
+const RECENT_ENTRIES: usize = 20;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct History {
+    examples: BTreeMap<String, Vec<Entry>>, // oldest first
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    run_id: String,
+    time: String, // RFC 3339 UTC
+    status: Status,
+    duration_ms: u128,
+    attempts: u32,
+}
+
+/// Statistics of an example over its recorded results.
+#[derive(Debug, Serialize)]
+pub struct ExampleStats {
+    pub name: String,
+    pub runs: usize,
+    pub passed: usize,   // passed, flaky or xpass
+    pub failed: usize,   // failed or xfail
+    pub flaky: usize,    // passed only on a retry
+    pub flake_rate: f64, // share of runs that were flaky or changed outcome compared to the run before
+    pub median_ms: u128,
+    pub p90_ms: u128,
+    pub last_status: Status,
+    pub last_run: String,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// Directory for state kept across runs and reboots: `$XDG_STATE_HOME/score-cli` (`~/.local/state/score-cli`).
+pub(crate) fn state_dir() -> PathBuf {
+    let state = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state")))
+        .unwrap_or_else(std::env::temp_dir);
+    state.join("score-cli")
+}
+
+fn path() -> PathBuf {
+    match std::env::var_os("SCORE_CLI_HISTORY") {
+        Some(path) => PathBuf::from(path),
+        None => state_dir().join("history.json"),
+    }
+}
+
+fn load() -> History {
+    fs::read_to_string(path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Adds the results of the examples that ran in `report`.
+pub fn record(report: &RunReport) -> Result<()> {
+    let mut history = load();
+    let time = clock::rfc3339(SystemTime::now());
+    for example in report.examples.iter().filter(|e| e.status != Status::Skipped) {
+        let entries = history.examples.entry(example.name.clone()).or_default();
+        entries.push(Entry {
+            run_id: report.run_id.clone(),
+            time: time.clone(),
+            status: example.status,
+            duration_ms: example.duration_ms,
+            attempts: example.attempts,
+        });
+        let excess = entries.len().saturating_sub(MAX_ENTRIES);
+        entries.drain(..excess);
+    }
+    let path = path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create history directory {:?}", dir))?;
+    }
+    // Written to a temporary file first, so concurrent runners never leave a truncated database.
+    let temp = path.with_extension(format!("json.{}", std::process::id()));
+    fs::write(&temp, serde_json::to_string(&history)?)
+        .with_context(|| format!("Failed to write history {:?}", temp))?;
+    fs::rename(&temp, &path).with_context(|| format!("Failed to write history {:?}", path))
+}
+
+/// Median duration of every example over its recent results.
+pub fn expected_durations() -> BTreeMap<String, Duration> {
+    load()
+        .examples
+        .into_iter()
+        .filter_map(|(name, entries)| {
+            let recent = &entries[entries.len().saturating_sub(RECENT_ENTRIES)..];
+            Some((name, Duration::from_millis(percentile(recent, 50)? as u64)))
+        })
+        .collect()
+}
+
+fn percentile(entries: &[Entry], percent: usize) -> Option<u128> {
+    let mut durations: Vec<u128> = entries.iter().map(|e| e.duration_ms).collect();
+    durations.sort_unstable();
+    let last = durations.len().checked_sub(1)?;
+    durations.get(last * percent / 100).copied()
+}
+
+/// Statistics of all examples with recorded results, the slowest first if `slowest` is set.
+pub fn stats(slowest: Option<usize>) -> Vec<ExampleStats> {
+    let mut stats: Vec<ExampleStats> = load()
+        .examples
+        .into_iter()
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(name, entries)| {
+            let count = |statuses: &[Status]| entries.iter().filter(|e| statuses.contains(&e.status)).count();
+            let passed = |status: Status| matches!(status, Status::Passed | Status::Flaky | Status::Xpass);
+            let flips = entries
+                .windows(2)
+                .filter(|w| passed(w[0].status) != passed(w[1].status))
+                .count();
+            let flaky = count(&[Status::Flaky]);
+            let last = entries.last().expect("not empty");
+            ExampleStats {
+                runs: entries.len(),
+                passed: count(&[Status::Passed, Status::Flaky, Status::Xpass]),
+                failed: count(&[Status::Failed, Status::Xfail]),
+                flaky,
+                flake_rate: (flaky + flips) as f64 / entries.len() as f64,
+                median_ms: percentile(&entries, 50).unwrap_or(0),
+                p90_ms: percentile(&entries, 90).unwrap_or(0),
+                last_status: last.status,
+                last_run: last.time.clone(),
+                name,
+            }
+        })
+        .collect();
+    if let Some(count) = slowest {
+        stats.sort_by_key(|s| std::cmp::Reverse(s.median_ms));
+        stats.truncate(count);
+    }
+    stats
+}
+
+/// Renders `stats` as a table or as JSON.
+pub fn render(stats: &[ExampleStats], format: Format) -> Result<String> {
+    if let Format::Json = format {
+        return Ok(serde_json::to_string_pretty(stats)? + "\n");
+    }
+    let width = stats.iter().map(|s| s.name.chars().count()).max().unwrap_or(0).max(7);
+    let mut out = format!(
+        "{:<width$}  {:>4}  {:>6}  {:>6}  {:>6}  {:>9}  {:>9}  {}\n",
+        "Example", "Runs", "Passed", "Failed", "Flake", "Median", "P90", "Last"
+    );
+    for s in stats {
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>4}  {:>6}  {:>6}  {:>5.0}%  {:>8.2}s  {:>8.2}s  {} ({})",
+            s.name,
+            s.runs,
+            s.passed,
+            s.failed,
+            s.flake_rate * 100.0,
+            s.median_ms as f64 / 1000.0,
+            s.p90_ms as f64 / 1000.0,
+            s.last_status.name(),
+            s.last_run
+        );
+    }
+    Ok(out)
+}
@@ -0,0 +1,260 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Facts about the host the examples run on, read from `/proc` and `/sys`.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Host requirements of an example. Examples whose requirements are not met are skipped.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct HostRequirements {
+    linux: Option<String>, // minimum kernel version, e.g. ">=5.15"
+    #[serde(default)]
+    needs_vcan: bool, // the vcan kernel module
+    #[serde(default)]
+    needs_root: bool,
+    min_mem_mb: Option<u64>, // total memory of the host
+    #[serde(default)]
+    arch: Vec<String>, // supported CPU architectures, e.g. ["x86_64", "aarch64"]
+}
+
+impl HostRequirements {
+    /// Why the example cannot run on this host, e.g. `requires vcan, root`, or `None` if it can.
+    pub fn skip_reason(&self) -> Result<Option<String>> {
+        let mut missing = Vec::new();
+        if let Some(ref spec) = self.linux {
+            let version = spec.trim().trim_start_matches(">=").trim();
+            let Some(minimum) = parse_version(version) else {
+                bail!("Invalid kernel version requirement \"{}\", use e.g. \">=5.15\"", spec);
+            };
+            if !cfg!(target_os = "linux") || kernel_version().is_none_or(|running| running < minimum) {
+                missing.push(format!("linux>={}", version));
+            }
+        }
+        if self.needs_vcan && !module_available("vcan") {
+            missing.push("vcan".to_string());
+        }
+        if self.needs_root && !is_root() {
+            missing.push("root".to_string());
+        }
+        if let Some(min_mem_mb) = self.min_mem_mb {
+            if total_memory_mb().is_none_or(|total| total < min_mem_mb) {
+                missing.push(format!("{} MiB memory", min_mem_mb));
+            }
+        }
+        if !self.arch.is_empty() && !self.arch.iter().any(|arch| arch == env::consts::ARCH) {
+            missing.push(format!("arch {}", self.arch.join("|")));
+        }
+        Ok((!missing.is_empty()).then(|| format!("requires {}", missing.join(", "))))
+    }
+}
+
+/// Description of the host a run was recorded on, embedded in reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub os: Option<String>, // PRETTY_NAME from /etc/os-release
+    pub kernel: Option<String>,
+    pub arch: String,
+    pub cpu_model: Option<String>,
+    pub cpus: usize,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub isolated_cpus: String,
+    pub memory_mb: Option<u64>,
+    pub cgroup: String,             // "v2", "v1" or "none"
+    pub container: Option<String>,  // container runtime the runner is in, e.g. "docker"
+    pub git_commit: Option<String>, // commit of the examples tree
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub git_dirty: bool, // the examples tree has uncommitted changes
+}
+
+impl HostInfo {
+    /// Describes this host, with the git commit of the examples in `examples_dir`.
+    pub fn collect(examples_dir: &Path) -> HostInfo {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(examples_dir)
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        let git_commit = git(&["rev-parse", "HEAD"]);
+        let git_dirty = git_commit.is_some() && git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty());
+        HostInfo {
+            os: os_name(),
+            kernel: kernel_release(),
+            arch: env::consts::ARCH.to_string(),
+            cpu_model: cpu_model(),
+            cpus: cpu_count(),
+            isolated_cpus: isolated_cpus(),
+            memory_mb: total_memory_mb(),
+            cgroup: if has_cgroup_v2() {
+                "v2"
+            } else if Path::new("/sys/fs/cgroup/memory").is_dir() {
+                "v1"
+            } else {
+                "none"
+            }
+            .to_string(),
+            container: container_runtime(),
+            git_commit,
+            git_dirty,
+        }
+    }
+}
+
+/// Name of the operating system from `/etc/os-release`, e.g. `Ubuntu 24.04.1 LTS`.
+fn os_name() -> Option<String> {
+    let release = fs::read_to_string("/etc/os-release").ok()?;
+    let line = release.lines().find(|line| line.starts_with("PRETTY_NAME="))?;
+    Some(line["PRETTY_NAME=".len()..].trim_matches('"').to_string())
+}
+
+/// Container runtime the runner is running in, if any.
+fn container_runtime() -> Option<String> {
+    // Set by podman, systemd-nspawn and LXC.
+    if let Ok(container) = env::var("container") {
+        return Some(container);
+    }
+    if Path::new("/.dockerenv").exists() {
+        return Some("docker".to_string());
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("podman".to_string());
+    }
+    let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+    ["kubepods", "docker", "containerd", "lxc"]
+        .iter()
+        .find(|runtime| cgroup.contains(*runtime))
+        .map(|runtime| runtime.to_string())
+}
+
+/// Kernel release, e.g. `6.8.0-45-generic`.
+pub fn kernel_release() -> Option<String> {
+    fs::read_to_string("/proc/sys/kernel/osrelease")
+        .ok()
+        .map(|release| release.trim().to_string())
+}
+
+/// Major and minor version of the running kernel.
+pub fn kernel_version() -> Option<(u32, u32)> {
+    parse_version(&kernel_release()?)
+}
+
+/// Parses the leading `major.minor` of a version like `5.15.0-91-generic`.
+pub fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+}
+
+/// CPU model, e.g. `Intel(R) Xeon(R) Gold 6248 CPU @ 2.50GHz`.
+pub fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    // x86 has "model name", many ARM kernels only "Hardware" or "Model".
+    ["model name", "Hardware", "Model"].iter().find_map(|key| {
+        cpuinfo.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name.trim() == *key).then(|| value.trim().to_string())
+        })
+    })
+}
+
+/// CPUs isolated from the scheduler with `isolcpus=`, e.g. `2-3`, empty if none.
+pub fn isolated_cpus() -> String {
+    fs::read_to_string("/sys/devices/system/cpu/isolated")
+        .map(|cpus| cpus.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Number of CPUs the runner may use.
+pub fn cpu_count() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// Whether the runner has root privileges (effective uid 0).
+pub fn is_root() -> bool {
+    status_field("Uid:")
+        .and_then(|uids| uids.split_whitespace().nth(1).map(|euid| euid == "0"))
+        .unwrap_or(false)
+}
+
+/// Whether the unified cgroup v2 hierarchy is mounted at `/sys/fs/cgroup`.
+pub fn has_cgroup_v2() -> bool {
+    Path::new("/sys/fs/cgroup/cgroup.controllers").is_file()
+}
+
+/// Whether a kernel module is loaded, built in, or installed and loadable with modprobe.
+pub fn module_available(name: &str) -> bool {
+    let normalized = name.replace('-', "_");
+    if Path::new("/sys/module").join(&normalized).exists() {
+        return true;
+    }
+    let Some(release) = kernel_release() else {
+        return false;
+    };
+    let dir = Path::new("/lib/modules").join(release);
+    ["modules.builtin", "modules.dep"].iter().any(|index| {
+        fs::read_to_string(dir.join(index)).is_ok_and(|content| {
+            content.lines().any(|line| {
+                let path = line.split(':').next().unwrap_or(line);
+                let file = path.rsplit('/').next().unwrap_or(path);
+                let module = file.split(".ko").next().unwrap_or(file);
+                module.replace('-', "_") == normalized
+            })
+        })
+    })
+}
+
+/// Total memory of the host in MiB.
+pub fn total_memory_mb() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb / 1024)
+}
+
+/// Size and available space of the file system containing `path`, in KiB.
+pub fn filesystem_kb(path: &str) -> Option<(u64, u64)> {
+    let output = Command::new("df").args(["-Pk", path]).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let fields: Vec<&str> = stdout.lines().nth(1)?.split_whitespace().collect();
+    Some((fields.get(1)?.parse().ok()?, fields.get(3)?.parse().ok()?))
+}
+
+/// Soft limit of a resource as listed in `/proc/self/limits`, `None` if unlimited.
+pub fn soft_limit(name: &str) -> Option<Option<u64>> {
+    let limits = fs::read_to_string("/proc/self/limits").ok()?;
+    let line = limits.lines().find(|line| line.starts_with(name))?;
+    let value = line[name.len()..].split_whitespace().next()?;
+    Some(value.parse().ok())
+}
+
+/// Finds an executable in `PATH`.
+pub fn find_in_path(program: &str) -> Option<PathBuf> {
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+fn status_field(field: &str) -> Option<String> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with(field))?;
+    Some(line[field.len()..].trim().to_string())
+}
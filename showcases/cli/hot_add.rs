@@ -0,0 +1,160 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Apps added to a running example, e.g. another subscriber attached mid-demo.
+//!
+//! With `--add-apps-dir <dir>` every `*.json` file appearing in the directory
+//! while an example runs holds an app like in a `*.score.json` file. The runner
+//! starts it as the next app of the example, tracks it like the others and
+//! reports it in the same run. Handled files are renamed to `*.json.added`, or
+//! `*.json.rejected` if the app could not be started, so each file is taken once.
+//! `score-cli serve` gives every example it runs such a directory of its own and
+//! writes the apps clients post to it, see `serve`.
+//!
+//! Writers are expected to write the file under another name and rename it to
+//! `*.json` once complete. Hidden files are left alone, and a file holding an
+//! incomplete JSON object is taken only once it is complete. The directory must
+//! belong to the user and be closed to writes by others, and files of other
+//! users are rejected, so nobody else can add apps to an example.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Write};
+use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{config_dir, overlay, presets, sys, AppConfig};
+
+/// Checks that only the user can add files to `dir`.
+pub fn check_dir(dir: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(dir).with_context(|| format!("Failed to inspect {:?}", dir))?;
+    if !metadata.is_dir() {
+        bail!("--add-apps-dir {:?} is not a directory", dir);
+    }
+    if metadata.uid() != sys::effective_uid() {
+        bail!(
+            "--add-apps-dir {:?} belongs to another user (uid {})",
+            dir,
+            metadata.uid()
+        );
+    }
+    if metadata.mode() & 0o022 != 0 {
+        bail!(
+            "--add-apps-dir {:?} is writable by other users (mode {:o})",
+            dir,
+            metadata.mode() & 0o777
+        );
+    }
+    Ok(())
+}
+
+/// The app files waiting in `dir`, in the order of their names, with the app each describes.
+pub fn take(dir: &Path) -> Vec<(PathBuf, Result<AppConfig>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let app = read(&file, dir);
+            // Still being written, taken at a later poll.
+            let incomplete = app.as_ref().is_err_and(|e| {
+                e.downcast_ref::<serde_json::Error>()
+                    .is_some_and(serde_json::Error::is_eof)
+            });
+            (!incomplete).then_some((file, app))
+        })
+        .collect()
+}
+
+/// Marks `file` as handled, so it is not taken again.
+pub fn done(file: &Path, added: bool) {
+    let mut name = file.as_os_str().to_owned();
+    name.push(if added { ".added" } else { ".rejected" });
+    if let Err(e) = fs::rename(file, &name) {
+        println!("⚠️  Failed to rename {:?}: {}", file, e);
+    }
+}
+
+/// Writes `app` to `dir` for the running example to take, under a temporary name first.
+pub fn submit(dir: &Path, app: &Value) -> Result<PathBuf> {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    let name = format!("app-{:06}.json", NEXT.fetch_add(1, Ordering::Relaxed));
+    let partial = dir.join(format!(".{}.tmp", name));
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .custom_flags(sys::O_NOFOLLOW)
+        .open(&partial)
+        .with_context(|| format!("Failed to create {:?}", partial))?;
+    file.write_all(serde_json::to_string(app)?.as_bytes())
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("Failed to write {:?}", partial))?;
+    let path = dir.join(name);
+    fs::rename(&partial, &path).with_context(|| format!("Failed to rename {:?}", partial))?;
+    Ok(path)
+}
+
+fn read(file: &Path, dir: &Path) -> Result<AppConfig> {
+    let mut opened = OpenOptions::new().read(true).custom_flags(sys::O_NOFOLLOW).open(file)?;
+    let metadata = opened.metadata()?;
+    if !metadata.is_file() {
+        bail!("it is no regular file");
+    }
+    if metadata.uid() != sys::effective_uid() {
+        bail!("it belongs to another user (uid {})", metadata.uid());
+    }
+    if metadata.mode() & 0o022 != 0 {
+        bail!("it is writable by other users (mode {:o})", metadata.mode() & 0o777);
+    }
+    let mut content = String::new();
+    opened.read_to_string(&mut content)?;
+    let app: Value = serde_json::from_str(&content)?;
+    let mut example = json!({
+        "name": "added app",
+        "description": "App added to a running example",
+        "apps": [app],
+    });
+    config_dir::anchor(&mut example, dir)?;
+    // Checked and resolved like the apps of an example file, except for presets of the directory.
+    let source = overlay::Source {
+        value: example,
+        file: file.to_path_buf(),
+        origins: Vec::new(),
+    };
+    let mut configs = crate::resolve_examples(vec![source], &presets::Presets::new(), None)?;
+    let Some(app) = configs.remove(0).apps.pop() else {
+        bail!("its \"when\" condition does not hold on this host");
+    };
+    // Set up once before the first app starts, or only meaningful while starting.
+    let unsupported = [
+        ("barrier", app.barrier),
+        ("delay", app.delay.is_some()),
+        ("sockets", !app.sockets.is_empty()),
+        ("secrets", !app.secrets.is_empty()),
+        ("forward_logs", app.forward_logs.is_some()),
+    ];
+    if let Some((field, _)) = unsupported.iter().find(|(_, used)| *used) {
+        bail!("\"{}\" cannot be used by an app added while the example runs", field);
+    }
+    Ok(app)
+}
@@ -0,0 +1,206 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! `score-cli init`: walks the user through the settings in
+//! [`settings::user_file`] and writes it.
+//!
+//! Every question starts with the current value, from the settings file or
+//! what the CLI would use without one, so running `init` again edits the file.
+//! Settings it does not ask for are kept.
+//! The presets and backends offered are the ones the examples in the chosen
+//! directory use, backends being the values their `when` conditions compare
+//! `${PROFILE}` with.
+
+use anyhow::{bail, Result};
+use cliclack::{confirm, input, intro, outro, select};
+use std::collections::BTreeSet;
+use std::env;
+use std::path::PathBuf;
+
+use crate::output::Timestamps;
+use crate::progress::ProgressMode;
+use crate::settings::{self, Settings, Value};
+use crate::{read_examples, timeline};
+
+/// Asks for the settings and writes them to the settings file of the user.
+pub fn run() -> Result<()> {
+    let Some(path) = settings::user_file() else {
+        bail!("Neither XDG_CONFIG_HOME nor HOME is set, there is no place for the settings");
+    };
+    // Only the settings of the user, those of the system and project stay in their files.
+    // Broken settings are what init is run to fix, so they only give no defaults.
+    let current = if path.exists() {
+        Settings::read(&path).unwrap_or_else(|e| {
+            eprintln!("⚠️  Not taking defaults from the current settings: {:#}", e);
+            Settings::default()
+        })
+    } else {
+        Settings::default()
+    };
+    let string = |key: &str| match current.get(key) {
+        Some(Value::String(value)) => Some(value.clone()),
+        Some(Value::List(values)) => env::join_paths(values).ok().map(|v| v.to_string_lossy().into_owned()),
+        _ => None,
+    };
+    intro(format!("score-cli settings in {}", path.display()))?;
+
+    let default_root = string("root").unwrap_or_else(|| crate::examples_root().display().to_string());
+    let root: String = input("Examples directory (several separated by ':')")
+        .default_input(&default_root)
+        .validate(
+            |value: &String| match env::split_paths(value).find(|dir| !dir.is_dir()) {
+                Some(dir) => Err(format!("{} is not a directory", dir.display())),
+                None => Ok(()),
+            },
+        )
+        .interact()?;
+    let roots: Vec<PathBuf> = env::split_paths(&root).collect();
+    let (examples, presets) = match read_examples(&roots) {
+        Ok(found) => found,
+        Err(e) => {
+            cliclack::log::warning(format!("{:#}", e))?;
+            Default::default()
+        }
+    };
+
+    let mut preset_names: Vec<&String> = presets.keys().collect();
+    preset_names.sort();
+    let preset = if preset_names.is_empty() {
+        None
+    } else {
+        let mut items = vec![(
+            String::new(),
+            "none".to_string(),
+            "use the presets the examples reference",
+        )];
+        items.extend(preset_names.iter().map(|name| (name.to_string(), name.to_string(), "")));
+        let chosen: String = select("Default environment preset")
+            .items(&items)
+            .initial_value(string("preset").unwrap_or_default())
+            .interact()?;
+        (!chosen.is_empty()).then_some(chosen)
+    };
+
+    let default_log_dir = string("log_dir").unwrap_or_else(|| timeline::runs_root().display().to_string());
+    let log_dir: String = input("Directory of the run logs")
+        .default_input(&default_log_dir)
+        .interact()?;
+
+    let color: bool = confirm("Use colors?")
+        .initial_value(current.get("color") != Some(&Value::Bool(false)))
+        .interact()?;
+    let progress = choose::<ProgressMode>("Show a progress line instead of app output", string("progress"))?;
+    let timestamps = choose::<Timestamps>("Timestamps of app output lines", string("timestamps"))?;
+
+    let backends = backends(examples.iter().map(|example| &example.value));
+    let backend = if backends.is_empty() {
+        let mut prompt = input("Target backend, the ${PROFILE} of `when` conditions (empty for none)").required(false);
+        if let Some(backend) = string("backend") {
+            prompt = prompt.default_input(&backend);
+        }
+        let value: String = prompt.interact()?;
+        value
+    } else {
+        let mut items = vec![(String::new(), "none".to_string(), "leave ${PROFILE} unset")];
+        items.extend(backends.iter().map(|name| (name.clone(), name.clone(), "")));
+        select("Target backend, the ${PROFILE} of `when` conditions")
+            .items(&items)
+            .initial_value(string("backend").unwrap_or_default())
+            .interact()?
+    };
+
+    let mut chosen = Settings::default();
+    let mut set = |key: &str, value: Value| chosen.values.push((key.to_string(), value));
+    set(
+        "root",
+        match roots.as_slice() {
+            [root] => Value::String(root.display().to_string()),
+            _ => Value::List(roots.iter().map(|root| root.display().to_string()).collect()),
+        },
+    );
+    if let Some(preset) = preset {
+        set("preset", Value::String(preset));
+    }
+    set("log_dir", Value::String(log_dir));
+    set("color", Value::Bool(color));
+    set("progress", Value::String(progress));
+    set("timestamps", Value::String(timestamps));
+    if !backend.trim().is_empty() {
+        set("backend", Value::String(backend.trim().to_string()));
+    }
+    // Keep the settings added to the file by hand.
+    let asked = [
+        "root",
+        "preset",
+        "log_dir",
+        "color",
+        "progress",
+        "timestamps",
+        "backend",
+    ];
+    chosen.values.extend(
+        current
+            .values
+            .into_iter()
+            .filter(|(key, _)| !asked.contains(&key.as_str())),
+    );
+
+    if path.exists()
+        && !confirm(format!("Overwrite {}?", path.display()))
+            .initial_value(true)
+            .interact()?
+    {
+        outro("Settings left unchanged")?;
+        return Ok(());
+    }
+    chosen.write(&path)?;
+    outro(format!("Settings written to {}", path.display()))?;
+    Ok(())
+}
+
+/// Asks for a value of the flag enum `T`, starting with `current` or the default.
+fn choose<T: clap::ValueEnum + Default>(prompt: &str, current: Option<String>) -> Result<String> {
+    let name = |value: &T| value.to_possible_value().expect("not skipped");
+    let items: Vec<(String, String, String)> = T::value_variants()
+        .iter()
+        .map(|value| {
+            let possible = name(value);
+            let help = possible.get_help().map(|help| help.to_string()).unwrap_or_default();
+            (possible.get_name().to_string(), possible.get_name().to_string(), help)
+        })
+        .collect();
+    let initial = current.unwrap_or_else(|| name(&T::default()).get_name().to_string());
+    Ok(select(prompt).items(&items).initial_value(initial).interact()?)
+}
+
+/// The values `${PROFILE}` is compared with in the `when` conditions of `examples`.
+fn backends<'a>(examples: impl Iterator<Item = &'a serde_json::Value>) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    for example in examples {
+        let text = example.to_string();
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find("${PROFILE}") {
+            rest = &rest[start + "${PROFILE}".len()..];
+            let compared = rest
+                .trim_start()
+                .strip_prefix("==")
+                .or_else(|| rest.trim_start().strip_prefix("!="));
+            let Some(value) = compared.and_then(|c| c.trim_start().strip_prefix('\'')) else {
+                continue;
+            };
+            if let Some(end) = value.find('\'') {
+                found.insert(value[..end].to_string());
+            }
+        }
+    }
+    found
+}
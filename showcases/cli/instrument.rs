@@ -0,0 +1,255 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Memory checker / sanitizer instrumentation of apps.
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::report::InstrumentationReport;
+use crate::{AppConfig, ScoreConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Instrument {
+    /// Run every app under valgrind memcheck
+    Valgrind,
+    /// Run the `_asan` variant of every app with ASAN_OPTIONS set
+    AsanEnv,
+}
+
+impl Instrument {
+    /// Fails if the tool is not available on the host operating system.
+    pub fn check_supported(self) -> Result<()> {
+        if self == Instrument::Valgrind && !cfg!(target_os = "linux") {
+            bail!("valgrind is only supported on Linux, use asan-env instead");
+        }
+        Ok(())
+    }
+
+    /// How much longer delays and timeouts get, as instrumented apps run slower.
+    pub fn timeout_factor(self) -> u64 {
+        match self {
+            Instrument::Valgrind => 10,
+            Instrument::AsanEnv => 2,
+        }
+    }
+}
+
+/// `secs` seconds of a delay or timeout scaled by `factor`, failing with the `field` on overflow.
+pub fn scale(secs: u64, factor: u64, field: &str) -> Result<u64> {
+    match secs.checked_mul(factor) {
+        Some(scaled) => Ok(scaled),
+        None => bail!("{} of {} seconds is too large", field, secs),
+    }
+}
+
+/// Rejects delays and timeouts that overflow once scaled for the slowest instrumentation.
+pub fn validate(configs: &[ScoreConfig]) -> Result<()> {
+    let factor = Instrument::value_variants()
+        .iter()
+        .map(|instrument| instrument.timeout_factor())
+        .max()
+        .unwrap_or(1);
+    for config in configs {
+        let fields = [
+            ("barrier_timeout", config.barrier_timeout),
+            ("someip_sd.timeout", config.someip_sd.as_ref().map(|sd| sd.timeout)),
+        ];
+        for (field, secs) in fields {
+            if let Some(secs) = secs {
+                scale(secs, factor, field).with_context(|| format!("Example '{}'", config.name))?;
+            }
+        }
+        for (i, app) in config.apps.iter().enumerate() {
+            let fields = [
+                ("stop_timeout", app.stop_timeout),
+                ("start_timeout", app.start_timeout),
+                ("delay", app.delay),
+            ];
+            for (field, secs) in fields {
+                if let Some(secs) = secs {
+                    scale(secs, factor, field).with_context(|| format!("Example '{}', app {}", config.name, i + 1))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// How to launch one app under instrumentation.
+pub struct Instrumented {
+    pub program: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+    log_prefix: Option<PathBuf>,
+}
+
+/// Determines program, leading arguments and environment for app `index`.
+///
+/// Tool logs are written to `dir` as `app<index>.<tool>.<pid>[.log]`. With
+/// `strict`, a missing ASan variant fails instead of running the plain binary.
+pub(crate) fn prepare(
+    instrument: Option<Instrument>,
+    strict: bool,
+    app: &AppConfig,
+    index: usize,
+    dir: &Path,
+) -> Result<Instrumented> {
+    Ok(match instrument {
+        None => Instrumented {
+            program: app.path.clone(),
+            args: Vec::new(),
+            env: Vec::new(),
+            log_prefix: None,
+        },
+        Some(Instrument::Valgrind) => {
+            let log_prefix = dir.join(format!("app{}.valgrind", index));
+            Instrumented {
+                program: "valgrind".to_string(),
+                args: vec![
+                    "--tool=memcheck".to_string(),
+                    "--leak-check=full".to_string(),
+                    format!("--log-file={}.%p.log", log_prefix.display()),
+                    app.path.clone(),
+                ],
+                env: Vec::new(),
+                log_prefix: Some(log_prefix),
+            }
+        }
+        Some(Instrument::AsanEnv) => {
+            let log_prefix = dir.join(format!("app{}.asan", index));
+            let program = match &app.asan_path {
+                Some(path) => path.clone(),
+                None => {
+                    let variant = format!("{}_asan", app.path);
+                    if Path::new(&variant).exists() {
+                        variant
+                    } else if strict {
+                        bail!("App {}: no ASan variant of {} found (--strict)", index, app.path);
+                    } else {
+                        println!(
+                            "App {}: no ASan variant of {} found, running the uninstrumented binary",
+                            index, app.path
+                        );
+                        app.path.clone()
+                    }
+                }
+            };
+            let mut options = app.env.get("ASAN_OPTIONS").cloned().unwrap_or_default();
+            if !options.is_empty() {
+                options.push(':');
+            }
+            options.push_str(&format!("detect_leaks=1:log_path={}", log_prefix.display()));
+            Instrumented {
+                program,
+                args: Vec::new(),
+                env: vec![("ASAN_OPTIONS".to_string(), options)],
+                log_prefix: Some(log_prefix),
+            }
+        }
+    })
+}
+
+impl Instrumented {
+    /// Parses the tool logs written by the app, if it was instrumented.
+    pub fn collect(&self, instrument: Option<Instrument>) -> Result<Option<InstrumentationReport>> {
+        let (Some(tool), Some(prefix)) = (instrument, &self.log_prefix) else {
+            return Ok(None);
+        };
+        let logs = find_logs(prefix)?;
+        let mut report = InstrumentationReport {
+            tool,
+            errors: 0,
+            leaked_bytes: 0,
+            findings: Vec::new(),
+            logs: logs.clone(),
+        };
+        for log in &logs {
+            let content = fs::read_to_string(log)?;
+            match tool {
+                Instrument::Valgrind => parse_valgrind(&content, &mut report),
+                Instrument::AsanEnv => parse_asan(&content, &mut report),
+            }
+        }
+        Ok(Some(report))
+    }
+}
+
+fn find_logs(prefix: &Path) -> Result<Vec<PathBuf>> {
+    let (Some(dir), Some(stem)) = (prefix.parent(), prefix.file_name().and_then(|n| n.to_str())) else {
+        return Ok(Vec::new());
+    };
+    let mut logs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let matches = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with(&format!("{}.", stem)))
+            .unwrap_or(false);
+        if matches {
+            logs.push(path);
+        }
+    }
+    logs.sort();
+    Ok(logs)
+}
+
+/// Extracts the first number following `marker` in `line`, ignoring thousands separators.
+fn number_after(line: &str, marker: &str) -> Option<u64> {
+    let rest = &line[line.find(marker)? + marker.len()..];
+    let digits: String = rest
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == ',')
+        .filter(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+fn parse_valgrind(content: &str, report: &mut InstrumentationReport) {
+    for line in content.lines() {
+        if let Some(errors) = number_after(line, "ERROR SUMMARY:") {
+            report.errors += errors;
+        }
+        if let Some(bytes) = number_after(line, "definitely lost:") {
+            report.leaked_bytes += bytes;
+        }
+        // Error headlines are the first line of each error block, e.g. "==123== Invalid read of size 4".
+        if let Some((_, message)) = line.split_once("== ") {
+            if message.starts_with("Invalid ")
+                || message.starts_with("Conditional jump")
+                || message.contains("are definitely lost")
+            {
+                report.findings.push(message.trim().to_string());
+            }
+        }
+    }
+}
+
+fn parse_asan(content: &str, report: &mut InstrumentationReport) {
+    for line in content.lines() {
+        if let Some((_, message)) = line.split_once("ERROR: ") {
+            if message.starts_with("AddressSanitizer") || message.starts_with("LeakSanitizer") {
+                report.errors += 1;
+                report.findings.push(message.trim().to_string());
+            }
+        }
+        if let Some(bytes) = number_after(line, "SUMMARY: AddressSanitizer:") {
+            report.leaked_bytes += bytes;
+        }
+    }
+}
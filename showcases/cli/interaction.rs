@@ -0,0 +1,208 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Interaction scripts driving menu-driven apps like a user at a terminal.
+//!
+//! An app with an `interaction` script runs on a pseudo terminal, so it sees an
+//! interactive session. The runner waits for output matching the `expect`
+//! pattern of each step, also a prompt without a line break, and then types its
+//! `send` text. The terminal is the controlling terminal of the app, which
+//! leads its own session. A step not matching within its timeout stops the app
+//! and fails the example, however the app exits.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use crate::output::Capture;
+use crate::pattern::Pattern;
+use crate::{supervisor, sys, ScoreConfig};
+
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+/// Output kept for matching while waiting, older output is dropped.
+const MAX_PENDING_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    pub expect: String, // regex the output since the previous step must match
+    #[serde(default)]
+    pub send: String, // typed once it matched, e.g. "2\n" (default: nothing)
+    pub timeout: Option<u64>, // seconds to wait for the match (default: 10)
+}
+
+/// Rejects invalid patterns and apps with both an interaction script and stdin data.
+pub fn validate(configs: &[ScoreConfig]) -> Result<()> {
+    for config in configs {
+        for (i, app) in config.apps.iter().enumerate() {
+            if app.interaction.is_empty() {
+                continue;
+            }
+            if app.stdin.is_some() {
+                bail!(
+                    "Example '{}', app {}: \"interaction\" and \"stdin\" both feed the input, use one",
+                    config.name,
+                    i + 1
+                );
+            }
+            for (n, step) in app.interaction.iter().enumerate() {
+                Pattern::new(&step.expect).with_context(|| {
+                    format!(
+                        "Example '{}', app {}: invalid expect pattern of interaction step {}",
+                        config.name,
+                        i + 1,
+                        n + 1
+                    )
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A pseudo terminal connected to the stdin, stdout and stderr of a command.
+pub struct Pty {
+    master: File,
+    slave: File,
+}
+
+impl Pty {
+    /// Opens a pseudo terminal for the next start of `cmd`.
+    pub fn attach(cmd: &mut Command) -> Result<Pty> {
+        let (master, slave) = sys::open_pty().context("Failed to open a pseudo terminal")?;
+        cmd.stdin(Stdio::from(slave.try_clone()?));
+        cmd.stdout(Stdio::from(slave.try_clone()?));
+        cmd.stderr(Stdio::from(slave.try_clone()?));
+        // SAFETY: the closure only issues setsid and ioctl, which are async-signal-safe.
+        // Standard input is the terminal by the time it runs.
+        unsafe {
+            cmd.pre_exec(|| sys::set_controlling_terminal(0));
+        }
+        Ok(Pty { master, slave })
+    }
+
+    /// Runs `steps` against app `index`, started from `cmd` with pid `pid`, and captures its output.
+    ///
+    /// Closes the terminal side kept by the runner, so the output ends when the app exits.
+    pub fn run(
+        self,
+        cmd: &mut Command,
+        capture: &Capture,
+        index: usize,
+        label: &str,
+        pid: u32,
+        steps: &[Step],
+    ) -> Result<Vec<JoinHandle<()>>> {
+        cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+        drop(self.slave);
+        let steps = steps
+            .iter()
+            .map(|step| Ok((Pattern::new(&step.expect)?, step.clone())))
+            .collect::<Result<Vec<_>>>()?;
+        let mut sink = capture.sink(index, label)?;
+        let mut reader = self.master;
+        let writer = reader.try_clone()?;
+        let (tx, rx) = mpsc::channel::<Vec<u8>>();
+        let output = supervisor::runtime().spawn_blocking(move || {
+            let mut buf = [0u8; 4096];
+            // Reading fails with EIO instead of returning 0 once the app closed the terminal.
+            while let Ok(n @ 1..) = reader.read(&mut buf) {
+                sink.write(&buf[..n]);
+                let _ = tx.send(buf[..n].to_vec());
+            }
+        });
+        let capture = capture.clone();
+        // Opened before the thread starts, while the app cannot have been reaped yet.
+        let pidfd = sys::Pidfd::open(pid).ok();
+        let script =
+            supervisor::runtime().spawn_blocking(move || play(&capture, index, pid, pidfd, &steps, rx, writer));
+        Ok(vec![output, script])
+    }
+}
+
+/// Waits for each step's pattern in the output received on `rx` and types its text on `terminal`.
+fn play(
+    capture: &Capture,
+    index: usize,
+    pid: u32,
+    pidfd: Option<sys::Pidfd>, // signals the app instead of its pid where pidfds exist
+    steps: &[(Pattern, Step)],
+    rx: mpsc::Receiver<Vec<u8>>,
+    mut terminal: File,
+) {
+    let mut pending = String::new();
+    for (n, (pattern, step)) in steps.iter().enumerate() {
+        let timeout = Duration::from_secs(step.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS));
+        let deadline = Instant::now() + timeout;
+        let end = loop {
+            if let Some((_, end)) = pattern.find(&pending) {
+                break end;
+            }
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(chunk) => {
+                    pending.push_str(&String::from_utf8_lossy(&chunk));
+                    if pending.len() > MAX_PENDING_BYTES {
+                        let mut cut = pending.len() - MAX_PENDING_BYTES;
+                        while !pending.is_char_boundary(cut) {
+                            cut += 1;
+                        }
+                        pending.drain(..cut);
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    let message = format!(
+                        "interaction step {}: no output matching /{}/ within {:?}, stopping the app",
+                        n + 1,
+                        step.expect,
+                        timeout
+                    );
+                    println!("App {}: {}", index, capture.redact(&message));
+                    capture.event(&format!("app {}: {}", index, message));
+                    capture.fail(
+                        index,
+                        format!(
+                            "interaction step {}: no output matching /{}/ within {:?}",
+                            n + 1,
+                            step.expect,
+                            timeout
+                        ),
+                    );
+                    // The runner may have reaped the app meanwhile, then only a pidfd is sure not to hit another process.
+                    let _ = match pidfd {
+                        Some(ref pidfd) => pidfd.send(sys::Signal::TERM),
+                        None => sys::send(pid, sys::Signal::TERM),
+                    };
+                    return;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    capture.event(&format!(
+                        "app {}: closed its terminal before interaction step {} matched",
+                        index,
+                        n + 1
+                    ));
+                    return;
+                }
+            }
+        };
+        pending.drain(..end);
+        capture.event(&format!("app {}: interaction step {} matched", index, n + 1));
+        if !step.send.is_empty() && terminal.write_all(step.send.as_bytes()).is_err() {
+            return;
+        }
+    }
+}
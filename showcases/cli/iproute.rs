@@ -0,0 +1,72 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Helpers for driving `ip`/`tc` (iproute2) to configure host networking.
+
+use anyhow::{bail, Context, Result};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+
+use crate::helper;
+
+/// Runs `ip <args>`, turning failures into errors with remediation hints.
+pub fn ip(args: &[&str]) -> Result<()> {
+    run("ip", args)
+}
+
+/// Runs `tc <args>`, turning failures into errors with remediation hints.
+pub fn tc(args: &[&str]) -> Result<()> {
+    run("tc", args)
+}
+
+fn run(tool: &str, args: &[&str]) -> Result<()> {
+    if let Some(result) = helper::run(tool, args) {
+        let (success, stderr) = result?;
+        return check(tool, args, success, &stderr);
+    }
+    let output = match Command::new(tool).args(args).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            bail!(
+                "`{}` (iproute2) is required to configure networking but was not found in PATH",
+                tool
+            )
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to run `{}`", tool)),
+    };
+    check(
+        tool,
+        args,
+        output.status.success(),
+        &String::from_utf8_lossy(&output.stderr),
+    )
+}
+
+fn check(tool: &str, args: &[&str], success: bool, stderr: &str) -> Result<()> {
+    if success {
+        return Ok(());
+    }
+
+    let hint = if stderr.contains("Operation not permitted") {
+        " (configuring network interfaces requires root or CAP_NET_ADMIN, see --privileged-helper)"
+    } else if stderr.contains("Unknown device type") || stderr.contains("not supported") {
+        " (kernel support is missing, try e.g. `modprobe vcan`, `modprobe can` or `modprobe sch_netem`)"
+    } else {
+        ""
+    };
+    bail!("`{} {}` failed: {}{}", tool, args.join(" "), stderr.trim(), hint)
+}
+
+pub fn interface_exists(interface: &str) -> bool {
+    Path::new("/sys/class/net").join(interface).exists()
+}
@@ -0,0 +1,104 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! JUnit XML export of a run report, for CI systems showing test results.
+//!
+//! Every example is a test case. Skipped and xfail examples are reported as
+//! skipped, so known issues show up in CI without failing the pipeline.
+
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::report::{RunReport, Status};
+
+pub fn write(report: &RunReport, path: &Path) -> Result<()> {
+    let failures = report.count(Status::Failed);
+    let skipped = report.count(Status::Skipped) + report.count(Status::Xfail);
+    let seconds: u128 = report.examples.iter().map(|e| e.duration_ms).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites><testsuite name=\"score-cli\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">",
+        report.examples.len(),
+        failures,
+        skipped,
+        seconds as f64 / 1000.0
+    );
+    let _ = writeln!(
+        xml,
+        "  <properties><property name=\"run_id\" value=\"{}\"/><property name=\"seed\" value=\"{}\"/></properties>",
+        escape(&report.run_id),
+        report.seed
+    );
+    for example in &report.examples {
+        let _ = write!(
+            xml,
+            "  <testcase classname=\"showcases\" name=\"{}\" time=\"{:.3}\">",
+            escape(&example.name),
+            example.duration_ms as f64 / 1000.0
+        );
+        let reason = example.reason.as_deref().unwrap_or("");
+        match example.status {
+            Status::Passed => {}
+            Status::Flaky => {
+                let _ = write!(xml, "<system-out>flaky: {}</system-out>", escape(reason));
+            }
+            Status::Xpass => {
+                let _ = write!(
+                    xml,
+                    "<system-out>passed although marked xfail: {}</system-out>",
+                    escape(reason)
+                );
+            }
+            Status::Skipped => {
+                let _ = write!(xml, "<skipped message=\"{}\"/>", escape(reason));
+            }
+            Status::Xfail => {
+                let _ = write!(xml, "<skipped message=\"xfail: {}\"/>", escape(reason));
+            }
+            Status::Failed => {
+                let message = example
+                    .first_failure
+                    .as_ref()
+                    .map(|f| format!("app {} {}", f.app, f.reason))
+                    .unwrap_or_else(|| "an app failed".to_string());
+                let output: Vec<&str> = example
+                    .first_failure
+                    .iter()
+                    .flat_map(|f| f.last_lines.iter().map(|line| line.text.as_str()))
+                    .collect();
+                let _ = write!(
+                    xml,
+                    "<failure message=\"{}\">{}</failure>",
+                    escape(&message),
+                    escape(&output.join("\n"))
+                );
+            }
+        }
+        xml.push_str("</testcase>\n");
+    }
+    xml.push_str("</testsuite></testsuites>\n");
+    fs::write(path, xml).with_context(|| format!("Failed to write JUnit report {:?}", path))
+}
+
+fn escape(text: &str) -> String {
+    text.chars()
+        .filter(|&c| c == '\t' || c == '\n' || c == '\r' || c >= ' ')
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
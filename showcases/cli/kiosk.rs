@@ -0,0 +1,178 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Kiosk mode: cycle through a playlist of examples for unattended exhibitions.
+//!
+//! Every example of the playlist is shown for its duration, then stopped like a
+//! keep-alive app, and the playlist starts over. An example ending or crashing
+//! before its time is up is started again after `restart_delay`, so a crash
+//! costs the booth seconds instead of the rest of the day. Only the artifacts of
+//! such early ends are kept. The loop ends once the `--stop-file` appears.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::monitor::Monitor;
+use crate::report::Status;
+use crate::{
+    new_report, new_run_id, parameters, parse_duration, random_seed, remove_stale_stop_file, run_score_with,
+    select_examples, slug, Run, RunOptions, RunningApp, ScoreConfig,
+};
+
+/// How often a wait checks for the stop file.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Playlist {
+    entries: Vec<Entry>,
+    restart_delay: Option<String>, // wait before starting an example that ended early again (default: 5s)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    example: String,
+    duration: String, // how long the example is shown, e.g. "10m"
+}
+
+/// An entry of the playlist, resolved against the examples.
+struct Slot {
+    index: usize,
+    duration: Duration,
+}
+
+/// Ends the example once its slot is over.
+struct SlotMonitor {
+    until: Instant,
+}
+
+impl Monitor for SlotMonitor {
+    fn poll(&mut self, _apps: &[&RunningApp]) -> bool {
+        Instant::now() >= self.until
+    }
+}
+
+/// Cycles through the examples of the `playlist` file until the stop file appears.
+pub fn run(configs: &mut [ScoreConfig], playlist: &Path, options: &RunOptions) -> Result<()> {
+    let content = fs::read_to_string(playlist).with_context(|| format!("Failed reading {:?}", playlist))?;
+    let playlist: Playlist =
+        serde_json::from_str(&content).with_context(|| format!("Invalid playlist {:?}", playlist))?;
+    if playlist.entries.is_empty() {
+        bail!("The playlist has no entries");
+    }
+    let restart_delay = parse_duration(playlist.restart_delay.as_deref().unwrap_or("5s"))
+        .map_err(|e| anyhow::anyhow!("Invalid restart_delay: {}", e))?;
+    let mut slots = Vec::new();
+    for entry in &playlist.entries {
+        let index = match select_examples(configs, std::slice::from_ref(&entry.example), false, options.prefixes())?[..]
+        {
+            [index] => index,
+            ref selected => bail!(
+                "A playlist entry shows a single example, \"{}\" selects {}",
+                entry.example,
+                selected.len()
+            ),
+        };
+        let config = &configs[index];
+        if !config.steps.is_empty() {
+            bail!(
+                "Kiosk mode shows examples with apps, '{}' is a composite example",
+                config.name
+            );
+        }
+        if let Some(reason) = config.skip_reason()? {
+            bail!("Example '{}' cannot run on this host: {}", config.name, reason);
+        }
+        let duration = parse_duration(&entry.duration)
+            .map_err(|e| anyhow::anyhow!("Playlist entry '{}': {}", entry.example, e))?;
+        slots.push(Slot { index, duration });
+    }
+    let indices: Vec<usize> = slots.iter().map(|slot| slot.index).collect();
+    parameters::resolve(configs, &indices, &parameters::parse_given(&options.params)?, false)?;
+    remove_stale_stop_file(options)?;
+
+    // One id for all slots, so the run directory and SCORE_RUN_ID of the apps match.
+    let run_id = new_run_id();
+    let run_dir = crate::run_dir(options, &run_id)?;
+    println!(
+        "Kiosk mode: {} examples in the playlist, artifacts of early ends in {}",
+        slots.len(),
+        run_dir.display()
+    );
+    let mut report = new_report(&run_id, 0, &run_dir, options);
+    let mut shown = 0;
+    let mut round = 0;
+    loop {
+        round += 1;
+        for slot in &slots {
+            let config = &configs[slot.index];
+            println!("━━ Round {}: showing '{}' for {:?}", round, config.name, slot.duration);
+            let until = Instant::now() + slot.duration;
+            while Instant::now() < until {
+                if options.stop_requested() {
+                    println!("Stop file found, leaving kiosk mode");
+                    return Ok(());
+                }
+                shown += 1;
+                let run = Run::new(run_id.clone(), options.seed.unwrap_or_else(random_seed));
+                let example_dir = run_dir.join(format!("{:05}-{}", shown, slug(&config.name)));
+                fs::create_dir_all(&example_dir)
+                    .with_context(|| format!("Failed to create run directory {:?}", example_dir))?;
+                report.seed = run.seed;
+                run.checkpoint.start_example(&report, config, &example_dir);
+                let outcome = run_score_with(config, options, &run, &example_dir, &mut SlotMonitor { until });
+                run.checkpoint.remove();
+                let left = until.saturating_duration_since(Instant::now());
+                let problem = match outcome {
+                    Ok(report) if report.status == Status::Failed => Some("it failed".to_string()),
+                    Ok(_) if !left.is_zero() && !options.stop_requested() => Some("it ended early".to_string()),
+                    Ok(_) => None,
+                    Err(e) => Some(format!("{:#}", e)),
+                };
+                let Some(problem) = problem else {
+                    let _ = fs::remove_dir_all(&example_dir);
+                    continue;
+                };
+                run.trace.write(&example_dir.join("trace.json"))?;
+                if options.stop_requested() {
+                    continue;
+                }
+                println!(
+                    "⚠️  '{}' stopped showing: {}. Artifacts kept in {}",
+                    config.name,
+                    problem,
+                    example_dir.display()
+                );
+                if !left.is_zero() {
+                    println!("Restarting '{}' in {:?}", config.name, restart_delay.min(left));
+                    wait(restart_delay.min(left), options);
+                }
+            }
+        }
+    }
+}
+
+/// Sleeps for `duration`, or until the stop file appears.
+fn wait(duration: Duration, options: &RunOptions) {
+    let until = Instant::now() + duration;
+    while !options.stop_requested() {
+        let left = until.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            break;
+        }
+        std::thread::sleep(left.min(STOP_CHECK_INTERVAL));
+    }
+}
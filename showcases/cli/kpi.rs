@@ -0,0 +1,146 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Key performance indicators declared by examples, checked on every run.
+//!
+//! `"kpis": {"max_startup_ms": 500, "min_msgs_per_s": 10000}` bounds a metric
+//! of the example from above (`max_`) or below (`min_`). The metrics are:
+//!
+//! - `duration_ms`: from the start of the example until its apps exited
+//! - `startup_ms`: until every app was ready, or started for apps without `ready`
+//! - `cpu_ms` and `memory_peak_kb`: of all processes of the example, from its cgroup
+//! - the values apps print as `SCORE_METRIC <name>=<value>` lines, as for benchmarks
+//!
+//! A missed KPI fails the example, and so does a KPI whose metric was not
+//! measured. KPIs of metrics the host cannot measure, `cpu_ms` and
+//! `memory_peak_kb` without a cgroup per example and `memory_peak_kb` without
+//! the memory controller, are skipped instead, unless with `--strict`.
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::ScoreConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    Max,
+    Min,
+}
+
+/// Result of a KPI in `report.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct KpiResult {
+    pub name: String, // as declared, e.g. "max_startup_ms"
+    pub limit: f64,
+    pub value: Option<f64>, // measured value of the metric, if it was measured
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>, // why the host cannot measure the metric
+}
+
+impl KpiResult {
+    /// One line on the KPI, like `startup_ms 620, more than 500`.
+    pub fn describe(&self) -> String {
+        let (bound, metric) = parse(&self.name).unwrap_or((Bound::Max, &self.name));
+        if let Some(ref reason) = self.skipped {
+            return format!("{} not measurable: {}", metric, reason);
+        }
+        match (self.value, self.passed, bound) {
+            (None, _, _) => format!("{} not measured", metric),
+            (Some(value), true, Bound::Max) => format!("{} {} ≤ {}", metric, number(value), number(self.limit)),
+            (Some(value), true, Bound::Min) => format!("{} {} ≥ {}", metric, number(value), number(self.limit)),
+            (Some(value), false, Bound::Max) => {
+                format!("{} {}, more than {}", metric, number(value), number(self.limit))
+            }
+            (Some(value), false, Bound::Min) => {
+                format!("{} {}, less than {}", metric, number(value), number(self.limit))
+            }
+        }
+    }
+}
+
+/// The KPI `name` as a condition, like `startup_ms ≤ 500`, for listings of the examples.
+pub fn describe_limit(name: &str, limit: f64) -> String {
+    match parse(name) {
+        Ok((Bound::Max, metric)) => format!("{} ≤ {}", metric, number(limit)),
+        Ok((Bound::Min, metric)) => format!("{} ≥ {}", metric, number(limit)),
+        Err(_) => format!("{} {}", name, number(limit)),
+    }
+}
+
+/// Fails on KPIs that are neither `max_<metric>` nor `min_<metric>`.
+pub fn validate(configs: &[ScoreConfig]) -> Result<()> {
+    for config in configs {
+        for (name, limit) in &config.kpis {
+            if let Err(e) = parse(name) {
+                bail!("Example '{}': {}", config.name, e);
+            }
+            if !limit.is_finite() {
+                bail!("Example '{}': KPI \"{}\" needs a number", config.name, name);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks `kpis` against the measured `metrics`, skipping those of the `unmeasurable` metrics, with why.
+pub(crate) fn check(
+    kpis: &BTreeMap<String, f64>,
+    metrics: &BTreeMap<String, f64>,
+    unmeasurable: &BTreeMap<&str, &str>,
+) -> Vec<KpiResult> {
+    kpis.iter()
+        .filter_map(|(name, &limit)| {
+            let (bound, metric) = parse(name).ok()?;
+            let value = metrics.get(metric).copied();
+            let passed = value.is_some_and(|value| match bound {
+                Bound::Max => value <= limit,
+                Bound::Min => value >= limit,
+            });
+            Some(KpiResult {
+                name: name.clone(),
+                limit,
+                value,
+                passed,
+                skipped: unmeasurable
+                    .get(metric)
+                    .filter(|_| value.is_none())
+                    .map(|reason| reason.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn parse(name: &str) -> Result<(Bound, &str)> {
+    let (bound, metric) = match name.split_once('_') {
+        Some(("max", metric)) => (Bound::Max, metric),
+        Some(("min", metric)) => (Bound::Min, metric),
+        _ => bail!(
+            "invalid KPI \"{}\", use max_<metric> or min_<metric>, e.g. max_startup_ms",
+            name
+        ),
+    };
+    if metric.is_empty() {
+        bail!("KPI \"{}\" names no metric", name);
+    }
+    Ok((bound, metric))
+}
+
+/// Whole numbers without decimals, others with up to three.
+fn number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.3}", value).trim_end_matches('0').to_string()
+    }
+}
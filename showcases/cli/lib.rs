@@ -0,0 +1,2314 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Runner of the SCORE showcase examples, used by the `score-cli` binary and
+//! by integration tests embedding runs.
+//!
+//! Tests load the examples, run the ones they need and assert on the returned
+//! [`RunReport`] instead of parsing CLI output:
+//!
+//! ```no_run
+//! let examples = score_runner::load_examples(&score_runner::examples_roots(), None)?;
+//! let report = score_runner::run_example(&examples, "IPC Example", &score_runner::RunOptions::default())?;
+//! assert_eq!(report.count(score_runner::report::Status::Failed), 0);
+//! # anyhow::Ok(())
+//! ```
+//!
+//! [`run_example_async`] does the same from async code, so a test can talk to
+//! the apps over IPC while the example runs.
+mod adhoc;
+mod barrier;
+mod bench;
+mod builtin;
+mod bundle;
+mod can;
+mod catalog;
+mod cgroup;
+mod channels;
+mod checkpoint;
+pub mod cli;
+mod clock;
+mod command_record;
+mod completion;
+mod condition;
+mod config_dir;
+mod control;
+mod cooldown;
+mod coverage;
+mod data_feed;
+mod diff;
+mod disk_guard;
+mod display;
+mod doctor;
+mod exit_codes;
+mod explain;
+mod faketime;
+mod fmt;
+mod forward;
+mod gc;
+mod graph;
+mod health;
+mod helper;
+mod history;
+mod host;
+mod hot_add;
+mod init;
+mod instrument;
+mod interaction;
+mod iproute;
+mod junit;
+mod kiosk;
+mod kpi;
+mod linker;
+mod locks;
+mod log_index;
+mod logs;
+mod lsp;
+mod manifest;
+mod monitor;
+mod mqtt_broker;
+mod network;
+mod observe;
+mod oom;
+mod output;
+mod overlay;
+mod parallel;
+mod parameters;
+mod pattern;
+mod presets;
+mod priority;
+mod private_dir;
+mod procfs;
+mod progress;
+mod readiness;
+mod redact;
+pub mod report;
+mod sbom;
+mod screen;
+mod secrets;
+mod selection;
+mod self_update;
+mod selftest;
+mod serve;
+mod session;
+mod settings;
+mod signing;
+mod soak;
+mod sockets;
+mod someip;
+mod static_server;
+mod stdin;
+mod steps;
+mod stress;
+mod strict;
+mod supervisor;
+mod sys;
+mod timeline;
+mod trace;
+mod traceability;
+mod tree_rev;
+
+use anyhow::{Context, Result};
+use clap::{Args, FromArgMatches};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+pub use completion::Action as CompletionAction;
+pub use instrument::Instrument;
+use instrument::Instrumented;
+use monitor::{Monitor, NoMonitor};
+pub use output::Timestamps;
+pub use progress::ProgressMode;
+pub use report::RunReport;
+use report::{AppReport, ExampleReport, Status};
+pub use screen::ScreenCapture;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+pub(crate) use supervisor::stop_on_interrupt;
+use tokio::task::JoinHandle;
+use trace::Trace;
+
+/// Time apps get to exit after their stop signal before they are killed, unless they set `stop_timeout`.
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+/// How often monitors, readiness probes and key commands are checked while apps run; exits are seen at once.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// How long to wait for remaining output of an app after it exited.
+const READER_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+/// How long the supervisor waits for the last output of an app before reporting its exit.
+const EXIT_OUTPUT_TIMEOUT: Duration = Duration::from_millis(50);
+/// How long barrier apps have to join the start barrier, unless the example sets `barrier_timeout`.
+const BARRIER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Options of a run, the flags of `score-cli run`.
+#[derive(Args, Clone, Debug)]
+pub struct RunOptions {
+    /// Run apps under a memory checker / sanitizer
+    #[arg(long, value_enum)]
+    pub instrument: Option<Instrument>,
+
+    /// Directory for logs and reports of this run (default: a new directory under $TMPDIR/score-cli-<uid>/runs)
+    #[arg(long)]
+    pub artifacts_dir: Option<PathBuf>,
+
+    /// Collect LLVM / gcov coverage profiles of all apps into the artifacts directory
+    #[arg(long)]
+    pub coverage: bool,
+
+    /// Merge collected LLVM profiles with llvm-profdata after the run (implies --coverage)
+    #[arg(long)]
+    pub merge_coverage: bool,
+
+    /// Seed exported to all apps as SCORE_RUN_SEED (default: random, recorded in the report)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Number of output lines of the first failing app shown after an example
+    #[arg(long, default_value_t = 20)]
+    pub failure_context: usize,
+
+    /// Timestamp prefix of app output lines
+    #[arg(long, value_enum, default_value_t = Timestamps::Monotonic)]
+    pub timestamps: Timestamps,
+
+    /// Redact output matching a built-in rule (vin, mac-address, token) or a regular expression (repeatable)
+    #[arg(long = "redact", value_name = "RULE")]
+    pub redact: Vec<String>,
+
+    /// Print the commands the apps would be started with, secrets masked, without running anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Sign report.json with this private key (PEM), writing a detached report.json.sig
+    #[arg(long)]
+    pub sign_key: Option<PathBuf>,
+
+    /// Repeat failing examples up to this many times; passing on a retry is reported as flaky
+    #[arg(long, default_value_t = 0)]
+    pub retries: u32,
+
+    /// Report an example the runner fails to run as failed and go on with the next, instead of ending the run
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// Stop logging app output while less than this many MB are free for the run directory (0: never)
+    #[arg(long, value_name = "MB", default_value_t = 256)]
+    pub min_free_mb: u64,
+
+    /// Write the results as a JUnit XML report to this file
+    #[arg(long)]
+    pub junit: Option<PathBuf>,
+
+    /// Tell operators the run finished: bell, flash, sound[:FILE] or exec:COMMAND (repeatable)
+    #[arg(long, value_name = "ACTION", value_parser = completion::parse_action)]
+    pub on_complete: Vec<completion::Action>,
+
+    /// Count syscalls (eBPF) and measure scheduling latency of all apps (Linux, needs root or CAP_BPF)
+    #[arg(long)]
+    pub observe: bool,
+
+    /// Capture the screen of examples tagged `gui` into their artifacts directory
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub capture_screen: Option<screen::ScreenCapture>,
+
+    /// With --capture-screen screenshots: time between two screenshots
+    #[arg(long, value_parser = screen::parse_interval, value_name = "DURATION", default_value = "5s")]
+    pub screenshot_interval: Duration,
+
+    /// Run privileged setup (CAN interfaces, network nodes) through a helper started once with this command, e.g. "sudo"
+    #[arg(long, value_name = "COMMAND")]
+    pub privileged_helper: Option<String>,
+
+    /// Set when examples were selected interactively, enabling key commands while they run
+    #[arg(skip)]
+    pub interactive: bool,
+
+    /// Fail on soft problems: unknown or deprecated config fields, example name prefixes, missing programs
+    /// like ASan variants, app output not logged or error lines not kept, and apps exiting non-zero when stopped
+    #[arg(long, conflicts_with = "ignore_missing")]
+    pub strict: bool,
+
+    /// Take an example name matching no example as the example it is a unique prefix of, ignoring case
+    #[arg(long)]
+    pub prefix_match: bool,
+
+    /// Do not show app output, only the runner's status lines (logs are still written)
+    #[arg(long, conflicts_with = "summary")]
+    pub quiet: bool,
+
+    /// Only print a table of the example and app results with durations at the end
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Show a progress line with ETA instead of app output (auto: for several examples on a terminal)
+    #[arg(long, value_enum, default_value_t = progress::ProgressMode::Auto)]
+    pub progress: progress::ProgressMode,
+
+    /// Stream all log lines as JSON to an external viewer at tcp://<host>:<port> or unix:<path>
+    #[arg(long, value_name = "ADDRESS")]
+    pub forward_logs: Option<String>,
+
+    /// Run the matching examples when some requested names or patterns match none, instead of failing
+    #[arg(long)]
+    pub ignore_missing: bool,
+
+    /// Stop the running example gracefully and end the run once this file exists
+    #[arg(long, value_name = "PATH")]
+    pub stop_file: Option<PathBuf>,
+
+    /// Start the apps of *.json files appearing in this directory as part of the running example
+    #[arg(long, value_name = "DIR")]
+    pub add_apps_dir: Option<PathBuf>,
+
+    /// Run up to this many examples at the same time ("auto": one per CPU), within the CPUs and memory they reserve
+    #[arg(long, value_name = "N", default_value = "1", value_parser = parallel::parse_workers,
+          conflicts_with_all = ["cooldown", "add_apps_dir"])]
+    pub parallel: u32,
+
+    /// Before each example but the first, wait up to this long for the system to settle (see --settle-*)
+    #[arg(long, value_parser = parse_duration, value_name = "DURATION")]
+    pub cooldown: Option<Duration>,
+
+    /// With --cooldown: highest 1-minute load average to continue at (default: the number of CPUs)
+    #[arg(long, value_name = "LOAD", requires = "cooldown")]
+    pub settle_load: Option<f64>,
+
+    /// With --cooldown: wait until no process command line matches this pattern (repeatable)
+    #[arg(long, value_name = "PATTERN", requires = "cooldown")]
+    pub settle_process: Vec<String>,
+
+    /// With --cooldown: wait until /dev/shm holds at most this many MiB
+    #[arg(long, value_name = "MIB", requires = "cooldown")]
+    pub settle_shm: Option<u64>,
+
+    /// Value of an example parameter (repeatable), prompted for or defaulted otherwise
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    pub params: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct AppConfig {
+    name: Option<String>, // label of the app in its output, the file name of `path` by default
+    #[serde(default)]
+    path: String, // program to run; relative paths with `/` are relative to the config file
+    dir: Option<String>,  // working directory, relative to the config file
+    #[serde(default)]
+    args: Vec<String>, // arguments of the program
+    #[serde(default)]
+    env: HashMap<String, String>, // environment variables of the app
+    #[serde(default = "default_true")]
+    inherit_env: bool, // false: start from an empty environment instead of the runner's
+    #[serde(default)]
+    env_remove: Vec<String>, // variables of the runner's environment the app does not inherit
+    #[serde(rename = "type", default)]
+    app_type: builtin::AppType, // "process" (default) or a built-in app like "static-server"
+    serve_dir: Option<String>, // static-server: directory to serve, formerly "root"
+    bind: Option<String>, // static-server: address to listen on, 127.0.0.1 by default
+    port: Option<u16>,    // static-server, mqtt-broker: TCP port
+    recording: Option<String>, // data-feed: CSV or MCAP recording to replay
+    target: Option<String>, // data-feed: "udp:<host>:<port>", "fifo:<path>" or "someip:<host>:<port>:<service>:<event>"
+    speed: Option<f64>,   // data-feed: replay speed factor (default 1.0)
+    delay: Option<u64>,   // delay in seconds before running the next app
+    node: Option<String>, // network node (namespace) to run the app in
+    faketime: Option<String>, // libfaketime FAKETIME spec, e.g. "@2030-01-01 12:00:00" or "+2d"
+    #[serde(default)]
+    preload: Vec<String>, // libraries added to LD_PRELOAD
+    #[serde(default)]
+    library_path: Vec<String>, // directories prepended to LD_LIBRARY_PATH
+    asan_path: Option<String>, // ASan build used with `--instrument asan-env` (default: `<path>_asan`)
+    seed: Option<u64>,    // overrides the run seed exported as SCORE_RUN_SEED
+    nice: Option<i32>,    // CPU nice level, -20 (highest priority) to 19
+    ionice: Option<String>, // I/O priority: "idle", "best-effort[:0-7]" or "realtime[:0-7]"
+    oom_score_adj: Option<i32>, // OOM-killer score adjustment, -1000 (never killed) to 1000 (killed first)
+    #[serde(default)]
+    secrets: HashMap<String, secrets::SecretSource>, // env variables resolved at runtime, masked in all output
+    stop_signal: Option<String>, // signal asking the app to exit, e.g. "SIGINT" or "10" (default: SIGTERM)
+    stop_timeout: Option<u64>, // seconds to wait for the app to exit before it is killed (default: 5)
+    #[serde(default)]
+    lifecycle: Lifecycle, // "run_to_completion" (default) or "keep_alive" for daemons stopped at the end
+    when: Option<String>, // condition like "${PROFILE} == 'qemu'" or "env_set(DISPLAY)", the app only runs if true
+    #[serde(default)]
+    requires_display: bool, // needs DISPLAY or WAYLAND_DISPLAY, `headless` applies without
+    #[serde(default)]
+    headless: display::Headless, // without a display: "skip" (default), "xvfb" or a variant {"path": ..., "args": [...]}
+    #[serde(default)]
+    barrier: bool, // joins the start barrier, released together with the other barrier apps
+    ready: Option<readiness::Ready>, // condition met once the app started up, e.g. {"log": "listening"}
+    start_timeout: Option<u64>,      // seconds the app has to become ready before the example fails
+    #[serde(default)]
+    sockets: Vec<sockets::SocketConfig>, // sockets opened by the runner, passed as LISTEN_FDS
+    forward_logs: Option<String>,    // viewer address the app's lines are streamed to, besides --forward-logs
+    stdin: Option<stdin::Input>,     // data fed to stdin: {"file": "input.txt"} or {"literal": "start\n"}
+    #[serde(default)]
+    interaction: Vec<interaction::Step>, // prompts answered on a pseudo terminal, e.g. [{"expect": "choice:", "send": "2\n"}]
+    #[serde(flatten)]
+    unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+    #[serde(skip)]
+    deprecated: Vec<(&'static str, &'static str)>, // deprecated fields given, with their replacement
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl AppConfig {
+    /// Whether the app gets the runner's value of the environment variable `key`.
+    fn inherits_env(&self, key: &str) -> bool {
+        self.inherit_env && !self.env_remove.iter().any(|name| name == key)
+    }
+}
+
+/// Whether the example waits for an app to exit.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Lifecycle {
+    /// The example completes once all these apps exited
+    #[default]
+    RunToCompletion,
+    /// Daemons and GUIs: not waited for, stopped once all run-to-completion apps exited
+    KeepAlive,
+}
+
+/// Marks an example as known to fail: its failures do not fail the run.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+struct KnownIssue {
+    tracking: String,       // link to the issue tracking the failure
+    reason: Option<String>, // short description of the issue
+}
+
+impl KnownIssue {
+    fn describe(&self) -> String {
+        match self.reason {
+            Some(ref reason) => format!("{} ({})", reason, self.tracking),
+            None => format!("known issue {}", self.tracking),
+        }
+    }
+}
+
+/// An example, as declared in a `*.score.json` file.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScoreConfig {
+    pub name: String,        // name of the example, used to select it
+    pub description: String, // one-line description shown in the selection and the catalog
+    #[serde(default)]
+    apps: Vec<AppConfig>, // apps started in this order
+    #[serde(default)]
+    steps: Vec<String>, // names of examples run one after another instead of apps
+    #[serde(default)]
+    can: Vec<can::CanConfig>, // virtual CAN interfaces set up for the example
+    someip_sd: Option<someip::SomeIpSdConfig>, // SOME/IP service discovery offers verified once the apps started
+    network: Option<network::NetworkConfig>, // network nodes (namespaces) on a shared bridge, selected by the apps' `node`
+    preset: Option<String>,                  // environment preset from a *.presets.json file
+    #[serde(default)]
+    tags: Vec<String>,  // free-form labels shown in the example catalog
+    #[serde(default)]
+    host: host::HostRequirements, // skip the example on hosts not meeting these
+    xfail: Option<KnownIssue>,               // expected to fail until the tracked issue is fixed
+    retries: Option<u32>,                    // times a failing example is repeated, overrides --retries
+    #[serde(default)]
+    requirements: Vec<String>, // ids of the requirements verified by the example
+    #[serde(default)]
+    redact: Vec<redact::RedactRule>, // rules masking sensitive data in app output
+    #[serde(default)]
+    parameters: Vec<parameters::Parameter>, // knobs set when running, used as {{name}} in the apps
+    #[serde(default)]
+    locks: Vec<String>, // exclusive resources like "can0" or "port:13400", never used by two runs at once
+    concurrency_group: Option<String>,       // with --parallel, examples of the same group never run at the same time
+    reserve: Option<parallel::Reservation>,  // CPUs and memory the example needs, packed onto the host with --parallel
+    barrier_timeout: Option<u64>,            // seconds for all barrier apps to join the start barrier (default: 30)
+    #[serde(default)]
+    channels: Vec<channels::ChannelConfig>, // FIFOs and unix sockets provisioned before the apps start
+    health_monitor: Option<health::HealthMonitor>, // reporter registering the runner with the SCORE health monitor
+    #[serde(default)]
+    kpis: BTreeMap<String, f64>, // bounds of metrics like "max_startup_ms": 500, checked on every run
+    #[serde(flatten)]
+    unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+impl ScoreConfig {
+    /// Why the example cannot run on this host, if it cannot: unmet host requirements or a missing display.
+    pub(crate) fn skip_reason(&self) -> Result<Option<String>> {
+        match self.host.skip_reason()? {
+            Some(reason) => Ok(Some(reason)),
+            None => Ok(display::skip_reason(self)),
+        }
+    }
+}
+
+/// Starts the privileged helper if requested and the runner is not root already.
+pub(crate) fn start_helper(options: &RunOptions) -> Result<()> {
+    match &options.privileged_helper {
+        Some(launcher) if !host::is_root() => helper::start(launcher),
+        _ => Ok(()),
+    }
+}
+
+/// Indices of the examples selected by the `requested` names or selection expressions, e.g. `all - slow_demo`.
+///
+/// With `prefixes`, a name matching no example is taken as the example it is a unique prefix of,
+/// see [`RunOptions::prefixes`].
+pub(crate) fn select_examples(
+    configs: &[ScoreConfig],
+    requested: &[String],
+    ignore_missing: bool,
+    prefixes: bool,
+) -> Result<Vec<usize>> {
+    let selection = selection::evaluate(configs, requested, prefixes)?;
+    if selection.selected.is_empty() {
+        anyhow::bail!(
+            "No examples found matching: {}.{}",
+            requested.join(","),
+            suggest(configs, &selection.unmatched)
+        );
+    }
+    if !selection.unmatched.is_empty() {
+        let names: Vec<&str> = selection.unmatched.iter().map(String::as_str).collect();
+        let message = format!(
+            "{} matched no example.{}",
+            quoted_list(&names),
+            suggest(configs, &selection.unmatched)
+        );
+        if !ignore_missing {
+            anyhow::bail!("{} Pass --ignore-missing to run the other examples anyway.", message);
+        }
+        eprintln!("⚠️  {} Running the other examples.", message);
+    }
+    for (prefix, name) in &selection.expanded {
+        println!("Taking \"{}\" as example \"{}\"", prefix, name);
+    }
+
+    if selection.selected.len() == configs.len() {
+        println!("Running all {} examples", configs.len());
+    } else {
+        let names: Vec<&str> = selection.selected.iter().map(|&i| configs[i].name.as_str()).collect();
+        println!("Running examples: {}", names.join(","));
+    }
+    Ok(selection.selected)
+}
+
+/// Index of the example named exactly `name`, for commands printing something about it.
+pub fn find_example(configs: &[ScoreConfig], name: &str) -> Result<usize> {
+    configs.iter().position(|config| config.name == name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No example named \"{}\".{}",
+            name,
+            suggest(configs, &[name.to_string()])
+        )
+    })
+}
+
+/// Suggestions for the names and patterns in `unmatched`, or the list of all examples if none is close.
+fn suggest(configs: &[ScoreConfig], unmatched: &[String]) -> String {
+    let mut message = String::new();
+    for word in unmatched {
+        let suggestions = selection::suggestions(configs, word);
+        if !suggestions.is_empty() {
+            message.push_str(&format!(" \"{}\": did you mean {}?", word, quoted_list(&suggestions)));
+        }
+    }
+    if message.is_empty() {
+        message = format!(
+            " Available examples: {}.",
+            configs.iter().map(|c| c.name.as_str()).collect::<Vec<_>>().join(", ")
+        );
+    }
+    message
+}
+
+/// Formats names as `"a"`, `"a" or "b"` and `"a", "b" or "c"`.
+fn quoted_list(names: &[&str]) -> String {
+    let quoted: Vec<String> = names.iter().map(|name| format!("\"{}\"", name)).collect();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => quoted.concat(),
+    }
+}
+
+/// Prints the commands the apps of the selected examples would be started with, without running anything.
+///
+/// Secrets are not resolved, their variables show the mask. Wrappers like `ip netns exec` are left out.
+pub(crate) fn dry_run(configs: &[ScoreConfig], selected: &[usize], options: &RunOptions) -> Result<()> {
+    for &index in selected {
+        let config = &configs[index];
+        let mut redactor = redact::Redactor::default();
+        for rule in &config.redact {
+            redactor.add_rule(rule)?;
+        }
+        for rule in &options.redact {
+            redactor.add_cli_rule(rule)?;
+        }
+        println!("▶ Example: {}", config.name);
+        if !config.steps.is_empty() {
+            println!(
+                "  runs the examples {}",
+                quoted_list(&config.steps.iter().map(String::as_str).collect::<Vec<_>>())
+            );
+        }
+        for (i, app) in config.apps.iter().enumerate() {
+            let mut cmd = Command::new(&app.path);
+            cmd.args(&app.args);
+            cmd.envs(&app.env);
+            cmd.envs(app.secrets.keys().map(|name| (name, redact::MASK)));
+            let node = app
+                .node
+                .as_ref()
+                .map(|node| format!(" (node {})", node))
+                .unwrap_or_default();
+            println!(
+                "  App {}{}: {}",
+                i + 1,
+                node,
+                command_record::command_line(&cmd, |text| redactor.redact(text))
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs the selected examples like `score-cli run`: besides [`execute`], it shows
+/// progress or a summary instead of app output if asked to, records the results
+/// in the history and tells operators the run finished.
+pub(crate) fn run_examples(configs: &[ScoreConfig], selected: &[usize], options: &RunOptions) -> Result<RunReport> {
+    let progress = (!options.quiet && !options.summary && options.progress.enabled(selected.len()))
+        .then(|| progress::Progress::start(selected.iter().map(|&index| configs[index].name.clone()).collect()));
+    let quiet_options;
+    let options = if progress.is_some() {
+        // App output would garble the progress line; the logs keep it.
+        quiet_options = RunOptions {
+            quiet: true,
+            ..options.clone()
+        };
+        &quiet_options
+    } else {
+        options
+    };
+    let silenced = if options.summary || progress.is_some() {
+        Some(sys::silence_stdout().context("Failed to silence output for --summary")?)
+    } else {
+        None
+    };
+    let report = execute(configs, selected, options, progress.as_ref())?;
+    if let Err(e) = history::record(&report) {
+        eprintln!("⚠️  Failed to record the results in the history: {:#}", e);
+    }
+    if silenced.is_some() {
+        drop(progress);
+        drop(silenced);
+        print!("{}", report.summary());
+    }
+    println!(
+        "Results: {} passed, {} flaky, {} failed, {} skipped, {} xfail, {} xpass",
+        report.count(Status::Passed),
+        report.count(Status::Flaky),
+        report.count(Status::Failed),
+        report.count(Status::Skipped),
+        report.count(Status::Xfail),
+        report.count(Status::Xpass)
+    );
+    for warning in &report.warnings {
+        println!("⚠️  {}", warning);
+    }
+    if options.keep_going {
+        for example in report.examples.iter().filter(|e| e.infra_error) {
+            println!(
+                "❌ '{}' could not run: {}",
+                example.name,
+                example.reason.as_deref().unwrap_or_default()
+            );
+        }
+    }
+    Ok(report)
+}
+
+/// Runs the selected examples and writes the run report, reporting the examples not selected as skipped.
+fn execute(
+    configs: &[ScoreConfig],
+    selected: &[usize],
+    options: &RunOptions,
+    progress: Option<&progress::Progress>,
+) -> Result<RunReport> {
+    if options.strict {
+        strict::check_programs(configs, selected, options)?;
+    }
+    let run = Run::new(new_run_id(), options.seed.unwrap_or_else(random_seed));
+    let run_dir = run_dir(options, &run.id)?;
+    fs::create_dir_all(&run_dir).with_context(|| format!("Failed to create run directory {:?}", run_dir))?;
+    run.trace.write_to(run_dir.join("trace.json"));
+    println!("Run id: {}", run.id);
+    println!("Run seed: {} (replay with --seed {})", run.seed, run.seed);
+    remove_stale_stop_file(options)?;
+    if let Some(ref dir) = options.add_apps_dir {
+        hot_add::check_dir(dir)?;
+    }
+    let disk_guard = disk_guard::start(&run_dir, options.min_free_mb, &run.log_pause);
+
+    let mut report = new_report(&run.id, run.seed, &run_dir, options);
+    run.checkpoint.start(&report, configs, selected);
+    if options.parallel > 1 {
+        let examples = parallel::run(configs, selected, options, &run, &run_dir, progress)?;
+        report.examples.extend(examples);
+    } else {
+        for (position, &index) in selected.iter().enumerate() {
+            if position > 0 && !options.stop_requested() {
+                cooldown::settle(options)?;
+            }
+            let example = run_selected(configs, index, options, &run, &run_dir, progress)?;
+            report.examples.push(example);
+        }
+    }
+    for (_, config) in configs.iter().enumerate().filter(|(i, _)| !selected.contains(i)) {
+        let reason = "filtered out".to_string();
+        report
+            .examples
+            .push(ExampleReport::skipped(&config.name, &config.requirements, reason));
+    }
+    run.trace.finish()?;
+    forward::flush();
+    report.warnings = disk_guard.map(disk_guard::Guard::finish).unwrap_or_default();
+
+    if options.merge_coverage {
+        report.coverage = coverage::merge(&run_dir)?;
+    }
+
+    sbom::enrich(&mut report, &examples_roots());
+    let report_path = run_dir.join("report.json");
+    report.write(&report_path)?;
+    run.checkpoint.remove();
+    println!("Run report written to {}", report_path.display());
+    if let Some(ref key) = options.sign_key {
+        let signature = signing::sign(&report_path, key)?;
+        println!("Run report signed: {}", signature.display());
+    }
+    if let Some(ref path) = options.junit {
+        junit::write(&report, path)?;
+        println!("JUnit report written to {}", path.display());
+    }
+    Ok(report)
+}
+
+/// Runs example `index` as part of a run, unless the stop file asks to end the run.
+fn run_selected(
+    configs: &[ScoreConfig],
+    index: usize,
+    options: &RunOptions,
+    run: &Run,
+    run_dir: &Path,
+    progress: Option<&progress::Progress>,
+) -> Result<ExampleReport> {
+    let config = &configs[index];
+    if options.stop_requested() {
+        let example = ExampleReport::skipped(&config.name, &config.requirements, options.stop_reason());
+        run.checkpoint.example_finished(index, &example);
+        return Ok(example);
+    }
+    let example_dir = run_dir.join(slug(&config.name));
+    fs::create_dir_all(&example_dir)?;
+    run.checkpoint.example_started(index, &example_dir);
+    session::running(&config.name, run_dir);
+    if let Some(progress) = progress {
+        progress.example_started();
+    }
+    let example = match run_config(configs, config, options, run, &example_dir) {
+        Ok(example) => example,
+        Err(e) if options.keep_going => {
+            eprintln!("❌ Failed to run example '{}': {:#}", config.name, e);
+            ExampleReport::errored(&config.name, &config.requirements, &e)
+        }
+        Err(e) => return Err(e),
+    };
+    run.checkpoint.example_finished(index, &example);
+    if let Some(progress) = progress {
+        progress.example_finished(&example);
+    }
+    Ok(example)
+}
+
+/// Runs an example: its steps if it is a composite example, otherwise its apps.
+fn run_config(
+    configs: &[ScoreConfig],
+    config: &ScoreConfig,
+    options: &RunOptions,
+    run: &Run,
+    example_dir: &Path,
+) -> Result<ExampleReport> {
+    if config.steps.is_empty() {
+        run_with_retries(config, options, run, example_dir)
+    } else {
+        steps::run(configs, config, options, run, example_dir)
+    }
+}
+
+/// Runs an example, repeating it up to its number of retries while it fails.
+///
+/// With retries, every attempt gets its own `attempt-<n>` directory, so the logs
+/// of failed attempts are kept. Passing on a retry makes the example `flaky`.
+fn run_with_retries(
+    config: &ScoreConfig,
+    options: &RunOptions,
+    run: &Run,
+    example_dir: &Path,
+) -> Result<ExampleReport> {
+    let retries = config.retries.unwrap_or(options.retries);
+    if retries == 0 {
+        return run_score(config, options, run, example_dir);
+    }
+    let mut attempt = 1;
+    loop {
+        let attempt_dir = example_dir.join(format!("attempt-{}", attempt));
+        fs::create_dir_all(&attempt_dir)?;
+        let mut report = run_score(config, options, run, &attempt_dir)?;
+        report.attempts = attempt;
+        if report.status != Status::Failed || attempt > retries {
+            if report.status == Status::Passed && attempt > 1 {
+                report.status = Status::Flaky;
+                report.reason = Some(format!("passed on attempt {} of {}", attempt, retries + 1));
+                println!(
+                    "⚠️  Example '{}' is flaky: {}",
+                    config.name,
+                    report.reason.as_deref().unwrap_or("")
+                );
+            }
+            return Ok(report);
+        }
+        attempt += 1;
+        println!(
+            "↻ Retrying example '{}' (attempt {} of {})",
+            config.name,
+            attempt,
+            retries + 1
+        );
+    }
+}
+
+/// Identity of one run, shared by all examples in it.
+struct Run {
+    id: String,
+    seed: u64,
+    trace: Trace,
+    checkpoint: checkpoint::Checkpoint,
+    log_pause: disk_guard::Pause,
+}
+
+impl Run {
+    fn new(id: String, seed: u64) -> Self {
+        let trace = Trace::new(&id);
+        Run {
+            id,
+            seed,
+            trace,
+            checkpoint: checkpoint::Checkpoint::default(),
+            log_pause: disk_guard::Pause::default(),
+        }
+    }
+}
+
+/// Random (version 4) UUID identifying a run, exported to apps as SCORE_RUN_ID.
+fn new_run_id() -> String {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&random_seed().to_le_bytes());
+    bytes[8..].copy_from_slice(&random_seed().to_le_bytes());
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn random_seed() -> u64 {
+    let mut bytes = [0u8; 8];
+    match fs::File::open("/dev/urandom").and_then(|mut f| std::io::Read::read_exact(&mut f, &mut bytes)) {
+        Ok(()) => u64::from_le_bytes(bytes),
+        Err(_) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64,
+    }
+}
+
+/// Report of the run `run_id` in `run_dir`, without examples yet.
+fn new_report(run_id: &str, seed: u64, run_dir: &Path, options: &RunOptions) -> RunReport {
+    RunReport {
+        run_dir: run_dir.to_path_buf(),
+        run_id: run_id.to_string(),
+        seed,
+        host: host::HostInfo::collect(&examples_root()),
+        instrument: options.instrument,
+        coverage: None,
+        sboms: Vec::new(),
+        warnings: Vec::new(),
+        examples: Vec::new(),
+    }
+}
+
+/// Directory of the run `run_id`: `--artifacts-dir`, or one in the private runs directory.
+fn run_dir(options: &RunOptions, run_id: &str) -> Result<PathBuf> {
+    match options.artifacts_dir {
+        Some(ref dir) => Ok(dir.clone()),
+        None => Ok(timeline::create_runs_root()?.join(run_id)),
+    }
+}
+
+/// File-system friendly version of an example name.
+pub(crate) fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Parses durations like `90s`, `30m`, `12h` or `2d`; plain numbers are seconds.
+pub(crate) fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = value.split_at(value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len()));
+    let number: u64 = number.parse().map_err(|_| format!("invalid duration \"{}\"", value))?;
+    let factor: u64 = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        _ => return Err(format!("invalid duration unit \"{}\", use s, m, h or d", unit)),
+    };
+    let seconds = number
+        .checked_mul(factor)
+        .ok_or_else(|| format!("duration \"{}\" is too long", value))?;
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Directories searched for examples, later ones overlaying earlier ones: `$SCORE_CLI_INIT_DIR`,
+/// separated by `:` like `PATH`, `/showcases` by default.
+pub fn examples_roots() -> Vec<PathBuf> {
+    match env::var_os("SCORE_CLI_INIT_DIR") {
+        Some(dirs) if !dirs.is_empty() => env::split_paths(&dirs).collect(),
+        _ => vec![PathBuf::from("/showcases")],
+    }
+}
+
+/// The first of the [`examples_roots`], the one the others overlay.
+pub(crate) fn examples_root() -> PathBuf {
+    examples_roots().swap_remove(0)
+}
+
+/// Loads and validates all examples below `roots`, applying the environment `preset` if given.
+pub fn load_examples(roots: &[PathBuf], preset: Option<&str>) -> Result<Vec<ScoreConfig>> {
+    let (examples, env_presets) = read_examples(roots)?;
+    resolve_examples(examples, &env_presets, preset)
+}
+
+/// The examples as read by [`read_examples`], parsed, with presets applied and all checks of loading done.
+pub(crate) fn resolve_examples(
+    examples: Vec<overlay::Source>,
+    env_presets: &presets::Presets,
+    preset: Option<&str>,
+) -> Result<Vec<ScoreConfig>> {
+    // Examples log to the directory named by the slug of their name.
+    let mut slugs: HashMap<String, &overlay::Source> = HashMap::new();
+    for example in &examples {
+        if let Some(other) = slugs.insert(slug(example.name()), example) {
+            anyhow::bail!(
+                "Examples '{}' in {:?} and '{}' in {:?} would log to the same directory, rename one",
+                other.name(),
+                other.file,
+                example.name(),
+                example.file
+            );
+        }
+    }
+    let mut configs = examples
+        .into_iter()
+        .map(|mut example| {
+            let apps = example.value.get_mut("apps").and_then(serde_json::Value::as_array_mut);
+            let mut deprecated = Vec::new();
+            for app in apps.into_iter().flatten().filter_map(serde_json::Value::as_object_mut) {
+                let used =
+                    strict::rename_deprecated(app).with_context(|| format!("Invalid example {:?}", example.file))?;
+                deprecated.push(used);
+            }
+            let mut config: ScoreConfig =
+                serde_json::from_value(example.value).with_context(|| format!("Invalid JSON in {:?}", example.file))?;
+            for (app, used) in config.apps.iter_mut().zip(deprecated) {
+                app.deprecated = used;
+            }
+            Ok(config)
+        })
+        .collect::<Result<Vec<ScoreConfig>>>()?;
+    presets::resolve(&mut configs, env_presets, preset)?;
+    steps::validate(&configs)?;
+    condition::filter_apps(&mut configs)?;
+    display::resolve(&mut configs);
+    readiness::validate(&configs)?;
+    kpi::validate(&configs)?;
+    interaction::validate(&configs)?;
+    instrument::validate(&configs)?;
+    builtin::expand(&mut configs)?;
+    Ok(configs)
+}
+
+/// The examples below `roots` as read from their files and layered, with the presets defined next to them.
+pub(crate) fn read_examples(roots: &[PathBuf]) -> Result<(Vec<overlay::Source>, presets::Presets)> {
+    read_examples_with(roots, None)
+}
+
+/// Like [`read_examples`], with the file `edited.0` read as `edited.1`, e.g. while it is edited and not saved.
+///
+/// The edited file is added to the root holding it if it does not exist yet.
+pub(crate) fn read_examples_with(
+    roots: &[PathBuf],
+    edited: Option<(&Path, &str)>,
+) -> Result<(Vec<overlay::Source>, presets::Presets)> {
+    let mut examples = Vec::new();
+    let mut env_presets = presets::Presets::new();
+    for root in roots {
+        let mut found = Vec::new();
+        let mut root_presets = presets::Presets::new();
+        visit_dir(root, &mut found, &mut root_presets, edited)?;
+        if let Some((path, content)) = edited {
+            if path.starts_with(root) && !found.iter().any(|source| source.file == path) {
+                found.extend(parse_score_file(path, content)?);
+            }
+        }
+        overlay::layer(&mut examples, found)?;
+        // Presets of later roots replace those of the same name.
+        env_presets.extend(root_presets);
+    }
+
+    if examples.is_empty() {
+        let roots: Vec<String> = roots.iter().map(|root| root.display().to_string()).collect();
+        anyhow::bail!("No *.score.json files found under {}", roots.join(", "));
+    }
+    Ok((examples, env_presets))
+}
+
+/// Runs the example `name` of `examples`, as loaded with [`load_examples`], and returns the run report.
+///
+/// Parameters take their values from `options.params` or their defaults. Apart
+/// from the run itself, nothing changes for the calling process: stdout is not
+/// redirected, no privileged helper is started, signals keep their handlers and
+/// the results are not recorded in the history.
+pub fn run_example(examples: &[ScoreConfig], name: &str, options: &RunOptions) -> Result<RunReport> {
+    let index = find_example(examples, name)?;
+    let mut configs = examples.to_vec();
+    parameters::resolve(
+        &mut configs,
+        &[index],
+        &parameters::parse_given(&options.params)?,
+        false,
+    )?;
+    execute(&configs, &[index], options, None)
+}
+
+/// Runs the example `name` like [`run_example`] without blocking the async runtime calling it.
+pub async fn run_example_async(examples: Vec<ScoreConfig>, name: String, options: RunOptions) -> Result<RunReport> {
+    tokio::task::spawn_blocking(move || run_example(&examples, &name, &options))
+        .await
+        .context("The run panicked")?
+}
+
+impl RunOptions {
+    /// Whether the `--stop-file` exists or the runner was interrupted, asking to stop the run.
+    pub(crate) fn stop_requested(&self) -> bool {
+        supervisor::interrupted() || self.stop_file.as_ref().is_some_and(|path| path.exists())
+    }
+
+    /// Why the run stopped early, for the examples it skips.
+    pub(crate) fn stop_reason(&self) -> String {
+        if supervisor::interrupted() {
+            "run interrupted".to_string()
+        } else {
+            "run stopped by the stop file".to_string()
+        }
+    }
+
+    /// Whether example names may be given by a unique prefix: with `--prefix-match`, unless `--strict`.
+    pub(crate) fn prefixes(&self) -> bool {
+        self.prefix_match && !self.strict
+    }
+}
+
+/// Removes a `--stop-file` left over from stopping an earlier run.
+pub(crate) fn remove_stale_stop_file(options: &RunOptions) -> Result<()> {
+    if let Some(stop_file) = options.stop_file.as_ref().filter(|path| path.exists()) {
+        fs::remove_file(stop_file).with_context(|| format!("Failed to remove stale stop file {:?}", stop_file))?;
+        println!("Removed stale stop file {}", stop_file.display());
+    }
+    Ok(())
+}
+
+impl Default for RunOptions {
+    /// The options of `score-cli run` without flags.
+    fn default() -> Self {
+        let command = RunOptions::augment_args(clap::Command::new("run"));
+        RunOptions::from_arg_matches(&command.get_matches_from(["run"])).expect("defaults of the run options are valid")
+    }
+}
+
+fn visit_dir(
+    dir: &Path,
+    examples: &mut Vec<overlay::Source>,
+    env_presets: &mut presets::Presets,
+    edited: Option<(&Path, &str)>,
+) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read directory {:?}", dir))? {
+        let entry = entry?;
+        let path = entry.path();
+
+        if path.is_symlink() {
+            continue;
+        }
+
+        if path.is_dir() {
+            visit_dir(&path, examples, env_presets, edited)?;
+            continue;
+        }
+
+        if presets::is_presets_file(&path) {
+            presets::load(&path, env_presets)?;
+        }
+
+        if is_score_file(&path) {
+            let content = match edited {
+                Some((edited, content)) if edited == path => content.to_string(),
+                _ => fs::read_to_string(&path).with_context(|| format!("Failed reading {:?}", path))?,
+            };
+            examples.extend(parse_score_file(&path, &content)?);
+        }
+    }
+    Ok(())
+}
+
+/// The examples of the `*.score.json` file `path` holding `content`, with paths anchored at its directory.
+fn parse_score_file(path: &Path, content: &str) -> Result<Vec<overlay::Source>> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).with_context(|| format!("Invalid JSON in {:?}", path))?;
+    let found = match value {
+        serde_json::Value::Array(values) => values,
+        value => vec![value],
+    };
+    let dir = path.parent().unwrap_or(Path::new("."));
+    found
+        .into_iter()
+        .map(|mut value| {
+            config_dir::anchor(&mut value, dir)?;
+            Ok(overlay::Source {
+                value,
+                file: path.to_path_buf(),
+                origins: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn is_score_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.ends_with(".score.json"))
+        .unwrap_or(false)
+}
+
+/// Short name of an app used to prefix its output.
+fn app_label(index: usize, path: &str) -> String {
+    let name = Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or(path);
+    format!("{}:{}", index, name)
+}
+
+/// An app spawned as part of the currently running example.
+struct RunningApp {
+    index: usize,
+    path: String,
+    label: String,
+    seed: u64,
+    started: Instant,
+    duration: Duration, // from spawn to exit, set once the app exited
+    stop_signal: sys::Signal,
+    stop_timeout: Duration,
+    keep_alive: bool,
+    command: std::process::Command,      // kept to restart the app
+    input: Option<Arc<[u8]>>,            // fed to stdin again on restarts
+    interaction: Vec<interaction::Step>, // played again on restarts
+    child: Child,
+    pidfd: Option<sys::Pidfd>, // of the child, where pidfds exist
+    instrumented: Instrumented,
+    readers: Vec<JoinHandle<()>>,
+}
+
+fn run_score(config: &ScoreConfig, options: &RunOptions, run: &Run, example_dir: &Path) -> Result<ExampleReport> {
+    run_score_with(config, options, run, example_dir, &mut NoMonitor)
+}
+
+/// Waits for `delay` in the start sequence, returning early with the index and status of a started app that failed.
+///
+/// Apps are reaped once the supervisor reports them exited; `poll_startup` also runs every [`POLL_INTERVAL`],
+/// so the readiness of the started apps is reported and their start timeouts are enforced during the delay.
+fn wait_while_starting(
+    delay: Duration,
+    children: &mut [RunningApp],
+    supervisor: &mut supervisor::Supervisor,
+    capture: &output::Capture,
+    poll_startup: &mut dyn FnMut() -> Result<()>,
+) -> Result<Option<(usize, ExitStatus)>> {
+    let until = Instant::now() + delay;
+    // Apps to reap: all at first, then those the supervisor reports.
+    let mut exited: Vec<usize> = (0..children.len()).collect();
+    let mut finished = vec![false; children.len()];
+    loop {
+        for position in exited.drain(..) {
+            let app = &mut children[position];
+            let status = app
+                .child
+                .try_wait()
+                .with_context(|| format!("Failed to wait for app {}: {}", app.index, app.path))?;
+            match status {
+                Some(status) if !status.success() => return Ok(Some((app.index, status))),
+                Some(_) => finished[position] = true,
+                None => {}
+            }
+        }
+        poll_startup()?;
+        let left = until.saturating_duration_since(Instant::now());
+        if left.is_zero() || supervisor::interrupted() {
+            return Ok(None);
+        }
+        let running: Vec<usize> = (0..children.len()).filter(|&position| !finished[position]).collect();
+        let pidfds: Vec<Option<&sys::Pidfd>> = running
+            .iter()
+            .map(|&position| children[position].pidfd.as_ref())
+            .collect();
+        exited = supervisor
+            .wait(&pidfds, capture.wake(), left.min(POLL_INTERVAL))?
+            .into_iter()
+            .map(|ready| running[ready])
+            .collect();
+    }
+}
+
+/// Reports the apps that became ready, keeping when the last one did in `started_up`.
+///
+/// Fails once an app did not become ready within its start timeout.
+#[allow(clippy::too_many_arguments)]
+fn poll_startup(
+    startup: &mut readiness::Startup,
+    capture: &output::Capture,
+    barrier: Option<&barrier::Barrier>,
+    trace: &Trace,
+    pid: usize,
+    now: Instant,
+    started_up: &mut Option<Duration>,
+) -> Result<()> {
+    for index in startup.poll(capture, barrier)? {
+        println!("{:?} App {}: ready", now.elapsed(), index);
+        capture.event(&format!("app {}: ready", index));
+        trace.instant(pid, index, "ready", Instant::now(), serde_json::json!({}));
+    }
+    if started_up.is_none() && startup.is_done() {
+        *started_up = Some(now.elapsed());
+    }
+    Ok(())
+}
+
+/// Records the exit of `app` in the trace, together with its whole lifetime, and keeps its run time for the report.
+fn trace_exit(trace: &Trace, pid: usize, app: &mut RunningApp, status: ExitStatus) {
+    let now = Instant::now();
+    app.duration = now - app.started;
+    let args = serde_json::json!({ "status": status.to_string() });
+    trace.instant(pid, app.index, "exit", now, args.clone());
+    trace.complete(pid, app.index, &app.label, app.started, now, args);
+}
+
+/// Stops the running apps one after another in reverse start order, each with its own
+/// stop signal and timeout, so daemons outlive the clients started after them.
+///
+/// Returns the indices of the stopped apps.
+fn stop_apps(
+    children: &mut [RunningApp],
+    statuses: &mut [Option<ExitStatus>],
+    capture: &output::Capture,
+    trace: &Trace,
+    pid: usize,
+) -> Result<Vec<usize>> {
+    let mut stopped = Vec::new();
+    for (app, status) in children.iter_mut().zip(statuses.iter_mut()).rev() {
+        if status.is_some() {
+            continue;
+        }
+        println!("App {}: stopping {} ({})", app.index, app.path, app.stop_signal);
+        capture.event(&format!("app {}: stopping with {}", app.index, app.stop_signal));
+        trace.instant(
+            pid,
+            app.index,
+            "stop",
+            Instant::now(),
+            serde_json::json!({ "signal": app.stop_signal.to_string() }),
+        );
+        let exit = sys::stop(&mut app.child, app.stop_signal, app.stop_timeout)
+            .with_context(|| format!("Failed to stop app {}: {}", app.index, app.path))?;
+        output::join_readers(&mut app.readers, EXIT_OUTPUT_TIMEOUT);
+        capture.event(&format!("app {}: {}", app.index, exit));
+        trace_exit(trace, pid, app, exit);
+        *status = Some(exit);
+        stopped.push(app.index);
+    }
+    Ok(stopped)
+}
+
+/// Fails if a barrier app exited before joining the start barrier, or not all joined within `timeout`.
+fn check_barrier(
+    barrier: &barrier::Barrier,
+    children: &[RunningApp],
+    statuses: &[Option<ExitStatus>],
+    elapsed: Duration,
+    timeout: Duration,
+) -> Result<()> {
+    for (app, status) in children.iter().zip(statuses) {
+        if let Some(status) = status {
+            if barrier.missing().contains(&app.index) {
+                anyhow::bail!(
+                    "App {} ({}) exited ({}) before joining the start barrier",
+                    app.index,
+                    app.path,
+                    status
+                );
+            }
+        }
+    }
+    if elapsed > timeout {
+        let missing: Vec<String> = barrier.missing().iter().map(ToString::to_string).collect();
+        anyhow::bail!(
+            "App(s) {} did not join the start barrier within {}s",
+            missing.join(", "),
+            timeout.as_secs()
+        );
+    }
+    Ok(())
+}
+
+/// Stops `app` if it is still running and starts it again with the same command.
+fn restart_app(
+    app: &mut RunningApp,
+    status: &mut Option<ExitStatus>,
+    capture: &output::Capture,
+    trace: &Trace,
+    pid: usize,
+    observer: Option<&mut observe::Observer>,
+) -> Result<()> {
+    println!("App {}: restarting {}", app.index, app.path);
+    capture.event(&format!("app {}: restarting on request", app.index));
+    if status.is_none() {
+        let exit = sys::stop(&mut app.child, app.stop_signal, app.stop_timeout)
+            .with_context(|| format!("Failed to stop app {}: {}", app.index, app.path))?;
+        capture.event(&format!("app {}: {}", app.index, exit));
+        trace_exit(trace, pid, app, exit);
+    }
+    let pty = if app.interaction.is_empty() {
+        None
+    } else {
+        Some(interaction::Pty::attach(&mut app.command)?)
+    };
+    app.child = app
+        .command
+        .spawn()
+        .with_context(|| format!("Failed to restart app {}: {}", app.index, app.path))?;
+    app.pidfd = sys::Pidfd::open(app.child.id()).ok();
+    app.started = Instant::now();
+    *status = None;
+    capture.event(&format!("app {}: spawned pid {}", app.index, app.child.id()));
+    trace.instant(
+        pid,
+        app.index,
+        "restart",
+        app.started,
+        serde_json::json!({ "path": app.path, "pid": app.child.id() }),
+    );
+    if let Some(observer) = observer {
+        observer.watch(app.index, app.child.id())?;
+    }
+    if let Some(ref input) = app.input {
+        stdin::feed(&mut app.child, input.clone());
+    }
+    let readers = match pty {
+        Some(pty) => pty.run(
+            &mut app.command,
+            capture,
+            app.index,
+            &app.label,
+            app.child.id(),
+            &app.interaction,
+        )?,
+        None => capture.attach(app.index, &app.label, &mut app.child)?,
+    };
+    app.readers.extend(readers);
+    Ok(())
+}
+
+/// What starting an app of an example needs besides the app's config.
+struct Launcher<'a> {
+    options: &'a RunOptions,
+    run: &'a Run,
+    example_dir: &'a Path,
+    network: &'a network::NetworkSetup,
+    sockets: &'a sockets::Sockets,
+    channels: &'a channels::ForApps,
+    virtual_display: Option<&'a str>, // DISPLAY of the apps needing a display on headless hosts
+    cgroup: Option<&'a cgroup::ExampleCgroup>,
+    barrier: Option<&'a barrier::Barrier>,
+    capture: &'a output::Capture,
+    trace: &'a Trace,
+    pid: usize,
+    timeout_factor: u64,
+    interactive: bool, // run control keys read the terminal, apps get no stdin
+}
+
+impl Launcher<'_> {
+    /// Spawns `app` as app `index` of the example and starts capturing its output.
+    fn spawn(
+        &self,
+        index: usize,
+        app: &AppConfig,
+        secrets: &[(String, String)],
+        startup: &mut readiness::Startup,
+        mut observer: Option<&mut observe::Observer>,
+    ) -> Result<RunningApp> {
+        let Launcher {
+            options,
+            run,
+            example_dir,
+            network,
+            sockets,
+            channels,
+            virtual_display,
+            cgroup,
+            barrier,
+            capture,
+            trace,
+            pid,
+            timeout_factor,
+            interactive,
+        } = *self;
+        let instrumented = instrument::prepare(options.instrument, options.strict, app, index, example_dir)?;
+        let app_seed = app.seed.unwrap_or(run.seed);
+        let mut loader_env = Vec::new();
+        let mut extra_preload = Vec::new();
+        if let Some(ref spec) = app.faketime {
+            let lib =
+                faketime::apply(&mut loader_env, spec).with_context(|| format!("App {}: invalid faketime", index))?;
+            extra_preload.push(lib);
+        }
+        linker::configure(
+            &mut loader_env,
+            &app.env,
+            |key| app.inherits_env(key),
+            &app.preload,
+            &app.library_path,
+            &extra_preload,
+        )
+        .with_context(|| format!("App {}: invalid dynamic linker configuration", index))?;
+        // The loader variables must only reach the app, not `ip netns exec` or `listen-pid` starting it.
+        let wrapped = app.node.is_some() || !app.sockets.is_empty();
+        let (program, env_args) = if wrapped && !loader_env.is_empty() {
+            let mut env_args: Vec<String> = loader_env
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            env_args.push(instrumented.program.clone());
+            ("env", env_args)
+        } else {
+            (instrumented.program.as_str(), Vec::new())
+        };
+        let mut cmd = if app.sockets.is_empty() {
+            network.command(app.node.as_deref(), program)?
+        } else {
+            // Started through the CLI itself, which sets LISTEN_PID to the app's pid before exec.
+            let exe = env::current_exe().context("Failed to find the path of the CLI executable")?;
+            let mut cmd = network.command(app.node.as_deref(), &exe.to_string_lossy())?;
+            cmd.args(["builtin", "listen-pid", "--", program]);
+            sockets
+                .pass(&mut cmd, &app.sockets)
+                .with_context(|| format!("App {}: invalid sockets", index))?;
+            cmd
+        };
+        cmd.args(&env_args);
+        cmd.args(&instrumented.args);
+        cmd.args(&app.args);
+        if !app.inherit_env {
+            cmd.env_clear();
+        }
+        for name in &app.env_remove {
+            cmd.env_remove(name);
+        }
+        cmd.env("SCORE_RUN_ID", &run.id);
+        cmd.env("SCORE_RUN_SEED", app_seed.to_string());
+        channels.prepare(&mut cmd);
+        if let (true, Some(display)) = (app.requires_display, virtual_display) {
+            cmd.env("DISPLAY", display);
+        }
+        cmd.envs(&app.env);
+        cmd.envs(secrets.iter().cloned());
+        cmd.envs(instrumented.env.iter().cloned());
+        if let Some(barrier) = barrier {
+            if app.barrier {
+                cmd.envs(barrier.env(index));
+            }
+        }
+        if options.coverage || options.merge_coverage {
+            cmd.envs(coverage::env(example_dir, index));
+        }
+        if !wrapped {
+            cmd.envs(loader_env);
+        }
+        priority::apply(&mut cmd, app.nice, app.ionice.as_deref())
+            .with_context(|| format!("App {}: invalid priority", index))?;
+        oom::apply(&mut cmd, app.oom_score_adj).with_context(|| format!("App {}: invalid oom_score_adj", index))?;
+        if let Some(ref dir) = app.dir {
+            cmd.current_dir(dir);
+        }
+        if let Some(cgroup) = cgroup {
+            cgroup.place(&mut cmd);
+        }
+
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        let input = app
+            .stdin
+            .as_ref()
+            .map(stdin::Input::read)
+            .transpose()
+            .with_context(|| format!("App {}: invalid stdin", index))?;
+        let pty = if app.interaction.is_empty() {
+            None
+        } else {
+            Some(interaction::Pty::attach(&mut cmd).with_context(|| format!("App {}: invalid interaction", index))?)
+        };
+        if input.is_some() {
+            cmd.stdin(Stdio::piped());
+        } else if interactive && pty.is_none() {
+            // Keys typed on the terminal control the example.
+            cmd.stdin(Stdio::null());
+        }
+
+        let stop_signal = match app.stop_signal {
+            Some(ref spec) => {
+                sys::Signal::parse(spec).with_context(|| format!("App {}: invalid stop_signal \"{}\"", index, spec))?
+            }
+            None => sys::Signal::TERM,
+        };
+        let stop_timeout = instrument::scale(
+            app.stop_timeout.unwrap_or(STOP_GRACE_PERIOD.as_secs()),
+            timeout_factor,
+            "stop_timeout",
+        )
+        .with_context(|| format!("App {}", index))?;
+        let stop_timeout = Duration::from_secs(stop_timeout);
+
+        command_record::CommandRecord::of(&cmd, app.inherit_env, |text| capture.redact(text))
+            .write(&example_dir.join(format!("app{}.command.json", index)))?;
+        let mut child = cmd
+            .spawn()
+            .with_context(|| format!("Failed to start app {}: {}", index, app.path))?;
+        let pidfd = sys::Pidfd::open(child.id()).ok();
+
+        if !options.quiet {
+            println!(
+                "App {}: spawned command {}",
+                index,
+                command_record::command_line(&cmd, |text| capture.redact(text))
+            );
+        }
+        capture.event(&format!("app {}: spawned pid {}", index, child.id()));
+        run.checkpoint.app_started(example_dir, index, &app.path, child.id());
+        if let Some(ref input) = input {
+            stdin::feed(&mut child, input.clone());
+        }
+
+        let started = Instant::now();
+        let label = app_label(index, app.name.as_deref().unwrap_or(&app.path));
+        trace.app(pid, index, &label);
+        trace.instant(
+            pid,
+            index,
+            "spawn",
+            started,
+            serde_json::json!({ "path": app.path, "pid": child.id() }),
+        );
+        if let Some(ref mut observer) = observer {
+            observer.watch(index, child.id())?;
+        }
+        let start_timeout = app
+            .start_timeout
+            .map(|secs| instrument::scale(secs, timeout_factor, "start_timeout").map(Duration::from_secs))
+            .transpose()
+            .with_context(|| format!("App {}", index))?;
+        startup.watch(index, app, start_timeout, capture)?;
+        let readers = match pty {
+            Some(pty) => pty.run(&mut cmd, capture, index, &label, child.id(), &app.interaction)?,
+            None => capture.attach(index, &label, &mut child)?,
+        };
+        Ok(RunningApp {
+            index,
+            path: app.path.clone(),
+            label,
+            seed: app_seed,
+            started,
+            duration: Duration::ZERO,
+            stop_signal,
+            stop_timeout,
+            keep_alive: app.lifecycle == Lifecycle::KeepAlive,
+            command: cmd,
+            input,
+            interaction: app.interaction.clone(),
+            child,
+            pidfd,
+            instrumented,
+            readers,
+        })
+    }
+}
+
+fn run_score_with(
+    config: &ScoreConfig,
+    options: &RunOptions,
+    run: &Run,
+    example_dir: &Path,
+    monitor: &mut dyn Monitor,
+) -> Result<ExampleReport> {
+    strict::check_fields(config, options.strict)?;
+    if let Some(reason) = config.skip_reason()? {
+        println!("⏭  Example '{}' skipped: {}", config.name, reason);
+        return Ok(ExampleReport::skipped(&config.name, &config.requirements, reason));
+    }
+    let _locks = locks::acquire(config, &run.id)?;
+    println!("▶ Running example: {}", config.name);
+    let trace = &run.trace;
+    let pid = trace.example(&config.name);
+
+    if let Some(instrument) = options.instrument {
+        instrument.check_supported()?;
+    }
+    let timeout_factor = options.instrument.map(Instrument::timeout_factor).unwrap_or(1);
+
+    let _can = can::setup(&config.can)?;
+    let network = network::setup(
+        config.network.as_ref(),
+        config.apps.iter().filter_map(|app| app.node.as_deref()),
+    )?;
+    let sd_start = Instant::now();
+    let sd_check = config
+        .someip_sd
+        .as_ref()
+        .map(|sd| {
+            someip::start_verification(&someip::SomeIpSdConfig {
+                timeout: instrument::scale(sd.timeout, timeout_factor, "someip_sd.timeout")?,
+                ..sd.clone()
+            })
+        })
+        .transpose()?;
+
+    let mut observer = if options.observe {
+        Some(observe::Observer::start()?)
+    } else {
+        None
+    };
+    let mut children: Vec<RunningApp> = Vec::new();
+    let mut controls = if options.interactive {
+        control::Controls::start()
+    } else {
+        None
+    };
+
+    let app_secrets = config
+        .apps
+        .iter()
+        .enumerate()
+        .map(|(i, app)| secrets::resolve(&app.secrets).with_context(|| format!("App {}", i + 1)))
+        .collect::<Result<Vec<_>>>()?;
+    let mut redactor = redact::Redactor::default();
+    redactor.add_secrets(app_secrets.iter().flatten().map(|(_, value)| value.clone()));
+    for rule in &config.redact {
+        redactor.add_rule(rule)?;
+    }
+    for rule in &options.redact {
+        redactor.add_cli_rule(rule)?;
+    }
+
+    let participants: BTreeSet<usize> = config
+        .apps
+        .iter()
+        .enumerate()
+        .filter(|(_, app)| app.barrier)
+        .map(|(i, _)| i + 1)
+        .collect();
+    let barrier = if participants.is_empty() {
+        None
+    } else {
+        Some(barrier::Barrier::start(participants)?)
+    };
+    let barrier_timeout = Duration::from_secs(instrument::scale(
+        config.barrier_timeout.unwrap_or(BARRIER_TIMEOUT.as_secs()),
+        timeout_factor,
+        "barrier_timeout",
+    )?);
+
+    let now = Instant::now();
+    let mut capture = output::Capture::new(
+        now,
+        options.failure_context,
+        options.timestamps,
+        example_dir,
+        redactor,
+        run.log_pause.clone(),
+    )?;
+    let mut forwarding = forward::Forwarding {
+        example: config.name.clone(),
+        all: options.forward_logs.as_deref().map(forward::viewer).transpose()?,
+        ..Default::default()
+    };
+    for (i, app) in config.apps.iter().enumerate() {
+        if let Some(ref address) = app.forward_logs {
+            forwarding.apps.insert(i + 1, forward::viewer(address)?);
+        }
+    }
+    capture.forward(forwarding);
+    if options.quiet || options.summary {
+        capture.hide_all();
+    }
+    let mut startup = readiness::Startup::default();
+    let sockets = sockets::Sockets::open(config.apps.iter().flat_map(|app| &app.sockets))?;
+    let mut channels = channels::Channels::create(&config.channels, example_dir)?;
+    let channels_for_apps = channels.for_apps();
+    let virtual_display = display::VirtualDisplay::start_if_needed(config)?;
+    let cgroup = cgroup::ExampleCgroup::create();
+    if let Some(ref cgroup) = cgroup {
+        run.checkpoint.cgroup_created(example_dir, cgroup.path());
+    }
+    let mut oom_watch = oom::Watch::start(cgroup.as_ref().map(cgroup::ExampleCgroup::path));
+    let recorder = match options.capture_screen {
+        Some(mode) if config.tags.iter().any(|tag| tag == screen::GUI_TAG) => screen::Recorder::start(
+            mode,
+            options.screenshot_interval,
+            virtual_display.as_ref().map(display::VirtualDisplay::display),
+            example_dir,
+            options.strict,
+        )?,
+        _ => None,
+    };
+    println!("{:?} Starting example '{}'", now.elapsed(), config.name);
+    capture.event(&format!("starting example '{}' (run {})", config.name, run.id));
+    let mut supervisor = supervisor::Supervisor::new()?;
+    let launcher = Launcher {
+        options,
+        run,
+        example_dir,
+        network: &network,
+        sockets: &sockets,
+        channels: &channels_for_apps,
+        virtual_display: virtual_display.as_ref().map(display::VirtualDisplay::display),
+        cgroup: cgroup.as_ref(),
+        barrier: barrier.as_ref(),
+        capture: &capture,
+        trace,
+        pid,
+        timeout_factor,
+        interactive: controls.is_some(),
+    };
+    let mut start_failure = None;
+    let mut started_up = None;
+    for (i, app) in config.apps.iter().enumerate() {
+        let app = app.clone(); // Clone for ownership
+        let mut poll = || {
+            poll_startup(
+                &mut startup,
+                &capture,
+                barrier.as_ref(),
+                trace,
+                pid,
+                now,
+                &mut started_up,
+            )
+        };
+        let waited = (|| {
+            let mut failure = None;
+            if let Some(delay_secs) = app.delay {
+                let delay_secs =
+                    instrument::scale(delay_secs, timeout_factor, "delay").with_context(|| format!("App {}", i + 1))?;
+                if delay_secs > 0 {
+                    println!(
+                        "{:?}  App {}: waiting {} seconds before start...",
+                        now.elapsed(),
+                        i + 1,
+                        delay_secs
+                    );
+                    let delay_start = Instant::now();
+                    failure = wait_while_starting(
+                        Duration::from_secs(delay_secs),
+                        &mut children,
+                        &mut supervisor,
+                        &capture,
+                        &mut poll,
+                    )?;
+                    trace.complete(
+                        pid,
+                        trace::RUNNER_TID,
+                        "delay",
+                        delay_start,
+                        Instant::now(),
+                        serde_json::json!({ "app": i + 1 }),
+                    );
+                }
+            }
+            match failure {
+                Some(failure) => Ok(Some(failure)),
+                None => wait_while_starting(Duration::ZERO, &mut children, &mut supervisor, &capture, &mut poll),
+            }
+        })();
+        start_failure = match waited {
+            Ok(failure) => failure,
+            Err(e) => {
+                let mut statuses: Vec<Option<ExitStatus>> = children.iter().map(|_| None).collect();
+                let _ = stop_apps(&mut children, &mut statuses, &capture, trace, pid);
+                capture.event(&format!("startup failed: {:#}", e));
+                return Err(e).with_context(|| format!("Example '{}' failed to start", config.name));
+            }
+        };
+        if let Some((index, status)) = start_failure {
+            println!(
+                "{:?} App {} exited with {} while the example was starting, not starting apps {} to {}",
+                now.elapsed(),
+                index,
+                status,
+                i + 1,
+                config.apps.len()
+            );
+            capture.event(&format!(
+                "app {}: exited with {} while starting, start aborted",
+                index, status
+            ));
+            break;
+        }
+
+        if options.stop_requested() {
+            println!("Run stopping, not starting apps {} to {}", i + 1, config.apps.len());
+            capture.event(&format!(
+                "run stopping, apps {} to {} not started",
+                i + 1,
+                config.apps.len()
+            ));
+            break;
+        }
+
+        println!("{:?} App {}: starting {}", now.elapsed(), i + 1, app.path);
+        capture.event(&format!("app {}: starting {}", i + 1, app.path));
+
+        match launcher.spawn(i + 1, &app, &app_secrets[i], &mut startup, observer.as_mut()) {
+            Ok(spawned) => children.push(spawned),
+            Err(e) => {
+                let mut statuses: Vec<Option<ExitStatus>> = children.iter().map(|_| None).collect();
+                let _ = stop_apps(&mut children, &mut statuses, &capture, trace, pid);
+                capture.event(&format!("app {}: failed to start: {:#}", i + 1, e));
+                return Err(e);
+            }
+        }
+    }
+
+    let mut statuses: Vec<Option<ExitStatus>> = children.iter().map(|_| None).collect();
+    if let Some(handle) = sd_check {
+        let result = handle
+            .join()
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("SOME/IP SD verification panicked")));
+        let outcome = match &result {
+            Ok(()) => "ok".to_string(),
+            Err(e) => format!("{:#}", e),
+        };
+        trace.complete(
+            pid,
+            trace::RUNNER_TID,
+            "SOME/IP SD verification",
+            sd_start,
+            Instant::now(),
+            serde_json::json!({ "result": outcome }),
+        );
+        if let Err(e) = result {
+            let _ = stop_apps(&mut children, &mut statuses, &capture, trace, pid);
+            capture.event(&format!("SOME/IP SD verification failed: {:#}", e));
+            return Err(e).with_context(|| format!("Example '{}' failed verification", config.name));
+        }
+    }
+
+    let mut health = match (&config.health_monitor, &start_failure) {
+        (Some(health_monitor), None) => {
+            capture.event("starting health monitor reporter");
+            match health::Reporter::start(health_monitor, &run.id, &config.name, &children) {
+                Ok(reporter) => Some(reporter),
+                Err(e) => {
+                    let _ = stop_apps(&mut children, &mut statuses, &capture, trace, pid);
+                    capture.event(&format!("health monitor reporter failed: {:#}", e));
+                    return Err(e).with_context(|| format!("Example '{}' failed to start", config.name));
+                }
+            }
+        }
+        _ => None,
+    };
+
+    // Wait for all children, letting the monitor observe them and stop the example early
+    let mut first_failure = None;
+    let mut runner_failure = None; // failed by the runner however the app exited, e.g. by its interaction script
+    let mut stopped = Vec::new();
+    let mut interrupted = false;
+    let mut barrier_reported = false;
+    // Apps to reap: all at first, then those the supervisor reports as exited.
+    let mut exited: Vec<usize> = (0..children.len()).collect();
+    loop {
+        for position in exited.drain(..) {
+            let (app, status) = (&mut children[position], &mut statuses[position]);
+            if status.is_none() {
+                *status = app
+                    .child
+                    .try_wait()
+                    .with_context(|| format!("Failed to wait for app {}: {}", app.index, app.path))?;
+                if let Some(exit) = status {
+                    output::join_readers(&mut app.readers, EXIT_OUTPUT_TIMEOUT);
+                    println!("App {}: finished {}", app.index, app.path);
+                    capture.event(&format!("app {}: {}", app.index, exit));
+                    trace_exit(trace, pid, app, *exit);
+                    if !exit.success() && first_failure.is_none() {
+                        first_failure = Some((app.index, format!("exited with {}", exit), now.elapsed()));
+                    }
+                }
+            }
+        }
+        for (index, reason) in capture.take_failures() {
+            if first_failure.is_none() {
+                first_failure = Some((index, reason.clone(), now.elapsed()));
+            }
+            runner_failure.get_or_insert((index, reason));
+        }
+        if let Some(ref mut health) = health {
+            health.poll(&children, &statuses, &capture);
+        }
+        channels.poll();
+
+        if start_failure.is_some() {
+            if let Some(ref mut controls) = controls {
+                controls.resume_all();
+            }
+            stopped.extend(stop_apps(&mut children, &mut statuses, &capture, trace, pid)?);
+            break;
+        }
+
+        if let Err(e) = poll_startup(
+            &mut startup,
+            &capture,
+            barrier.as_ref(),
+            trace,
+            pid,
+            now,
+            &mut started_up,
+        ) {
+            let _ = stop_apps(&mut children, &mut statuses, &capture, trace, pid);
+            capture.event(&format!("startup failed: {:#}", e));
+            return Err(e).with_context(|| format!("Example '{}' failed to start", config.name));
+        }
+
+        if let Some(ref barrier) = barrier {
+            match barrier.released() {
+                Some(released) if !barrier_reported => {
+                    barrier_reported = true;
+                    println!("{:?} Start barrier released", now.elapsed());
+                    capture.event("start barrier released");
+                    trace.instant(
+                        pid,
+                        trace::RUNNER_TID,
+                        "barrier released",
+                        released,
+                        serde_json::json!({}),
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    if let Err(e) = check_barrier(barrier, &children, &statuses, now.elapsed(), barrier_timeout) {
+                        let _ = stop_apps(&mut children, &mut statuses, &capture, trace, pid);
+                        capture.event(&format!("start barrier failed: {:#}", e));
+                        return Err(e).with_context(|| format!("Example '{}' failed to start", config.name));
+                    }
+                }
+            }
+        }
+
+        if let Some(ref mut controls) = controls {
+            let running: Vec<(usize, u32)> = children
+                .iter()
+                .zip(statuses.iter())
+                .filter(|(_, status)| status.is_none())
+                .map(|(app, _)| (app.index, app.child.id()))
+                .collect();
+            let mut stop = false;
+            for action in controls.poll(&running, &capture) {
+                match action {
+                    control::Action::Restart(index) => {
+                        if let Some(position) = children.iter().position(|app| app.index == index) {
+                            let app = &mut children[position];
+                            restart_app(app, &mut statuses[position], &capture, trace, pid, observer.as_mut())?;
+                            run.checkpoint
+                                .app_started(example_dir, app.index, &app.path, app.child.id());
+                        }
+                    }
+                    control::Action::StopExample => stop = true,
+                }
+            }
+            if stop {
+                println!("Stopping example on request");
+                capture.event("stopping example on request");
+                stopped.extend(stop_apps(&mut children, &mut statuses, &capture, trace, pid)?);
+                break;
+            }
+        }
+
+        if let Some(ref dir) = options.add_apps_dir {
+            for (file, app) in hot_add::take(dir) {
+                let index = children.len() + 1;
+                let added = app.and_then(|app| {
+                    println!(
+                        "{:?} App {}: adding {} from {}",
+                        now.elapsed(),
+                        index,
+                        app.path,
+                        file.display()
+                    );
+                    capture.event(&format!("app {}: adding {} from {:?}", index, app.path, file));
+                    // Added apps cannot declare secrets, `hot_add` rejects them.
+                    launcher.spawn(index, &app, &[], &mut startup, observer.as_mut())
+                });
+                hot_add::done(&file, added.is_ok());
+                match added {
+                    Ok(app) => {
+                        children.push(app);
+                        statuses.push(None);
+                    }
+                    Err(e) => {
+                        println!("⚠️  Not adding app from {}: {:#}", file.display(), e);
+                        capture.event(&format!("not adding app from {:?}: {:#}", file, e));
+                    }
+                }
+            }
+        }
+
+        if options.stop_requested() {
+            interrupted = supervisor::interrupted();
+            if interrupted {
+                println!("Runner interrupted, stopping example");
+                capture.event("stopping example, runner interrupted");
+            } else {
+                println!("Stop file found, stopping example");
+                capture.event("stopping example, stop file found");
+            }
+            if let Some(ref mut controls) = controls {
+                controls.resume_all();
+            }
+            stopped.extend(stop_apps(&mut children, &mut statuses, &capture, trace, pid)?);
+            break;
+        }
+
+        let positions: Vec<usize> = (0..children.len())
+            .filter(|&position| statuses[position].is_none())
+            .collect();
+        let running: Vec<&RunningApp> = positions.iter().map(|&position| &children[position]).collect();
+        if running.is_empty() {
+            break;
+        }
+        if running.iter().all(|app| app.keep_alive) && children.iter().any(|app| !app.keep_alive) {
+            println!("All run-to-completion apps exited, stopping keep-alive apps");
+            if let Some(ref mut controls) = controls {
+                controls.resume_all();
+            }
+            stopped.extend(stop_apps(&mut children, &mut statuses, &capture, trace, pid)?);
+            break;
+        }
+
+        oom_watch.poll(&running);
+        if let Some(ref cgroup) = cgroup {
+            cgroup.poll();
+        }
+        if monitor.poll(&running) {
+            if let Some(ref mut controls) = controls {
+                controls.resume_all();
+            }
+            stopped.extend(stop_apps(&mut children, &mut statuses, &capture, trace, pid)?);
+            break;
+        }
+        let pidfds: Vec<Option<&sys::Pidfd>> = running.iter().map(|app| app.pidfd.as_ref()).collect();
+        exited = supervisor
+            .wait(&pidfds, capture.wake(), POLL_INTERVAL)?
+            .into_iter()
+            .map(|ready| positions[ready])
+            .collect();
+    }
+
+    let cgroup_usage = cgroup.as_ref().map(|cgroup| {
+        let usage = cgroup.finish();
+        if usage.leftover_processes > 0 {
+            println!(
+                "⚠️  Killed {} processes left in the cgroup of example '{}'",
+                usage.leftover_processes, config.name
+            );
+            capture.event(&format!(
+                "killed {} processes left in the cgroup",
+                usage.leftover_processes
+            ));
+        }
+        println!(
+            "Example '{}': {:.2} s on CPU{}",
+            config.name,
+            usage.cpu_ms as f64 / 1000.0,
+            usage
+                .memory_peak_kb
+                .map(|kb| format!(", {} MiB peak memory", kb / 1024))
+                .unwrap_or_default()
+        );
+        usage
+    });
+    if let Some(health) = health {
+        health.finish(&children, &statuses);
+    }
+    if let Some(recorder) = recorder {
+        let files = recorder.finish();
+        println!("Screen capture: {} files", files.len());
+        capture.event(&format!("screen captured into {} files", files.len()));
+    }
+    let labels: HashMap<usize, String> = children.iter().map(|app| (app.index, app.label.clone())).collect();
+    for app in children.iter_mut() {
+        output::join_readers(&mut app.readers, READER_DRAIN_TIMEOUT);
+    }
+    let kpis = if config.kpis.is_empty() {
+        Vec::new()
+    } else {
+        let mut metrics = bench::app_metric_values(example_dir)
+            .with_context(|| format!("Failed to read the metrics of example '{}'", config.name))?;
+        metrics.insert("duration_ms".to_string(), now.elapsed().as_millis() as f64);
+        if let Some(started_up) = started_up {
+            metrics.insert("startup_ms".to_string(), started_up.as_millis() as f64);
+        }
+        let mut unmeasurable = BTreeMap::new();
+        match cgroup_usage {
+            Some(ref usage) => {
+                metrics.insert("cpu_ms".to_string(), usage.cpu_ms as f64);
+                match usage.memory_peak_kb {
+                    Some(kb) => {
+                        metrics.insert("memory_peak_kb".to_string(), kb as f64);
+                    }
+                    None => {
+                        unmeasurable.insert("memory_peak_kb", "no memory controller for the example's cgroup");
+                    }
+                }
+            }
+            None => {
+                unmeasurable.insert("cpu_ms", "no cgroup per example on this host");
+                unmeasurable.insert("memory_peak_kb", "no cgroup per example on this host");
+            }
+        }
+        let kpis = kpi::check(&config.kpis, &metrics, &unmeasurable);
+        println!("━━ KPIs of '{}'", config.name);
+        for kpi in &kpis {
+            let icon = match (kpi.passed, &kpi.skipped) {
+                (true, _) => "✅",
+                (false, Some(_)) if !options.strict => "⏭ ",
+                (false, _) => "❌",
+            };
+            println!("  {} {}", icon, kpi.describe());
+        }
+        kpis
+    };
+    // Skipped KPIs only fail the example with --strict.
+    let missed: Vec<String> = kpis
+        .iter()
+        .filter(|kpi| !kpi.passed && (kpi.skipped.is_none() || options.strict))
+        .map(|kpi| format!("KPI {}", kpi.describe()))
+        .collect();
+    let first_failure = match first_failure {
+        Some((index, reason, at)) => Some(capture.failure_context(index, reason, at + output::RELATED_ERRORS_WINDOW)),
+        // Told by the runner, with the error lines of all apps during the example.
+        None if !missed.is_empty() => Some(capture.failure_context(0, missed.join(", "), now.elapsed())),
+        None => None,
+    };
+    if let Some(ref context) = first_failure {
+        let mut labels = labels;
+        labels.insert(0, "runner".to_string());
+        output::print_failure_context(context, &labels);
+    }
+
+    let mut observations = observer.map(observe::Observer::finish).unwrap_or_default();
+    let mut apps = Vec::new();
+    for (app, status) in children.into_iter().zip(statuses) {
+        let i = app.index;
+        let status = status.expect("all apps exited");
+
+        let instrumentation = app
+            .instrumented
+            .collect(options.instrument)
+            .with_context(|| format!("Failed to collect instrumentation results of app {}", i))?;
+        if let Some(ref result) = instrumentation {
+            println!(
+                "App {}: {} errors, {} bytes leaked ({} logs in {})",
+                i,
+                result.errors,
+                result.leaked_bytes,
+                result.logs.len(),
+                example_dir.display()
+            );
+        }
+        let stopped = stopped.contains(&i);
+        let mut warnings = Vec::new();
+        if options.strict && stopped && !status.success() && sys::Signal::of(&status) != Some(app.stop_signal) {
+            println!(
+                "❌ App {}: {} exited with {} when stopped (--strict)",
+                i, app.path, status
+            );
+            warnings.push(format!("exited with {} when stopped with {}", status, app.stop_signal));
+        }
+        let killed = !stopped && sys::Signal::of(&status) == Some(sys::Signal::KILL);
+        let oom = if killed { oom_watch.oom_killed() } else { Some(false) };
+        let oom_killed = oom == Some(true);
+        if oom_killed {
+            println!("💥 App {}: {} was killed by the OOM killer", i, app.path);
+            capture.event(&format!("app {}: OOM-killed", i));
+        } else if oom.is_none() {
+            println!(
+                "💥 App {}: {} was killed by SIGKILL, by the OOM killer or not is unknown without a cgroup",
+                i, app.path
+            );
+        }
+        let observation = observations.remove(&i);
+        if let Some(ref observed) = observation {
+            println!(
+                "App {}: {} syscalls, {:.1} ms on CPU, {:.1} µs average scheduling latency",
+                i, observed.syscalls, observed.cpu_ms, observed.avg_sched_latency_us
+            );
+        }
+        apps.push(AppReport {
+            index: i,
+            path: app.path,
+            exit_code: status.code(),
+            stopped,
+            oom_killed,
+            oom_unknown: oom.is_none(),
+            duration_ms: app.duration.as_millis(),
+            seed: app.seed,
+            component: None,
+            instrumentation,
+            observation,
+            samples: if killed { oom_watch.samples(i) } else { Vec::new() },
+            warnings,
+        });
+    }
+
+    capture.event(&format!("example '{}' finished", config.name));
+    trace.complete(
+        pid,
+        trace::RUNNER_TID,
+        "example",
+        now,
+        Instant::now(),
+        serde_json::json!({ "name": config.name }),
+    );
+    // Apps stopped by the runner pass however they exit, unless --strict left a warning on them.
+    let status = Status::of(
+        apps.iter()
+            .all(|app| app.exit_code == Some(0) || (app.stopped && app.warnings.is_empty())),
+        config.xfail.is_some(),
+    );
+    let mut reason = config.xfail.as_ref().map(KnownIssue::describe);
+    let not_logged = capture.not_logged_lines();
+    let status = if interrupted {
+        println!("❌ Example '{}': the runner was interrupted", config.name);
+        reason = Some("run interrupted".to_string());
+        Status::Failed
+    } else if let Some((index, message)) = runner_failure {
+        println!(
+            "❌ Example '{}': app {}: {}",
+            config.name,
+            index,
+            capture.redact(&message)
+        );
+        if config.xfail.is_none() {
+            reason = Some(format!("app {}: {}", index, message));
+        }
+        Status::of(false, config.xfail.is_some())
+    } else if !missed.is_empty() && matches!(status, Status::Passed | Status::Xpass) {
+        let message = missed.join(", ");
+        println!("❌ Example '{}': {}", config.name, message);
+        if config.xfail.is_none() {
+            reason = Some(message);
+        }
+        Status::of(false, config.xfail.is_some())
+    } else if options.strict && not_logged > 0 {
+        let message = format!(
+            "{} lines of app output were not logged for lack of disk space",
+            not_logged
+        );
+        println!("❌ Example '{}': {} (--strict)", config.name, message);
+        reason = Some(message);
+        Status::Failed
+    } else if options.strict && capture.dropped_error_lines() > 0 {
+        let message = format!(
+            "{} error lines of the apps were not kept for the failure context, beyond the first {}",
+            capture.dropped_error_lines(),
+            output::MAX_ERROR_LINES
+        );
+        println!("❌ Example '{}': {} (--strict)", config.name, message);
+        reason = Some(message);
+        Status::Failed
+    } else if capture.unlogged_lines() > 0 {
+        let message = format!(
+            "{} lines of app output could not be written to the logs",
+            capture.unlogged_lines()
+        );
+        println!("❌ Example '{}': {}", config.name, message);
+        reason = Some(message);
+        Status::Failed
+    } else {
+        status
+    };
+    match status {
+        Status::Passed => println!("✅ Example '{}' finished successfully.", config.name),
+        Status::Xfail => println!("⚠️  Example '{}' failed as expected (xfail).", config.name),
+        Status::Xpass => println!(
+            "✅ Example '{}' passed although marked xfail, remove the marker if the issue is fixed.",
+            config.name
+        ),
+        _ => println!("❌ Example '{}' failed.", config.name),
+    }
+    Ok(ExampleReport {
+        name: config.name.clone(),
+        requirements: config.requirements.clone(),
+        status,
+        reason,
+        infra_error: false,
+        attempts: 1,
+        steps: Vec::new(),
+        duration_ms: now.elapsed().as_millis(),
+        apps,
+        first_failure,
+        cgroup: cgroup_usage,
+        kpis,
+    })
+}
@@ -0,0 +1,147 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Dynamic linker configuration (`LD_PRELOAD`, `LD_LIBRARY_PATH`) per app.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const SYSTEM_LIBRARY_DIRS: &[&str] = &[
+    "/lib",
+    "/usr/lib",
+    "/lib64",
+    "/usr/lib64",
+    "/usr/local/lib",
+    "/lib/x86_64-linux-gnu",
+    "/usr/lib/x86_64-linux-gnu",
+    "/lib/aarch64-linux-gnu",
+    "/usr/lib/aarch64-linux-gnu",
+    "/proc/boot", // QNX image filesystem
+    "/lib/dll",
+];
+
+/// Adds the `LD_PRELOAD` and `LD_LIBRARY_PATH` an app needs to `env`.
+///
+/// Values the app already gets (from its `env` or the runner's environment, for
+/// variables it `inherits`) are kept; `preload`, `library_path` and
+/// `extra_preload` are appended / prepended. Every preloaded library must exist
+/// and match the host architecture.
+pub fn configure(
+    env: &mut Vec<(String, String)>,
+    app_env: &HashMap<String, String>,
+    inherits: impl Fn(&str) -> bool,
+    preload: &[String],
+    library_path: &[String],
+    extra_preload: &[PathBuf],
+) -> Result<()> {
+    for dir in library_path {
+        if !Path::new(dir).is_dir() {
+            bail!("library_path entry {} is not a directory", dir);
+        }
+    }
+
+    let mut preloaded = Vec::new();
+    for lib in preload {
+        let resolved = resolve(lib, library_path).with_context(|| format!("Cannot preload {}", lib))?;
+        check_architecture(&resolved).with_context(|| format!("Cannot preload {}", lib))?;
+        preloaded.push(lib.clone());
+    }
+    preloaded.extend(extra_preload.iter().map(|p| p.display().to_string()));
+
+    if !preloaded.is_empty() {
+        let mut entries: Vec<String> = inherited(app_env, &inherits, "LD_PRELOAD").into_iter().collect();
+        entries.extend(preloaded);
+        env.push(("LD_PRELOAD".to_string(), entries.join(":")));
+    }
+
+    if !library_path.is_empty() {
+        let mut entries = library_path.to_vec();
+        entries.extend(inherited(app_env, &inherits, "LD_LIBRARY_PATH"));
+        env.push(("LD_LIBRARY_PATH".to_string(), entries.join(":")));
+    }
+    Ok(())
+}
+
+fn inherited(app_env: &HashMap<String, String>, inherits: impl Fn(&str) -> bool, key: &str) -> Option<String> {
+    app_env
+        .get(key)
+        .cloned()
+        .or_else(|| env::var(key).ok().filter(|_| inherits(key)))
+        .filter(|value| !value.is_empty())
+}
+
+/// Finds a library the way the dynamic linker would: paths are taken as is,
+/// bare names are searched in `library_path` and the system directories.
+fn resolve(lib: &str, library_path: &[String]) -> Result<PathBuf> {
+    if lib.contains('/') {
+        let path = PathBuf::from(lib);
+        if !path.is_file() {
+            bail!("{} does not exist", lib);
+        }
+        return Ok(path);
+    }
+    library_path
+        .iter()
+        .map(String::as_str)
+        .chain(SYSTEM_LIBRARY_DIRS.iter().copied())
+        .map(|dir| Path::new(dir).join(lib))
+        .find(|path| path.is_file())
+        .ok_or_else(|| anyhow::anyhow!("{} not found in library_path or system library directories", lib))
+}
+
+fn expected_machine() -> Option<u16> {
+    match env::consts::ARCH {
+        "x86" => Some(3),
+        "arm" => Some(40),
+        "x86_64" => Some(62),
+        "aarch64" => Some(183),
+        "riscv64" => Some(243),
+        _ => None,
+    }
+}
+
+/// Checks the ELF header of `path` against the architecture the CLI runs on.
+fn check_architecture(path: &Path) -> Result<()> {
+    let mut header = [0u8; 20];
+    File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .with_context(|| format!("Failed to read {:?}", path))?;
+
+    if header[..4] != [0x7f, b'E', b'L', b'F'] {
+        bail!("{:?} is not an ELF shared library", path);
+    }
+    let class = if cfg!(target_pointer_width = "64") { 2 } else { 1 };
+    if header[4] != class {
+        bail!("{:?} is a {}-bit library", path, if header[4] == 2 { 64 } else { 32 });
+    }
+    // EI_DATA: the byte order of the fields after the identification bytes.
+    let machine = match header[5] {
+        1 => u16::from_le_bytes([header[18], header[19]]),
+        2 => u16::from_be_bytes([header[18], header[19]]),
+        data => bail!("{:?} has an invalid ELF data encoding {}", path, data),
+    };
+    if let Some(expected) = expected_machine() {
+        if machine != expected {
+            bail!(
+                "{:?} is built for ELF machine {}, but this host is {}",
+                path,
+                machine,
+                env::consts::ARCH
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Exclusive resources of examples, so concurrent runs never fight over them.
+//!
+//! Every resource is a lock file in the user's private `locks` directory (see
+//! [`private_dir`]) holding an advisory lock while an example uses it. Locks are
+//! shared by all CLI instances of the user and released by the kernel when a
+//! runner exits, so a crashed run never leaves a stale lock behind.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Write;
+
+use crate::{private_dir, sys, ScoreConfig};
+
+/// Locks held for an example, released when this is dropped.
+pub struct Locks {
+    _files: Vec<File>,
+}
+
+/// Resources used by `config`: its declared `locks`, CAN interfaces and network bridge.
+pub fn resources(config: &ScoreConfig) -> Vec<String> {
+    let mut resources: Vec<String> = config.locks.clone();
+    resources.extend(config.can.iter().map(|can| can.interface.clone()));
+    resources.extend(config.network.iter().map(|network| network.bridge.clone()));
+    // A fixed order keeps two runners from each holding a lock the other one waits for.
+    resources.sort();
+    resources.dedup();
+    resources
+}
+
+/// Locks all resources of `config`, waiting for runs holding any of them.
+pub fn acquire(config: &ScoreConfig, run_id: &str) -> Result<Locks> {
+    let resources = resources(config);
+    let mut files = Vec::new();
+    if resources.is_empty() {
+        return Ok(Locks { _files: files });
+    }
+    let dir = private_dir::runtime("locks")?;
+    for resource in resources {
+        let path = dir.join(format!("{}.lock", file_name(&resource)));
+        let mut file = private_dir::open(&path).context("Failed to open lock file")?;
+        if !sys::lock_exclusive(&file, false)? {
+            let holder = fs::read_to_string(&path).unwrap_or_default();
+            let holder = if holder.trim().is_empty() {
+                "another run"
+            } else {
+                holder.trim()
+            };
+            println!("⏳ Waiting for '{}', in use by {}", resource, holder);
+            sys::lock_exclusive(&file, true).with_context(|| format!("Failed to lock '{}'", resource))?;
+        }
+        // Tells waiting runners who holds the resource.
+        file.set_len(0)?;
+        write!(
+            file,
+            "example '{}' (run {}, pid {})",
+            config.name,
+            run_id,
+            std::process::id()
+        )?;
+        files.push(file);
+    }
+    Ok(Locks { _files: files })
+}
+
+/// Lock file name of a resource, e.g. `port-13400` for `port:13400`.
+fn file_name(resource: &str) -> String {
+    resource
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
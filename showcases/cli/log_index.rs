@@ -0,0 +1,151 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Line index written next to every log file, so queries on large runs read only what they show.
+//!
+//! `app1.log.idx` holds one 16 byte record per line of `app1.log`: the offset of
+//! the line in the log and its timestamp in microseconds since the example
+//! started, both little endian. The timestamps never decrease, a line read a
+//! moment before a line of the other stream of the app counts with the later
+//! time. Queries binary search the index for the start and end of a time range
+//! and read only that part of the log, so a log cut short meanwhile gives fewer
+//! lines rather than an error.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const RECORD_SIZE: usize = 16;
+
+/// Path of the index of the log file `log`.
+pub fn index_path(log: &Path) -> PathBuf {
+    let mut path = log.as_os_str().to_owned();
+    path.push(".idx");
+    PathBuf::from(path)
+}
+
+/// Writes the index of a log file while lines are appended to it.
+pub struct Writer {
+    path: PathBuf,
+    out: Option<BufWriter<File>>, // None once writing failed
+    offset: u64,
+    latest_us: u64,
+}
+
+impl Writer {
+    pub fn create(log: &Path) -> Result<Writer> {
+        let path = index_path(log);
+        let file = File::create(&path).with_context(|| format!("Failed to create log index {:?}", path))?;
+        Ok(Writer {
+            path,
+            out: Some(BufWriter::new(file)),
+            offset: 0,
+            latest_us: 0,
+        })
+    }
+
+    /// Records a line of `len` bytes, including its line break, appended to the log at `elapsed`.
+    pub fn line(&mut self, len: usize, elapsed: Duration) {
+        self.latest_us = self.latest_us.max(elapsed.as_micros() as u64);
+        let mut record = [0u8; RECORD_SIZE];
+        record[..8].copy_from_slice(&self.offset.to_le_bytes());
+        record[8..].copy_from_slice(&self.latest_us.to_le_bytes());
+        self.offset += len as u64;
+        let Some(out) = self.out.as_mut() else {
+            return;
+        };
+        if let Err(e) = out.write_all(&record).and_then(|()| out.flush()) {
+            // A partly written index would misplace every later line, without one queries read the whole log.
+            eprintln!("⚠️  Failed to write log index {:?}, removing it: {}", self.path, e);
+            self.out = None;
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// A log file and its index, read on demand.
+pub struct Indexed {
+    log: File,
+    index: File,
+    records: usize,
+}
+
+impl Indexed {
+    /// Opens the log `path` with its index, `None` if it has none or it does not match the log.
+    pub fn open(path: &Path) -> Option<Indexed> {
+        let log = File::open(path).ok()?;
+        let index = File::open(index_path(path)).ok()?;
+        let records = (index.metadata().ok()?.len() / RECORD_SIZE as u64) as usize;
+        let indexed = Indexed { log, index, records };
+        // An index cut short by a crash still covers the lines it has.
+        let last = match records.checked_sub(1) {
+            Some(last) => indexed.offset(last).ok()?,
+            None => 0,
+        };
+        (last <= indexed.log.metadata().ok()?.len()).then_some(indexed)
+    }
+
+    fn record(&self, record: usize) -> io::Result<[u8; RECORD_SIZE]> {
+        let mut bytes = [0u8; RECORD_SIZE];
+        self.index.read_exact_at(&mut bytes, (record * RECORD_SIZE) as u64)?;
+        Ok(bytes)
+    }
+
+    fn offset(&self, record: usize) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.record(record)?[..8].try_into().unwrap()))
+    }
+
+    fn time_us(&self, record: usize) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.record(record)?[8..].try_into().unwrap()))
+    }
+
+    /// The lines logged from `since` on until `until`, as the index dates them and as far as the log still has them.
+    pub fn read(&self, since: Option<Duration>, until: Option<Duration>) -> io::Result<Vec<u8>> {
+        let first = |limit: Option<Duration>, default: usize| match limit {
+            Some(limit) => partition_point(self.records, |i| Ok(self.time_us(i)? < limit.as_micros() as u64)),
+            None => Ok(default),
+        };
+        let start = first(since, 0)?;
+        let end = first(until.map(|t| t + Duration::from_micros(1)), self.records)?;
+        if start >= end {
+            return Ok(Vec::new());
+        }
+        let from = self.offset(start)?;
+        let mut log = &self.log;
+        log.seek(SeekFrom::Start(from))?;
+        let mut lines = Vec::new();
+        if end < self.records {
+            log.take(self.offset(end)?.saturating_sub(from))
+                .read_to_end(&mut lines)?;
+        } else {
+            log.read_to_end(&mut lines)?;
+        }
+        Ok(lines)
+    }
+}
+
+/// First of the `len` records for which `before` does not hold, `before` holding for a prefix.
+fn partition_point(len: usize, before: impl Fn(usize) -> io::Result<bool>) -> io::Result<usize> {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if before(mid)? {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
@@ -0,0 +1,202 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Stored app output of a run (`score-cli logs`), printed like the runner showed it.
+//!
+//! With `-f` the logs are followed while the run goes on: lines appended to the
+//! logs, and logs of apps and examples started later, are printed as they come,
+//! until the run wrote its report.
+
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::gc;
+use crate::timeline::{self, Entry};
+
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A log file read so far.
+#[derive(Default)]
+struct Tail {
+    offset: u64,
+    partial: Vec<u8>, // a line not completely written yet
+}
+
+struct Logs {
+    run_dir: PathBuf,
+    apps: Vec<String>, // app index, "app<N>", program name or "runner"; all apps if empty
+    tails: HashMap<PathBuf, Tail>,
+    names: HashMap<PathBuf, HashMap<String, String>>, // per example directory: source -> program name
+    example: Option<PathBuf>,                         // the example printed last
+}
+
+/// Prints the app logs of the run in `run_dir`, following them with `follow`.
+pub fn show(run_dir: &Path, apps: Vec<String>, follow: bool) -> Result<()> {
+    let mut logs = Logs {
+        run_dir: run_dir.to_path_buf(),
+        apps,
+        tails: HashMap::new(),
+        names: HashMap::new(),
+        example: None,
+    };
+    loop {
+        // Checked first, so the lines written before the report are all printed.
+        let finished = run_dir.join("report.json").exists();
+        match logs.print_new() {
+            // The reader went away, e.g. `score-cli logs | head`.
+            Err(e)
+                if e.downcast_ref::<io::Error>()
+                    .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) =>
+            {
+                return Ok(())
+            }
+            result => result?,
+        }
+        if !follow || finished {
+            break;
+        }
+        std::thread::sleep(FOLLOW_INTERVAL);
+    }
+    if logs.tails.is_empty() && !follow {
+        bail!("No log files found in {:?}", run_dir);
+    }
+    Ok(())
+}
+
+impl Logs {
+    /// Prints the lines added to the logs since the last call, example by example.
+    fn print_new(&mut self) -> Result<()> {
+        let mut by_example: Vec<(PathBuf, Vec<Entry>)> = Vec::new();
+        for (dir, files) in example_dirs(&self.run_dir) {
+            let mut entries = Vec::new();
+            for (source, path) in files {
+                if !self.wanted(&dir, &source) {
+                    continue;
+                }
+                let tail = self.tails.entry(path.clone()).or_default();
+                for line in read_new(&path, tail)? {
+                    entries.extend(timeline::parse_line(&source, &line));
+                }
+            }
+            if !entries.is_empty() {
+                entries.sort_by(|a, b| a.at.total_cmp(&b.at));
+                by_example.push((dir, entries));
+            }
+        }
+        // Examples of a run follow each other, the earliest first.
+        by_example.sort_by(|(_, a), (_, b)| a[0].wall.cmp(&b[0].wall));
+
+        let mut out = io::stdout().lock();
+        for (dir, entries) in by_example {
+            if self.example.as_ref() != Some(&dir) {
+                let name = dir.strip_prefix(&self.run_dir).unwrap_or(&dir).display().to_string();
+                writeln!(out, "━━ {}", if name.is_empty() { "." } else { &name })?;
+                self.example = Some(dir.clone());
+            }
+            let names = self.names.get(&dir);
+            for entry in entries {
+                let label = match names.and_then(|names| names.get(&entry.source)) {
+                    Some(name) => format!("{}:{}", entry.source.trim_start_matches("app"), name),
+                    None => entry.source.clone(),
+                };
+                let marker = if entry.stream == "err" { "!" } else { " " };
+                writeln!(out, "[{}]{} {}", label, marker, entry.text)?;
+            }
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Whether lines of `source` in the example `dir` are shown. The runner's events only on request.
+    fn wanted(&mut self, dir: &Path, source: &str) -> bool {
+        if source == "runner" {
+            return self.apps.iter().any(|app| app == "runner");
+        }
+        let names = self.names.entry(dir.to_path_buf()).or_default();
+        if !names.contains_key(source) {
+            // Apps are named once they started.
+            *names = program_names(dir);
+        }
+        let name = names.get(source);
+        self.apps.is_empty()
+            || self
+                .apps
+                .iter()
+                .any(|app| app == source || source.strip_prefix("app") == Some(app.as_str()) || name == Some(app))
+    }
+}
+
+/// Example directories below `dir` with their log files, as (source, path).
+fn example_dirs(dir: &Path) -> Vec<(PathBuf, Vec<(String, PathBuf)>)> {
+    let mut found = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return found;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            found.extend(example_dirs(&path));
+        } else if let Some(source) = timeline::log_source(&path) {
+            files.push((source, path));
+        }
+    }
+    if !files.is_empty() {
+        found.push((dir.to_path_buf(), files));
+    }
+    found
+}
+
+/// Program names of the apps of an example, from the runner's "starting" events.
+fn program_names(dir: &Path) -> HashMap<String, String> {
+    let events = dir.join("runner.log");
+    let compressed = dir.join("runner.log.zst");
+    let Ok(events) = gc::read_log(if compressed.exists() { &compressed } else { &events }) else {
+        return HashMap::new();
+    };
+    events
+        .lines()
+        .filter_map(|line| timeline::parse_line("runner", line))
+        .filter_map(|entry| {
+            let (app, path) = entry.text.strip_prefix("app ")?.split_once(": starting ")?;
+            let name = Path::new(path).file_name()?.to_string_lossy().into_owned();
+            Some((format!("app{}", app), name))
+        })
+        .collect()
+}
+
+/// Complete lines appended to `path` since `tail`.
+fn read_new(path: &Path, tail: &mut Tail) -> Result<Vec<String>> {
+    if path.extension().is_some_and(|ext| ext == "zst") {
+        // Compressed by `gc`, so complete and never appended to.
+        if tail.offset > 0 {
+            return Ok(Vec::new());
+        }
+        tail.offset = 1;
+        return Ok(gc::read_log(path)?.lines().map(str::to_string).collect());
+    }
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(tail.offset))?;
+    let mut added = Vec::new();
+    tail.offset += file.read_to_end(&mut added)? as u64;
+    tail.partial.extend_from_slice(&added);
+    let Some(end) = tail.partial.iter().rposition(|&b| b == b'\n') else {
+        return Ok(Vec::new());
+    };
+    let complete: Vec<u8> = tail.partial.drain(..=end).collect();
+    Ok(String::from_utf8_lossy(&complete).lines().map(str::to_string).collect())
+}
@@ -0,0 +1,546 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Minimal language server for `*.score.json` files (`score-cli lsp`).
+//!
+//! Speaks LSP over stdin / stdout and offers:
+//!
+//! - diagnostics: JSON and schema errors at their position, and the errors of
+//!   loading all examples with the edited file as it is, published whenever a
+//!   file is opened or changed
+//! - hover documentation of config fields, from the comments of the config structs
+//! - completion of app paths and tags used by the examples in the examples
+//!   directory, and of executables next to those apps
+//!
+//! Documents are synchronized in full; positions count UTF-16 code units, as
+//! LSP defines by default.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::io::{BufRead, Write};
+use std::os::unix::ffi::OsStringExt;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use crate::{examples_roots, load_examples, overlay, read_examples_with, resolve_examples, ScoreConfig};
+
+/// Source of the config structs: the comments of their fields are the documentation shown on hover.
+const CONFIG_SOURCE: &str = include_str!("lib.rs");
+/// The structs of examples and apps in [`CONFIG_SOURCE`].
+const CONFIG_STRUCTS: &[&str] = &["pub struct ScoreConfig {", "struct AppConfig {"];
+
+/// Values offered for completion, from the examples in the examples directory.
+#[derive(Default)]
+struct Known {
+    paths: BTreeSet<String>,
+    tags: BTreeSet<String>,
+}
+
+impl Known {
+    fn load() -> Known {
+        let mut known = Known::default();
+        let Ok(configs) = load_examples(&examples_roots(), None) else {
+            return known;
+        };
+        for config in &configs {
+            known.tags.extend(config.tags.iter().cloned());
+            for app in config.apps.iter().filter(|app| !app.path.is_empty()) {
+                known.paths.insert(app.path.clone());
+                if let Some(dir) = Path::new(&app.path).parent() {
+                    known.paths.extend(executables(dir));
+                }
+            }
+        }
+        known
+    }
+}
+
+fn executables(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.metadata()
+                .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        })
+        .map(|path| path.display().to_string())
+        .collect()
+}
+
+/// Serves the language server protocol on stdin / stdout until the client exits.
+pub fn serve() -> Result<()> {
+    let known = Known::load();
+    let mut documents: HashMap<String, String> = HashMap::new();
+    let mut input = std::io::stdin().lock();
+    let mut output = std::io::stdout().lock();
+    while let Some(message) = read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default().to_string();
+        let result = match method {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1, // full documents
+                    "hoverProvider": true,
+                    "completionProvider": { "triggerCharacters": ["\"", "/"] },
+                },
+                "serverInfo": { "name": "score-cli" },
+            })),
+            "shutdown" => Some(Value::Null),
+            "exit" => return Ok(()),
+            "textDocument/didOpen" | "textDocument/didChange" => {
+                let text = match method {
+                    "textDocument/didOpen" => &params["textDocument"]["text"],
+                    _ => &params["contentChanges"][0]["text"],
+                };
+                let text = text.as_str().unwrap_or_default().to_string();
+                publish(&mut output, &uri, diagnostics(&text, file_of(&uri).as_deref()))?;
+                documents.insert(uri, text);
+                None
+            }
+            "textDocument/didClose" => {
+                documents.remove(&uri);
+                publish(&mut output, &uri, Vec::new())?;
+                None
+            }
+            "textDocument/hover" => Some(
+                documents
+                    .get(&uri)
+                    .and_then(|text| hover(text, offset(text, &params["position"])))
+                    .unwrap_or(Value::Null),
+            ),
+            "textDocument/completion" => Some(
+                documents
+                    .get(&uri)
+                    .map(|text| completion(text, offset(text, &params["position"]), &known))
+                    .unwrap_or_else(|| json!([])),
+            ),
+            _ => None,
+        };
+        // Requests carry an id and get a response, notifications do not.
+        if let Some(id) = message.get("id") {
+            let response = match result {
+                Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                None => {
+                    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": -32601, "message": "method not found" } })
+                }
+            };
+            write_message(&mut output, &response)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_message(input: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = Some(value.trim().parse::<usize>().context("Invalid Content-Length")?);
+        }
+    }
+    let Some(length) = length else {
+        bail!("LSP message without Content-Length");
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
+
+fn publish(output: &mut impl Write, uri: &str, diagnostics: Vec<Value>) -> Result<()> {
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    });
+    write_message(output, &notification)
+}
+
+fn diagnostic(line: usize, character: usize, message: String) -> Value {
+    json!({
+        "range": {
+            "start": { "line": line, "character": character },
+            "end": { "line": line, "character": character + 1 },
+        },
+        "severity": 1, // error
+        "source": "score-cli",
+        "message": message,
+    })
+}
+
+/// Errors of a config: syntax and schema errors, then the errors of loading the examples with `file` holding `text`.
+fn diagnostics(text: &str, file: Option<&Path>) -> Vec<Value> {
+    let value = match serde_json::from_str::<Value>(text) {
+        Ok(value) => value,
+        Err(e) => return vec![json_error(text, &e)],
+    };
+    let examples: Vec<&Value> = value
+        .as_array()
+        .map(|examples| examples.iter().collect())
+        .unwrap_or(vec![&value]);
+    // Patches of examples in an earlier examples directory are only complete once layered.
+    if !examples.iter().any(|example| overlay::is_patch(example)) {
+        let parsed = if value.is_array() {
+            serde_json::from_str::<Vec<ScoreConfig>>(text).map(drop)
+        } else {
+            serde_json::from_str::<ScoreConfig>(text).map(drop)
+        };
+        if let Err(e) = parsed {
+            return vec![json_error(text, &e)];
+        }
+    }
+    let Some(file) = file else {
+        return Vec::new();
+    };
+    match load(file, text) {
+        Ok(()) => Vec::new(),
+        Err(e) => {
+            // Reported at the name of the example the message refers to.
+            let message = format!("{:#}", e);
+            let line = examples
+                .iter()
+                .filter_map(|example| example["name"].as_str())
+                .find(|name| message.contains(&format!("'{}'", name)))
+                .and_then(|name| text.lines().position(|line| line.contains(&format!("\"{}\"", name))))
+                .unwrap_or(0);
+            vec![diagnostic(line, 0, message)]
+        }
+    }
+}
+
+/// A JSON syntax or schema error at its position.
+fn json_error(text: &str, error: &serde_json::Error) -> Value {
+    let message = error.to_string();
+    let message = message.split(" at line ").next().unwrap_or_default().to_string();
+    // serde_json counts lines from 1 and columns in bytes from 1.
+    let line = error.line().saturating_sub(1);
+    let column = text.lines().nth(line).map_or(0, |content| {
+        let mut end = error.column().saturating_sub(1).min(content.len());
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        content[..end].encode_utf16().count()
+    });
+    diagnostic(line, column, message)
+}
+
+/// Loads all examples like running them does, with `file` holding `text`.
+///
+/// The examples directories are used if `file` is in one of them, otherwise its own directory.
+fn load(file: &Path, text: &str) -> Result<()> {
+    let roots: Vec<PathBuf> = examples_roots()
+        .into_iter()
+        .map(|root| root.canonicalize().unwrap_or(root))
+        .collect();
+    let roots = if roots.iter().any(|root| file.starts_with(root)) {
+        roots
+    } else {
+        vec![file.parent().unwrap_or(Path::new("/")).to_path_buf()]
+    };
+    let (examples, env_presets) = read_examples_with(&roots, Some((file, text)))?;
+    resolve_examples(examples, &env_presets, None).map(drop)
+}
+
+/// The file of a `file://` URI.
+fn file_of(uri: &str) -> Option<PathBuf> {
+    let mut rest = uri.strip_prefix("file://")?.as_bytes();
+    let mut path = Vec::new();
+    while let Some((&byte, tail)) = rest.split_first() {
+        // Percent-encoded bytes like %20.
+        let decoded = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match decoded {
+            Some(decoded) => {
+                path.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                path.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    Some(PathBuf::from(OsString::from_vec(path)))
+}
+
+/// Character offset in `text` of an LSP position, whose `character` counts UTF-16 code units.
+fn offset(text: &str, position: &Value) -> usize {
+    let line = position["line"].as_u64().unwrap_or(0) as usize;
+    let character = position["character"].as_u64().unwrap_or(0) as usize;
+    let mut lines = text.split_inclusive('\n');
+    let line_start: usize = lines.by_ref().take(line).map(|l| l.chars().count()).sum();
+    let mut units = 0;
+    let column = lines
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take_while(|c| {
+            units += c.len_utf16();
+            units <= character
+        })
+        .count();
+    line_start + column
+}
+
+/// Documentation of the config field `name`: the comment of the field in the config structs.
+fn field_doc(name: &str) -> Option<String> {
+    field_docs()
+        .into_iter()
+        .find(|(field, _)| field == name)
+        .map(|(_, doc)| doc)
+}
+
+/// The documented fields of the config structs by their JSON name, with their comments.
+fn field_docs() -> Vec<(String, String)> {
+    let mut docs = Vec::new();
+    for start in CONFIG_STRUCTS {
+        let Some(at) = CONFIG_SOURCE.find(start) else {
+            continue;
+        };
+        let (mut rename, mut flatten) = (None, false);
+        for line in CONFIG_SOURCE[at + start.len()..].lines().map(str::trim) {
+            if line == "}" {
+                break;
+            }
+            if let Some(attribute) = line.strip_prefix("#[serde(") {
+                rename = attribute
+                    .split("rename = \"")
+                    .nth(1)
+                    .and_then(|rest| rest.split('"').next())
+                    .map(str::to_string);
+                flatten = attribute.contains("flatten");
+                continue;
+            }
+            let Some((field, rest)) = line.trim_start_matches("pub ").split_once(':') else {
+                continue;
+            };
+            let name = rename.take().unwrap_or_else(|| field.to_string());
+            if std::mem::take(&mut flatten) {
+                continue;
+            }
+            if let Some((_, doc)) = rest.split_once("// ") {
+                let mut chars = doc.trim().chars();
+                let doc: String = chars
+                    .next()
+                    .into_iter()
+                    .flat_map(char::to_uppercase)
+                    .chain(chars)
+                    .collect();
+                docs.push((name, doc));
+            }
+        }
+    }
+    docs
+}
+
+/// The string literal around `offset`: its content and whether it is followed by `:`, i.e. a key.
+fn string_at(chars: &[char], offset: usize) -> Option<(String, bool)> {
+    let offset = offset.min(chars.len());
+    let start = chars[..offset].iter().rposition(|&c| c == '"' || c == '\n')?;
+    if chars[start] != '"' || quote_count(&chars[..start]) % 2 == 1 {
+        return None;
+    }
+    let end = start + 1 + chars[start + 1..].iter().position(|&c| c == '"' || c == '\n')?;
+    let is_key = chars[end + 1..].iter().find(|c| !c.is_whitespace()) == Some(&':');
+    Some((chars[start + 1..end].iter().collect(), is_key))
+}
+
+/// Unescaped quotes in `chars` on the current line, to tell opening from closing quotes.
+fn quote_count(chars: &[char]) -> usize {
+    let line_start = chars.iter().rposition(|&c| c == '\n').map_or(0, |p| p + 1);
+    let line = &chars[line_start..];
+    line.iter()
+        .enumerate()
+        .filter(|&(i, &c)| c == '"' && (i == 0 || line[i - 1] != '\\'))
+        .count()
+}
+
+fn hover(text: &str, offset: usize) -> Option<Value> {
+    let chars: Vec<char> = text.chars().collect();
+    let (word, is_key) = string_at(&chars, offset)?;
+    if !is_key {
+        return None;
+    }
+    let doc = field_doc(&word)?;
+    Some(json!({ "contents": { "kind": "markdown", "value": format!("**{}**: {}", word, doc) } }))
+}
+
+fn completion(text: &str, offset: usize, known: &Known) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    let before: String = chars[..offset.min(chars.len())].iter().collect();
+    let items: Vec<&String> = if value_of_key(&before, "path") {
+        known.paths.iter().collect()
+    } else if in_array_of_key(&before, "tags") {
+        known.tags.iter().collect()
+    } else {
+        Vec::new()
+    };
+    let items: Vec<Value> = items
+        .into_iter()
+        .map(|label| json!({ "label": label, "kind": 12 })) // value
+        .collect();
+    json!(items)
+}
+
+/// Whether `before` ends inside the string value of `"key":`.
+fn value_of_key(before: &str, key: &str) -> bool {
+    let line = before.rsplit('\n').next().unwrap_or_default();
+    let Some(at) = line.rfind(&format!("\"{}\"", key)) else {
+        return false;
+    };
+    let rest = line[at + key.len() + 2..].trim_start();
+    rest.strip_prefix(':')
+        .map(str::trim_start)
+        .and_then(|value| value.strip_prefix('"'))
+        .is_some_and(|value| !value.contains('"'))
+}
+
+/// Whether `before` ends inside the array value of `"key":`.
+fn in_array_of_key(before: &str, key: &str) -> bool {
+    let Some(at) = before.rfind(&format!("\"{}\"", key)) else {
+        return false;
+    };
+    let rest = before[at + key.len() + 2..].trim_start();
+    rest.strip_prefix(':')
+        .map(str::trim_start)
+        .and_then(|value| value.strip_prefix('['))
+        .is_some_and(|value| !value.contains([']', '{', '}']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(line: u64, character: u64) -> Value {
+        json!({ "line": line, "character": character })
+    }
+
+    #[test]
+    fn messages() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"method":"initialize"}"#;
+        let input = format!("Content-Length: {}\r\nContent-Type: x\r\n\r\n{}", body.len(), body);
+        let message = read_message(&mut input.as_bytes()).unwrap().unwrap();
+        assert_eq!(message["method"], "initialize");
+        assert!(read_message(&mut "".as_bytes()).unwrap().is_none());
+        assert!(read_message(&mut "Content-Type: x\r\n\r\n{}".as_bytes()).is_err());
+
+        let mut output = Vec::new();
+        write_message(&mut output, &json!({ "id": 1 })).unwrap();
+        assert_eq!(output, b"Content-Length: 8\r\n\r\n{\"id\":1}");
+    }
+
+    #[test]
+    fn offsets_count_utf16_code_units() {
+        let text = "{\n  \"description\": \"caf\u{e9} \u{1f697} go\"\n}";
+        assert_eq!(offset(text, &position(0, 0)), 0);
+        assert_eq!(offset(text, &position(1, 2)), 4);
+        // The car is two UTF-16 code units, but one character.
+        let car = text.chars().position(|c| c == '\u{1f697}').unwrap();
+        assert_eq!(offset(text, &position(1, 23)), car);
+        assert_eq!(offset(text, &position(1, 24)), car);
+        assert_eq!(offset(text, &position(1, 25)), car + 1);
+    }
+
+    #[test]
+    fn errors_at_utf16_positions() {
+        let text = "{\n  \"name\": \"\u{1f697}\", \"description\": 1\n}";
+        let errors = diagnostics(text, None);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["range"]["start"]["line"], 1);
+        // At the value, 33 bytes but 31 UTF-16 code units into the line.
+        assert_eq!(errors[0]["range"]["start"]["character"], 31);
+        assert!(errors[0]["message"].as_str().unwrap().contains("expected a string"));
+
+        let errors = diagnostics("{\"name\": ", None);
+        assert_eq!(errors[0]["range"]["start"]["line"], 0);
+        assert!(diagnostics(r#"{"name": "a", "description": "b", "apps": []}"#, None).is_empty());
+        // Patches are only checked once layered onto the example they patch.
+        assert!(diagnostics(r#"{"name": "a", "patch": true, "apps": [{"index": 1}]}"#, None).is_empty());
+    }
+
+    #[test]
+    fn loader_errors() {
+        let dir = std::env::temp_dir().join(format!("score-cli-lsp-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("demo.score.json");
+        let text = "{\n  \"name\": \"demo\",\n  \"description\": \"d\",\n  \"steps\": [\"missing\"]\n}";
+        let errors = diagnostics(text, Some(&file));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["range"]["start"]["line"], 1);
+        assert!(errors[0]["message"].as_str().unwrap().contains("missing"));
+    }
+
+    #[test]
+    fn strings_and_keys() {
+        let chars: Vec<char> = r#"{ "path": "./app", "args": ["a\"b"] }"#.chars().collect();
+        assert_eq!(string_at(&chars, 4), Some(("path".to_string(), true)));
+        assert_eq!(string_at(&chars, 12), Some(("./app".to_string(), false)));
+        assert_eq!(string_at(&chars, 1), None);
+        assert!(value_of_key(r#"{ "path": "./a"#, "path"));
+        assert!(!value_of_key(r#"{ "path": "./app", "#, "path"));
+        assert!(in_array_of_key("{ \"tags\": [\"a\", \"", "tags"));
+        assert!(!in_array_of_key("{ \"tags\": [\"a\"], \"", "tags"));
+    }
+
+    #[test]
+    fn uris() {
+        assert_eq!(
+            file_of("file:///tmp/my%20demo.score.json"),
+            Some(PathBuf::from("/tmp/my demo.score.json"))
+        );
+        assert_eq!(file_of("untitled:Untitled-1"), None);
+    }
+
+    #[test]
+    fn field_docs_come_from_the_config_structs() {
+        let docs = field_docs();
+        for field in ["name", "apps", "kpis", "path", "type", "headless", "interaction"] {
+            assert!(
+                docs.iter().any(|(name, _)| name == field),
+                "{} is not documented",
+                field
+            );
+        }
+        assert!(!docs.iter().any(|(name, _)| name == "unknown" || name == "app_type"));
+        assert_eq!(
+            field_doc("delay").as_deref(),
+            Some("Delay in seconds before running the next app")
+        );
+        let hovered = hover(r#"{ "stop_timeout": 3 }"#, 5).unwrap();
+        assert!(hovered["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .starts_with("**stop_timeout**: Seconds"));
+    }
+}
@@ -0,0 +1,204 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Checksum manifests of showcase installations (`score-cli manifest`), to verify
+//! a machine is untampered before a customer demo.
+//!
+//! The manifest lists the SHA-256 sum of every `*.score.json` and
+//! `*.presets.json` file in the examples directories and of every file the
+//! apps reference: their binaries, ASan builds, preloaded libraries, env files,
+//! stdin files and data recordings. It is written in the format of `sha256sum`, so `sha256sum -c`
+//! checks it as well. Verifying reports every file that was modified, removed,
+//! or added since, like an example dropped into the tree. Sums are computed with
+//! `openssl dgst`. With `--sign-key` the manifest is signed like a report, see
+//! `signing`, and verifying with `--key` refuses a manifest whose signature does
+//! not match, so a manifest rewritten along with the files is noticed.
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::{is_score_file, presets, read_examples, signing};
+
+/// File name of the manifest in the first examples directory, unless given.
+pub const DEFAULT_NAME: &str = "showcases.sha256";
+
+/// Files hashed with one run of openssl.
+const BATCH: usize = 256;
+
+#[derive(Subcommand, Debug)]
+pub enum Action {
+    /// Write the checksums of the configs and the files they reference
+    Create {
+        /// Manifest file (default: showcases.sha256 in the examples directory)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Private key (PEM) to sign the manifest with, writing <manifest>.sig
+        #[arg(long, value_name = "PEM")]
+        sign_key: Option<PathBuf>,
+    },
+    /// Check the installation against a manifest, reporting every mismatching file
+    Verify {
+        /// Manifest file (default: showcases.sha256 in the examples directory)
+        #[arg(long)]
+        manifest: Option<PathBuf>,
+
+        /// Public key (PEM) the manifest has to be signed with
+        #[arg(long, value_name = "PEM")]
+        key: Option<PathBuf>,
+    },
+}
+
+/// Writes the manifest of the examples in `roots` to `output`, signed with `sign_key` if given.
+pub fn create(roots: &[PathBuf], output: &Path, sign_key: Option<&Path>) -> Result<()> {
+    let files = files(roots, output)?;
+    let sums = sha256(&files.iter().cloned().collect::<Vec<_>>())?;
+    let mut manifest = String::new();
+    for (file, sum) in &sums {
+        manifest.push_str(&format!("{}  {}\n", sum, file.display()));
+    }
+    fs::write(output, manifest).with_context(|| format!("Failed to write manifest {:?}", output))?;
+    println!("✅ Wrote the checksums of {} files to {}", sums.len(), output.display());
+    if let Some(key) = sign_key {
+        let signature = signing::sign(output, key)?;
+        println!("🔏 Signed the manifest, signature in {}", signature.display());
+    }
+    Ok(())
+}
+
+/// Checks the examples in `roots` against the manifest `path`, returning whether everything matches.
+///
+/// With `key`, the manifest has to be signed with the matching private key.
+pub fn verify(roots: &[PathBuf], path: &Path, key: Option<&Path>) -> Result<bool> {
+    match key {
+        Some(key) => signing::verify(path, &signing::signature_path(path), key)
+            .with_context(|| format!("Refusing to check the installation against {:?}", path))?,
+        None => println!(
+            "⚠️  The manifest is not checked against a key (--key), it only reveals changes made without updating it"
+        ),
+    }
+    let content = fs::read_to_string(path).with_context(|| format!("Failed reading manifest {:?}", path))?;
+    let mut expected = BTreeMap::new();
+    for (number, line) in content.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+        let Some((sum, file)) = line.split_once("  ") else {
+            bail!("{:?} line {}: expected \"<sha256>  <path>\"", path, number + 1);
+        };
+        expected.insert(PathBuf::from(file), sum.to_string());
+    }
+
+    let current = files(roots, path)?;
+    let present: Vec<PathBuf> = expected.keys().filter(|file| file.is_file()).cloned().collect();
+    let sums = sha256(&present)?;
+    let mut mismatches = 0;
+    for (file, sum) in &expected {
+        let problem = match sums.get(file) {
+            None => "MISSING ",
+            Some(actual) if actual != sum => "MODIFIED",
+            Some(_) => continue,
+        };
+        println!("{} {}", problem, file.display());
+        mismatches += 1;
+    }
+    for file in current.iter().filter(|file| !expected.contains_key(*file)) {
+        println!("ADDED    {}", file.display());
+        mismatches += 1;
+    }
+    if mismatches == 0 {
+        println!("✅ All {} files match {}", expected.len(), path.display());
+    } else {
+        println!(
+            "❌ {} of {} files do not match {}",
+            mismatches,
+            expected.len(),
+            path.display()
+        );
+    }
+    Ok(mismatches == 0)
+}
+
+/// The config files below `roots` and the files referenced by their apps, without the `manifest` itself.
+fn files(roots: &[PathBuf], manifest: &Path) -> Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::new();
+    for root in roots {
+        config_files(root, &mut files)?;
+    }
+    let (examples, _) = read_examples(roots)?;
+    for example in &examples {
+        let apps = example.value["apps"].as_array().into_iter().flatten();
+        for app in apps {
+            let referenced = ["path", "asan_path", "env_file", "recording"]
+                .iter()
+                .filter_map(|key| app[key].as_str())
+                .chain(app["stdin"]["file"].as_str())
+                .chain(
+                    app["preload"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str),
+                );
+            for file in referenced.map(Path::new) {
+                // Programs looked up in PATH belong to the host, not the installation.
+                if file.is_absolute() && file.is_file() {
+                    files.insert(fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf()));
+                }
+            }
+        }
+    }
+    if let Ok(manifest) = fs::canonicalize(manifest) {
+        files.remove(&manifest);
+    }
+    Ok(files)
+}
+
+fn config_files(dir: &Path, files: &mut BTreeSet<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read directory {:?}", dir))? {
+        let path = entry?.path();
+        if path.is_symlink() {
+            continue;
+        }
+        if path.is_dir() {
+            config_files(&path, files)?;
+        } else if is_score_file(&path) || presets::is_presets_file(&path) {
+            files.insert(fs::canonicalize(&path).unwrap_or(path));
+        }
+    }
+    Ok(())
+}
+
+/// SHA-256 sums of `files` as hex strings.
+pub(crate) fn sha256(files: &[PathBuf]) -> Result<BTreeMap<PathBuf, String>> {
+    let mut sums = BTreeMap::new();
+    for batch in files.chunks(BATCH) {
+        let output = Command::new("openssl")
+            .args(["dgst", "-sha256", "-r"])
+            .args(batch)
+            .stderr(Stdio::inherit())
+            .output()
+            .context("Failed to run `openssl`, which computes the checksums")?;
+        if !output.status.success() {
+            bail!("openssl failed to compute checksums: {}", output.status);
+        }
+        // One "<sum> *<file>" line per file, in the order given.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for (file, line) in batch.iter().zip(stdout.lines()) {
+            let sum = line.split_whitespace().next().unwrap_or_default();
+            sums.insert(file.clone(), sum.to_string());
+        }
+    }
+    Ok(sums)
+}
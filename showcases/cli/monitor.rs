@@ -0,0 +1,31 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Hooks into the supervision loop of a running example.
+
+use crate::RunningApp;
+
+/// Observes the apps of an example while they run.
+pub trait Monitor {
+    /// Called periodically with the apps that are still running.
+    /// Returning `true` stops the example.
+    fn poll(&mut self, apps: &[&RunningApp]) -> bool;
+}
+
+/// Monitor for plain runs, which last until all apps exited on their own.
+pub struct NoMonitor;
+
+impl Monitor for NoMonitor {
+    fn poll(&mut self, _apps: &[&RunningApp]) -> bool {
+        false
+    }
+}
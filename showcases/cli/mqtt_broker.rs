@@ -0,0 +1,302 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Minimal MQTT 3.1.1 broker, the `mqtt-broker` built-in app.
+//!
+//! Enough for showcases talking to a local broker: sessions are not persisted,
+//! messages are delivered with QoS 0 (QoS 1 publishes are acknowledged),
+//! retained messages and the `+` and `#` wildcards are supported. There is no
+//! authentication, credentials sent by clients are ignored.
+//!
+//! Every client has a writer thread sending the packets of its bounded queue,
+//! so a slow subscriber never blocks the others. Messages for a subscriber
+//! whose queue is full are dropped, as QoS 0 allows.
+
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+
+const CONNECT: u8 = 1;
+const CONNACK: u8 = 2;
+const PUBLISH: u8 = 3;
+const PUBACK: u8 = 4;
+const SUBSCRIBE: u8 = 8;
+const SUBACK: u8 = 9;
+const UNSUBSCRIBE: u8 = 10;
+const UNSUBACK: u8 = 11;
+const PINGREQ: u8 = 12;
+const PINGRESP: u8 = 13;
+const DISCONNECT: u8 = 14;
+/// Packets waiting to be sent to a client.
+const QUEUE: usize = 256;
+
+struct Client {
+    id: usize,
+    outbox: SyncSender<Vec<u8>>, // encoded packets for the client's writer thread
+    filters: Vec<String>,
+}
+
+#[derive(Default)]
+struct Broker {
+    clients: Vec<Client>,
+    retained: BTreeMap<String, Vec<u8>>,
+    next_id: usize,
+}
+
+struct Packet {
+    kind: u8,
+    flags: u8,
+    body: Vec<u8>,
+}
+
+pub fn serve(port: u16) -> Result<()> {
+    let listener =
+        TcpListener::bind(("0.0.0.0", port)).with_context(|| format!("Failed to listen on port {}", port))?;
+    println!("MQTT broker listening on port {}", port);
+    let broker = Arc::new(Mutex::new(Broker::default()));
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let broker = broker.clone();
+        std::thread::spawn(move || {
+            let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+            let id = {
+                let mut broker = broker.lock().unwrap();
+                broker.next_id += 1;
+                broker.next_id
+            };
+            if let Err(e) = session(&broker, id, stream) {
+                println!("Client {} ({}): {:#}", id, peer, e);
+            }
+            broker.lock().unwrap().clients.retain(|c| c.id != id);
+        });
+    }
+    Ok(())
+}
+
+fn session(broker: &Mutex<Broker>, id: usize, mut stream: TcpStream) -> Result<()> {
+    let connect = read_packet(&mut stream)?.context("Connection closed before CONNECT")?;
+    if connect.kind != CONNECT {
+        bail!("Expected CONNECT, got packet type {}", connect.kind);
+    }
+    let client_id = parse_connect(&connect.body)?;
+    println!("Client {} connected as {:?}", id, client_id);
+    let outbox = start_writer(stream.try_clone()?);
+    let send = |header: u8, body: &[u8]| {
+        outbox
+            .send(encode(header, body))
+            .ok()
+            .context("Connection closed while sending")
+    };
+    send(CONNACK << 4, &[0, 0])?;
+    broker.lock().unwrap().clients.push(Client {
+        id,
+        outbox: outbox.clone(),
+        filters: Vec::new(),
+    });
+
+    while let Some(packet) = read_packet(&mut stream)? {
+        let mut body = packet.body.as_slice();
+        match packet.kind {
+            PUBLISH => {
+                let qos = (packet.flags >> 1) & 3;
+                let retain = packet.flags & 1 != 0;
+                let topic = take_string(&mut body)?;
+                if qos > 0 {
+                    let packet_id = take(&mut body, 2)?;
+                    send(PUBACK << 4, packet_id)?;
+                }
+                publish(broker, &topic, body, retain)?;
+            }
+            SUBSCRIBE => {
+                let packet_id = take(&mut body, 2)?.to_vec();
+                let mut filters = Vec::new();
+                while !body.is_empty() {
+                    filters.push(take_string(&mut body)?);
+                    take(&mut body, 1)?; // requested QoS, always granted as 0
+                }
+                println!("Client {} subscribed to {}", id, filters.join(", "));
+                let mut ack = packet_id;
+                ack.extend(std::iter::repeat_n(0, filters.len()));
+                send(SUBACK << 4, &ack)?;
+                let retained: Vec<Vec<u8>> = {
+                    let mut broker = broker.lock().unwrap();
+                    let retained = broker
+                        .retained
+                        .iter()
+                        .filter(|(topic, _)| filters.iter().any(|f| matches(f, topic)))
+                        .map(|(topic, payload)| publish_body(topic, payload))
+                        .collect();
+                    if let Some(client) = broker.clients.iter_mut().find(|c| c.id == id) {
+                        client.filters.extend(filters);
+                    }
+                    retained
+                };
+                for body in retained {
+                    send(PUBLISH << 4 | 1, &body)?;
+                }
+            }
+            UNSUBSCRIBE => {
+                let packet_id = take(&mut body, 2)?.to_vec();
+                let mut filters = Vec::new();
+                while !body.is_empty() {
+                    filters.push(take_string(&mut body)?);
+                }
+                if let Some(client) = broker.lock().unwrap().clients.iter_mut().find(|c| c.id == id) {
+                    client.filters.retain(|f| !filters.contains(f));
+                }
+                send(UNSUBACK << 4, &packet_id)?;
+            }
+            PINGREQ => send(PINGRESP << 4, &[])?,
+            DISCONNECT => break,
+            kind => bail!("Unsupported packet type {}", kind),
+        }
+    }
+    println!("Client {} disconnected", id);
+    Ok(())
+}
+
+/// Delivers a message to all subscribers, keeping it for later subscribers if `retain`.
+fn publish(broker: &Mutex<Broker>, topic: &str, payload: &[u8], retain: bool) -> Result<()> {
+    let mut broker = broker.lock().unwrap();
+    if retain {
+        // An empty retained message clears the retained message of the topic.
+        if payload.is_empty() {
+            broker.retained.remove(topic);
+        } else {
+            broker.retained.insert(topic.to_string(), payload.to_vec());
+        }
+    }
+    let packet = encode(PUBLISH << 4, &publish_body(topic, payload));
+    for client in &broker.clients {
+        if client.filters.iter().any(|f| matches(f, topic)) {
+            // Never blocks; a failing subscriber is cleaned up by its own session.
+            if let Err(TrySendError::Full(_)) = client.outbox.try_send(packet.clone()) {
+                println!("Client {} does not keep up, dropping a message on {}", client.id, topic);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Starts the thread writing the packets queued for a client to `stream`.
+fn start_writer(mut stream: TcpStream) -> SyncSender<Vec<u8>> {
+    let (sender, receiver) = mpsc::sync_channel::<Vec<u8>>(QUEUE);
+    std::thread::spawn(move || {
+        for packet in receiver {
+            if stream.write_all(&packet).is_err() {
+                // Ends the session reading from the client as well.
+                let _ = stream.shutdown(Shutdown::Both);
+                return;
+            }
+        }
+    });
+    sender
+}
+
+/// Whether `topic` matches the subscription `filter` with its `+` and `#` wildcards.
+fn matches(filter: &str, topic: &str) -> bool {
+    // Wildcards at the first level do not match topics starting with `$`.
+    if topic.starts_with('$') && !filter.starts_with('$') {
+        return false;
+    }
+    let mut levels = topic.split('/');
+    for part in filter.split('/') {
+        match (part, levels.next()) {
+            ("#", _) => return true,
+            ("+", Some(_)) => {}
+            (part, Some(level)) if part == level => {}
+            _ => return false,
+        }
+    }
+    levels.next().is_none()
+}
+
+fn parse_connect(mut body: &[u8]) -> Result<String> {
+    let protocol = take_string(&mut body)?;
+    let level = take(&mut body, 1)?[0];
+    if protocol != "MQTT" || level != 4 {
+        bail!(
+            "Unsupported protocol {:?} level {}, only MQTT 3.1.1 is supported",
+            protocol,
+            level
+        );
+    }
+    take(&mut body, 3)?; // connect flags and keep alive
+    take_string(&mut body)
+}
+
+fn publish_body(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(topic.len() + payload.len() + 2);
+    body.extend((topic.len() as u16).to_be_bytes());
+    body.extend(topic.as_bytes());
+    body.extend(payload);
+    body
+}
+
+fn take<'a>(body: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    if body.len() < n {
+        bail!("Malformed packet");
+    }
+    let (head, rest) = body.split_at(n);
+    *body = rest;
+    Ok(head)
+}
+
+fn take_string(body: &mut &[u8]) -> Result<String> {
+    let len = take(body, 2)?;
+    let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+    Ok(String::from_utf8_lossy(take(body, len)?).into_owned())
+}
+
+/// Reads the next packet, `None` once the client closed the connection.
+fn read_packet(stream: &mut TcpStream) -> Result<Option<Packet>> {
+    let mut header = [0u8; 1];
+    if stream.read(&mut header)? == 0 {
+        return Ok(None);
+    }
+    let mut len = 0usize;
+    for shift in (0..28).step_by(7) {
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte)?;
+        len |= ((byte[0] & 0x7f) as usize) << shift;
+        if byte[0] & 0x80 == 0 {
+            let mut body = vec![0u8; len];
+            stream.read_exact(&mut body)?;
+            return Ok(Some(Packet {
+                kind: header[0] >> 4,
+                flags: header[0] & 0x0f,
+                body,
+            }));
+        }
+    }
+    bail!("Malformed remaining length")
+}
+
+fn encode(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![header];
+    let mut len = body.len();
+    loop {
+        let byte = (len % 128) as u8;
+        len /= 128;
+        packet.push(if len > 0 { byte | 0x80 } else { byte });
+        if len == 0 {
+            break;
+        }
+    }
+    packet.extend(body);
+    packet
+}
@@ -0,0 +1,207 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Network namespace topology for multi-node examples.
+//!
+//! Every node gets its own network namespace connected to a shared bridge
+//! through a veth pair, so apps assigned to different nodes talk over
+//! separate IP stacks like distinct ECUs would. Optionally the link of a
+//! node is degraded with `tc netem` to demonstrate robustness.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::iproute::{interface_exists, ip, tc};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NetworkConfig {
+    #[serde(default = "default_bridge")]
+    pub bridge: String,
+    pub nodes: Vec<NodeConfig>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NodeConfig {
+    pub name: String,
+    pub address: String, // CIDR notation, e.g. "10.200.0.1/24"
+    pub impairment: Option<ImpairmentConfig>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+/// Network impairment applied to the node's interface via `tc netem`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ImpairmentConfig {
+    pub latency_ms: Option<u32>,
+    pub jitter_ms: Option<u32>,
+    pub loss_percent: Option<f64>,
+    pub rate: Option<String>, // tc rate, e.g. "1mbit"
+}
+
+impl ImpairmentConfig {
+    fn netem_args(&self) -> Result<Vec<String>> {
+        let mut args = Vec::new();
+        match (self.latency_ms, self.jitter_ms) {
+            (Some(latency), jitter) => {
+                args.extend(["delay".to_string(), format!("{}ms", latency)]);
+                args.extend(jitter.map(|j| format!("{}ms", j)));
+            }
+            (None, Some(_)) => bail!("jitter_ms requires latency_ms to be set"),
+            (None, None) => {}
+        }
+        if let Some(loss) = self.loss_percent {
+            if !(0.0..=100.0).contains(&loss) {
+                bail!("loss_percent must be between 0 and 100, got {}", loss);
+            }
+            args.extend(["loss".to_string(), format!("{}%", loss)]);
+        }
+        if let Some(rate) = &self.rate {
+            args.extend(["rate".to_string(), rate.clone()]);
+        }
+        Ok(args)
+    }
+}
+
+fn default_bridge() -> String {
+    "score-br0".to_string()
+}
+
+/// Namespaces and bridge created for an example. They are removed when this is dropped.
+#[derive(Default)]
+pub struct NetworkSetup {
+    nodes: Vec<String>,
+    veths: Vec<String>, // host ends, left behind if setup failed before their peer moved into a namespace
+    bridge: Option<String>,
+}
+
+impl NetworkSetup {
+    /// Builds the command for `program`, running it inside the namespace of `node` if given.
+    pub fn command(&self, node: Option<&str>, program: &str) -> Result<Command> {
+        let Some(node) = node else {
+            return Ok(Command::new(program));
+        };
+        if !self.nodes.iter().any(|n| n == node) {
+            bail!("Unknown network node '{}'", node);
+        }
+        let mut cmd = Command::new("ip");
+        cmd.args(["netns", "exec", &namespace(node), program]);
+        Ok(cmd)
+    }
+}
+
+impl Drop for NetworkSetup {
+    fn drop(&mut self) {
+        // Deleting a namespace also destroys the veth pair reaching into it, any other pair is deleted here.
+        for node in self.nodes.iter().rev() {
+            if let Err(e) = ip(&["netns", "delete", &namespace(node)]) {
+                eprintln!("Network node {}: teardown failed: {:#}", node, e);
+            }
+        }
+        for veth in self.veths.iter().filter(|veth| interface_exists(veth)) {
+            if let Err(e) = ip(&["link", "delete", "dev", veth]) {
+                eprintln!("Network interface {}: teardown failed: {:#}", veth, e);
+            }
+        }
+        if let Some(bridge) = &self.bridge {
+            if let Err(e) = ip(&["link", "delete", "dev", bridge]) {
+                eprintln!("Network bridge {}: teardown failed: {:#}", bridge, e);
+            }
+        }
+    }
+}
+
+fn namespace(node: &str) -> String {
+    format!("score-{}", node)
+}
+
+/// Path of the network namespace of `node`, as `ip netns` creates it.
+pub fn namespace_path(node: &str) -> PathBuf {
+    Path::new("/run/netns").join(namespace(node))
+}
+
+/// Creates the configured topology and checks that every node used by an app is declared.
+pub fn setup<'a>(config: Option<&NetworkConfig>, used_nodes: impl Iterator<Item = &'a str>) -> Result<NetworkSetup> {
+    if config.is_some() && !cfg!(target_os = "linux") {
+        bail!("Network topologies need Linux (network namespaces)");
+    }
+    let declared: Vec<&str> = config
+        .map(|c| c.nodes.iter().map(|n| n.name.as_str()).collect())
+        .unwrap_or_default();
+    for node in used_nodes {
+        if !declared.contains(&node) {
+            bail!(
+                "App is assigned to node '{}' which is not declared in the network section",
+                node
+            );
+        }
+    }
+
+    let mut setup = NetworkSetup::default();
+    let Some(config) = config else {
+        return Ok(setup);
+    };
+
+    if interface_exists(&config.bridge) {
+        bail!(
+            "Network bridge {} already exists, is another example still running?",
+            config.bridge
+        );
+    }
+    ip(&["link", "add", "name", &config.bridge, "type", "bridge"])
+        .with_context(|| format!("Failed to create network bridge {}", config.bridge))?;
+    setup.bridge = Some(config.bridge.clone());
+    ip(&["link", "set", "dev", &config.bridge, "up"])?;
+
+    for (i, node) in config.nodes.iter().enumerate() {
+        let ns = namespace(&node.name);
+        ip(&["netns", "add", &ns]).with_context(|| format!("Failed to create network node {}", node.name))?;
+        setup.nodes.push(node.name.clone());
+
+        // veth names are limited to 15 characters, so they are derived from the node index.
+        let host_end = format!("score-v{}", i);
+        let node_end = format!("score-p{}", i);
+        ip(&["link", "add", &host_end, "type", "veth", "peer", "name", &node_end])?;
+        setup.veths.push(host_end.clone());
+        ip(&["link", "set", "dev", &host_end, "master", &config.bridge])?;
+        ip(&["link", "set", "dev", &host_end, "up"])?;
+        ip(&["link", "set", "dev", &node_end, "netns", &ns])?;
+
+        ip(&["-n", &ns, "link", "set", "dev", &node_end, "name", "eth0"])?;
+        ip(&["-n", &ns, "addr", "add", &node.address, "dev", "eth0"])
+            .with_context(|| format!("Failed to assign address {} to node {}", node.address, node.name))?;
+        ip(&["-n", &ns, "link", "set", "dev", "eth0", "up"])?;
+        ip(&["-n", &ns, "link", "set", "dev", "lo", "up"])?;
+        // Service discovery relies on multicast, which needs a route in the otherwise empty namespace.
+        ip(&["-n", &ns, "route", "add", "224.0.0.0/4", "dev", "eth0"])?;
+
+        if let Some(impairment) = &node.impairment {
+            let netem = impairment
+                .netem_args()
+                .with_context(|| format!("Invalid impairment for node {}", node.name))?;
+            if !netem.is_empty() {
+                let mut args = vec!["-n", &ns, "qdisc", "add", "dev", "eth0", "root", "netem"];
+                args.extend(netem.iter().map(String::as_str));
+                tc(&args).with_context(|| format!("Failed to apply impairment to node {}", node.name))?;
+                println!("Network node {}: impairment {}", node.name, netem.join(" "));
+            }
+        }
+
+        println!("Network node {}: {} in namespace {}", node.name, node.address, ns);
+    }
+    Ok(setup)
+}
@@ -0,0 +1,604 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Opt-in kernel-level observation of the apps of an example.
+//!
+//! Small eBPF programs, assembled here and loaded through the `bpf(2)` syscall so
+//! no external tooling is needed, count per process in a hash map pre-filled with
+//! the apps' pids:
+//! - syscalls, from the `sys_enter` raw tracepoint;
+//! - CPU time, timeslices and scheduling latency (time spent runnable, waiting
+//!   for a CPU), from the `sched_wakeup`, `sched_wakeup_new` and `sched_switch`
+//!   tracepoints. Their record layout is read from tracefs.
+//!
+//! The counters survive the exit of the apps, so they cover their whole life.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Observation {
+    pub syscalls: u64,             // counted from the spawn of the app on
+    pub cpu_ms: f64,               // time spent running on a CPU
+    pub sched_wait_ms: f64,        // time spent runnable but waiting for a CPU
+    pub timeslices: u64,           // times the app was scheduled onto a CPU
+    pub avg_sched_latency_us: f64, // average wait per timeslice
+}
+
+/// What the probes counted for a process, summed over its threads.
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    syscalls: u64,
+    run_ns: u64,
+    wait_ns: u64,
+    timeslices: u64,
+}
+
+pub struct Observer {
+    probes: Probes,
+    pids: HashMap<usize, u32>,
+}
+
+impl Observer {
+    pub fn start() -> Result<Self> {
+        Ok(Observer {
+            probes: Probes::load()?,
+            pids: HashMap::new(),
+        })
+    }
+
+    /// Starts observing app `index` running as `pid`.
+    pub fn watch(&mut self, index: usize, pid: u32) -> Result<()> {
+        self.probes.watch(pid)?;
+        self.pids.insert(index, pid);
+        Ok(())
+    }
+
+    pub fn finish(self) -> HashMap<usize, Observation> {
+        self.pids
+            .iter()
+            .map(|(&index, &pid)| {
+                let counters = self.probes.counters(pid);
+                let observation = Observation {
+                    syscalls: counters.syscalls,
+                    cpu_ms: counters.run_ns as f64 / 1e6,
+                    sched_wait_ms: counters.wait_ns as f64 / 1e6,
+                    timeslices: counters.timeslices,
+                    avg_sched_latency_us: if counters.timeslices > 0 {
+                        counters.wait_ns as f64 / counters.timeslices as f64 / 1e3
+                    } else {
+                        0.0
+                    },
+                };
+                (index, observation)
+            })
+            .collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+use linux::Probes;
+
+#[cfg(not(target_os = "linux"))]
+struct Probes;
+
+#[cfg(not(target_os = "linux"))]
+impl Probes {
+    fn load() -> Result<Self> {
+        anyhow::bail!("--observe needs Linux (eBPF)")
+    }
+
+    fn watch(&mut self, _pid: u32) -> Result<()> {
+        Ok(())
+    }
+
+    fn counters(&self, _pid: u32) -> Counters {
+        Counters::default()
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use anyhow::{bail, Context, Result};
+    use std::collections::HashMap;
+    use std::fs;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::path::{Path, PathBuf};
+
+    use super::Counters;
+    use crate::sys;
+
+    const BPF_MAP_CREATE: i32 = 0;
+    const BPF_MAP_LOOKUP_ELEM: i32 = 1;
+    const BPF_MAP_UPDATE_ELEM: i32 = 2;
+    const BPF_PROG_LOAD: i32 = 5;
+    const BPF_RAW_TRACEPOINT_OPEN: i32 = 17;
+    const BPF_MAP_TYPE_HASH: u32 = 1;
+    const BPF_MAP_TYPE_LRU_HASH: u32 = 9;
+    const BPF_PROG_TYPE_TRACEPOINT: u32 = 5;
+    const BPF_PROG_TYPE_RAW_TRACEPOINT: u32 = 17;
+    const BPF_PSEUDO_MAP_FD: u8 = 1;
+    const BPF_FUNC_MAP_LOOKUP_ELEM: i32 = 1;
+    const BPF_FUNC_MAP_UPDATE_ELEM: i32 = 2;
+    const BPF_FUNC_MAP_DELETE_ELEM: i32 = 3;
+    const BPF_FUNC_KTIME_GET_NS: i32 = 5;
+    const BPF_FUNC_GET_CURRENT_PID_TGID: i32 = 14;
+    const PERF_TYPE_TRACEPOINT: u32 = 2;
+    /// Size of `struct perf_event_attr` as of PERF_ATTR_SIZE_VER5, accepted by all kernels with eBPF.
+    const PERF_ATTR_SIZE: usize = 112;
+    /// Size passed as `union bpf_attr`; the kernel accepts larger, zero-filled buffers.
+    const ATTR_SIZE: usize = 128;
+    const MAX_APPS: u32 = 1024;
+    /// Threads whose process and scheduling state are tracked, the least recently used ones are evicted.
+    const MAX_THREADS: u32 = 16384;
+    const TRACEFS: &[&str] = &["/sys/kernel/tracing", "/sys/kernel/debug/tracing"];
+
+    /// Offsets of the [`Counters`] in the values of the per-process map.
+    const SYSCALLS: i16 = 0;
+    const RUN_NS: i16 = 8;
+    const WAIT_NS: i16 = 16;
+    const TIMESLICES: i16 = 24;
+    const COUNTERS_SIZE: u32 = 32;
+
+    /// Stack slots of the programs: a thread id, a process id and a timestamp.
+    const TID: i32 = -8;
+    const TGID: i32 = -4;
+    const TIME: i32 = -16;
+
+    const R0: u8 = 0;
+    const R1: u8 = 1;
+    const R2: u8 = 2;
+    const R3: u8 = 3;
+    const R4: u8 = 4;
+    const R6: u8 = 6;
+    const R7: u8 = 7;
+    const R8: u8 = 8;
+    const R9: u8 = 9;
+    const R10: u8 = 10;
+
+    /// Assembles an eBPF program, resolving jumps to labels.
+    #[derive(Default)]
+    struct Asm {
+        insns: Vec<[u8; 8]>,
+        labels: HashMap<&'static str, usize>,
+        jumps: Vec<(usize, &'static str)>,
+    }
+
+    impl Asm {
+        fn op(&mut self, code: u8, dst: u8, src: u8, off: i16, imm: i32) -> &mut Self {
+            let mut insn = [0u8; 8];
+            insn[0] = code;
+            insn[1] = dst | (src << 4);
+            insn[2..4].copy_from_slice(&off.to_le_bytes());
+            insn[4..8].copy_from_slice(&imm.to_le_bytes());
+            self.insns.push(insn);
+            self
+        }
+
+        fn mov(&mut self, dst: u8, src: u8) -> &mut Self {
+            self.op(0xbf, dst, src, 0, 0)
+        }
+
+        fn mov_imm(&mut self, dst: u8, imm: i32) -> &mut Self {
+            self.op(0xb7, dst, 0, 0, imm)
+        }
+
+        fn sub(&mut self, dst: u8, src: u8) -> &mut Self {
+            self.op(0x1f, dst, src, 0, 0)
+        }
+
+        fn and_imm(&mut self, dst: u8, imm: i32) -> &mut Self {
+            self.op(0x57, dst, 0, 0, imm)
+        }
+
+        fn rsh_imm(&mut self, dst: u8, imm: i32) -> &mut Self {
+            self.op(0x77, dst, 0, 0, imm)
+        }
+
+        /// `dst = *(u32 or u64 *)(src + off)`
+        fn load(&mut self, size: usize, dst: u8, src: u8, off: i16) -> &mut Self {
+            self.op(if size == 8 { 0x79 } else { 0x61 }, dst, src, off, 0)
+        }
+
+        /// `*(u32 or u64 *)(r10 + slot) = src`
+        fn store(&mut self, size: usize, slot: i32, src: u8) -> &mut Self {
+            self.op(if size == 8 { 0x7b } else { 0x63 }, R10, src, slot as i16, 0)
+        }
+
+        /// `lock *(u64 *)(dst + off) += src`
+        fn atomic_add(&mut self, dst: u8, off: i16, src: u8) -> &mut Self {
+            self.op(0xdb, dst, src, off, 0)
+        }
+
+        /// `dst = r10 + slot`
+        fn stack_ptr(&mut self, dst: u8, slot: i32) -> &mut Self {
+            self.mov(dst, R10).op(0x07, dst, 0, 0, slot)
+        }
+
+        /// `dst = map`, as a 64-bit immediate taking two instructions.
+        fn map(&mut self, dst: u8, map: &OwnedFd) -> &mut Self {
+            self.op(0x18, dst, BPF_PSEUDO_MAP_FD, 0, map.as_raw_fd())
+                .op(0x00, 0, 0, 0, 0)
+        }
+
+        fn call(&mut self, helper: i32) -> &mut Self {
+            self.op(0x85, 0, 0, 0, helper)
+        }
+
+        /// `r0 = map[key]`, a pointer to the value or 0.
+        fn lookup(&mut self, map: &OwnedFd, key: i32) -> &mut Self {
+            self.map(R1, map).stack_ptr(R2, key).call(BPF_FUNC_MAP_LOOKUP_ELEM)
+        }
+
+        /// `map[key] = value`
+        fn update(&mut self, map: &OwnedFd, key: i32, value: i32) -> &mut Self {
+            self.map(R1, map)
+                .stack_ptr(R2, key)
+                .stack_ptr(R3, value)
+                .mov_imm(R4, 0)
+                .call(BPF_FUNC_MAP_UPDATE_ELEM)
+        }
+
+        fn delete(&mut self, map: &OwnedFd, key: i32) -> &mut Self {
+            self.map(R1, map).stack_ptr(R2, key).call(BPF_FUNC_MAP_DELETE_ELEM)
+        }
+
+        fn jump_if_zero(&mut self, reg: u8, label: &'static str) -> &mut Self {
+            self.jumps.push((self.insns.len(), label));
+            self.op(0x15, reg, 0, 0, 0)
+        }
+
+        fn jump_if_not_zero(&mut self, reg: u8, label: &'static str) -> &mut Self {
+            self.jumps.push((self.insns.len(), label));
+            self.op(0x55, reg, 0, 0, 0)
+        }
+
+        fn label(&mut self, label: &'static str) -> &mut Self {
+            self.labels.insert(label, self.insns.len());
+            self
+        }
+
+        /// `return 0`, at label `exit`.
+        fn exit(&mut self) -> &mut Self {
+            self.label("exit").mov_imm(R0, 0).op(0x95, 0, 0, 0, 0)
+        }
+
+        fn finish(&mut self) -> Vec<[u8; 8]> {
+            for &(at, label) in &self.jumps {
+                let target = self.labels[label];
+                let offset = (target as i64 - at as i64 - 1) as i16;
+                self.insns[at][2..4].copy_from_slice(&offset.to_le_bytes());
+            }
+            std::mem::take(&mut self.insns)
+        }
+    }
+
+    /// Where the scheduler tracepoints keep the fields the programs read.
+    struct SchedFields {
+        wakeup_pid: i16,
+        wakeup_new_pid: i16,
+        prev_state: (i16, usize),
+        next_pid: i16,
+    }
+
+    /// `counters[tgid].syscalls += 1` for processes present in `apps`.
+    fn syscall_program(apps: &OwnedFd) -> Vec<[u8; 8]> {
+        let mut asm = Asm::default();
+        asm.call(BPF_FUNC_GET_CURRENT_PID_TGID)
+            .rsh_imm(R0, 32)
+            .store(4, TGID, R0)
+            .lookup(apps, TGID)
+            .jump_if_zero(R0, "exit")
+            .mov_imm(R1, 1)
+            .atomic_add(R0, SYSCALLS, R1)
+            .exit()
+            .finish()
+    }
+
+    /// Notes when a thread of an app was woken up, i.e. became runnable.
+    fn wakeup_program(maps: &Maps, pid: i16) -> Vec<[u8; 8]> {
+        let mut asm = Asm::default();
+        asm.load(4, R6, R1, pid)
+            .store(4, TID, R6)
+            .lookup(&maps.threads, TID)
+            .jump_if_not_zero(R0, "watched")
+            // Main threads are not known as threads before they ran, but their id is the pid.
+            .lookup(&maps.apps, TID)
+            .jump_if_zero(R0, "exit")
+            .label("watched")
+            .call(BPF_FUNC_KTIME_GET_NS)
+            .store(8, TIME, R0)
+            .update(&maps.queued, TID, TIME)
+            .exit()
+            .finish()
+    }
+
+    /// Accounts the CPU time of the thread leaving the CPU and the wait of the one getting it.
+    fn switch_program(maps: &Maps, fields: &SchedFields) -> Vec<[u8; 8]> {
+        let (prev_state, prev_state_size) = fields.prev_state;
+        let mut asm = Asm::default();
+        asm.mov(R6, R1)
+            .call(BPF_FUNC_KTIME_GET_NS)
+            .mov(R7, R0)
+            // The previous thread is the current one.
+            .call(BPF_FUNC_GET_CURRENT_PID_TGID)
+            .mov(R8, R0)
+            .rsh_imm(R0, 32)
+            .store(4, TGID, R0)
+            .lookup(&maps.apps, TGID)
+            .jump_if_zero(R0, "next")
+            .mov(R9, R0)
+            .store(4, TID, R8)
+            .update(&maps.threads, TID, TGID)
+            .lookup(&maps.oncpu, TID)
+            .jump_if_zero(R0, "preempted")
+            .load(8, R1, R0, 0)
+            .mov(R2, R7)
+            .sub(R2, R1)
+            .atomic_add(R9, RUN_NS, R2)
+            .label("preempted")
+            // A thread leaving the CPU in the running state stays runnable, waiting from now on.
+            .load(prev_state_size, R1, R6, prev_state)
+            .and_imm(R1, 0xff)
+            .jump_if_not_zero(R1, "next")
+            .store(8, TIME, R7)
+            .update(&maps.queued, TID, TIME)
+            .label("next")
+            .load(4, R1, R6, fields.next_pid)
+            .store(4, TID, R1)
+            .store(4, TGID, R1)
+            .lookup(&maps.threads, TID)
+            .jump_if_zero(R0, "known")
+            .load(4, R1, R0, 0)
+            .store(4, TGID, R1)
+            .label("known")
+            .lookup(&maps.apps, TGID)
+            .jump_if_zero(R0, "exit")
+            .mov(R9, R0)
+            .mov_imm(R1, 1)
+            .atomic_add(R9, TIMESLICES, R1)
+            .store(8, TIME, R7)
+            .update(&maps.oncpu, TID, TIME)
+            .lookup(&maps.queued, TID)
+            .jump_if_zero(R0, "exit")
+            .load(8, R1, R0, 0)
+            .mov(R2, R7)
+            .sub(R2, R1)
+            .atomic_add(R9, WAIT_NS, R2)
+            .delete(&maps.queued, TID)
+            .exit()
+            .finish()
+    }
+
+    fn put_u32(attr: &mut [u8], offset: usize, value: u32) {
+        attr[offset..offset + 4].copy_from_slice(&value.to_ne_bytes());
+    }
+
+    fn put_u64(attr: &mut [u8], offset: usize, value: u64) {
+        attr[offset..offset + 8].copy_from_slice(&value.to_ne_bytes());
+    }
+
+    fn new_fd(cmd: i32, attr: &mut [u8]) -> std::io::Result<OwnedFd> {
+        let fd = sys::bpf(cmd, attr)?;
+        // SAFETY: the bpf command returned a new descriptor owned by nobody else.
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    fn create_map(map_type: u32, value_size: u32, entries: u32) -> std::io::Result<OwnedFd> {
+        let mut attr = [0u8; ATTR_SIZE];
+        put_u32(&mut attr, 0, map_type);
+        put_u32(&mut attr, 4, 4); // key: a process or thread id
+        put_u32(&mut attr, 8, value_size);
+        put_u32(&mut attr, 12, entries);
+        new_fd(BPF_MAP_CREATE, &mut attr)
+    }
+
+    fn load_program(prog_type: u32, insns: &[[u8; 8]], name: &str, hint: &str) -> Result<OwnedFd> {
+        let license = c"Apache-2.0";
+        let mut log = vec![0u8; 64 * 1024];
+        let mut attr = [0u8; ATTR_SIZE];
+        put_u32(&mut attr, 0, prog_type);
+        put_u32(&mut attr, 4, insns.len() as u32);
+        put_u64(&mut attr, 8, insns.as_ptr() as u64);
+        put_u64(&mut attr, 16, license.as_ptr() as u64);
+        put_u32(&mut attr, 24, 1); // log level
+        put_u32(&mut attr, 28, log.len() as u32);
+        put_u64(&mut attr, 32, log.as_mut_ptr() as u64);
+        new_fd(BPF_PROG_LOAD, &mut attr).with_context(|| {
+            let end = log.iter().position(|&b| b == 0).unwrap_or(log.len());
+            format!(
+                "Failed to load the {} ({}): {}",
+                name,
+                hint,
+                String::from_utf8_lossy(&log[..end]).trim()
+            )
+        })
+    }
+
+    /// The tracefs mount, which describes the tracepoints.
+    fn tracefs() -> Result<PathBuf> {
+        TRACEFS
+            .iter()
+            .map(PathBuf::from)
+            .find(|dir| dir.join("events/sched").is_dir())
+            .context("--observe needs tracefs, mount it with `mount -t tracefs nodev /sys/kernel/tracing`")
+    }
+
+    /// Offset and size of `name` in the records of the scheduler tracepoint `event`.
+    fn field(tracefs: &Path, event: &str, name: &str) -> Result<(i16, usize)> {
+        let path = tracefs.join("events/sched").join(event).join("format");
+        let format = fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        // e.g. "field:pid_t next_pid;	offset:56;	size:4;	signed:1;"
+        for line in format.lines() {
+            let parts: Vec<&str> = line.split(';').map(str::trim).collect();
+            let [declaration, offset, size, ..] = parts[..] else {
+                continue;
+            };
+            let Some(declaration) = declaration.strip_prefix("field:") else {
+                continue;
+            };
+            if declaration.rsplit(' ').next() != Some(name) {
+                continue;
+            }
+            let offset = offset.strip_prefix("offset:").and_then(|o| o.parse().ok());
+            let size = size.strip_prefix("size:").and_then(|s| s.parse().ok());
+            if let (Some(offset), Some(size @ (4 | 8))) = (offset, size) {
+                return Ok((offset, size));
+            }
+        }
+        bail!("{:?} describes no field {}", path, name)
+    }
+
+    fn sched_fields(tracefs: &Path) -> Result<SchedFields> {
+        Ok(SchedFields {
+            wakeup_pid: field(tracefs, "sched_wakeup", "pid")?.0,
+            wakeup_new_pid: field(tracefs, "sched_wakeup_new", "pid")?.0,
+            prev_state: field(tracefs, "sched_switch", "prev_state")?,
+            next_pid: field(tracefs, "sched_switch", "next_pid")?.0,
+        })
+    }
+
+    /// The online CPUs, from a list like `0-3,6`.
+    fn online_cpus() -> Result<Vec<u32>> {
+        let list = fs::read_to_string("/sys/devices/system/cpu/online").context("Failed to list the online CPUs")?;
+        let mut cpus = Vec::new();
+        for range in list.trim().split(',') {
+            let (first, last) = range.split_once('-').unwrap_or((range, range));
+            match (first.parse::<u32>(), last.parse::<u32>()) {
+                (Ok(first), Ok(last)) => cpus.extend(first..=last),
+                _ => bail!("Invalid list of online CPUs \"{}\"", list.trim()),
+            }
+        }
+        Ok(cpus)
+    }
+
+    /// Runs `prog` on every event of the scheduler tracepoint `event` on all CPUs.
+    fn attach_tracepoint(tracefs: &Path, event: &str, prog: &OwnedFd, hint: &str) -> Result<Vec<OwnedFd>> {
+        let path = tracefs.join("events/sched").join(event).join("id");
+        let id: u64 = fs::read_to_string(&path)
+            .ok()
+            .and_then(|id| id.trim().parse().ok())
+            .with_context(|| format!("Failed to read the tracepoint id from {:?}", path))?;
+        let mut events = Vec::new();
+        for cpu in online_cpus()? {
+            let mut attr = [0u8; PERF_ATTR_SIZE];
+            put_u32(&mut attr, 0, PERF_TYPE_TRACEPOINT);
+            put_u32(&mut attr, 4, PERF_ATTR_SIZE as u32);
+            put_u64(&mut attr, 8, id);
+            put_u64(&mut attr, 16, 1); // sample period
+            put_u32(&mut attr, 48, 1); // wakeup events
+            let event_fd = sys::perf_event_open(&mut attr, cpu)
+                .and_then(|event_fd| sys::attach_perf_event(&event_fd, prog).map(|()| event_fd))
+                .with_context(|| format!("Failed to attach to the {} tracepoint on CPU {} ({})", event, cpu, hint))?;
+            events.push(event_fd);
+        }
+        Ok(events)
+    }
+
+    struct Maps {
+        apps: OwnedFd,    // counters by pid of the watched apps
+        threads: OwnedFd, // pid by thread id, for the threads of the apps that ran
+        queued: OwnedFd,  // since when a thread is runnable, by thread id
+        oncpu: OwnedFd,   // since when a thread is on a CPU, by thread id
+    }
+
+    pub struct Probes {
+        maps: Maps,
+        _programs: Vec<OwnedFd>,
+        _attachments: Vec<OwnedFd>,
+    }
+
+    impl Probes {
+        pub fn load() -> Result<Self> {
+            let hint = "--observe needs root or CAP_BPF and CAP_PERFMON";
+
+            let maps = Maps {
+                apps: create_map(BPF_MAP_TYPE_HASH, COUNTERS_SIZE, MAX_APPS).context(hint)?,
+                threads: create_map(BPF_MAP_TYPE_LRU_HASH, 4, MAX_THREADS).context(hint)?,
+                queued: create_map(BPF_MAP_TYPE_LRU_HASH, 8, MAX_THREADS).context(hint)?,
+                oncpu: create_map(BPF_MAP_TYPE_LRU_HASH, 8, MAX_THREADS).context(hint)?,
+            };
+            let tracefs = tracefs()?;
+            let fields = sched_fields(&tracefs)?;
+
+            let syscalls = load_program(
+                BPF_PROG_TYPE_RAW_TRACEPOINT,
+                &syscall_program(&maps.apps),
+                "syscall counter",
+                hint,
+            )?;
+            let name = c"sys_enter";
+            let mut attr = [0u8; ATTR_SIZE];
+            put_u64(&mut attr, 0, name.as_ptr() as u64);
+            put_u32(&mut attr, 8, syscalls.as_raw_fd() as u32);
+            let mut attachments = vec![new_fd(BPF_RAW_TRACEPOINT_OPEN, &mut attr)
+                .with_context(|| format!("Failed to attach to the sys_enter tracepoint ({})", hint))?];
+
+            let wakeup = load_program(
+                BPF_PROG_TYPE_TRACEPOINT,
+                &wakeup_program(&maps, fields.wakeup_pid),
+                "wakeup probe",
+                hint,
+            )?;
+            let wakeup_new = load_program(
+                BPF_PROG_TYPE_TRACEPOINT,
+                &wakeup_program(&maps, fields.wakeup_new_pid),
+                "wakeup probe",
+                hint,
+            )?;
+            let switch = load_program(
+                BPF_PROG_TYPE_TRACEPOINT,
+                &switch_program(&maps, &fields),
+                "scheduler probe",
+                hint,
+            )?;
+            attachments.extend(attach_tracepoint(&tracefs, "sched_wakeup", &wakeup, hint)?);
+            attachments.extend(attach_tracepoint(&tracefs, "sched_wakeup_new", &wakeup_new, hint)?);
+            attachments.extend(attach_tracepoint(&tracefs, "sched_switch", &switch, hint)?);
+
+            Ok(Probes {
+                maps,
+                _programs: vec![syscalls, wakeup, wakeup_new, switch],
+                _attachments: attachments,
+            })
+        }
+
+        pub fn watch(&mut self, pid: u32) -> Result<()> {
+            let zero = [0u64; 4];
+            let mut attr = [0u8; ATTR_SIZE];
+            put_u32(&mut attr, 0, self.maps.apps.as_raw_fd() as u32);
+            put_u64(&mut attr, 8, &pid as *const u32 as u64);
+            put_u64(&mut attr, 16, zero.as_ptr() as u64);
+            sys::bpf(BPF_MAP_UPDATE_ELEM, &mut attr).with_context(|| format!("Failed to observe pid {}", pid))?;
+            Ok(())
+        }
+
+        pub fn counters(&self, pid: u32) -> Counters {
+            let mut values = [0u64; 4];
+            let mut attr = [0u8; ATTR_SIZE];
+            put_u32(&mut attr, 0, self.maps.apps.as_raw_fd() as u32);
+            put_u64(&mut attr, 8, &pid as *const u32 as u64);
+            put_u64(&mut attr, 16, values.as_mut_ptr() as u64);
+            if sys::bpf(BPF_MAP_LOOKUP_ELEM, &mut attr).is_err() {
+                return Counters::default();
+            }
+            let [syscalls, run_ns, wait_ns, timeslices] = values;
+            Counters {
+                syscalls,
+                run_ns,
+                wait_ns,
+                timeslices,
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! OOM-killer handling of apps: their `oom_score_adj`, and telling OOM kills
+//! apart from other SIGKILLs.
+//!
+//! Kills are attributed by the `oom_kill` counter in `memory.events` of the
+//! example's cgroup, which only counts kills of processes of the example: an app
+//! killed by SIGKILL counts as OOM-killed for every kill the counter went up by.
+//! Without a cgroup with the memory controller, whether a SIGKILL came from the
+//! OOM killer is unknown and reported as such. The memory of every app is
+//! sampled every second, so the report has the last samples of an app killed by
+//! SIGKILL.
+
+use anyhow::{bail, Result};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::procfs::{self, ProcessSample};
+use crate::{sys, RunningApp};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// Samples kept per app, the last minute before it was killed.
+const MAX_SAMPLES: usize = 60;
+
+/// Makes the app start with the given OOM score adjustment.
+///
+/// Set in the forked child right before exec, like the nice level, so wrappers
+/// pass it on to the app. Values below the runner's need CAP_SYS_RESOURCE.
+pub fn apply(cmd: &mut Command, oom_score_adj: Option<i32>) -> Result<()> {
+    let Some(value) = oom_score_adj else {
+        return Ok(());
+    };
+    if !(-1000..=1000).contains(&value) {
+        bail!(
+            "invalid oom_score_adj {}, use -1000 (never killed) to 1000 (killed first)",
+            value
+        );
+    }
+    if !cfg!(target_os = "linux") {
+        bail!("oom_score_adj needs Linux");
+    }
+    // SAFETY: the closure only issues open, write and close, which are async-signal-safe.
+    unsafe {
+        cmd.pre_exec(move || sys::set_oom_score_adj(value));
+    }
+    Ok(())
+}
+
+/// Watches the apps of an example for OOM kills.
+pub(crate) struct Watch {
+    events: Option<PathBuf>,                          // memory.events of the example's cgroup
+    attributed: u64, // oom_kill count at the start, plus the kills attributed to apps since
+    samples: HashMap<usize, VecDeque<ProcessSample>>, // by app index
+    start: Instant,
+    next_sample: Instant,
+}
+
+impl Watch {
+    /// Watches the apps placed in the cgroup at `cgroup`, if any.
+    pub(crate) fn start(cgroup: Option<&Path>) -> Self {
+        let events = cgroup.map(|cgroup| cgroup.join("memory.events"));
+        let attributed = events.as_deref().and_then(read_counter);
+        let start = Instant::now();
+        Watch {
+            events: events.filter(|_| attributed.is_some()),
+            attributed: attributed.unwrap_or(0),
+            samples: HashMap::new(),
+            start,
+            next_sample: start,
+        }
+    }
+
+    /// Samples the memory of the running apps if due.
+    pub(crate) fn poll(&mut self, apps: &[&RunningApp]) {
+        let now = Instant::now();
+        if now >= self.next_sample {
+            self.next_sample = now + SAMPLE_INTERVAL;
+            for app in apps {
+                if let Some(sample) = procfs::sample(app.child.id(), now - self.start) {
+                    let samples = self.samples.entry(app.index).or_default();
+                    if samples.len() == MAX_SAMPLES {
+                        samples.pop_front();
+                    }
+                    samples.push_back(sample);
+                }
+            }
+        }
+    }
+
+    /// Whether an app killed by SIGKILL was killed by the OOM killer, `None` if that is unknown.
+    ///
+    /// Every OOM kill in the example's cgroup is attributed to one app.
+    pub(crate) fn oom_killed(&mut self) -> Option<bool> {
+        let count = read_counter(self.events.as_deref()?)?;
+        if count > self.attributed {
+            self.attributed += 1;
+            return Some(true);
+        }
+        Some(false)
+    }
+
+    /// The last memory samples of app `index`.
+    pub(crate) fn samples(&mut self, index: usize) -> Vec<ProcessSample> {
+        self.samples.remove(&index).map(Vec::from).unwrap_or_default()
+    }
+}
+
+fn read_counter(file: &Path) -> Option<u64> {
+    let content = fs::read_to_string(file).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("oom_kill "))
+        .and_then(|value| value.trim().parse().ok())
+}
@@ -0,0 +1,516 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Capture of app output.
+//!
+//! Output of every app is read line by line by a task of the supervisor's runtime, echoed to the terminal with the
+//! app and a timestamp as prefix, written to a per-app log file, and the
+//! recent history kept to explain failures. Sensitive values are masked before
+//! a line is displayed or stored anywhere.
+//!
+//! Log file lines have the format `<seconds since start> <RFC 3339 UTC time> <out|err> <text>`.
+//! Events of the runner itself (apps starting, exiting, being stopped) go to `runner.log`
+//! in the same format with the stream `evt`.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::os::fd::OwnedFd;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::unix::pipe;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use crate::clock;
+use crate::disk_guard;
+use crate::forward::{self, Forwarding};
+use crate::log_index;
+use crate::pattern::Pattern;
+use crate::redact::Redactor;
+use crate::supervisor;
+
+/// Case-insensitive patterns marking a line as error output.
+const ERROR_PATTERNS: &[&str] = &[
+    "error",
+    "fatal",
+    "panic",
+    "abort",
+    "exception",
+    "segmentation fault",
+    "failed",
+];
+/// Upper bound of error lines kept per example.
+pub(crate) const MAX_ERROR_LINES: usize = 1000;
+/// How long after a failure error lines of other apps are still considered related.
+pub const RELATED_ERRORS_WINDOW: Duration = Duration::from_secs(2);
+
+/// Timestamp prefix of app output lines on the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Timestamps {
+    /// No timestamp
+    None,
+    /// Seconds since the example started
+    #[default]
+    Monotonic,
+    /// Wall-clock time of day (UTC)
+    Wall,
+    /// Wall-clock time and seconds since start
+    Both,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Line {
+    pub app: usize,
+    pub at_ms: u128, // since the example started
+    pub text: String,
+}
+
+/// Focused output around the first app failure of an example.
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureContext {
+    pub app: usize,
+    pub reason: String,
+    pub last_lines: Vec<Line>,
+    pub related_errors: Vec<Line>, // error lines of other apps in the same time window
+}
+
+#[derive(Default)]
+struct History {
+    tails: HashMap<usize, VecDeque<Line>>,
+    errors: Vec<Line>,
+    errors_dropped: u64, // error lines beyond MAX_ERROR_LINES
+}
+
+#[derive(Clone)]
+pub struct Capture {
+    start: Instant,
+    tail_lines: usize,
+    timestamps: Timestamps,
+    log_dir: PathBuf,
+    history: Arc<Mutex<History>>,
+    events: LogFile,
+    app_logs: Arc<Mutex<HashMap<usize, LogFile>>>,
+    muted: Arc<Mutex<HashSet<usize>>>, // apps whose output is only logged, not shown (shown with hide_all)
+    wake: Arc<Notify>,                 // wakes the supervisor when an app printed its ready line
+    watches: Arc<Mutex<HashMap<usize, (Pattern, bool)>>>, // ready line pattern per app, and whether it was seen
+    failures: Arc<Mutex<Vec<(usize, String)>>>, // apps failed by the runner, e.g. by their interaction script, with the reason
+    unlogged: Arc<AtomicU64>,                   // app output lines that could not be written to their log file
+    pause: disk_guard::Pause,                   // set while the disk is too full for logging app output
+    not_logged: Arc<AtomicU64>,                 // app output lines dropped while logging was paused
+    forwarding: Arc<Forwarding>,
+    hide_all: bool, // --quiet: no app output on the terminal unless shown with a key
+    redactor: Arc<Redactor>,
+}
+
+/// A log file and its line index.
+struct Log {
+    path: PathBuf,
+    out: BufWriter<File>,
+    index: log_index::Writer,
+    failed: bool, // writing failed once, the file ends with the lines before
+}
+
+type LogFile = Arc<Mutex<Log>>;
+
+/// Path of the log file of app `index` inside an example's artifacts directory.
+pub fn log_path(example_dir: &Path, index: usize) -> PathBuf {
+    example_dir.join(format!("app{}.log", index))
+}
+
+/// Path of the runner event log inside an example's artifacts directory.
+pub fn events_path(example_dir: &Path) -> PathBuf {
+    example_dir.join("runner.log")
+}
+
+fn create_log(path: &Path) -> Result<LogFile> {
+    let file = File::create(path).with_context(|| format!("Failed to create log file {:?}", path))?;
+    Ok(Arc::new(Mutex::new(Log {
+        path: path.to_path_buf(),
+        out: BufWriter::new(file),
+        index: log_index::Writer::create(path)?,
+        failed: false,
+    })))
+}
+
+/// Appends a line to `log` and indexes it, returning whether it was written.
+fn write_log(log: &LogFile, elapsed: Duration, wall: SystemTime, stream: &str, text: &str) -> bool {
+    let mut log = log.lock().unwrap_or_else(|e| e.into_inner());
+    if log.failed {
+        return false;
+    }
+    let line = format!(
+        "{:.6} {} {} {}\n",
+        elapsed.as_secs_f64(),
+        clock::rfc3339(wall),
+        stream,
+        text
+    );
+    if let Err(e) = log.out.write_all(line.as_bytes()).and_then(|()| log.out.flush()) {
+        // Where the line ends is unknown now, so nothing more is appended or indexed.
+        eprintln!("⚠️  Failed to write {:?}, not logging to it anymore: {}", log.path, e);
+        log.failed = true;
+        return false;
+    }
+    log.index.line(line.len(), elapsed);
+    true
+}
+
+impl Capture {
+    pub fn new(
+        start: Instant,
+        tail_lines: usize,
+        timestamps: Timestamps,
+        log_dir: &Path,
+        redactor: Redactor,
+        pause: disk_guard::Pause,
+    ) -> Result<Self> {
+        Ok(Capture {
+            start,
+            tail_lines,
+            timestamps,
+            log_dir: log_dir.to_path_buf(),
+            history: Arc::default(),
+            events: create_log(&events_path(log_dir))?,
+            app_logs: Arc::default(),
+            muted: Arc::default(),
+            wake: Arc::default(),
+            watches: Arc::default(),
+            failures: Arc::default(),
+            unlogged: Arc::default(),
+            pause,
+            not_logged: Arc::default(),
+            forwarding: Arc::default(),
+            hide_all: false,
+            redactor: Arc::new(redactor),
+        })
+    }
+
+    /// Sends all lines, including runner events, to external viewers as well.
+    pub fn forward(&mut self, forwarding: Forwarding) {
+        self.forwarding = Arc::new(forwarding);
+    }
+
+    /// Hides the output of all apps on the terminal, only writing it to the log files.
+    pub fn hide_all(&mut self) {
+        self.hide_all = true;
+    }
+
+    /// Records a runner event in `runner.log`.
+    pub fn event(&self, text: &str) {
+        let (elapsed, wall, text) = (self.start.elapsed(), SystemTime::now(), self.redactor.redact(text));
+        write_log(&self.events, elapsed, wall, "evt", &text);
+        self.send(0, "runner", "evt", elapsed, wall, &text);
+    }
+
+    fn send(&self, app: usize, label: &str, stream: &str, elapsed: Duration, wall: SystemTime, text: &str) {
+        self.forwarding.send(&forward::Event {
+            example: &self.forwarding.example,
+            app,
+            label,
+            stream,
+            elapsed_s: elapsed.as_secs_f64(),
+            time: clock::rfc3339(wall),
+            text,
+        });
+    }
+
+    /// Masks sensitive values in runner output that is not captured app output.
+    pub fn redact(&self, text: &str) -> String {
+        self.redactor.redact(text)
+    }
+
+    /// Starts reading the piped stdout/stderr of `child` into the log file of app `index`.
+    ///
+    /// A restarted app continues the log file of its previous instance.
+    pub fn attach(&self, index: usize, label: &str, child: &mut Child) -> Result<Vec<JoinHandle<()>>> {
+        let log = self.app_log(index)?;
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(self.spawn_reader(index, label, stdout, false, log.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(self.spawn_reader(index, label, stderr, true, log));
+        }
+        Ok(readers)
+    }
+
+    fn app_log(&self, index: usize) -> Result<LogFile> {
+        Ok(match self.app_logs.lock().unwrap().entry(index) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => entry.insert(create_log(&log_path(&self.log_dir, index))?).clone(),
+        })
+    }
+
+    /// Returns a sink taking the raw output of app `index` from a stream the caller reads itself,
+    /// such as the pseudo terminal of an app with an interaction script.
+    pub(crate) fn sink(&self, index: usize, label: &str) -> Result<Sink> {
+        Ok(Sink {
+            capture: self.clone(),
+            index,
+            label: label.to_string(),
+            log: self.app_log(index)?,
+            partial: Vec::new(),
+        })
+    }
+
+    fn spawn_reader(
+        &self,
+        index: usize,
+        label: &str,
+        stream: impl Into<OwnedFd>,
+        stderr: bool,
+        log: LogFile,
+    ) -> JoinHandle<()> {
+        let capture = self.clone();
+        let label = label.to_string();
+        let stream = stream.into();
+        supervisor::runtime().spawn(async move {
+            let pipe = match pipe::Receiver::from_owned_fd(stream) {
+                Ok(pipe) => pipe,
+                Err(e) => {
+                    capture.event(&format!("app {}: cannot read its output: {}", index, e));
+                    return;
+                }
+            };
+            let mut reader = BufReader::new(pipe);
+            let mut buf = Vec::new();
+            while matches!(reader.read_until(b'\n', &mut buf).await, Ok(n) if n > 0) {
+                capture.line(index, &label, &buf, stderr, &log);
+                buf.clear();
+            }
+        })
+    }
+
+    /// Prints, watches, logs and forwards a line of output of app `index`.
+    fn line(&self, index: usize, label: &str, raw: &[u8], stderr: bool, log: &LogFile) {
+        let text = self
+            .redactor
+            .redact(String::from_utf8_lossy(raw).trim_end_matches(['\n', '\r']));
+        let elapsed = self.start.elapsed();
+        let wall = SystemTime::now();
+
+        let prefix = match self.timestamps {
+            Timestamps::None => format!("[{}]", label),
+            Timestamps::Monotonic => format!("{:>9.3}s [{}]", elapsed.as_secs_f64(), label),
+            Timestamps::Wall => format!("{} [{}]", clock::time_of_day(wall), label),
+            Timestamps::Both => format!(
+                "{} {:>9.3}s [{}]",
+                clock::time_of_day(wall),
+                elapsed.as_secs_f64(),
+                label
+            ),
+        };
+        // Hidden output still goes to the log file.
+        if self.muted.lock().unwrap().contains(&index) == self.hide_all {
+            if stderr {
+                eprintln!("{} {}", prefix, text);
+            } else {
+                println!("{} {}", prefix, text);
+            }
+        }
+
+        if let Some((pattern, seen)) = self.watches.lock().unwrap().get_mut(&index) {
+            if !*seen && pattern.is_match(&text) {
+                *seen = true;
+                self.wake.notify_one();
+            }
+        }
+        let stream = if stderr { "err" } else { "out" };
+        if self.pause.drop_line() {
+            self.not_logged.fetch_add(1, Ordering::Relaxed);
+        } else if !write_log(log, elapsed, wall, stream, &text) {
+            self.unlogged.fetch_add(1, Ordering::Relaxed);
+        }
+        self.send(index, label, stream, elapsed, wall, &text);
+        self.record(index, elapsed, text);
+    }
+
+    /// App output lines that could not be written to the log files.
+    pub fn unlogged_lines(&self) -> u64 {
+        self.unlogged.load(Ordering::Relaxed)
+    }
+
+    /// App output lines not logged for lack of disk space.
+    pub fn not_logged_lines(&self) -> u64 {
+        self.not_logged.load(Ordering::Relaxed)
+    }
+
+    /// Error lines not kept for the failure context, beyond the first [`MAX_ERROR_LINES`].
+    pub fn dropped_error_lines(&self) -> u64 {
+        self.history.lock().unwrap_or_else(|e| e.into_inner()).errors_dropped
+    }
+
+    /// Notified when an app printed its ready line, for the supervisor to wait on.
+    pub(crate) fn wake(&self) -> &Notify {
+        &self.wake
+    }
+
+    /// Watches the output of app `index` for a line matching `pattern`.
+    pub fn watch_for_line(&self, index: usize, pattern: Pattern) {
+        self.watches.lock().unwrap().insert(index, (pattern, false));
+    }
+
+    /// Whether app `index` printed a line matching its watched pattern.
+    pub fn line_seen(&self, index: usize) -> bool {
+        self.watches.lock().unwrap().get(&index).is_some_and(|(_, seen)| *seen)
+    }
+
+    /// Fails the example because of app `index`, however the app exits, and wakes the supervisor.
+    pub fn fail(&self, index: usize, reason: String) {
+        self.failures.lock().unwrap().push((index, reason));
+        self.wake.notify_one();
+    }
+
+    /// The apps failed with [`Capture::fail`] since the last call, with the reasons.
+    pub fn take_failures(&self) -> Vec<(usize, String)> {
+        std::mem::take(&mut *self.failures.lock().unwrap())
+    }
+
+    /// Hides the output of app `index` on the terminal, or shows it again. Returns whether it is hidden now.
+    pub fn toggle_muted(&self, index: usize) -> bool {
+        let mut muted = self.muted.lock().unwrap();
+        if !muted.remove(&index) {
+            muted.insert(index);
+        }
+        // With all apps hidden, the set holds the apps shown.
+        muted.contains(&index) != self.hide_all
+    }
+
+    fn record(&self, app: usize, at: Duration, text: String) {
+        let line = Line {
+            app,
+            at_ms: at.as_millis(),
+            text,
+        };
+        let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        if is_error_line(&line.text) {
+            if history.errors.len() < MAX_ERROR_LINES {
+                history.errors.push(line.clone());
+            } else {
+                history.errors_dropped += 1;
+            }
+        }
+        let tail = history.tails.entry(app).or_default();
+        tail.push_back(line);
+        if tail.len() > self.tail_lines {
+            tail.pop_front();
+        }
+    }
+
+    /// Collects the last lines of `app` and error lines other apps printed from then on until `until`.
+    pub fn failure_context(&self, app: usize, reason: String, until: Duration) -> FailureContext {
+        let history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        let last_lines: Vec<Line> = history
+            .tails
+            .get(&app)
+            .map(|t| t.iter().cloned().collect())
+            .unwrap_or_default();
+        let from = last_lines.first().map(|l| l.at_ms).unwrap_or(0);
+        let until = until.as_millis();
+        let related_errors = history
+            .errors
+            .iter()
+            .filter(|l| l.app != app && l.at_ms >= from && l.at_ms <= until)
+            .cloned()
+            .collect();
+        FailureContext {
+            app,
+            reason,
+            last_lines,
+            related_errors,
+        }
+    }
+}
+
+/// Output of an app read by the caller, split into lines here.
+///
+/// Text without a line break yet, like a prompt, stays pending until more
+/// output arrives or the stream ends.
+pub(crate) struct Sink {
+    capture: Capture,
+    index: usize,
+    label: String,
+    log: LogFile,
+    partial: Vec<u8>,
+}
+
+impl Sink {
+    /// Takes a chunk of output, capturing every line it completes.
+    pub(crate) fn write(&mut self, chunk: &[u8]) {
+        self.partial.extend_from_slice(chunk);
+        while let Some(end) = self.partial.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.partial.drain(..=end).collect();
+            self.capture.line(self.index, &self.label, &line, false, &self.log);
+        }
+    }
+}
+
+impl Drop for Sink {
+    /// Captures the last, unterminated line.
+    fn drop(&mut self) {
+        if !self.partial.is_empty() {
+            let line = std::mem::take(&mut self.partial);
+            self.capture.line(self.index, &self.label, &line, false, &self.log);
+        }
+    }
+}
+
+fn is_error_line(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    ERROR_PATTERNS.iter().any(|p| lower.contains(p))
+}
+
+/// Waits up to `timeout` for the readers to drain the pipes, keeping those still reading.
+///
+/// Readers can outlive their app if it left background processes holding the pipe; those are detached
+/// when dropped.
+pub fn join_readers(readers: &mut Vec<JoinHandle<()>>, timeout: Duration) {
+    supervisor::runtime().block_on(async {
+        let _ = tokio::time::timeout(timeout, async {
+            for reader in readers.iter_mut() {
+                let _ = reader.await;
+            }
+        })
+        .await;
+    });
+    // Awaited readers must not be polled again.
+    readers.retain(|reader| !reader.is_finished());
+}
+
+pub fn print_failure_context(context: &FailureContext, labels: &HashMap<usize, String>) {
+    let label = |app: &usize| labels.get(app).cloned().unwrap_or_else(|| app.to_string());
+    println!("━━ First failure: [{}] {}", label(&context.app), context.reason);
+    println!("Last {} lines of [{}]:", context.last_lines.len(), label(&context.app));
+    for line in &context.last_lines {
+        println!("  {:>8.3}s {}", line.at_ms as f64 / 1000.0, line.text);
+    }
+    if !context.related_errors.is_empty() {
+        println!("Errors of other apps in the same time window:");
+        for line in &context.related_errors {
+            println!(
+                "  {:>8.3}s [{}] {}",
+                line.at_ms as f64 / 1000.0,
+                label(&line.app),
+                line.text
+            );
+        }
+    }
+}
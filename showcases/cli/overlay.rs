@@ -0,0 +1,272 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Layered examples directories (`--root`, repeatable).
+//!
+//! Examples are loaded from every root in order. An example named like one of
+//! an earlier root replaces it, unless it sets `"patch": true`. Then it is
+//! merged into the earlier example like a JSON merge patch: objects like `env`
+//! are merged key by key with `null` removing a key, and other values replace
+//! the earlier value.
+//!
+//! - Lists like `args` or `tags` are replaced too, unless the patch gives
+//!   `{"append": [...]}` and / or `{"prepend": [...]}`, which extend them.
+//! - `apps` are patched by name: every app of the patch patches the app with
+//!   the same name, its `name` or else the file name of its `path`. Apps of the
+//!   patch matching none are added at the end.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+
+const PATCH_KEY: &str = "patch";
+
+/// An example as read from a `*.score.json` file, before it is parsed.
+pub(crate) struct Source {
+    pub value: Value,
+    pub file: PathBuf,        // the file last defining or patching the example
+    pub origins: Vec<Origin>, // the files defining and patching the example, in the order they apply
+}
+
+/// A file contributing to an example, for `config explain`.
+pub(crate) struct Origin {
+    pub file: PathBuf,
+    pub patch: bool,
+    pub apps: Vec<(usize, Value)>, // the apps the file sets, by their index in the example
+}
+
+impl Source {
+    pub(crate) fn name(&self) -> &str {
+        self.value.get("name").and_then(Value::as_str).unwrap_or_default()
+    }
+}
+
+/// Whether `example` patches an example of an earlier root instead of defining one.
+pub(crate) fn is_patch(example: &Value) -> bool {
+    example.get(PATCH_KEY) == Some(&Value::Bool(true))
+}
+
+/// Adds the examples of the next root to `examples`, replacing or patching those of earlier roots.
+pub(crate) fn layer(examples: &mut Vec<Source>, root: Vec<Source>) -> Result<()> {
+    // Examples of the same root with the same name do not overlay each other.
+    let earlier = examples.len();
+    for mut source in root {
+        let patch = is_patch(&source.value);
+        if let Some(example) = source.value.as_object_mut() {
+            example.remove(PATCH_KEY);
+        }
+        let existing = examples[..earlier]
+            .iter_mut()
+            .find(|existing| existing.name() == source.name());
+        if !patch {
+            let apps = source
+                .value
+                .get("apps")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            source.origins = vec![Origin {
+                file: source.file.clone(),
+                patch: false,
+                apps: apps.into_iter().enumerate().collect(),
+            }];
+        }
+        match existing {
+            Some(existing) if patch => {
+                let apps = merge_example(&mut existing.value, source.value)
+                    .with_context(|| format!("{:?} cannot patch example '{}'", source.file, existing.name()))?;
+                existing.origins.push(Origin {
+                    file: source.file.clone(),
+                    patch: true,
+                    apps,
+                });
+                existing.file = source.file;
+            }
+            Some(existing) => *existing = source,
+            None if patch => bail!(
+                "{:?} patches example '{}', which no earlier examples directory defines",
+                source.file,
+                source.name()
+            ),
+            None => examples.push(source),
+        }
+    }
+    Ok(())
+}
+
+/// Patches `example` with `patch`, returning the apps of the patch by the index of the app they patch or added.
+fn merge_example(example: &mut Value, mut patch: Value) -> Result<Vec<(usize, Value)>> {
+    let apps = patch.as_object_mut().and_then(|patch| patch.remove("apps"));
+    merge(example, patch);
+    let Some(apps) = apps else {
+        return Ok(Vec::new());
+    };
+    let Value::Array(apps) = apps else {
+        bail!("\"apps\" of a patch must be a list");
+    };
+    let Some(example) = example.as_object_mut() else {
+        bail!("The example is not an object");
+    };
+    let Value::Array(existing) = example.entry("apps").or_insert(Value::Array(Vec::new())) else {
+        bail!("\"apps\" of the example is not a list");
+    };
+    let mut patched = Vec::new();
+    for app in apps {
+        let Some(name) = app_name(&app) else {
+            bail!("Apps of a patch need a \"name\" or \"path\" naming the app they patch");
+        };
+        let matching: Vec<usize> = (0..existing.len())
+            .filter(|&index| app_name(&existing[index]) == Some(name))
+            .collect();
+        match matching[..] {
+            [] => {
+                patched.push((existing.len(), app.clone()));
+                existing.push(app);
+            }
+            [index] => {
+                patched.push((index, app.clone()));
+                merge(&mut existing[index], app);
+            }
+            _ => bail!(
+                "Several apps are named \"{}\", give them distinct names to patch them",
+                name
+            ),
+        }
+    }
+    Ok(patched)
+}
+
+/// The name of `app` in its output: its `name`, or else the file name of its `path`.
+pub(crate) fn app_name(app: &Value) -> Option<&str> {
+    match app.get("name").and_then(Value::as_str) {
+        Some(name) => Some(name),
+        None => {
+            let path = app.get("path").and_then(Value::as_str)?;
+            Some(path.rsplit('/').next().unwrap_or(path)).filter(|name| !name.is_empty())
+        }
+    }
+}
+
+/// The list operations of `patch`, if it is `{"append": [...]}`, `{"prepend": [...]}` or both.
+fn list_patch(patch: &Map<String, Value>) -> Option<(&[Value], &[Value])> {
+    if patch.is_empty() || !patch.keys().all(|key| key == "append" || key == "prepend") {
+        return None;
+    }
+    let list = |key: &str| match patch.get(key) {
+        Some(Value::Array(values)) => Some(values.as_slice()),
+        Some(_) => None,
+        None => Some(&[][..]),
+    };
+    Some((list("prepend")?, list("append")?))
+}
+
+/// Applies `patch` to `value` like a JSON merge patch (RFC 7396).
+pub(crate) fn merge(value: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *value = patch;
+        return;
+    };
+    if value.is_array() || value.is_null() {
+        if let Some((prepend, append)) = list_patch(&patch) {
+            let existing = match value.take() {
+                Value::Array(existing) => existing,
+                _ => Vec::new(),
+            };
+            *value = Value::Array(
+                prepend
+                    .iter()
+                    .cloned()
+                    .chain(existing)
+                    .chain(append.iter().cloned())
+                    .collect(),
+            );
+            return;
+        }
+    }
+    if !value.is_object() {
+        *value = Value::Object(Default::default());
+    }
+    let Value::Object(object) = value else {
+        unreachable!("replaced by an object above");
+    };
+    for (key, patch) in patch {
+        if patch.is_null() {
+            object.remove(&key);
+        } else {
+            merge(object.entry(key).or_insert(Value::Null), patch);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patched(example: Value, patch: Value) -> Result<Value> {
+        let mut example = example;
+        merge_example(&mut example, patch)?;
+        Ok(example)
+    }
+
+    #[test]
+    fn apps_are_patched_by_name() {
+        let example = json!({
+            "name": "demo",
+            "apps": [
+                { "path": "/opt/bin/server", "args": ["--port", "1"] },
+                { "name": "client", "path": "/opt/bin/app" },
+            ],
+        });
+        let patch = json!({
+            "apps": [
+                { "name": "client", "args": ["-v"] },
+                { "path": "/elsewhere/server", "env": { "A": "1" } },
+                { "path": "/opt/bin/extra" },
+            ],
+        });
+        let example = patched(example, patch).unwrap();
+        let apps = example["apps"].as_array().unwrap();
+        assert_eq!(apps.len(), 3);
+        // Matched by the file name of its path, which the patch replaces.
+        assert_eq!(apps[0]["path"], "/elsewhere/server");
+        assert_eq!(apps[0]["args"], json!(["--port", "1"]));
+        assert_eq!(apps[0]["env"], json!({ "A": "1" }));
+        assert_eq!(apps[1]["args"], json!(["-v"]));
+        assert_eq!(apps[2]["path"], "/opt/bin/extra");
+    }
+
+    #[test]
+    fn apps_without_name_or_unambiguous_match_fail() {
+        let example = json!({ "apps": [{ "path": "/a/app" }, { "path": "/b/app" }] });
+        assert!(patched(example.clone(), json!({ "apps": [{ "args": [] }] })).is_err());
+        assert!(patched(example, json!({ "apps": [{ "path": "app" }] })).is_err());
+    }
+
+    #[test]
+    fn lists_are_replaced_or_extended() {
+        let mut value = json!({ "args": ["a", "b"], "tags": ["x"], "env": { "K": "v" } });
+        merge(
+            &mut value,
+            json!({
+                "args": { "prepend": ["0"], "append": ["c"] },
+                "tags": ["y"],
+                "preload": { "append": ["/lib/x.so"] },
+                "env": { "append": "not a list" },
+            }),
+        );
+        assert_eq!(value["args"], json!(["0", "a", "b", "c"]));
+        assert_eq!(value["tags"], json!(["y"]));
+        assert_eq!(value["preload"], json!(["/lib/x.so"]));
+        assert_eq!(value["env"], json!({ "K": "v", "append": "not a list" }));
+    }
+}
@@ -0,0 +1,186 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Running several examples at the same time with `--parallel <n>`.
+//!
+//! Each of the `n` workers takes the first example of the selection whose
+//! `concurrency_group` is not running already and whose `reserve` fits into the
+//! CPUs and memory of the host left by the running examples, so examples
+//! sharing e.g. the display never overlap and heavy examples do not oversubscribe
+//! the host, while light headless examples keep all workers busy. Examples
+//! without `reserve` take one CPU. An example needing more than the host has
+//! runs once nothing else does. Run control keys are off, as the examples would
+//! read the same terminal.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Condvar, Mutex};
+
+use crate::host;
+use crate::progress::Progress;
+use crate::report::ExampleReport;
+use crate::{run_selected, Run, RunOptions, ScoreConfig};
+
+/// Approximate resources an example needs while it runs.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Reservation {
+    #[serde(default)]
+    cpus: f64, // CPUs kept busy by all apps together, e.g. 0.5 or 4
+    #[serde(default)]
+    memory_mb: u64, // memory of all apps together
+}
+
+/// Resources of the host left for examples to start.
+#[derive(Clone, Copy)]
+struct Capacity {
+    cpus: f64,
+    memory_mb: u64,
+}
+
+impl Capacity {
+    fn host() -> Self {
+        Capacity {
+            cpus: host::cpu_count() as f64,
+            memory_mb: host::total_memory_mb().unwrap_or(u64::MAX),
+        }
+    }
+
+    /// What `config` reserves, at most all of the host so that every example can run.
+    fn needed(&self, config: &ScoreConfig) -> Capacity {
+        let reserve = config.reserve.clone().unwrap_or(Reservation {
+            cpus: 1.0,
+            memory_mb: 0,
+        });
+        Capacity {
+            cpus: reserve.cpus.max(0.0).min(self.cpus),
+            memory_mb: reserve.memory_mb.min(self.memory_mb),
+        }
+    }
+
+    fn fits(&self, needed: &Capacity) -> bool {
+        // A little slack for reservations like 3 x 0.33 CPUs.
+        needed.cpus <= self.cpus + 1e-6 && needed.memory_mb <= self.memory_mb
+    }
+
+    fn take(&mut self, needed: &Capacity) {
+        self.cpus -= needed.cpus;
+        self.memory_mb -= needed.memory_mb;
+    }
+
+    fn give_back(&mut self, needed: &Capacity) {
+        self.cpus += needed.cpus;
+        self.memory_mb += needed.memory_mb;
+    }
+}
+
+/// Parses `--parallel`: a number of workers or "auto" for one per CPU.
+pub(crate) fn parse_workers(value: &str) -> Result<u32, String> {
+    let workers = if value == "auto" {
+        host::cpu_count() as u32
+    } else {
+        value
+            .parse()
+            .map_err(|_| format!("expected a number of examples or \"auto\", got \"{}\"", value))?
+    };
+    if workers == 0 {
+        return Err("at least one example has to run".to_string());
+    }
+    Ok(workers)
+}
+
+struct Queue {
+    pending: Vec<usize>,          // positions in the selection not started yet
+    busy: HashSet<String>,        // concurrency groups of the running examples
+    free: Capacity,               // resources not reserved by the running examples
+    error: Option<anyhow::Error>, // the first example the runner failed to run, ends the run
+}
+
+/// Runs the `selected` examples on `options.parallel` workers, returning their reports in the order of the selection.
+pub fn run(
+    configs: &[ScoreConfig],
+    selected: &[usize],
+    options: &RunOptions,
+    run: &Run,
+    run_dir: &Path,
+    progress: Option<&Progress>,
+) -> Result<Vec<ExampleReport>> {
+    let options = RunOptions {
+        interactive: false,
+        ..options.clone()
+    };
+    let capacity = Capacity::host();
+    let queue = Mutex::new(Queue {
+        pending: (0..selected.len()).collect(),
+        busy: HashSet::new(),
+        free: capacity,
+        error: None,
+    });
+    let wakeup = Condvar::new();
+    let reports: Mutex<Vec<Option<ExampleReport>>> = Mutex::new(selected.iter().map(|_| None).collect());
+    let group = |position: usize| configs[selected[position]].concurrency_group.as_ref();
+    let needed = |position: usize| capacity.needed(&configs[selected[position]]);
+
+    std::thread::scope(|scope| {
+        for _ in 0..(options.parallel as usize).min(selected.len()) {
+            scope.spawn(|| loop {
+                let position = {
+                    let mut queue = queue.lock().unwrap();
+                    loop {
+                        if queue.error.is_some() || queue.pending.is_empty() {
+                            return;
+                        }
+                        let next = queue.pending.iter().position(|&position| {
+                            group(position).is_none_or(|group| !queue.busy.contains(group))
+                                && queue.free.fits(&needed(position))
+                        });
+                        if let Some(next) = next {
+                            let position = queue.pending.remove(next);
+                            if let Some(group) = group(position) {
+                                queue.busy.insert(group.clone());
+                            }
+                            queue.free.take(&needed(position));
+                            break position;
+                        }
+                        // All examples left wait for a group in use or for resources.
+                        queue = wakeup.wait(queue).unwrap();
+                    }
+                };
+                let result = run_selected(configs, selected[position], &options, run, run_dir, progress);
+                let mut queue = queue.lock().unwrap();
+                if let Some(group) = group(position) {
+                    queue.busy.remove(group);
+                }
+                queue.free.give_back(&needed(position));
+                match result {
+                    Ok(report) => reports.lock().unwrap()[position] = Some(report),
+                    Err(e) => {
+                        queue.error.get_or_insert(e);
+                    }
+                }
+                wakeup.notify_all();
+            });
+        }
+    });
+
+    if let Some(e) = queue.into_inner().unwrap().error {
+        return Err(e);
+    }
+    Ok(reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|report| report.expect("all examples ran"))
+        .collect())
+}
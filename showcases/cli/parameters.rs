@@ -0,0 +1,214 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Parameters of examples, knobs like "number of vehicles" set when running them.
+//!
+//! An example declares its `parameters` and uses them as `{{name}}` in the
+//! path, working directory, arguments and environment of its apps. Values come
+//! from `--param name=value`, from prompts in interactive mode, or from the
+//! parameter's default.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use crate::ScoreConfig;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Parameter {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: Kind,
+    pub default: Option<serde_json::Value>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    #[default]
+    String,
+    Integer,
+    Number,
+    Boolean,
+}
+
+impl Parameter {
+    /// The default as it is substituted, `None` if the parameter has none.
+    pub fn default_value(&self) -> Option<String> {
+        match self.default.as_ref()? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+
+    /// Checks `value` against the parameter's type, normalizing booleans.
+    fn check(&self, value: &str) -> Result<String> {
+        let valid = match self.kind {
+            Kind::String => true,
+            Kind::Integer => value.parse::<i64>().is_ok(),
+            Kind::Number => value.parse::<f64>().is_ok_and(f64::is_finite),
+            Kind::Boolean => {
+                return match value.to_ascii_lowercase().as_str() {
+                    "true" | "yes" | "1" => Ok("true".to_string()),
+                    "false" | "no" | "0" => Ok("false".to_string()),
+                    _ => bail!("Parameter '{}' must be true or false, got {:?}", self.name, value),
+                }
+            }
+        };
+        if !valid {
+            bail!(
+                "Parameter '{}' must be {}, got {:?}",
+                self.name,
+                self.kind.describe(),
+                value
+            );
+        }
+        Ok(value.to_string())
+    }
+}
+
+impl Kind {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Kind::String => "a string",
+            Kind::Integer => "an integer",
+            Kind::Number => "a number",
+            Kind::Boolean => "a boolean",
+        }
+    }
+}
+
+/// Parses `--param` arguments of the form `name=value`.
+pub fn parse_given(params: &[String]) -> Result<BTreeMap<String, String>> {
+    params
+        .iter()
+        .map(|param| match param.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.to_string())),
+            _ => bail!("Invalid --param {:?}, expected name=value", param),
+        })
+        .collect()
+}
+
+/// Resolves the parameters of the `selected` examples and the examples they run
+/// as steps, substituting their values into the apps.
+///
+/// Parameters missing from `given` are prompted for if `interactive`, otherwise
+/// they take their default.
+pub fn resolve(
+    configs: &mut [ScoreConfig],
+    selected: &[usize],
+    given: &BTreeMap<String, String>,
+    interactive: bool,
+) -> Result<()> {
+    let mut pending: Vec<usize> = selected.to_vec();
+    let mut resolved: Vec<usize> = Vec::new();
+    let mut values: BTreeMap<String, String> = BTreeMap::new();
+    while let Some(index) = pending.pop() {
+        if resolved.contains(&index) {
+            continue;
+        }
+        resolved.push(index);
+        for step in &configs[index].steps {
+            if let Some(step) = configs.iter().position(|c| &c.name == step) {
+                pending.push(step);
+            }
+        }
+
+        let config = &configs[index];
+        let mut example_values = BTreeMap::new();
+        for parameter in &config.parameters {
+            // A parameter shared by several examples is asked for only once.
+            let value = match (given.get(&parameter.name), values.get(&parameter.name)) {
+                (Some(value), _) => parameter.check(value)?,
+                (None, Some(value)) => value.clone(),
+                (None, None) if interactive => prompt(&config.name, parameter)?,
+                (None, None) => parameter.default_value().with_context(|| {
+                    format!(
+                        "Example '{}' needs parameter '{}', set it with --param {}=<value>",
+                        config.name, parameter.name, parameter.name
+                    )
+                })?,
+            };
+            values.insert(parameter.name.clone(), value.clone());
+            example_values.insert(parameter.name.clone(), value);
+        }
+        if !example_values.is_empty() {
+            substitute(&mut configs[index], &example_values)?;
+        }
+    }
+
+    if let Some(unknown) = given.keys().find(|name| !values.contains_key(*name)) {
+        bail!("Unknown parameter '{}': no selected example declares it", unknown);
+    }
+    Ok(())
+}
+
+fn prompt(example: &str, parameter: &Parameter) -> Result<String> {
+    let label = if parameter.description.is_empty() {
+        format!("{}: {}", example, parameter.name)
+    } else {
+        format!("{}: {}", example, parameter.description)
+    };
+    if parameter.kind == Kind::Boolean {
+        let initial = parameter.default_value().is_some_and(|d| d == "true");
+        let value: bool = cliclack::confirm(label).initial_value(initial).interact()?;
+        return Ok(value.to_string());
+    }
+    let checked = parameter.clone();
+    let mut input = cliclack::input(label).validate(move |value: &String| checked.check(value).map(|_| ()));
+    if let Some(default) = parameter.default_value() {
+        input = input.default_input(&default);
+    }
+    let value: String = input.interact()?;
+    parameter.check(&value)
+}
+
+/// Replaces `{{name}}` in the apps of `config` by the parameter values.
+fn substitute(config: &mut ScoreConfig, values: &BTreeMap<String, String>) -> Result<()> {
+    let name = config.name.clone();
+    let apply = |text: &mut String| -> Result<()> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find("}}") else {
+                bail!("Example '{}': unterminated {{{{ in {:?}", name, text);
+            };
+            let parameter = rest[start + 2..start + end].trim();
+            match values.get(parameter) {
+                Some(value) => out.push_str(value),
+                None => bail!("Example '{}' uses undeclared parameter '{}'", name, parameter),
+            }
+            rest = &rest[start + end + 2..];
+        }
+        out.push_str(rest);
+        *text = out;
+        Ok(())
+    };
+    for app in &mut config.apps {
+        apply(&mut app.path)?;
+        if let Some(dir) = &mut app.dir {
+            apply(dir)?;
+        }
+        for arg in &mut app.args {
+            apply(arg)?;
+        }
+        for value in app.env.values_mut() {
+            apply(value)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,646 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Small regular expression matcher for log filtering.
+//!
+//! Supports literals, `.`, `^`, `$`, `\b`, classes (`[a-z]`, `[^0-9]`), escapes
+//! (`\d \w \s \D \W \S`, escaped metacharacters), groups (`(...)`, `(?:...)`),
+//! alternation `|`, the quantifiers `* + ? {n} {n,} {n,m}` and a leading `(?i)`
+//! for case-insensitive matching. Matching is leftmost, with greedy quantifiers.
+//!
+//! Patterns are compiled to a program for a Pike VM, which runs all alternatives
+//! in lockstep instead of backtracking. Matching therefore takes time linear in
+//! the length of the text and constant stack, however long the line of an app
+//! or however nested the quantifiers are.
+
+use anyhow::{bail, Context, Result};
+use std::fmt;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Char(char),
+    Any,
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    Start,
+    End,
+    WordBoundary,
+    Group(Vec<Vec<Node>>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+/// Instruction of a compiled pattern.
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    Start,
+    End,
+    WordBoundary,
+    /// Continues at both targets, preferring the first.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+/// Upper bound of the program size, which bounds the work per character.
+const MAX_PROGRAM_LEN: usize = 2000;
+
+#[derive(Clone)]
+pub struct Pattern {
+    source: String,
+    program: Vec<Inst>,
+    case_insensitive: bool,
+}
+
+impl fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Pattern({:?})", self.source)
+    }
+}
+
+impl Pattern {
+    pub fn new(source: &str) -> Result<Self> {
+        let (case_insensitive, body) = match source.strip_prefix("(?i)") {
+            Some(body) => (true, body),
+            None => (false, source),
+        };
+        let mut parser = Parser {
+            chars: body.chars().collect(),
+            pos: 0,
+        };
+        let alternatives = parser.alternatives()?;
+        if parser.pos < parser.chars.len() {
+            bail!("Invalid pattern {:?}: unbalanced ')'", source);
+        }
+        let mut compiler = Compiler { program: Vec::new() };
+        compiler
+            .alternatives(&alternatives)
+            .and_then(|()| compiler.push(Inst::Match))
+            .with_context(|| format!("Invalid pattern {:?}", source))?;
+        Ok(Pattern {
+            source: source.to_string(),
+            program: compiler.program,
+            case_insensitive,
+        })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    /// Byte range of the leftmost match in `text`.
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        let chars: Vec<char> = text.chars().collect();
+        let (start, end) = self.search(&chars, 0)?;
+        let offset = |i: usize| text.char_indices().nth(i).map_or(text.len(), |(o, _)| o);
+        Some((offset(start), offset(end)))
+    }
+
+    /// Character range of the leftmost match in `input` starting at or after `from`.
+    ///
+    /// Threads are kept in priority order, so the first thread to reach `Match`
+    /// wins over all later ones, as in a backtracking matcher.
+    fn search(&self, input: &[char], from: usize) -> Option<(usize, usize)> {
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut stack = Vec::new();
+        let mut matched = None;
+        for pos in from..=input.len() {
+            if matched.is_none() {
+                // A new thread for a match starting here, after all earlier starts.
+                self.add_thread(&mut current, &mut stack, input, 0, pos, pos);
+            }
+            if current.list.is_empty() {
+                if matched.is_some() {
+                    break;
+                }
+                // The seed may have failed an assertion after marking it.
+                current.clear();
+                continue;
+            }
+            for &(pc, start) in &current.list {
+                let c = input.get(pos).copied();
+                let step = match &self.program[pc] {
+                    Inst::Match => {
+                        matched = Some((start, pos));
+                        // Threads after this one have lower priority.
+                        break;
+                    }
+                    Inst::Char(expected) => c.is_some_and(|c| self.char_eq(c, *expected)),
+                    Inst::Any => c.is_some_and(|c| c != '\n'),
+                    Inst::Class { ranges, negated } => c.is_some_and(|c| self.in_class(c, ranges) != *negated),
+                    _ => unreachable!("only consuming instructions and Match are queued"),
+                };
+                if step {
+                    self.add_thread(&mut next, &mut stack, input, pc + 1, start, pos + 1);
+                }
+            }
+            std::mem::swap(&mut current, &mut next);
+            next.clear();
+        }
+        matched
+    }
+
+    /// Adds the thread at `pc` and everything reachable from it without consuming input.
+    fn add_thread(
+        &self,
+        threads: &mut Threads,
+        stack: &mut Vec<usize>,
+        input: &[char],
+        pc: usize,
+        start: usize,
+        pos: usize,
+    ) {
+        stack.push(pc);
+        while let Some(pc) = stack.pop() {
+            if std::mem::replace(&mut threads.seen[pc], true) {
+                continue;
+            }
+            match &self.program[pc] {
+                Inst::Jump(target) => stack.push(*target),
+                Inst::Split(first, second) => {
+                    // Popped in reverse order, so `first` is followed before `second`.
+                    stack.push(*second);
+                    stack.push(*first);
+                }
+                Inst::Start => {
+                    if pos == 0 {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::End => {
+                    if pos == input.len() {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::WordBoundary => {
+                    let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+                    let before = pos > 0 && input.get(pos - 1).is_some_and(is_word);
+                    let after = input.get(pos).is_some_and(is_word);
+                    if before != after {
+                        stack.push(pc + 1);
+                    }
+                }
+                _ => threads.list.push((pc, start)),
+            }
+        }
+    }
+
+    fn char_eq(&self, a: char, b: char) -> bool {
+        a == b || (self.case_insensitive && a.to_lowercase().eq(b.to_lowercase()))
+    }
+
+    fn in_class(&self, c: char, ranges: &[(char, char)]) -> bool {
+        let matches = |c: char| ranges.iter().any(|&(low, high)| low <= c && c <= high);
+        matches(c) || (self.case_insensitive && (c.to_lowercase().any(matches) || c.to_uppercase().any(matches)))
+    }
+
+    /// Replaces all non-overlapping matches in `text` with `replacement`.
+    pub fn replace_all(&self, text: &str, replacement: &str) -> String {
+        // Built once per text, as searches continue in character positions.
+        let offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).chain([text.len()]).collect();
+        let chars: Vec<char> = text.chars().collect();
+        let mut result = String::with_capacity(text.len());
+        let mut pos = 0;
+        while let Some((start, end)) = self.search(&chars, pos) {
+            result.push_str(&text[offsets[pos]..offsets[start]]);
+            result.push_str(replacement);
+            pos = end;
+            if end == start {
+                // Empty match: step over one character to make progress.
+                let Some(&c) = chars.get(end) else {
+                    return result;
+                };
+                result.push(c);
+                pos += 1;
+            }
+        }
+        result.push_str(&text[offsets[pos]..]);
+        result
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<Node>>> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node> {
+        let c = self.next().expect("caller checked for input");
+        Ok(match c {
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '(' => {
+                if self.chars[self.pos..].starts_with(&['?', ':']) {
+                    self.pos += 2;
+                }
+                let alternatives = self.alternatives()?;
+                if self.next() != Some(')') {
+                    bail!("Invalid pattern: missing ')'");
+                }
+                Node::Group(alternatives)
+            }
+            '[' => self.class()?,
+            '\\' => self.escape()?,
+            '*' | '+' | '?' | '{' => bail!("Invalid pattern: '{}' has nothing to repeat", c),
+            c => Node::Char(c),
+        })
+    }
+
+    fn escape(&mut self) -> Result<Node> {
+        let Some(c) = self.next() else {
+            bail!("Invalid pattern: trailing '\\'");
+        };
+        Ok(match c {
+            'd' | 'D' | 'w' | 'W' | 's' | 'S' => Node::Class {
+                ranges: class_ranges(c.to_ascii_lowercase()),
+                negated: c.is_ascii_uppercase(),
+            },
+            'b' => Node::WordBoundary,
+            'n' => Node::Char('\n'),
+            't' => Node::Char('\t'),
+            c => Node::Char(c),
+        })
+    }
+
+    fn class(&mut self) -> Result<Node> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let Some(c) = self.next() else {
+                bail!("Invalid pattern: missing ']'");
+            };
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let low = if c == '\\' {
+                match self.next() {
+                    Some(e @ ('d' | 'w' | 's')) => {
+                        ranges.extend(class_ranges(e));
+                        continue;
+                    }
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some(e) => e,
+                    None => bail!("Invalid pattern: missing ']'"),
+                }
+            } else {
+                c
+            };
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&n| n != ']') {
+                self.pos += 1;
+                let high = self.next().expect("checked above");
+                if high < low {
+                    bail!("Invalid pattern: range {}-{} out of order", low, high);
+                }
+                ranges.push((low, high));
+            } else {
+                ranges.push((low, low));
+            }
+        }
+        Ok(Node::Class { ranges, negated })
+    }
+
+    fn quantified(&mut self, atom: Node) -> Result<Node> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => return self.counted(atom),
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        self.repeat(atom, min, max)
+    }
+
+    fn counted(&mut self, atom: Node) -> Result<Node> {
+        let close = self.chars[self.pos..]
+            .iter()
+            .position(|&c| c == '}')
+            .map(|i| self.pos + i);
+        let Some(close) = close else {
+            bail!("Invalid pattern: missing '}}'");
+        };
+        let body: String = self.chars[self.pos + 1..close].iter().collect();
+        let parse = |s: &str| s.trim().parse::<usize>().ok();
+        let (min, max) = match body.split_once(',') {
+            None => (parse(&body), parse(&body)),
+            Some((min, "")) => (parse(min), None),
+            Some((min, max)) => (parse(min), parse(max)),
+        };
+        let Some(min) = min else {
+            bail!("Invalid pattern: bad repetition {{{}}}", body);
+        };
+        if max.is_some_and(|max| max < min) {
+            bail!("Invalid pattern: bad repetition {{{}}}", body);
+        }
+        self.pos = close + 1;
+        self.repeat(atom, min, max)
+    }
+
+    fn repeat(&mut self, atom: Node, min: usize, max: Option<usize>) -> Result<Node> {
+        if self.peek() == Some('?') {
+            bail!("Invalid pattern: lazy quantifiers are not supported");
+        }
+        if matches!(atom, Node::Start | Node::End | Node::WordBoundary) {
+            bail!("Invalid pattern: anchors cannot be repeated");
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        })
+    }
+}
+
+fn class_ranges(class: char) -> Vec<(char, char)> {
+    match class {
+        'd' => vec![('0', '9')],
+        'w' => vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')],
+        's' => vec![(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r'), ('\x0b', '\x0c')],
+        _ => Vec::new(),
+    }
+}
+
+/// Threads of the Pike VM at one position: (instruction, start of the match).
+struct Threads {
+    list: Vec<(usize, usize)>,
+    seen: Vec<bool>,
+}
+
+impl Threads {
+    fn new(len: usize) -> Self {
+        Threads {
+            list: Vec::new(),
+            seen: vec![false; len],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.list.clear();
+        self.seen.fill(false);
+    }
+}
+
+struct Compiler {
+    program: Vec<Inst>,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> Result<()> {
+        if self.program.len() >= MAX_PROGRAM_LEN {
+            bail!(
+                "more than {} instructions, use smaller repetition counts",
+                MAX_PROGRAM_LEN
+            );
+        }
+        self.program.push(inst);
+        Ok(())
+    }
+
+    /// Pushes a placeholder to be patched with [`Compiler::patch`].
+    fn hole(&mut self) -> Result<usize> {
+        self.push(Inst::Jump(usize::MAX))?;
+        Ok(self.program.len() - 1)
+    }
+
+    fn patch(&mut self, at: usize, inst: Inst) {
+        self.program[at] = inst;
+    }
+
+    fn alternatives(&mut self, alternatives: &[Vec<Node>]) -> Result<()> {
+        let mut exits = Vec::new();
+        for (i, sequence) in alternatives.iter().enumerate() {
+            let last = i + 1 == alternatives.len();
+            let split = if last { None } else { Some(self.hole()?) };
+            for node in sequence {
+                self.node(node)?;
+            }
+            if let Some(split) = split {
+                exits.push(self.hole()?);
+                let next = self.program.len();
+                self.patch(split, Inst::Split(split + 1, next));
+            }
+        }
+        let end = self.program.len();
+        for exit in exits {
+            self.patch(exit, Inst::Jump(end));
+        }
+        Ok(())
+    }
+
+    fn node(&mut self, node: &Node) -> Result<()> {
+        match node {
+            Node::Char(c) => self.push(Inst::Char(*c)),
+            Node::Any => self.push(Inst::Any),
+            Node::Class { ranges, negated } => self.push(Inst::Class {
+                ranges: ranges.clone(),
+                negated: *negated,
+            }),
+            Node::Start => self.push(Inst::Start),
+            Node::End => self.push(Inst::End),
+            Node::WordBoundary => self.push(Inst::WordBoundary),
+            Node::Group(alternatives) => self.alternatives(alternatives),
+            Node::Repeat { node, min, max } => self.repeat(node, *min, *max),
+        }
+    }
+
+    fn repeat(&mut self, node: &Node, min: usize, max: Option<usize>) -> Result<()> {
+        for _ in 0..min {
+            self.node(node)?;
+        }
+        match max {
+            None => {
+                // Greedy loop: prefer another repetition over leaving.
+                let split = self.hole()?;
+                self.node(node)?;
+                self.push(Inst::Jump(split))?;
+                let end = self.program.len();
+                self.patch(split, Inst::Split(split + 1, end));
+            }
+            Some(max) => {
+                let mut splits = Vec::new();
+                for _ in min..max {
+                    splits.push(self.hole()?);
+                    self.node(node)?;
+                }
+                let end = self.program.len();
+                for split in splits {
+                    self.patch(split, Inst::Split(split + 1, end));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pattern;
+
+    fn find(pattern: &str, text: &str) -> Option<(usize, usize)> {
+        Pattern::new(pattern).unwrap().find(text)
+    }
+
+    #[test]
+    fn literals_and_any() {
+        assert_eq!(find("error", "an error here"), Some((3, 8)));
+        assert_eq!(find("e.r", "beer"), Some((1, 4)));
+        assert_eq!(find("a.c", "a\nc"), None);
+        assert_eq!(find("", "abc"), Some((0, 0)));
+    }
+
+    #[test]
+    fn anchors_and_word_boundaries() {
+        assert!(Pattern::new("^ok$").unwrap().is_match("ok"));
+        assert!(!Pattern::new("^ok$").unwrap().is_match("not ok"));
+        assert_eq!(find(r"\bcat\b", "concat cat"), Some((7, 10)));
+        assert_eq!(find(r"\bcat\b", "concatenate"), None);
+    }
+
+    #[test]
+    fn classes_and_escapes() {
+        assert_eq!(find("[0-9]+", "pid 4711 up"), Some((4, 8)));
+        assert_eq!(find("[^a-z ]", "abc def!"), Some((7, 8)));
+        assert_eq!(find(r"\d\s\w", "x 1 a"), Some((2, 5)));
+        assert_eq!(find(r"\D+", "12ab34"), Some((2, 4)));
+        assert_eq!(find(r"[\d-]+", "tel 0711-42"), Some((4, 11)));
+        assert_eq!(find(r"\.\*", "a.*b"), Some((1, 3)));
+        assert_eq!(find("[]a]", "x]"), Some((1, 2)));
+    }
+
+    #[test]
+    fn groups_and_alternation() {
+        assert_eq!(find("warn|error", "an error"), Some((3, 8)));
+        assert_eq!(find("(?:ab)+c", "xababc"), Some((1, 6)));
+        assert_eq!(find("a(b|cd)e", "acde abe"), Some((0, 4)));
+        // Leftmost-first: the first alternative matching at a position wins.
+        assert_eq!(find("a|ab", "ab"), Some((0, 1)));
+    }
+
+    #[test]
+    fn quantifiers_are_greedy_and_backtrack() {
+        assert_eq!(find("a*", "aaab"), Some((0, 3)));
+        assert_eq!(find("a+b", "caaab"), Some((1, 5)));
+        assert_eq!(find("colou?r", "color"), Some((0, 5)));
+        assert_eq!(find(".*x", "axbxc"), Some((0, 4)));
+        assert_eq!(find("a{2}", "aaa"), Some((0, 2)));
+        assert_eq!(find("a{2,}", "aaaa"), Some((0, 4)));
+        assert_eq!(find("a{1,2}b", "aaab"), Some((1, 4)));
+        assert_eq!(find("(a*)*b", "aab"), Some((0, 3)));
+    }
+
+    #[test]
+    fn long_lines_and_nested_quantifiers_take_linear_time() {
+        // Each would exhaust the stack or backtrack for ages in a backtracking matcher.
+        let line = format!("a{}z", "b".repeat(200_000));
+        assert_eq!(find("a.*z", &line), Some((0, line.len())));
+        assert_eq!(find("a.*y", &line), None);
+        let token = format!("Bearer {}", "x".repeat(200_000));
+        assert_eq!(find("[Bb]earer [A-Za-z0-9._~+/-]+=*", &token), Some((0, token.len())));
+        let a = "a".repeat(10_000);
+        assert_eq!(find("(a*)*b", &a), None);
+        assert_eq!(find("(a|aa)+$", &a), Some((0, a.len())));
+        assert_eq!(find("^(a+)+b", &format!("{}!", a)), None);
+    }
+
+    #[test]
+    fn case_insensitive() {
+        assert_eq!(find("(?i)error", "ERROR: x"), Some((0, 5)));
+        assert_eq!(find("(?i)[a-c]+", "xABCd"), Some((1, 4)));
+        assert_eq!(find("error", "ERROR"), None);
+    }
+
+    #[test]
+    fn byte_ranges_of_non_ascii_text() {
+        assert_eq!(find("ü+", "grüüß"), Some((2, 6)));
+        assert_eq!(find("ß$", "grüüß"), Some((6, 8)));
+    }
+
+    #[test]
+    fn replace_all() {
+        let pattern = Pattern::new(r"\d+").unwrap();
+        assert_eq!(pattern.replace_all("a1b22c", "#"), "a#b#c");
+        assert_eq!(Pattern::new("x*").unwrap().replace_all("ab", "-"), "-a-b-");
+        assert_eq!(Pattern::new("ü").unwrap().replace_all("üaü", "u"), "uau");
+        let line = "ab ".repeat(100_000);
+        assert_eq!(Pattern::new("b").unwrap().replace_all(&line, ""), "a ".repeat(100_000));
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        for pattern in [
+            "(ab",
+            "ab)",
+            "[ab",
+            "*a",
+            "a{x}",
+            "a{3,1}",
+            "a*?",
+            "^*",
+            "[z-a]",
+            "a\\",
+            "(a{100}){100}",
+        ] {
+            assert!(Pattern::new(pattern).is_err(), "{:?} should be invalid", pattern);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Named environment presets shared by examples.
+//!
+//! Presets are defined in `*.presets.json` files next to the example configs,
+//! as an object mapping preset names to their environment.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::path::Path;
+
+use crate::ScoreConfig;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct EnvPreset {
+    #[serde(default)]
+    env: HashMap<String, String>, // variables set unless the app sets them itself
+    #[serde(default)]
+    path: Vec<String>, // directories prepended to PATH
+    #[serde(default)]
+    library_path: Vec<String>, // directories appended to the apps' `library_path`
+}
+
+impl EnvPreset {
+    pub(crate) fn env(&self) -> &HashMap<String, String> {
+        &self.env
+    }
+
+    pub(crate) fn path(&self) -> &[String] {
+        &self.path
+    }
+}
+
+pub type Presets = HashMap<String, EnvPreset>;
+
+pub fn is_presets_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(".presets.json"))
+}
+
+/// Adds the presets defined in the file `path` to `presets`.
+pub fn load(path: &Path, presets: &mut Presets) -> Result<()> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed reading {:?}", path))?;
+    let found: Presets = serde_json::from_str(&content).with_context(|| format!("Invalid presets in {:?}", path))?;
+    for (name, preset) in found {
+        if presets.contains_key(&name) {
+            bail!(
+                "Preset \"{}\" in {:?} is already defined in another presets file",
+                name,
+                path
+            );
+        }
+        presets.insert(name, preset);
+    }
+    Ok(())
+}
+
+/// Merges the preset of every example, or `selected` for all examples, into its apps.
+pub fn resolve(configs: &mut [ScoreConfig], presets: &Presets, selected: Option<&str>) -> Result<()> {
+    for config in configs.iter_mut() {
+        let Some(name) = selected.or(config.preset.as_deref()) else {
+            continue;
+        };
+        let Some(preset) = presets.get(name) else {
+            let mut available: Vec<&str> = presets.keys().map(String::as_str).collect();
+            available.sort();
+            bail!(
+                "Example '{}': unknown preset \"{}\". Available presets: {}",
+                config.name,
+                name,
+                if available.is_empty() {
+                    "none".to_string()
+                } else {
+                    available.join(", ")
+                }
+            );
+        };
+        for app in config.apps.iter_mut() {
+            for (key, value) in &preset.env {
+                app.env.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+            if !preset.path.is_empty() && !app.env.contains_key("PATH") {
+                let inherited = env::var("PATH")
+                    .ok()
+                    .filter(|_| app.inherits_env("PATH"))
+                    .unwrap_or_default();
+                let dirs = preset.path.iter().map(String::as_str).chain([inherited.as_str()]);
+                let path = dirs.filter(|d| !d.is_empty()).collect::<Vec<_>>().join(":");
+                app.env.insert("PATH".to_string(), path);
+            }
+            app.library_path.extend(preset.library_path.iter().cloned());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! CPU nice level and I/O priority of apps.
+
+use anyhow::{bail, Result};
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+use crate::sys;
+
+const IOPRIO_CLASS_RT: u32 = 1;
+const IOPRIO_CLASS_BE: u32 = 2;
+const IOPRIO_CLASS_IDLE: u32 = 3;
+
+/// Parses an I/O priority like `"idle"`, `"best-effort"`, `"best-effort:7"` or `"realtime:0"`
+/// into an ioprio class and level. Levels go from 0 (highest) to 7, best-effort defaults to 4.
+fn parse_ionice(spec: &str) -> Result<(u32, u32)> {
+    let (class, level) = match spec.split_once(':') {
+        Some((class, level)) => match level.trim().parse::<u32>() {
+            Ok(level) if level <= 7 => (class.trim(), Some(level)),
+            _ => bail!("invalid I/O priority level \"{}\", use 0 (highest) to 7", level),
+        },
+        None => (spec.trim(), None),
+    };
+    match class {
+        "realtime" => Ok((IOPRIO_CLASS_RT, level.unwrap_or(4))),
+        "best-effort" => Ok((IOPRIO_CLASS_BE, level.unwrap_or(4))),
+        "idle" if level.is_none() => Ok((IOPRIO_CLASS_IDLE, 0)),
+        "idle" => bail!("the idle I/O class has no level"),
+        _ => bail!("invalid I/O class \"{}\", use realtime, best-effort or idle", class),
+    }
+}
+
+/// Makes the app start with the given nice level and I/O priority.
+///
+/// Both are set in the forked child right before exec, so they also apply to
+/// wrappers like `ip netns exec` or valgrind and are inherited by the app.
+pub fn apply(cmd: &mut Command, nice: Option<i32>, ionice: Option<&str>) -> Result<()> {
+    if let Some(nice) = nice {
+        if !(-20..=19).contains(&nice) {
+            bail!("invalid nice level {}, use -20 (highest priority) to 19", nice);
+        }
+    }
+    let ionice = ionice.map(parse_ionice).transpose()?;
+    if ionice.is_some() && !sys::IO_PRIORITIES {
+        bail!("I/O priorities need Linux on an architecture whose ioprio_set syscall the runner knows");
+    }
+    if nice.is_none() && ionice.is_none() {
+        return Ok(());
+    }
+    // SAFETY: the closure only issues the setpriority / ioprio_set syscalls, which are async-signal-safe.
+    unsafe {
+        cmd.pre_exec(move || {
+            if let Some(nice) = nice {
+                sys::set_nice(nice)?;
+            }
+            if let Some((class, level)) = ionice {
+                sys::set_io_priority(class, level)?;
+            }
+            Ok(())
+        });
+    }
+    Ok(())
+}
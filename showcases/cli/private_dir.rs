@@ -0,0 +1,132 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Private per-user directories for state other users must not tamper with.
+//!
+//! Lock files, session files and the like live below `$XDG_RUNTIME_DIR/score-cli`,
+//! `/run/score-cli` for root, or else `$TMPDIR/score-cli-<uid>`. Larger scratch
+//! data, like bundles, always lives below `$TMPDIR/score-cli-<uid>`. Directories are
+//! created with mode 0700 and refused unless they are owned by the user and
+//! closed to others. Files in them are opened without following symlinks and
+//! refused unless the user owns them.
+
+use anyhow::{bail, Context, Result};
+use std::fs::{self, DirBuilder, File, OpenOptions};
+use std::io;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+
+use crate::sys;
+
+fn runtime_root() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("score-cli"),
+        _ if sys::effective_uid() == 0 && Path::new("/run").is_dir() => PathBuf::from("/run/score-cli"),
+        _ => temp_root(),
+    }
+}
+
+/// The private temporary directory of the user, `$TMPDIR/score-cli-<uid>`, not created.
+pub fn temp_root() -> PathBuf {
+    std::env::temp_dir().join(format!("score-cli-{}", sys::effective_uid()))
+}
+
+/// The private runtime directory `name`, e.g. `locks`, created if missing.
+pub fn runtime(name: &str) -> Result<PathBuf> {
+    let root = runtime_root();
+    ensure(&root)?;
+    let dir = root.join(name);
+    ensure(&dir)?;
+    Ok(dir)
+}
+
+/// The private temporary directory `name`, e.g. `bundles`, created if missing.
+pub fn temp(name: &str) -> Result<PathBuf> {
+    let root = temp_root();
+    ensure(&root)?;
+    let dir = root.join(name);
+    ensure(&dir)?;
+    Ok(dir)
+}
+
+/// Creates a new directory with mode 0700 below the private directory `parent`, like `mkdtemp`.
+pub fn fresh(parent: &Path, prefix: &str) -> Result<PathBuf> {
+    let mut attempt = 0;
+    loop {
+        let dir = parent.join(format!("{}-{}-{}", prefix, std::process::id(), attempt));
+        match DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(e).with_context(|| format!("Failed to create directory {:?}", dir)),
+        }
+    }
+}
+
+/// Creates `dir` with mode 0700, or checks that the existing one is private.
+pub fn ensure(dir: &Path) -> Result<()> {
+    match DirBuilder::new().mode(0o700).create(dir) {
+        Err(e) if e.kind() != io::ErrorKind::AlreadyExists => {
+            return Err(e).with_context(|| format!("Failed to create directory {:?}", dir));
+        }
+        _ => {}
+    }
+    let metadata = fs::symlink_metadata(dir).with_context(|| format!("Failed to inspect {:?}", dir))?;
+    if !metadata.is_dir() {
+        bail!("{:?} is not a directory, refusing to use it", dir);
+    }
+    check_owner(dir, &metadata)?;
+    if metadata.mode() & 0o077 != 0 {
+        bail!(
+            "{:?} is accessible by other users (mode {:o}), refusing to use it",
+            dir,
+            metadata.mode() & 0o777
+        );
+    }
+    Ok(())
+}
+
+/// Opens the file `path` for reading and writing, creating it with mode 0600.
+///
+/// Fails if `path` is a symlink or belongs to another user.
+pub fn open(path: &Path) -> Result<File> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o600)
+        .custom_flags(sys::O_NOFOLLOW)
+        .open(path)
+        .with_context(|| format!("Failed to open {:?}", path))?;
+    check_owner(path, &file.metadata()?)?;
+    Ok(file)
+}
+
+/// Checks that `path` is no symlink and belongs to the user.
+pub fn check_owned(path: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(path).with_context(|| format!("Failed to inspect {:?}", path))?;
+    if metadata.file_type().is_symlink() {
+        bail!("{:?} is a symlink, refusing to use it", path);
+    }
+    check_owner(path, &metadata)
+}
+
+fn check_owner(path: &Path, metadata: &fs::Metadata) -> Result<()> {
+    if metadata.uid() != sys::effective_uid() {
+        bail!(
+            "{:?} belongs to another user (uid {}), refusing to use it",
+            path,
+            metadata.uid()
+        );
+    }
+    Ok(())
+}
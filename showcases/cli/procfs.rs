@@ -0,0 +1,111 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Per-process resource usage read from `/proc`.
+//!
+//! Only the Linux `/proc` layout is supported. On other systems, QNX among them,
+//! all functions return `None`; features built on them check [`SUPPORTED`] and
+//! report themselves as unsupported instead of silently doing nothing.
+
+use serde::Serialize;
+use std::fs;
+use std::time::Duration;
+
+use crate::sys;
+
+/// Whether processes can be inspected on this platform.
+pub const SUPPORTED: bool = cfg!(target_os = "linux");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessSample {
+    pub elapsed_s: u64, // since the example started
+    pub rss_kb: u64,
+    pub cpu_s: f64, // user + system time consumed so far
+    pub fds: usize,
+}
+
+/// Start time of `pid` in clock ticks since boot, or `None` if the process is gone or a zombie.
+///
+/// Together with the pid it identifies a process, as pids are reused.
+pub fn start_time(pid: u32) -> Option<u64> {
+    if !SUPPORTED {
+        return None;
+    }
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    if *fields.first()? == "Z" {
+        return None;
+    }
+    fields.get(19)?.parse().ok()
+}
+
+/// Samples the resource usage of `pid`, or `None` if the process is gone.
+pub fn sample(pid: u32, elapsed: Duration) -> Option<ProcessSample> {
+    if !SUPPORTED {
+        return None;
+    }
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let rss_kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+        .unwrap_or(0);
+
+    // The command name in `stat` may contain spaces, so fields are counted after its closing parenthesis.
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+
+    let fds = fs::read_dir(format!("/proc/{}/fd", pid))
+        .map(|d| d.count())
+        .unwrap_or(0);
+
+    Some(ProcessSample {
+        elapsed_s: elapsed.as_secs(),
+        rss_kb,
+        cpu_s: (utime + stime) as f64 / sys::clock_ticks_per_second() as f64,
+        fds,
+    })
+}
+
+/// The user owning process `pid`, its effective uid.
+pub fn owner(pid: u32) -> Option<u32> {
+    if !SUPPORTED {
+        return None;
+    }
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    // "Uid: <real> <effective> <saved> <filesystem>"
+    let uids = status.lines().find_map(|line| line.strip_prefix("Uid:"))?;
+    uids.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// The file name of the executable of process `pid`, also if the file was replaced since.
+pub fn executable_name(pid: u32) -> Option<String> {
+    if !SUPPORTED {
+        return None;
+    }
+    let exe = fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+    let name = exe.file_name()?.to_str()?;
+    Some(name.strip_suffix(" (deleted)").unwrap_or(name).to_string())
+}
+
+/// The parent of process `pid`.
+pub fn parent(pid: u32) -> Option<u32> {
+    if !SUPPORTED {
+        return None;
+    }
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // "<pid> (<comm>) <state> <ppid> ..."
+    stat.rsplit_once(')')?.1.split_whitespace().nth(1)?.parse().ok()
+}
@@ -0,0 +1,194 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Progress display of runs with several examples.
+//!
+//! Instead of the output of every app, a single line on stderr shows the running
+//! example with a spinner, the overall position, the elapsed time and an ETA from
+//! the durations of earlier runs. Finished examples stay as one line each.
+
+use clap::ValueEnum;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::history;
+use crate::report::{ExampleReport, Status};
+
+const SPINNER: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// When to show progress instead of app output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProgressMode {
+    /// For runs of several examples on a terminal, not in CI
+    #[default]
+    Auto,
+    /// Always
+    Always,
+    /// Never, show all output
+    Never,
+}
+
+impl ProgressMode {
+    /// Whether a run of `examples` examples shows progress.
+    pub fn enabled(self, examples: usize) -> bool {
+        match self {
+            ProgressMode::Auto => {
+                examples > 1 && std::io::stdout().is_terminal() && std::io::stderr().is_terminal() && !in_ci()
+            }
+            ProgressMode::Always => true,
+            ProgressMode::Never => false,
+        }
+    }
+}
+
+/// Whether the runner runs in CI, where the full output belongs in the job log.
+///
+/// Most CI services set `CI`, e.g. GitHub Actions, GitLab CI and Buildkite.
+fn in_ci() -> bool {
+    std::env::var_os("CI").is_some_and(|value| !value.is_empty() && value != "false" && value != "0")
+}
+
+struct State {
+    names: Vec<String>,
+    expected: Vec<Option<Duration>>, // from earlier runs, per example
+    done: usize,
+    started: Instant,
+    current_started: Instant,
+}
+
+/// The progress line, cleared when this is dropped.
+pub struct Progress {
+    state: Arc<Mutex<State>>,
+    stop: Arc<AtomicBool>,
+    redraw: Option<JoinHandle<()>>,
+}
+
+impl Progress {
+    /// Starts showing progress of running the examples `names` in this order.
+    pub fn start(names: Vec<String>) -> Progress {
+        let history = history::expected_durations();
+        let expected = names.iter().map(|name| history.get(name).copied()).collect();
+        let now = Instant::now();
+        let state = Arc::new(Mutex::new(State {
+            names,
+            expected,
+            done: 0,
+            started: now,
+            current_started: now,
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+        let redraw = {
+            let (state, stop) = (state.clone(), stop.clone());
+            std::thread::spawn(move || {
+                let mut frame = 0;
+                while !stop.load(Ordering::Relaxed) {
+                    let line = state.lock().unwrap().line(SPINNER[frame % SPINNER.len()]);
+                    eprint!("\r\x1b[2K{}", line);
+                    let _ = std::io::stderr().flush();
+                    frame += 1;
+                    std::thread::sleep(REDRAW_INTERVAL);
+                }
+            })
+        };
+        Progress {
+            state,
+            stop,
+            redraw: Some(redraw),
+        }
+    }
+
+    /// Records that the next example started.
+    pub fn example_started(&self) {
+        self.state.lock().unwrap().current_started = Instant::now();
+    }
+
+    /// Replaces the progress line by the result of the finished example.
+    pub fn example_finished(&self, report: &ExampleReport) {
+        let mut state = self.state.lock().unwrap();
+        let icon = match report.status {
+            Status::Passed | Status::Xfail => "✅",
+            Status::Flaky | Status::Xpass => "⚠️ ",
+            Status::Failed => "❌",
+            Status::Skipped => "⏭ ",
+        };
+        eprintln!(
+            "\r\x1b[2K{} [{}/{}] {} {} in {}",
+            icon,
+            state.done + 1,
+            state.names.len(),
+            report.name,
+            report.status.name(),
+            clock(Duration::from_millis(report.duration_ms as u64))
+        );
+        state.done += 1;
+        state.current_started = Instant::now();
+    }
+}
+
+impl State {
+    fn line(&self, spinner: char) -> String {
+        let Some(name) = self.names.get(self.done) else {
+            return String::new();
+        };
+        let mut line = format!(
+            "{} [{}/{}] {} {} | elapsed {}",
+            spinner,
+            self.done + 1,
+            self.names.len(),
+            name,
+            clock(self.current_started.elapsed()),
+            clock(self.started.elapsed())
+        );
+        if let Some(eta) = self.eta() {
+            line.push_str(&format!(", ETA {}", clock(eta)));
+        }
+        line
+    }
+
+    /// Remaining time from the durations of earlier runs. Examples never run before count with the average.
+    fn eta(&self) -> Option<Duration> {
+        let known: Vec<Duration> = self.expected.iter().flatten().copied().collect();
+        if known.is_empty() {
+            return None;
+        }
+        let average = known.iter().sum::<Duration>() / known.len() as u32;
+        let mut expected = self.expected[self.done..]
+            .iter()
+            .map(|expected| expected.unwrap_or(average));
+        let current = expected.next()?.saturating_sub(self.current_started.elapsed());
+        Some(current + expected.sum::<Duration>())
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(redraw) = self.redraw.take() {
+            let _ = redraw.join();
+        }
+        eprint!("\r\x1b[2K");
+    }
+}
+
+/// Formats `duration` as `m:ss`, or `h:mm:ss` from an hour on.
+fn clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
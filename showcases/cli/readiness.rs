@@ -0,0 +1,186 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Readiness of apps and their start deadlines.
+//!
+//! An app is ready once its `ready` condition holds, or, for barrier apps
+//! without one, once it joined the start barrier. With a `start_timeout`, an app
+//! not ready in time fails the example with a startup error instead of hanging
+//! until an overall timeout; the deadline also runs while later apps of the
+//! start sequence wait for their `delay`.
+//!
+//! Ports are probed in the network namespace of the app's node, and relative
+//! file paths are taken relative to the app's `dir`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::net::{SocketAddr, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::barrier::Barrier;
+use crate::network;
+use crate::output::Capture;
+use crate::pattern::Pattern;
+use crate::sys;
+use crate::{AppConfig, ScoreConfig};
+
+/// How long a TCP readiness probe waits for the connection.
+const PORT_PROBE_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Condition an app meets once it finished starting up.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Ready {
+    /// A line of the app's output matches this regular expression
+    Log(String),
+    /// A TCP connection to this port on localhost succeeds, in the app's network node if it has one
+    Port(u16),
+    /// This file exists, e.g. a unix socket or a PID file, relative to the app's `dir`
+    File(String),
+}
+
+impl Ready {
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Ready::Log(pattern) => format!("output line matching \"{}\"", pattern),
+            Ready::Port(port) => format!("TCP port {}", port),
+            Ready::File(path) => format!("file {}", path),
+        }
+    }
+}
+
+/// Rejects invalid log patterns and start timeouts of apps without a readiness condition.
+pub fn validate(configs: &[ScoreConfig]) -> Result<()> {
+    for config in configs {
+        for (i, app) in config.apps.iter().enumerate() {
+            if let Some(Ready::Log(pattern)) = &app.ready {
+                Pattern::new(pattern)
+                    .with_context(|| format!("Example '{}', app {}: invalid ready log pattern", config.name, i + 1))?;
+            }
+            if app.start_timeout.is_some() && app.ready.is_none() && !app.barrier {
+                anyhow::bail!(
+                    "Example '{}', app {}: start_timeout needs a \"ready\" condition or \"barrier\": true",
+                    config.name,
+                    i + 1
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+enum Condition {
+    Ready(Ready),
+    Barrier,
+}
+
+struct Starting {
+    index: usize,
+    path: String,
+    condition: Condition,
+    namespace: Option<PathBuf>, // network namespace ports are probed in
+    timeout: Option<Duration>,
+    started: Instant,
+}
+
+/// Apps still starting up.
+#[derive(Default)]
+pub struct Startup {
+    starting: Vec<Starting>,
+}
+
+impl Startup {
+    /// Watches app `index` until it is ready. Call before attaching its output, so no line is missed.
+    pub fn watch(&mut self, index: usize, app: &AppConfig, timeout: Option<Duration>, capture: &Capture) -> Result<()> {
+        if let Some(Ready::Log(pattern)) = &app.ready {
+            let pattern = Pattern::new(pattern).with_context(|| format!("App {}: invalid ready log pattern", index))?;
+            capture.watch_for_line(index, pattern);
+        }
+        let condition = match &app.ready {
+            Some(Ready::File(path)) => {
+                let path = match &app.dir {
+                    Some(dir) => Path::new(dir).join(path),
+                    None => PathBuf::from(path),
+                };
+                Condition::Ready(Ready::File(path.display().to_string()))
+            }
+            Some(ready) => Condition::Ready(ready.clone()),
+            None if app.barrier => Condition::Barrier,
+            None => return Ok(()),
+        };
+        self.starting.push(Starting {
+            index,
+            path: app.path.clone(),
+            condition,
+            namespace: app.node.as_deref().map(network::namespace_path),
+            timeout,
+            started: Instant::now(),
+        });
+        Ok(())
+    }
+
+    /// Whether every watched app is ready.
+    pub fn is_done(&self) -> bool {
+        self.starting.is_empty()
+    }
+
+    /// Returns the apps that became ready since the last poll, or an error for an app past its start deadline.
+    pub fn poll(&mut self, capture: &Capture, barrier: Option<&Barrier>) -> Result<Vec<usize>> {
+        let mut ready = Vec::new();
+        let mut still_starting = Vec::new();
+        for app in std::mem::take(&mut self.starting) {
+            let is_ready = match &app.condition {
+                Condition::Ready(Ready::Log(_)) => capture.line_seen(app.index),
+                Condition::Ready(Ready::Port(port)) => match app.namespace {
+                    Some(ref namespace) => {
+                        sys::in_network_namespace(namespace, || port_open(*port)).with_context(|| {
+                            format!("App {}: failed to probe port {} in {:?}", app.index, port, namespace)
+                        })?
+                    }
+                    None => port_open(*port),
+                },
+                Condition::Ready(Ready::File(path)) => Path::new(path).exists(),
+                Condition::Barrier => barrier.is_some_and(|barrier| !barrier.missing().contains(&app.index)),
+            };
+            if is_ready {
+                ready.push(app.index);
+            } else {
+                still_starting.push(app);
+            }
+        }
+        self.starting = still_starting;
+        for app in &self.starting {
+            if let Some(timeout) = app.timeout {
+                if app.started.elapsed() > timeout {
+                    let waited_for = match &app.condition {
+                        Condition::Ready(ready) => ready.describe(),
+                        Condition::Barrier => "joining the start barrier".to_string(),
+                    };
+                    anyhow::bail!(
+                        "App {} ({}) failed to become ready within {}s (waited for {})",
+                        app.index,
+                        app.path,
+                        timeout.as_secs(),
+                        waited_for
+                    );
+                }
+            }
+        }
+        Ok(ready)
+    }
+}
+
+/// Whether a TCP connection to `port` on localhost succeeds.
+fn port_open(port: u16) -> bool {
+    TcpStream::connect_timeout(&SocketAddr::from(([127, 0, 0, 1], port)), PORT_PROBE_TIMEOUT).is_ok()
+}
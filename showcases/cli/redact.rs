@@ -0,0 +1,120 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Removal of sensitive values from everything the runner displays or persists.
+//!
+//! Secret values are always masked; redaction rules additionally replace
+//! everything matching a regular expression, e.g. VINs or MAC addresses.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::pattern::Pattern;
+
+/// Replacement of redacted values.
+pub const MASK: &str = "***";
+
+/// Rules available by name: (name, pattern, replacement).
+const BUILTIN_RULES: &[(&str, &str, &str)] = &[
+    ("vin", r"\b[A-HJ-NPR-Z0-9]{17}\b", "<vin>"),
+    ("mac-address", r"\b[0-9A-Fa-f]{2}([:-][0-9A-Fa-f]{2}){5}\b", "<mac>"),
+    (
+        "token",
+        r"[Bb]earer [A-Za-z0-9._~+/-]+=*|\beyJ[A-Za-z0-9_-]*\.[A-Za-z0-9_-]*\.[A-Za-z0-9_-]*",
+        "<token>",
+    ),
+];
+
+/// A redaction rule of an example: either a built-in rule or an own pattern.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RedactRule {
+    builtin: Option<String>,     // "vin", "mac-address" or "token"
+    pattern: Option<String>,     // regular expression of the text to replace
+    replacement: Option<String>, // default: "***", or "<vin>" etc. for built-in rules
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Pattern,
+    replacement: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    secrets: Vec<String>,
+    rules: Vec<Rule>,
+}
+
+impl Redactor {
+    /// Adds secret values, which are masked wherever they appear.
+    pub fn add_secrets(&mut self, values: impl IntoIterator<Item = String>) {
+        self.secrets.extend(values.into_iter().filter(|v| !v.is_empty()));
+        // Longest first, so a secret containing another one is masked as a whole.
+        self.secrets.sort_by_key(|v| std::cmp::Reverse(v.len()));
+        self.secrets.dedup();
+    }
+
+    pub fn add_rule(&mut self, rule: &RedactRule) -> Result<()> {
+        let (pattern, default_replacement) = match (&rule.builtin, &rule.pattern) {
+            (Some(name), None) => {
+                let (_, pattern, replacement) = builtin(name)?;
+                (pattern, replacement)
+            }
+            (None, Some(pattern)) => (pattern.as_str(), MASK),
+            _ => bail!("A redaction rule needs either \"builtin\" or \"pattern\""),
+        };
+        self.rules.push(Rule {
+            pattern: Pattern::new(pattern).with_context(|| format!("Invalid redaction rule {:?}", pattern))?,
+            replacement: rule.replacement.as_deref().unwrap_or(default_replacement).to_string(),
+        });
+        Ok(())
+    }
+
+    /// Adds a rule given on the command line: the name of a built-in rule or a pattern.
+    pub fn add_cli_rule(&mut self, spec: &str) -> Result<()> {
+        let is_builtin = BUILTIN_RULES.iter().any(|(name, _, _)| *name == spec);
+        self.add_rule(&RedactRule {
+            builtin: is_builtin.then(|| spec.to_string()),
+            pattern: (!is_builtin).then(|| spec.to_string()),
+            replacement: None,
+        })
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for secret in &self.secrets {
+            if text.contains(secret.as_str()) {
+                text = text.replace(secret.as_str(), MASK);
+            }
+        }
+        for rule in &self.rules {
+            text = rule.pattern.replace_all(&text, &rule.replacement);
+        }
+        text
+    }
+}
+
+fn builtin(name: &str) -> Result<(&'static str, &'static str, &'static str)> {
+    match BUILTIN_RULES.iter().find(|(builtin, _, _)| *builtin == name) {
+        Some(rule) => Ok(*rule),
+        None => bail!(
+            "Unknown built-in redaction rule \"{}\", available: {}",
+            name,
+            BUILTIN_RULES
+                .iter()
+                .map(|(name, _, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
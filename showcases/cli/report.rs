@@ -0,0 +1,261 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Machine-readable report of a run, written as `report.json` into the run directory.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub use crate::cgroup::CgroupUsage;
+pub use crate::host::HostInfo;
+pub use crate::instrument::Instrument;
+pub use crate::kpi::KpiResult;
+pub use crate::observe::Observation;
+pub use crate::output::FailureContext;
+pub use crate::procfs::ProcessSample;
+pub use crate::sbom::Component;
+
+#[derive(Debug, Serialize)]
+pub struct RunReport {
+    #[serde(skip)]
+    pub run_dir: PathBuf, // logs and reports of the run
+    pub run_id: String,
+    pub seed: u64,
+    pub host: HostInfo,
+    pub instrument: Option<Instrument>,
+    pub coverage: Option<PathBuf>, // merged coverage profile
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sboms: Vec<PathBuf>, // SBOMs the components of the apps were taken from
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>, // problems of the run itself, like logging paused on a full disk
+    pub examples: Vec<ExampleReport>,
+}
+
+/// Result of an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Passed,  // all apps exited successfully
+    Flaky,   // failed, but passed when retried
+    Failed,  // an app failed
+    Skipped, // not run: host requirements unmet or filtered out
+    Xfail,   // failed, but marked as known to fail
+    Xpass,   // passed although marked as known to fail
+}
+
+impl Status {
+    /// Name of the status as written to `report.json`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Passed => "passed",
+            Status::Flaky => "flaky",
+            Status::Failed => "failed",
+            Status::Skipped => "skipped",
+            Status::Xfail => "xfail",
+            Status::Xpass => "xpass",
+        }
+    }
+
+    /// Result of an example whose apps all succeeded (`passed`) or not, given whether it is marked xfail.
+    pub fn of(passed: bool, xfail: bool) -> Self {
+        match (passed, xfail) {
+            (true, false) => Status::Passed,
+            (false, false) => Status::Failed,
+            (false, true) => Status::Xfail,
+            (true, true) => Status::Xpass,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExampleReport {
+    pub name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requirements: Vec<String>, // verified by the example, when it passed
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>, // why the example was skipped, its known issue when marked xfail, its passing attempt, or why it could not run
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub infra_error: bool, // the runner could not run the example, `reason` has its error
+    #[serde(skip_serializing_if = "is_one")]
+    pub attempts: u32, // attempts made with retries, the report describes the last one
+    pub duration_ms: u128,
+    pub apps: Vec<AppReport>,
+    pub first_failure: Option<FailureContext>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<ExampleReport>, // reports of the steps of a composite example
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<CgroupUsage>, // resources of all processes of the example, from its cgroup
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub kpis: Vec<KpiResult>, // the KPIs declared by the example, checked against its metrics
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppReport {
+    pub index: usize,
+    pub path: String,
+    pub exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stopped: bool, // stopped by the runner, so its exit status is no failure
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub oom_killed: bool, // killed by the kernel's OOM killer, `samples` has its memory before
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub oom_unknown: bool, // killed by SIGKILL, without a cgroup telling whether by the OOM killer
+    pub duration_ms: u128, // from spawn, or the last restart, to exit
+    pub seed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<Component>, // the app binary as listed in an SBOM
+    pub instrumentation: Option<InstrumentationReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub observation: Option<Observation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub samples: Vec<ProcessSample>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Findings parsed from the output of a memory checker / sanitizer.
+#[derive(Debug, Serialize)]
+pub struct InstrumentationReport {
+    pub tool: Instrument,
+    pub errors: u64,
+    pub leaked_bytes: u64,
+    pub findings: Vec<String>,
+    pub logs: Vec<PathBuf>,
+}
+
+impl ExampleReport {
+    /// Report of an example that did not run.
+    pub fn skipped(name: &str, requirements: &[String], reason: String) -> Self {
+        ExampleReport {
+            name: name.to_string(),
+            requirements: requirements.to_vec(),
+            status: Status::Skipped,
+            reason: Some(reason),
+            infra_error: false,
+            attempts: 1,
+            duration_ms: 0,
+            apps: Vec::new(),
+            first_failure: None,
+            steps: Vec::new(),
+            cgroup: None,
+            kpis: Vec::new(),
+        }
+    }
+
+    /// An example the runner failed to run, with `--keep-going`.
+    pub fn errored(name: &str, requirements: &[String], error: &anyhow::Error) -> Self {
+        ExampleReport {
+            status: Status::Failed,
+            reason: Some(format!("{:#}", error)),
+            infra_error: true,
+            ..ExampleReport::skipped(name, requirements, String::new())
+        }
+    }
+}
+
+impl RunReport {
+    /// Number of examples with the given status.
+    pub fn count(&self, status: Status) -> usize {
+        self.examples.iter().filter(|e| e.status == status).count()
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content).with_context(|| format!("Failed to write run report {:?}", path))
+    }
+}
+
+impl RunReport {
+    /// Table of the examples that ran and their apps, with results and durations.
+    pub fn summary(&self) -> String {
+        let mut rows = vec![[
+            "Example / app".to_string(),
+            "Result".to_string(),
+            "Duration".to_string(),
+        ]];
+        for example in self
+            .examples
+            .iter()
+            .filter(|e| e.reason.as_deref() != Some("filtered out"))
+        {
+            example.summary_rows(0, &mut rows);
+        }
+        let widths: Vec<usize> = (0..3)
+            .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+        let mut out = String::new();
+        for (i, row) in rows.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{:<w0$}  {:<w1$}  {:>w2$}",
+                row[0],
+                row[1],
+                row[2],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            );
+            if i == 0 {
+                let _ = writeln!(out, "{}", "-".repeat(widths.iter().sum::<usize>() + 4));
+            }
+        }
+        out
+    }
+}
+
+impl ExampleReport {
+    fn summary_rows(&self, depth: usize, rows: &mut Vec<[String; 3]>) {
+        let indent = "  ".repeat(depth);
+        rows.push([
+            format!("{}{}", indent, self.name),
+            self.status.name().to_string(),
+            format!("{:.2}s", self.duration_ms as f64 / 1000.0),
+        ]);
+        for step in &self.steps {
+            step.summary_rows(depth + 1, rows);
+        }
+        for app in &self.apps {
+            let result = match (app.stopped, app.exit_code) {
+                (true, _) => "stopped".to_string(),
+                (false, Some(code)) => format!("exit {}", code),
+                (false, None) if app.oom_killed => "OOM-killed".to_string(),
+                (false, None) if app.oom_unknown => "SIGKILL, OOM unknown".to_string(),
+                (false, None) => "killed by signal".to_string(),
+            };
+            rows.push([
+                format!("{}  {}: {}", indent, app.index, app.path),
+                result,
+                format!("{:.2}s", app.duration_ms as f64 / 1000.0),
+            ]);
+        }
+        for kpi in &self.kpis {
+            rows.push([
+                format!("{}  KPI {}", indent, kpi.describe()),
+                match (kpi.passed, &kpi.skipped) {
+                    (true, _) => "met",
+                    (false, Some(_)) => "skipped",
+                    (false, None) => "missed",
+                }
+                .to_string(),
+                String::new(),
+            ]);
+        }
+    }
+}
+
+fn is_one(value: &u32) -> bool {
+    *value == 1
+}
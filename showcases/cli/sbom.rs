@@ -0,0 +1,296 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Software bill of materials of the apps in run reports.
+//!
+//! SBOMs are looked up in the examples directories and the directories holding
+//! them: SPDX 2 JSON files named `*.spdx.json` and CycloneDX JSON files named
+//! `*.cdx.json` or `bom.json`. Every app binary of the report is resolved against
+//! their packages, files and components, by SHA-256 sum where the SBOM lists
+//! sums and by file name otherwise. The component name, version and license
+//! found are added to the app in the report, the SBOMs used to the run.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::host::find_in_path;
+use crate::manifest;
+use crate::report::{ExampleReport, RunReport};
+
+/// The component of an app binary, as listed in an SBOM.
+#[derive(Debug, Clone, Serialize)]
+pub struct Component {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>, // SPDX license expression
+    pub matched_by: &'static str, // "sha256" or "file name"
+}
+
+/// A package, file or component of an SBOM.
+struct Entry {
+    name: String,
+    version: Option<String>,
+    license: Option<String>,
+    sha256: Vec<String>,     // lower case hex
+    file_names: Vec<String>, // file names without directories matching the entry
+}
+
+/// Adds the SBOM components of the app binaries to `report`, if SBOMs are found next to `roots`.
+pub fn enrich(report: &mut RunReport, roots: &[PathBuf]) {
+    let files = find(roots);
+    if files.is_empty() {
+        return;
+    }
+    let mut entries = Vec::new();
+    for file in &files {
+        match read(file) {
+            Ok(found) => entries.extend(found),
+            Err(e) => eprintln!("⚠️  Ignoring SBOM {}: {:#}", file.display(), e),
+        }
+    }
+    report.sboms = files;
+
+    let mut binaries: HashMap<String, Option<PathBuf>> = HashMap::new();
+    visit_apps(&mut report.examples, &mut |path, _| {
+        binaries.entry(path.to_string()).or_insert_with(|| resolve(path));
+    });
+    let sums = if entries.iter().any(|entry| !entry.sha256.is_empty()) {
+        let mut files: Vec<PathBuf> = binaries.values().flatten().cloned().collect();
+        files.sort();
+        files.dedup();
+        manifest::sha256(&files).unwrap_or_else(|e| {
+            eprintln!("⚠️  Failed to compute the checksums of the app binaries: {:#}", e);
+            Default::default()
+        })
+    } else {
+        Default::default()
+    };
+    visit_apps(&mut report.examples, &mut |path, component| {
+        let Some(binary) = binaries.get(path).cloned().flatten() else {
+            return;
+        };
+        *component = lookup(&entries, &binary, sums.get(&binary));
+    });
+}
+
+/// Whether an SBOM below `roots` lists checksums, which are then compared with the app binaries.
+pub(crate) fn has_checksums(roots: &[PathBuf]) -> bool {
+    find(roots)
+        .iter()
+        .filter_map(|file| read(file).ok())
+        .flatten()
+        .any(|entry| !entry.sha256.is_empty())
+}
+
+fn visit_apps(examples: &mut [ExampleReport], visit: &mut impl FnMut(&str, &mut Option<Component>)) {
+    for example in examples {
+        for app in &mut example.apps {
+            visit(&app.path, &mut app.component);
+        }
+        visit_apps(&mut example.steps, visit);
+    }
+}
+
+/// The file an app path names, looked up in `PATH` if it has no directory.
+fn resolve(path: &str) -> Option<PathBuf> {
+    let file = if path.contains('/') {
+        PathBuf::from(path)
+    } else {
+        find_in_path(path)?
+    };
+    file.is_file().then(|| fs::canonicalize(&file).unwrap_or(file))
+}
+
+fn lookup(entries: &[Entry], binary: &Path, sha256: Option<&String>) -> Option<Component> {
+    let component = |entry: &Entry, matched_by| Component {
+        name: entry.name.clone(),
+        version: entry.version.clone(),
+        license: entry.license.clone(),
+        matched_by,
+    };
+    if let Some(sum) = sha256 {
+        if let Some(entry) = entries.iter().find(|entry| entry.sha256.contains(sum)) {
+            return Some(component(entry, "sha256"));
+        }
+    }
+    let name = binary.file_name()?.to_str()?;
+    let entry = entries
+        .iter()
+        .find(|entry| entry.file_names.iter().any(|n| n == name))?;
+    Some(component(entry, "file name"))
+}
+
+/// SBOM files in the examples directories and their parents.
+fn find(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        let root = fs::canonicalize(root).unwrap_or_else(|_| root.clone());
+        dirs.extend(root.parent().map(Path::to_path_buf));
+        dirs.push(root);
+    }
+    dirs.dedup();
+    let mut files = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+                path.is_file() && (name.ends_with(".spdx.json") || name.ends_with(".cdx.json") || name == "bom.json")
+            })
+            .collect();
+        found.sort();
+        files.extend(found);
+    }
+    files
+}
+
+fn read(file: &Path) -> Result<Vec<Entry>> {
+    let content = fs::read_to_string(file).with_context(|| format!("Failed reading {:?}", file))?;
+    let document: Value = serde_json::from_str(&content).context("invalid JSON")?;
+    if document.get("spdxVersion").is_some() {
+        Ok(spdx(&document))
+    } else if document["bomFormat"] == "CycloneDX" {
+        let mut entries = Vec::new();
+        cyclonedx(&document["components"], None, &mut entries);
+        Ok(entries)
+    } else {
+        anyhow::bail!("neither an SPDX nor a CycloneDX document")
+    }
+}
+
+/// Packages of an SPDX document, with the files they contain, and files outside packages.
+fn spdx(document: &Value) -> Vec<Entry> {
+    let text = |value: &Value| {
+        value
+            .as_str()
+            .filter(|text| !["NOASSERTION", "NONE", ""].contains(text))
+            .map(str::to_string)
+    };
+    let sha256 = |item: &Value| -> Vec<String> {
+        let checksums = item["checksums"].as_array().into_iter().flatten();
+        checksums
+            .filter(|checksum| checksum["algorithm"] == "SHA256")
+            .filter_map(|checksum| checksum["checksumValue"].as_str())
+            .map(str::to_ascii_lowercase)
+            .collect()
+    };
+    let file_name = |path: &str| Path::new(path).file_name().map(|n| n.to_string_lossy().into_owned());
+
+    let mut package_of_file: HashMap<&str, &str> = HashMap::new();
+    for relationship in document["relationships"].as_array().into_iter().flatten() {
+        let (Some(from), Some(to)) = (
+            relationship["spdxElementId"].as_str(),
+            relationship["relatedSpdxElement"].as_str(),
+        ) else {
+            continue;
+        };
+        match relationship["relationshipType"].as_str() {
+            Some("CONTAINS") => package_of_file.insert(to, from),
+            Some("CONTAINED_BY") => package_of_file.insert(from, to),
+            _ => None,
+        };
+    }
+    let packages: Vec<&Value> = document["packages"].as_array().into_iter().flatten().collect();
+    for package in &packages {
+        let (Some(id), Some(files)) = (package["SPDXID"].as_str(), package["hasFiles"].as_array()) else {
+            continue;
+        };
+        for file in files.iter().filter_map(Value::as_str) {
+            package_of_file.insert(file, id);
+        }
+    }
+    let package = |id: &str| packages.iter().find(|package| package["SPDXID"] == id).copied();
+    let license = |item: &Value| text(&item["licenseConcluded"]).or_else(|| text(&item["licenseDeclared"]));
+
+    let mut entries = Vec::new();
+    for file in document["files"].as_array().into_iter().flatten() {
+        let id = file["SPDXID"].as_str().unwrap_or_default();
+        let owner = package_of_file.get(id).and_then(|id| package(id));
+        let path = file["fileName"].as_str().unwrap_or_default();
+        entries.push(Entry {
+            name: owner
+                .and_then(|package| text(&package["name"]))
+                .or_else(|| file_name(path))
+                .unwrap_or_default(),
+            version: owner.and_then(|package| text(&package["versionInfo"])),
+            license: license(file).or_else(|| owner.and_then(license)),
+            sha256: sha256(file),
+            file_names: file_name(path).into_iter().collect(),
+        });
+    }
+    for package in packages {
+        let Some(name) = text(&package["name"]) else {
+            continue;
+        };
+        entries.push(Entry {
+            version: text(&package["versionInfo"]),
+            license: license(package),
+            sha256: sha256(package),
+            file_names: vec![name.clone()],
+            name,
+        });
+    }
+    entries
+}
+
+/// Components of a CycloneDX document, including nested ones. Files nested in a component are listed as part of it.
+fn cyclonedx(components: &Value, parent: Option<&Entry>, entries: &mut Vec<Entry>) {
+    for component in components.as_array().into_iter().flatten() {
+        let Some(name) = component["name"].as_str() else {
+            continue;
+        };
+        let licenses: Vec<&str> = component["licenses"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|license| {
+                license["expression"]
+                    .as_str()
+                    .or_else(|| license["license"]["id"].as_str())
+                    .or_else(|| license["license"]["name"].as_str())
+            })
+            .collect();
+        let hashes = component["hashes"].as_array().into_iter().flatten();
+        // Components of type "file" are often named by their path.
+        let file_name = Path::new(name)
+            .file_name()
+            .map_or(name.to_string(), |n| n.to_string_lossy().into_owned());
+        let mut entry = Entry {
+            name: name.to_string(),
+            version: component["version"].as_str().map(str::to_string),
+            license: (!licenses.is_empty()).then(|| licenses.join(" AND ")),
+            sha256: hashes
+                .filter(|hash| hash["alg"] == "SHA-256")
+                .filter_map(|hash| hash["content"].as_str())
+                .map(str::to_ascii_lowercase)
+                .collect(),
+            file_names: vec![file_name],
+        };
+        if let Some(parent) = parent.filter(|_| component["type"] == "file") {
+            entry.name = parent.name.clone();
+            entry.version = entry.version.or_else(|| parent.version.clone());
+            entry.license = entry.license.or_else(|| parent.license.clone());
+        }
+        cyclonedx(&component["components"], Some(&entry), entries);
+        entries.push(entry);
+    }
+}
@@ -0,0 +1,211 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Screen capture of GUI examples, so reviewers of CI runs can see what a
+//! visualization rendered.
+//!
+//! With `--capture-screen`, examples tagged `gui` are captured from before
+//! their first app starts until their apps exited, into `screen/` in the
+//! example's artifacts directory: periodic screenshots `shot-0001.png`, ... or
+//! a recording `screen.mp4`. X11 displays, including the virtual display of
+//! headless apps, are captured with `ffmpeg`, Wayland displays with
+//! `wf-recorder` (video) and `grim` (screenshots). A missing tool or display
+//! is a warning, the example runs anyway.
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::{host, sys};
+
+/// Tag of the examples captured.
+pub const GUI_TAG: &str = "gui";
+/// Frame rate of recordings, enough to follow a visualization at a small file size.
+const VIDEO_FRAME_RATE: &str = "10";
+/// Time a recorder has to finish its file once asked to stop.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ScreenCapture {
+    /// A screenshot every --screenshot-interval
+    Screenshots,
+    /// A screen recording
+    Video,
+}
+
+/// Parses `--screenshot-interval`, a duration of at least a second.
+pub(crate) fn parse_interval(value: &str) -> Result<Duration, String> {
+    let interval = crate::parse_duration(value)?;
+    if interval.is_zero() {
+        return Err("the interval between screenshots has to be at least 1s".to_string());
+    }
+    Ok(interval)
+}
+
+/// A running capture, stopped by [`Recorder::finish`] or when dropped.
+pub(crate) struct Recorder {
+    dir: PathBuf,
+    backend: Backend,
+}
+
+enum Backend {
+    Process(Child),                                  // ffmpeg or wf-recorder, finishing its file on SIGINT
+    Thread(Arc<AtomicBool>, Option<JoinHandle<()>>), // grim called periodically until the flag is set
+}
+
+impl Recorder {
+    /// Starts capturing `display` (an X11 `DISPLAY`, or the session's if `None`) into `screen/` below `example_dir`.
+    ///
+    /// Returns `None` after printing why if the screen cannot be captured, or fails with `strict`.
+    pub(crate) fn start(
+        mode: ScreenCapture,
+        interval: Duration,
+        display: Option<&str>,
+        example_dir: &Path,
+        strict: bool,
+    ) -> Result<Option<Recorder>> {
+        match Self::try_start(mode, interval, display, example_dir) {
+            Ok(recorder) => {
+                println!("Capturing the screen into {}", recorder.dir.display());
+                Ok(Some(recorder))
+            }
+            Err(e) if strict => Err(e.context("Cannot capture the screen (--strict)")),
+            Err(e) => {
+                println!("⚠️  Not capturing the screen: {:#}", e);
+                Ok(None)
+            }
+        }
+    }
+
+    fn try_start(
+        mode: ScreenCapture,
+        interval: Duration,
+        display: Option<&str>,
+        example_dir: &Path,
+    ) -> Result<Recorder> {
+        let x11 = display
+            .map(str::to_string)
+            .or_else(|| env::var("DISPLAY").ok().filter(|display| !display.is_empty()));
+        let wayland = env::var_os("WAYLAND_DISPLAY").is_some_and(|display| !display.is_empty());
+        let dir = example_dir.join("screen");
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let backend = match (x11, wayland, mode) {
+            (Some(display), _, mode) => {
+                require("ffmpeg", "ffmpeg")?;
+                let mut cmd = Command::new("ffmpeg");
+                cmd.args(["-nostdin", "-loglevel", "error", "-y", "-f", "x11grab"]);
+                match mode {
+                    ScreenCapture::Video => {
+                        cmd.args(["-framerate", VIDEO_FRAME_RATE, "-i", &display]);
+                        cmd.args(["-c:v", "libx264", "-preset", "ultrafast", "-pix_fmt", "yuv420p"]);
+                        cmd.arg(dir.join("screen.mp4"));
+                    }
+                    ScreenCapture::Screenshots => {
+                        cmd.args(["-framerate", "1", "-i", &display]);
+                        cmd.args(["-vf", &format!("fps=1/{}", interval.as_secs_f64())]);
+                        cmd.arg(dir.join("shot-%04d.png"));
+                    }
+                }
+                Backend::Process(spawn(cmd)?)
+            }
+            (None, true, ScreenCapture::Video) => {
+                require("wf-recorder", "wf-recorder")?;
+                let mut cmd = Command::new("wf-recorder");
+                cmd.arg("-f").arg(dir.join("screen.mp4"));
+                Backend::Process(spawn(cmd)?)
+            }
+            (None, true, ScreenCapture::Screenshots) => {
+                require("grim", "grim")?;
+                let stop = Arc::new(AtomicBool::new(false));
+                let handle = std::thread::spawn({
+                    let stop = stop.clone();
+                    let dir = dir.clone();
+                    move || take_screenshots(&dir, interval, &stop)
+                });
+                Backend::Thread(stop, Some(handle))
+            }
+            (None, false, _) => bail!("no display to capture, neither DISPLAY nor WAYLAND_DISPLAY is set"),
+        };
+        Ok(Recorder { dir, backend })
+    }
+
+    /// Stops capturing and returns the files written.
+    pub(crate) fn finish(mut self) -> Vec<PathBuf> {
+        self.stop();
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn stop(&mut self) {
+        match self.backend {
+            Backend::Process(ref mut child) => {
+                let _ = sys::stop(child, sys::Signal::INT, STOP_GRACE);
+            }
+            Backend::Thread(ref stop, ref mut handle) => {
+                stop.store(true, Ordering::SeqCst);
+                if let Some(handle) = handle.take() {
+                    let _ = handle.join();
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn require(program: &str, package: &str) -> Result<()> {
+    if host::find_in_path(program).is_none() {
+        bail!("{} not found in PATH, install the {} package", program, package);
+    }
+    Ok(())
+}
+
+fn spawn(mut cmd: Command) -> Result<Child> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to start {:?}", cmd.get_program()))
+}
+
+/// Calls `grim` every `interval` until `stop` is set.
+fn take_screenshots(dir: &Path, interval: Duration, stop: &AtomicBool) {
+    let mut number = 1;
+    while !stop.load(Ordering::SeqCst) {
+        let file = dir.join(format!("shot-{:04}.png", number));
+        if Command::new("grim").arg(&file).stdout(Stdio::null()).status().is_ok() {
+            number += 1;
+        }
+        // Sleep in small steps, so stopping does not wait for a whole interval.
+        let mut slept = Duration::ZERO;
+        while slept < interval && !stop.load(Ordering::SeqCst) {
+            let step = Duration::from_millis(100).min(interval - slept);
+            std::thread::sleep(step);
+            slept += step;
+        }
+    }
+}
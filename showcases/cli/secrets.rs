@@ -0,0 +1,72 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Secrets injected into app environments without being stored in config files.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Where the value of a secret comes from, resolved when the example starts.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum SecretSource {
+    #[serde(rename = "from_env")]
+    Env(String), // environment variable of the CLI
+    #[serde(rename = "from_file")]
+    File(PathBuf), // file content, without the trailing newline
+    #[serde(rename = "from_command")]
+    Command(String), // stdout of a shell command, e.g. "pass show demo/mqtt"
+}
+
+impl SecretSource {
+    fn resolve(&self) -> Result<String> {
+        let value = match self {
+            SecretSource::Env(name) => {
+                env::var(name).with_context(|| format!("environment variable {} is not set", name))?
+            }
+            SecretSource::File(path) => {
+                fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?
+            }
+            SecretSource::Command(command) => {
+                // The command's stderr goes to the terminal, so it can prompt (e.g. for a GPG passphrase).
+                let output = Command::new("sh")
+                    .args(["-c", command])
+                    .stdin(Stdio::inherit())
+                    .stderr(Stdio::inherit())
+                    .output()
+                    .with_context(|| format!("failed to run `{}`", command))?;
+                if !output.status.success() {
+                    bail!("`{}` exited with {}", command, output.status);
+                }
+                String::from_utf8(output.stdout).with_context(|| format!("`{}` printed invalid UTF-8", command))?
+            }
+        };
+        Ok(value.trim_end_matches(['\n', '\r']).to_string())
+    }
+}
+
+/// Resolves the secrets of an app into environment variables.
+pub fn resolve(secrets: &HashMap<String, SecretSource>) -> Result<Vec<(String, String)>> {
+    let mut resolved = Vec::new();
+    for (name, source) in secrets {
+        let value = source
+            .resolve()
+            .with_context(|| format!("Failed to resolve secret {}", name))?;
+        resolved.push((name.clone(), value));
+    }
+    Ok(resolved)
+}
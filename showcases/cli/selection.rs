@@ -0,0 +1,439 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Selection expressions choosing the examples to run.
+//!
+//! An expression combines example names, name globs (`ipc_*`, `demo?`),
+//! `tag:<glob>` and `all` with set operations, from lowest to highest precedence:
+//!
+//! - `a or b`, `a | b`, `a + b`: union, and `a - b`: difference (left to right)
+//! - `a and b`, `a & b`: intersection
+//! - `not a`, `!a`: all examples except `a`
+//! - `( ... )`: grouping
+//!
+//! Operators are separate words, so names like `can-demo` stay intact, and
+//! consecutive words form one name, so `IPC Example` needs no quotes. With
+//! `--prefix-match`, a name matching no example is taken as the example it is an
+//! unambiguous prefix of, ignoring case, so `fixed` selects
+//! `fixed_execution_order` if no other example starts with it.
+
+use anyhow::{bail, Result};
+use std::collections::BTreeSet;
+
+use crate::ScoreConfig;
+
+type Set = BTreeSet<usize>;
+
+/// Suggestions shown for a name matching no example.
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Not,
+    And,
+    Or,
+    Minus,
+    Word(String),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::Not => write!(f, "not"),
+            Token::And => write!(f, "and"),
+            Token::Or => write!(f, "or"),
+            Token::Minus => write!(f, "-"),
+            Token::Word(word) => write!(f, "{}", word),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    for word in expression.split_whitespace() {
+        let mut word = word;
+        loop {
+            if let Some(rest) = word.strip_prefix('(') {
+                tokens.push(Token::Open);
+                word = rest;
+            } else if let Some(rest) = word.strip_prefix('!') {
+                tokens.push(Token::Not);
+                word = rest;
+            } else {
+                break;
+            }
+        }
+        let mut closing = 0;
+        while let Some(rest) = word.strip_suffix(')') {
+            closing += 1;
+            word = rest;
+        }
+        match word {
+            "" => {}
+            "not" => tokens.push(Token::Not),
+            "and" | "&" => tokens.push(Token::And),
+            "or" | "|" | "+" => tokens.push(Token::Or),
+            "-" => tokens.push(Token::Minus),
+            _ => match tokens.last_mut() {
+                // Consecutive words form one name like "IPC Example".
+                Some(Token::Word(name)) => {
+                    name.push(' ');
+                    name.push_str(word);
+                }
+                _ => tokens.push(Token::Word(word.to_string())),
+            },
+        }
+        for _ in 0..closing {
+            tokens.push(Token::Close);
+        }
+    }
+    tokens
+}
+
+/// Result of evaluating a selection: the examples and the names or patterns that matched none.
+pub struct Selection {
+    pub selected: Vec<usize>,
+    pub unmatched: Vec<String>,
+    pub expanded: Vec<(String, String)>, // prefixes taken as the example they start, with its name
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    configs: &'a [ScoreConfig],
+    prefixes: bool, // take unmatched names as the example they are a unique prefix of
+    unmatched: Vec<String>,
+    expanded: Vec<(String, String)>,
+}
+
+/// Evaluates the `requested` expressions, selecting the union of their examples.
+///
+/// With `prefixes`, a name matching no example selects the example it is an unambiguous prefix of.
+pub fn evaluate(configs: &[ScoreConfig], requested: &[String], prefixes: bool) -> Result<Selection> {
+    let mut selected = Set::new();
+    let mut unmatched = Vec::new();
+    let mut expanded = Vec::new();
+    for expression in requested {
+        let expression = expression.trim();
+        // Exact names always work, even if they contain operator words.
+        if let Some(index) = configs.iter().position(|c| c.name == expression) {
+            selected.insert(index);
+            continue;
+        }
+        let mut parser = Parser {
+            tokens: tokenize(expression),
+            position: 0,
+            configs,
+            prefixes,
+            unmatched: Vec::new(),
+            expanded: Vec::new(),
+        };
+        let set = parser.union()?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            bail!("Unexpected \"{}\" in selection \"{}\"", token, expression);
+        }
+        selected.extend(set);
+        unmatched.append(&mut parser.unmatched);
+        expanded.append(&mut parser.expanded);
+    }
+    Ok(Selection {
+        selected: selected.into_iter().collect(),
+        unmatched,
+        expanded,
+    })
+}
+
+impl Parser<'_> {
+    fn next_is(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.position) == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn union(&mut self) -> Result<Set> {
+        let mut set = self.intersection()?;
+        loop {
+            if self.next_is(&Token::Or) {
+                set.extend(self.intersection()?);
+            } else if self.next_is(&Token::Minus) {
+                let other = self.intersection()?;
+                set.retain(|index| !other.contains(index));
+            } else {
+                return Ok(set);
+            }
+        }
+    }
+
+    fn intersection(&mut self) -> Result<Set> {
+        let mut set = self.negation()?;
+        while self.next_is(&Token::And) {
+            let other = self.negation()?;
+            set.retain(|index| other.contains(index));
+        }
+        Ok(set)
+    }
+
+    fn negation(&mut self) -> Result<Set> {
+        if self.next_is(&Token::Not) {
+            let set = self.negation()?;
+            return Ok((0..self.configs.len()).filter(|index| !set.contains(index)).collect());
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Set> {
+        if self.next_is(&Token::Open) {
+            let set = self.union()?;
+            if !self.next_is(&Token::Close) {
+                bail!("Missing \")\" in selection");
+            }
+            return Ok(set);
+        }
+        let Some(Token::Word(word)) = self.tokens.get(self.position).cloned() else {
+            bail!("Expected an example name, pattern or \"tag:<name>\" in selection");
+        };
+        self.position += 1;
+        let set: Set = if word.eq_ignore_ascii_case("all") {
+            (0..self.configs.len()).collect()
+        } else if let Some(tag) = word.strip_prefix("tag:") {
+            self.matching(|config| config.tags.iter().any(|t| glob_match(tag, t)))
+        } else {
+            self.matching(|config| glob_match(&word, &config.name))
+        };
+        let set = if set.is_empty() && self.prefixes && !word.contains(['*', '?']) {
+            self.unique_prefix(&word).unwrap_or(set)
+        } else {
+            set
+        };
+        if set.is_empty() {
+            self.unmatched.push(word);
+        }
+        Ok(set)
+    }
+
+    /// The example whose name starts with `prefix`, ignoring case, if it is the only one.
+    fn unique_prefix(&mut self, prefix: &str) -> Option<Set> {
+        let lowercase = prefix.to_lowercase();
+        let set = self.matching(|config| config.name.to_lowercase().starts_with(&lowercase));
+        if set.len() != 1 {
+            return None;
+        }
+        let index = *set.first()?;
+        self.expanded
+            .push((prefix.to_string(), self.configs[index].name.clone()));
+        Some(set)
+    }
+
+    fn matching(&self, matches: impl Fn(&ScoreConfig) -> bool) -> Set {
+        self.configs
+            .iter()
+            .enumerate()
+            .filter(|(_, config)| matches(config))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+/// Matches `text` against a glob with `*` (any characters) and `?` (one character).
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Names of examples `word` was probably meant to be, the closest first, ignoring case.
+///
+/// Names containing `word` or contained in it come first, then names within an
+/// edit distance of a third of the length of `word`.
+pub fn suggestions<'a>(configs: &'a [ScoreConfig], word: &str) -> Vec<&'a str> {
+    let word = word.trim_matches('*').to_lowercase();
+    if word.is_empty() {
+        return Vec::new();
+    }
+    let max_distance = word.chars().count() / 3 + 1;
+    let mut candidates: Vec<(usize, &str)> = configs
+        .iter()
+        .filter_map(|config| {
+            let name = config.name.to_lowercase();
+            let distance = if name.contains(&word) || word.contains(&name) {
+                0
+            } else {
+                edit_distance(&word, &name)
+            };
+            (distance <= max_distance).then_some((distance, config.name.as_str()))
+        })
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// Levenshtein distance: the number of characters to insert, remove or replace to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(ca != cb);
+            current.push(replace.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, glob_match, suggestions, tokenize, Token};
+    use crate::ScoreConfig;
+
+    fn configs() -> Vec<ScoreConfig> {
+        [
+            ("ipc_basic", &["communication"][..]),
+            ("ipc_zero_copy", &["communication", "gui"]),
+            ("slow_demo", &[]),
+            ("IPC Example", &["communication"]),
+            ("can-demo", &["gui"]),
+        ]
+        .iter()
+        .map(|(name, tags)| {
+            serde_json::from_value(serde_json::json!({
+                "name": name,
+                "description": "test",
+                "apps": [],
+                "tags": tags,
+            }))
+            .unwrap()
+        })
+        .collect()
+    }
+
+    fn select(expression: &str) -> Vec<usize> {
+        evaluate(&configs(), &[expression.to_string()], false).unwrap().selected
+    }
+
+    fn word(name: &str) -> Token {
+        Token::Word(name.to_string())
+    }
+
+    #[test]
+    fn tokens() {
+        assert_eq!(
+            tokenize("!(a or b) - c"),
+            vec![
+                Token::Not,
+                Token::Open,
+                word("a"),
+                Token::Or,
+                word("b"),
+                Token::Close,
+                Token::Minus,
+                word("c")
+            ]
+        );
+        assert_eq!(
+            tokenize("IPC Example & can-demo"),
+            vec![word("IPC Example"), Token::And, word("can-demo")]
+        );
+        assert_eq!(
+            tokenize("((a))"),
+            vec![Token::Open, Token::Open, word("a"), Token::Close, Token::Close]
+        );
+    }
+
+    #[test]
+    fn names_and_globs() {
+        assert_eq!(select("slow_demo"), vec![2]);
+        assert_eq!(select("IPC Example"), vec![3]);
+        assert_eq!(select("ipc_*"), vec![0, 1]);
+        assert_eq!(select("*demo"), vec![2, 4]);
+        assert!(glob_match("ipc_?asic", "ipc_basic"));
+        assert!(!glob_match("ipc_?", "ipc_basic"));
+    }
+
+    #[test]
+    fn set_operations_and_precedence() {
+        assert_eq!(select("all - slow_demo"), vec![0, 1, 3, 4]);
+        assert_eq!(select("tag:communication and not tag:gui"), vec![0, 3]);
+        assert_eq!(select("slow_demo or ipc_* and tag:gui"), vec![1, 2]);
+        assert_eq!(select("(slow_demo or ipc_*) and tag:gui"), vec![1]);
+        assert_eq!(select("!tag:communication"), vec![2, 4]);
+        assert_eq!(select("all - ipc_* - can-demo"), vec![2, 3]);
+    }
+
+    #[test]
+    fn union_of_several_expressions() {
+        let requested = ["can-demo".to_string(), "ipc_basic".to_string()];
+        assert_eq!(evaluate(&configs(), &requested, false).unwrap().selected, vec![0, 4]);
+    }
+
+    #[test]
+    fn unmatched_names_and_suggestions() {
+        let selection = evaluate(&configs(), &["ipc_* or slow_dmeo".to_string()], false).unwrap();
+        assert_eq!(selection.selected, vec![0, 1]);
+        assert_eq!(selection.unmatched, vec!["slow_dmeo"]);
+        assert_eq!(suggestions(&configs(), "slow_dmeo"), vec!["slow_demo"]);
+    }
+
+    #[test]
+    fn unique_prefixes_only_when_enabled() {
+        let requested = ["slow".to_string(), "ipc".to_string()];
+        let selection = evaluate(&configs(), &requested, false).unwrap();
+        assert!(selection.selected.is_empty());
+        assert_eq!(selection.unmatched, vec!["slow", "ipc"]);
+        let selection = evaluate(&configs(), &requested, true).unwrap();
+        assert_eq!(selection.selected, vec![2]);
+        assert_eq!(selection.expanded, vec![("slow".to_string(), "slow_demo".to_string())]);
+        // "ipc" starts three examples, so it stays unmatched.
+        assert_eq!(selection.unmatched, vec!["ipc"]);
+    }
+
+    #[test]
+    fn syntax_errors() {
+        assert!(evaluate(&configs(), &["(ipc_basic".to_string()], false).is_err());
+        assert!(evaluate(&configs(), &["ipc_basic )".to_string()], false).is_err());
+        assert!(evaluate(&configs(), &["all -".to_string()], false).is_err());
+    }
+}
@@ -0,0 +1,329 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Updating a deployed CLI from a release server (`score-cli self-update`), so
+//! exhibition machines are updated remotely instead of re-imaged.
+//!
+//! A channel is a directory below the release URL holding the binary for each
+//! platform as `score-cli-<os>-<arch>`, optionally the example tree as
+//! `showcases.tar.gz`, and the release manifest `release.json` naming the
+//! version, the channel and the SHA-256 sum of every file. The manifest has a
+//! detached signature `release.json.sig` made with `score-cli`'s report signing
+//! (`openssl dgst -sha256 -sign`). Files are downloaded with `curl` and only
+//! used once the signature of the manifest matches the key of the installation
+//! and their sum matches the manifest. A manifest of another channel, or of a
+//! version not newer than the installed one, is refused, so an old signed
+//! release cannot be replayed to downgrade a machine. The installed version is
+//! recorded in `<binary>.release` and `<examples>/.score-cli-release`. The
+//! binary is swapped atomically, the previous one is kept as `<binary>.previous`.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{examples_root, manifest, signing};
+
+/// Version of this build, until a release installed by `self-update` is recorded.
+const BUILD_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Name of the release record in the examples directory.
+const CONFIGS_RELEASE: &str = ".score-cli-release";
+
+pub struct Options {
+    pub url: String,     // release server, e.g. https://releases.example.com/score-cli
+    pub channel: String, // e.g. "stable"
+    pub key: PathBuf,    // public key releases are signed with
+    pub sync_configs: bool,
+    pub check: bool, // only tell whether an update is available
+}
+
+/// The signed manifest of a release.
+#[derive(Debug, Deserialize)]
+struct Release {
+    version: String,
+    channel: String,
+    files: BTreeMap<String, String>, // file name to SHA-256 sum
+}
+
+/// What is installed, recorded after an update.
+#[derive(Debug, Serialize, Deserialize)]
+struct Installed {
+    version: String,
+    channel: String,
+}
+
+/// Updates the running binary, and the examples with `sync_configs`, from the channel.
+pub fn run(options: &Options) -> Result<()> {
+    let exe = env::current_exe().context("Failed to find the path of the CLI executable")?;
+    let exe = fs::canonicalize(&exe).unwrap_or(exe);
+    // Next to the binary, so it is replaced by a rename within one filesystem.
+    let staging = exe.with_file_name(format!(".score-cli-update-{}", std::process::id()));
+    fs::create_dir_all(&staging).with_context(|| format!("Failed to create {:?}", staging))?;
+    let result = update(options, &exe, &staging);
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+fn update(options: &Options, exe: &Path, staging: &Path) -> Result<()> {
+    let release = fetch_release(options, staging)?;
+    let binary = format!("score-cli-{}-{}", env::consts::OS, env::consts::ARCH);
+    let record = release_path(exe);
+    let installed = installed_version(&record)?.unwrap_or_else(|| BUILD_VERSION.to_string());
+    if !newer(&release, &installed, "the CLI")? {
+        println!(
+            "✅ The CLI is up to date with channel '{}' (version {})",
+            options.channel, installed
+        );
+    } else if options.check {
+        println!(
+            "An update of the CLI from version {} to {} is available on channel '{}'",
+            installed, release.version, options.channel
+        );
+    } else {
+        let downloaded = fetch(options, &release, &binary, staging)?;
+        fs::set_permissions(&downloaded, fs::Permissions::from_mode(0o755))?;
+        let previous = previous_path(exe);
+        fs::hard_link(exe, &previous)
+            .or_else(|_| fs::copy(exe, &previous).map(|_| ()))
+            .with_context(|| format!("Failed to keep the current binary as {:?}", previous))?;
+        fs::rename(&downloaded, exe).with_context(|| format!("Failed to replace {:?}", exe))?;
+        record_release(&record, &release)?;
+        println!(
+            "✅ Updated {} to version {} from channel '{}', the previous binary is kept as {}",
+            exe.display(),
+            release.version,
+            options.channel,
+            previous.display()
+        );
+    }
+    if options.sync_configs {
+        sync_configs(options, &release, staging)?;
+    }
+    Ok(())
+}
+
+/// Replaces the examples directory by the one of the channel.
+fn sync_configs(options: &Options, release: &Release, staging: &Path) -> Result<()> {
+    let root = examples_root();
+    let installed = installed_version(&root.join(CONFIGS_RELEASE))?;
+    if let Some(ref installed) = installed {
+        if !newer(release, installed, "the examples")? {
+            println!(
+                "✅ The examples in {} are up to date with channel '{}' (version {})",
+                root.display(),
+                options.channel,
+                installed
+            );
+            return Ok(());
+        }
+    }
+    if options.check {
+        println!(
+            "Would replace the examples in {} by those of version {} of the channel",
+            root.display(),
+            release.version
+        );
+        return Ok(());
+    }
+    let archive = fetch(options, release, "showcases.tar.gz", staging)?;
+    let mut new_root = root.as_os_str().to_owned();
+    new_root.push(".new");
+    let new_root = PathBuf::from(new_root);
+    let _ = fs::remove_dir_all(&new_root);
+    fs::create_dir_all(&new_root).with_context(|| format!("Failed to create {:?}", new_root))?;
+    let status = Command::new("tar")
+        .arg("-xzf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&new_root)
+        .status()
+        .context("Failed to run tar")?;
+    if !status.success() {
+        let _ = fs::remove_dir_all(&new_root);
+        bail!(
+            "Failed to unpack the examples of channel '{}': tar {}",
+            options.channel,
+            status
+        );
+    }
+    record_release(&new_root.join(CONFIGS_RELEASE), release)?;
+    let previous = previous_path(&root);
+    let _ = fs::remove_dir_all(&previous);
+    if root.exists() {
+        fs::rename(&root, &previous).with_context(|| format!("Failed to move {:?} aside", root))?;
+    }
+    fs::rename(&new_root, &root).with_context(|| format!("Failed to install the examples in {:?}", root))?;
+    println!(
+        "✅ Synced the examples in {} from channel '{}', the previous ones are kept in {}",
+        root.display(),
+        options.channel,
+        previous.display()
+    );
+    Ok(())
+}
+
+fn url(options: &Options, name: &str) -> String {
+    format!("{}/{}/{}", options.url.trim_end_matches('/'), options.channel, name)
+}
+
+/// Downloads the manifest of the channel and its signature into `staging` and verifies them.
+fn fetch_release(options: &Options, staging: &Path) -> Result<Release> {
+    let file = staging.join("release.json");
+    let signature = signing::signature_path(&file);
+    let url = url(options, "release.json");
+    download(&url, &file)?;
+    download(&format!("{}.sig", url), &signature)?;
+    signing::verify(&file, &signature, &options.key).with_context(|| format!("Refusing to use {}", url))?;
+    let content = fs::read_to_string(&file).with_context(|| format!("Failed reading {:?}", file))?;
+    let release: Release =
+        serde_json::from_str(&content).with_context(|| format!("Invalid release manifest {}", url))?;
+    if release.channel != options.channel {
+        bail!(
+            "Refusing to use {}: it is the manifest of channel '{}', not '{}'",
+            url,
+            release.channel,
+            options.channel
+        );
+    }
+    parse_version(&release.version).with_context(|| format!("Invalid release manifest {}", url))?;
+    Ok(release)
+}
+
+/// Downloads `name` from the channel into `staging` and checks it against the manifest.
+fn fetch(options: &Options, release: &Release, name: &str, staging: &Path) -> Result<PathBuf> {
+    let url = url(options, name);
+    let Some(expected) = release.files.get(name) else {
+        bail!(
+            "Release {} of channel '{}' has no {}",
+            release.version,
+            options.channel,
+            name
+        );
+    };
+    let file = staging.join(name);
+    download(&url, &file)?;
+    let sums = manifest::sha256(std::slice::from_ref(&file))?;
+    if sums.get(&file).is_none_or(|sum| !sum.eq_ignore_ascii_case(expected)) {
+        bail!(
+            "Refusing to install {}: its SHA-256 sum does not match the signed manifest",
+            url
+        );
+    }
+    Ok(file)
+}
+
+/// Whether `release` is newer than the `installed` version of `what`, failing if it is older.
+fn newer(release: &Release, installed: &str, what: &str) -> Result<bool> {
+    match parse_version(&release.version)?.cmp(&parse_version(installed)?) {
+        Ordering::Greater => Ok(true),
+        Ordering::Equal => Ok(false),
+        Ordering::Less => bail!(
+            "Refusing to downgrade {} from version {} to {}: the channel offers an older release",
+            what,
+            installed,
+            release.version
+        ),
+    }
+}
+
+/// Parses a version like `1.4.2` for comparison, without trailing zeros, so `1.9` equals `1.9.0`.
+fn parse_version(version: &str) -> Result<Vec<u64>> {
+    let mut parts = version
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()
+        .with_context(|| format!("\"{}\" is no version like 1.4.2", version))?;
+    while parts.last() == Some(&0) {
+        parts.pop();
+    }
+    Ok(parts)
+}
+
+/// The version recorded in `record`, `None` if nothing was recorded yet.
+fn installed_version(record: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(record) {
+        Ok(content) => {
+            let installed: Installed =
+                serde_json::from_str(&content).with_context(|| format!("Invalid release record {:?}", record))?;
+            Ok(Some(installed.version))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed reading {:?}", record)),
+    }
+}
+
+fn record_release(record: &Path, release: &Release) -> Result<()> {
+    let installed = Installed {
+        version: release.version.clone(),
+        channel: release.channel.clone(),
+    };
+    fs::write(record, serde_json::to_string_pretty(&installed)? + "\n")
+        .with_context(|| format!("Failed to record the release in {:?}", record))
+}
+
+fn release_path(exe: &Path) -> PathBuf {
+    let mut name = exe.as_os_str().to_owned();
+    name.push(".release");
+    PathBuf::from(name)
+}
+
+fn download(url: &str, to: &Path) -> Result<()> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--output"])
+        .arg(to)
+        .arg(url)
+        .output()
+        .context("Failed to run `curl`, which downloads the updates")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to download {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn previous_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".previous");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(version: &str) -> Release {
+        Release {
+            version: version.to_string(),
+            channel: "stable".to_string(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn only_newer_releases_are_installed() {
+        assert!(newer(&release("1.10.0"), "1.9.3", "the CLI").unwrap());
+        assert!(!newer(&release("1.9.3"), "1.9.3", "the CLI").unwrap());
+        assert!(!newer(&release("1.9.0"), "1.9", "the CLI").unwrap());
+        assert!(!newer(&release("2"), "2.0.0", "the CLI").unwrap());
+        assert!(newer(&release("1.9.0.1"), "1.9", "the CLI").unwrap());
+        assert!(newer(&release("1.9.2"), "1.9.3", "the CLI").is_err());
+        assert!(newer(&release("1.9.x"), "1.9.3", "the CLI").is_err());
+    }
+}
@@ -0,0 +1,290 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Smoke checks of the installed SCORE stack (`score-cli selftest`), telling
+//! stack problems apart from problems of the showcases.
+//!
+//! `selftest ipc` runs the echo test app shipped with the communication stack,
+//! `ipc_bridge_cpp`, with its own `mw_com_config.json`: a subscriber first,
+//! then a publisher sending messages at a fixed interval. The check passes if
+//! the subscriber received the messages it waits for and both exited cleanly.
+//! Every output line is timestamped when read, so the latency of a message is
+//! the time between the publisher's line about sending it and the subscriber's
+//! line about receiving it, paired by the first number in the lines, or in
+//! order if they have none.
+
+use anyhow::{bail, Context, Result};
+use clap::Subcommand;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::sys::{self, Signal};
+
+/// Time the subscriber gets to find the service before the publisher starts.
+const SUBSCRIBE_DELAY: Duration = Duration::from_millis(500);
+/// Time for both apps to start up and discover each other, on top of the sending time.
+const STARTUP_ALLOWANCE: Duration = Duration::from_secs(10);
+
+#[derive(Subcommand, Debug)]
+pub enum Test {
+    /// Publish messages with the communication stack's echo app and check they are received
+    Ipc {
+        /// Messages the subscriber has to receive
+        #[arg(long, default_value_t = 100)]
+        messages: u32,
+
+        /// Time between two messages in milliseconds
+        #[arg(long, value_name = "MS", default_value_t = 10)]
+        interval_ms: u64,
+
+        /// The stack's echo app (default: bin/ipc_bridge_cpp in the examples directory)
+        #[arg(long)]
+        app: Option<PathBuf>,
+
+        /// Directory with the app's etc/mw_com_config.json (default: data/comm in the examples directory)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Text of the publisher's lines about a sent message
+        #[arg(long, default_value = "Sending sample")]
+        sent_line: String,
+
+        /// Text of the subscriber's lines about a received message
+        #[arg(long, default_value = "received valid data")]
+        received_line: String,
+    },
+}
+
+/// Runs `test` on the installation in `root`, returning whether it passed.
+pub fn run(test: Test, root: &Path) -> Result<bool> {
+    match test {
+        Test::Ipc {
+            messages,
+            interval_ms,
+            app,
+            dir,
+            sent_line,
+            received_line,
+        } => ipc(&Ipc {
+            messages,
+            interval: Duration::from_millis(interval_ms),
+            app: app.unwrap_or_else(|| root.join("bin").join("ipc_bridge_cpp")),
+            dir: dir.unwrap_or_else(|| root.join("data").join("comm")),
+            sent_line,
+            received_line,
+        }),
+    }
+}
+
+struct Ipc {
+    messages: u32,
+    interval: Duration,
+    app: PathBuf,
+    dir: PathBuf,
+    sent_line: String,
+    received_line: String,
+}
+
+/// A line of output of a selftest app, with the time it was read.
+struct Line {
+    at: Instant,
+    text: String,
+}
+
+fn ipc(test: &Ipc) -> Result<bool> {
+    if !test.app.is_file() {
+        bail!(
+            "The communication stack's echo app {} is not installed, give it with --app",
+            test.app.display()
+        );
+    }
+    let config = test.dir.join("etc").join("mw_com_config.json");
+    if !config.is_file() {
+        bail!(
+            "No communication config {}, give the directory holding etc/mw_com_config.json with --dir",
+            config.display()
+        );
+    }
+    println!(
+        "Self-test ipc: {} messages every {:?} with {}",
+        test.messages,
+        test.interval,
+        test.app.display()
+    );
+
+    // Time for sending all messages, checked before anything is started.
+    let wait = test
+        .interval
+        .checked_mul(test.messages)
+        .and_then(|sending| sending.checked_mul(2))
+        .and_then(|sending| sending.checked_add(STARTUP_ALLOWANCE))
+        .filter(|wait| Instant::now().checked_add(*wait).is_some())
+        .context("Too many messages at this interval")?;
+    let interval_ms = test.interval.as_millis().to_string();
+    // The publisher sends twice as many messages, so the subscriber gets all it waits for even if it subscribes late.
+    let published = (u64::from(test.messages) * 2).to_string();
+    let received = test.messages.to_string();
+    let (mut subscriber, subscriber_lines) = spawn(test, &["-n", &received, "-t", &interval_ms, "-m", "recv"])?;
+    std::thread::sleep(SUBSCRIBE_DELAY);
+    let (mut publisher, publisher_lines) = match spawn(test, &["-n", &published, "-t", &interval_ms, "-m", "send"]) {
+        Ok(spawned) => spawned,
+        Err(e) => {
+            let _ = sys::stop(&mut subscriber, Signal::TERM, Duration::from_secs(1));
+            return Err(e);
+        }
+    };
+
+    let deadline = Instant::now() + wait;
+    let subscriber_status = wait_until(&mut subscriber, deadline)?;
+    let publisher_status = match publisher.try_wait()? {
+        Some(status) => Some(status),
+        // Still sending the spare messages.
+        None => {
+            sys::stop(&mut publisher, Signal::TERM, Duration::from_secs(2))?;
+            None
+        }
+    };
+    if subscriber_status.is_none() {
+        sys::stop(&mut subscriber, Signal::TERM, Duration::from_secs(2))?;
+    }
+    let sent = collect(publisher_lines, &test.sent_line);
+    let received_lines = collect(subscriber_lines, &test.received_line);
+
+    println!("Messages sent:     {}", sent.len());
+    println!("Messages received: {}", received_lines.len());
+    let latencies = latencies(&sent, &received_lines);
+    if latencies.is_empty() {
+        println!("Latency:           not measured, no matching sent and received lines");
+    } else {
+        let mut sorted = latencies.clone();
+        sorted.sort();
+        let average = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+        println!(
+            "Latency:           min {:.3} ms, average {:.3} ms, p99 {:.3} ms, max {:.3} ms ({} messages)",
+            ms(sorted[0]),
+            ms(average),
+            ms(sorted[(sorted.len() - 1) * 99 / 100]),
+            ms(sorted[sorted.len() - 1]),
+            sorted.len()
+        );
+    }
+
+    let mut problems = Vec::new();
+    match subscriber_status {
+        None => problems.push(format!(
+            "the subscriber did not receive {} messages in time",
+            test.messages
+        )),
+        Some(status) if !status.success() => problems.push(format!("the subscriber exited with {}", status)),
+        Some(_) => {}
+    }
+    if let Some(status) = publisher_status.filter(|status| !status.success()) {
+        problems.push(format!("the publisher exited with {}", status));
+    }
+    if !received_lines.is_empty() && received_lines.len() < test.messages as usize {
+        problems.push(format!(
+            "the subscriber reported {} of {} messages",
+            received_lines.len(),
+            test.messages
+        ));
+    }
+    if problems.is_empty() {
+        println!("✅ The communication stack works");
+    } else {
+        println!("❌ The communication stack failed: {}", problems.join(", "));
+    }
+    Ok(problems.is_empty())
+}
+
+fn spawn(test: &Ipc, args: &[&str]) -> Result<(Child, JoinHandle<Vec<Line>>)> {
+    let mut child = Command::new(&test.app)
+        .args(args)
+        .current_dir(&test.dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to start {}", test.app.display()))?;
+    let (lines, received) = mpsc::channel();
+    for stream in [
+        Box::new(child.stdout.take().expect("piped")) as Box<dyn std::io::Read + Send>,
+        Box::new(child.stderr.take().expect("piped")),
+    ] {
+        let lines = lines.clone();
+        std::thread::spawn(move || {
+            for text in BufReader::new(stream).lines().map_while(Result::ok) {
+                let _ = lines.send(Line {
+                    at: Instant::now(),
+                    text,
+                });
+            }
+        });
+    }
+    drop(lines);
+    Ok((child, std::thread::spawn(move || received.into_iter().collect())))
+}
+
+fn wait_until(child: &mut Child, deadline: Instant) -> Result<Option<ExitStatus>> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// The lines of an app containing `text`, in the order they were read.
+fn collect(lines: JoinHandle<Vec<Line>>, text: &str) -> Vec<Line> {
+    let mut lines: Vec<Line> = lines.join().unwrap_or_default();
+    lines.retain(|line| line.text.contains(text));
+    lines.sort_by_key(|line| line.at);
+    lines
+}
+
+/// Latencies of the messages both sent and received, paired by their number or else in order.
+fn latencies(sent: &[Line], received: &[Line]) -> Vec<Duration> {
+    let number = |line: &Line| -> Option<u64> {
+        let digits: String = line
+            .text
+            .chars()
+            .skip_while(|c| !c.is_ascii_digit())
+            .take_while(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    };
+    let numbered = sent.iter().chain(received).all(|line| number(line).is_some());
+    if numbered {
+        received
+            .iter()
+            .filter_map(|r| {
+                let s = sent.iter().find(|s| number(s) == number(r))?;
+                Some(r.at.saturating_duration_since(s.at))
+            })
+            .collect()
+    } else {
+        sent.iter()
+            .zip(received)
+            .map(|(s, r)| r.at.saturating_duration_since(s.at))
+            .collect()
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -0,0 +1,635 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Serve mode (`score-cli serve`): a run queue shared by several clients, like a
+//! web UI, people on the CLI and CI jobs.
+//!
+//! Clients connect over TCP and send one JSON line with the token of the server,
+//! either a request to run examples,
+//! `{"token": "...", "examples": ["IPC zero copy"], "priority": 10, "client": "ci"}`,
+//! or `{"token": "...", "list": true}` for the queue. Examples are queued by
+//! priority, higher first and in order of arrival within a priority, and run one
+//! at a time, each as a run of its own: runs share the state of the process,
+//! like the session and the disk guard, so they cannot overlap. Every queued
+//! example is a job with an id, and holds the session lock while it runs. The server answers with one JSON event per line
+//! until all examples of the request ran: `queued`, `started` and `finished`
+//! per job, then `done`. Examples queued by a client that disconnects still
+//! run. `score-cli enqueue` is the client for the CLI and CI.
+//!
+//! `{"token": "...", "job": 3, "add_app": {"path": "..."}}` adds the app to the
+//! running job instead, like `--add-apps-dir` does for `score-cli run`: every
+//! job has a private directory the server writes the app to. The job may also
+//! be given by the name of its example in `examples`. The server answers with
+//! `added` once the example can take the app, or with `error` if the job is not
+//! running.
+//!
+//! The token is read from `--token-file` or `$SCORE_CLI_SERVE_TOKEN`. Without
+//! either, the server makes up one and writes it to `serve.token` in the private
+//! runtime directory of the user, where local clients find it. Requests are
+//! limited in size and time, and so is the number of clients at a time.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+use crate::completion::{self, Ended};
+use crate::report::Status;
+use crate::{hot_add, private_dir, run_examples, select_examples, session, RunOptions, RunReport, ScoreConfig};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+/// Environment variable holding the token, for servers and clients.
+pub const TOKEN_VARIABLE: &str = "SCORE_CLI_SERVE_TOKEN";
+/// Longest request line accepted, apps included.
+const MAX_REQUEST: u64 = 64 * 1024;
+/// Time a client has to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Clients connected at a time, further connections are turned away.
+const MAX_CLIENTS: usize = 64;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    #[serde(default)]
+    token: String,
+    #[serde(default)]
+    examples: Vec<String>, // names or selection expressions like in `score-cli run`
+    #[serde(default)]
+    priority: i32, // higher runs first
+    #[serde(default)]
+    client: String, // shown in the queue, e.g. "ci" or "web"
+    #[serde(default)]
+    list: bool, // only reply with the queue
+    #[serde(default)]
+    job: Option<u64>, // running job to add the app to
+    #[serde(default)]
+    add_app: Option<Value>, // app to add to the running job, or the running example named in `examples`
+}
+
+/// Replies of the server, one JSON object per line.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Queued {
+        job: u64,
+        example: String,
+        position: usize, // examples ahead in the queue
+    },
+    Started {
+        job: u64,
+        example: String,
+    },
+    Finished {
+        job: u64,
+        example: String,
+        status: Option<Status>, // none if the runner failed to run it
+        #[serde(skip_serializing_if = "Option::is_none")]
+        run_dir: Option<PathBuf>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Done {
+        failed: usize,
+    },
+    Queue {
+        items: Vec<QueueItem>,
+    },
+    Added {
+        job: u64,
+        example: String,
+    },
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueueItem {
+    pub job: u64,
+    pub example: String,
+    pub priority: i32,
+    pub client: String,
+    pub running: bool,
+}
+
+struct Item {
+    job: u64,     // also the order of arrival
+    index: usize, // into the configs
+    priority: i32,
+    client: String,
+    events: Sender<Event>,
+}
+
+/// The job being run.
+struct Running {
+    job: u64,
+    index: usize,
+    priority: i32,
+    client: String,
+    add_apps: PathBuf, // directory the example takes added apps from
+}
+
+#[derive(Default)]
+struct Queue {
+    pending: Vec<Item>,
+    running: Option<Running>,
+    last_job: u64,
+}
+
+struct Server<'a> {
+    configs: &'a [ScoreConfig],
+    options: RunOptions,
+    token: String,
+    add_apps: PathBuf, // private directory holding the directories of the running jobs for added apps
+    queue: Mutex<Queue>,
+    wakeup: Condvar,
+    clients: AtomicUsize,
+}
+
+/// Serves the run queue on `address` until the process is stopped.
+pub fn run(configs: &[ScoreConfig], address: &str, options: &RunOptions, token_file: Option<&Path>) -> Result<()> {
+    if options.parallel > 1 {
+        bail!("The run queue runs one example at a time, --parallel cannot be used with `serve`");
+    }
+    let token = match server_token(token_file)? {
+        Some(token) => token,
+        None => {
+            let token = new_token()?;
+            let path = default_token_file()?;
+            let mut file = private_dir::open(&path)?;
+            file.set_len(0)?;
+            file.write_all(token.as_bytes())
+                .with_context(|| format!("Failed to write {:?}", path))?;
+            println!("Clients authenticate with the token in {}", path.display());
+            token
+        }
+    };
+    let listener = TcpListener::bind(address).with_context(|| format!("Failed to listen on {}", address))?;
+    let server = Server {
+        configs,
+        add_apps: private_dir::runtime(&format!("serve-{}", std::process::id()))?,
+        options: RunOptions {
+            interactive: false,
+            ..options.clone()
+        },
+        token,
+        queue: Mutex::new(Queue::default()),
+        wakeup: Condvar::new(),
+        clients: AtomicUsize::new(0),
+    };
+    println!("Serving the run queue of {} examples on {}", configs.len(), address);
+    std::thread::scope(|scope| {
+        scope.spawn(|| server.work());
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+            if server.clients.fetch_add(1, Ordering::SeqCst) >= MAX_CLIENTS {
+                server.clients.fetch_sub(1, Ordering::SeqCst);
+                let message = format!("too many clients, at most {} at a time", MAX_CLIENTS);
+                let _ = send(&mut &stream, &Event::Error { message });
+                println!(
+                    "⚠️  Client {}: turned away, {} clients are connected",
+                    peer, MAX_CLIENTS
+                );
+                continue;
+            }
+            let server = &server;
+            scope.spawn(move || {
+                if let Err(e) = server.handle(stream) {
+                    println!("⚠️  Client {}: {:#}", peer, e);
+                }
+                server.clients.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+    Ok(())
+}
+
+impl Server<'_> {
+    /// Takes requests of one client and streams the events of its examples back.
+    fn handle(&self, stream: TcpStream) -> Result<()> {
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        let mut out = &stream;
+        let request = match read_request(&stream) {
+            Ok(request) if self.authorized(&request.token) => request,
+            Ok(_) => {
+                send(
+                    &mut out,
+                    &Event::Error {
+                        message: "invalid token".to_string(),
+                    },
+                )?;
+                bail!("rejected a request with an invalid token");
+            }
+            Err(e) => {
+                return send(
+                    &mut out,
+                    &Event::Error {
+                        message: format!("invalid request: {:#}", e),
+                    },
+                )
+            }
+        };
+        if request.list {
+            return send(&mut out, &self.list());
+        }
+        if let Some(app) = request.add_app {
+            let event = match self.add_app(request.job, &request.examples, &app) {
+                Ok((job, example)) => Event::Added { job, example },
+                Err(e) => Event::Error {
+                    message: format!("{:#}", e),
+                },
+            };
+            return send(&mut out, &event);
+        }
+        let selected = match select_examples(self.configs, &request.examples, false, self.options.prefixes()) {
+            Ok(selected) => selected,
+            Err(e) => {
+                return send(
+                    &mut out,
+                    &Event::Error {
+                        message: format!("{:#}", e),
+                    },
+                )
+            }
+        };
+
+        let (events, received) = mpsc::channel();
+        {
+            let mut queue = self.queue.lock().unwrap();
+            for &index in &selected {
+                queue.last_job += 1;
+                let item = Item {
+                    job: queue.last_job,
+                    index,
+                    priority: request.priority,
+                    client: request.client.clone(),
+                    events: events.clone(),
+                };
+                let position = queue.pending.iter().filter(|other| runs_before(other, &item)).count()
+                    + usize::from(queue.running.is_some());
+                let _ = events.send(Event::Queued {
+                    job: item.job,
+                    example: self.configs[index].name.clone(),
+                    position,
+                });
+                queue.pending.push(item);
+            }
+            println!(
+                "Queued {} examples for {} with priority {}",
+                selected.len(),
+                if request.client.is_empty() {
+                    "a client"
+                } else {
+                    &request.client
+                },
+                request.priority
+            );
+        }
+        drop(events);
+        self.wakeup.notify_all();
+
+        let mut failed = 0;
+        for event in received {
+            if let Event::Finished { status, .. } = event {
+                if status.is_none_or(|status| status == Status::Failed) {
+                    failed += 1;
+                }
+            }
+            // A client gone keeps its examples queued, they are only not reported.
+            let _ = send(&mut out, &event);
+        }
+        send(&mut out, &Event::Done { failed })
+    }
+
+    /// Whether `token` is the token of the server, compared in constant time.
+    fn authorized(&self, token: &str) -> bool {
+        token.len() == self.token.len()
+            && token
+                .bytes()
+                .zip(self.token.bytes())
+                .fold(0, |difference, (a, b)| difference | (a ^ b))
+                == 0
+    }
+
+    /// Hands `app` to the running job `job`, or the running example named in `examples`.
+    fn add_app(&self, job: Option<u64>, examples: &[String], app: &Value) -> Result<(u64, String)> {
+        let queue = self.queue.lock().unwrap();
+        let Some(ref running) = queue.running else {
+            bail!("no example is running");
+        };
+        let example = &self.configs[running.index].name;
+        match (job, examples) {
+            (Some(job), _) if job != running.job => bail!("job {} is not running", job),
+            (Some(_), _) => {}
+            (None, [name]) if name != example => bail!("example '{}' is not running", name),
+            (None, [_]) => {}
+            (None, _) => bail!("an added app needs the job or the one example to add it to"),
+        }
+        let file = hot_add::submit(&running.add_apps, app)?;
+        println!(
+            "Added an app to job {} ('{}') as {}",
+            running.job,
+            example,
+            file.display()
+        );
+        Ok((running.job, example.clone()))
+    }
+
+    fn list(&self) -> Event {
+        let queue = self.queue.lock().unwrap();
+        let item = |job, index: usize, priority, client: &str, running| QueueItem {
+            job,
+            example: self.configs[index].name.clone(),
+            priority,
+            client: client.to_string(),
+            running,
+        };
+        let mut pending: Vec<&Item> = queue.pending.iter().collect();
+        pending.sort_by(|a, b| {
+            if runs_before(a, b) {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        });
+        let items = queue
+            .running
+            .iter()
+            .map(|running| item(running.job, running.index, running.priority, &running.client, true))
+            .chain(
+                pending
+                    .iter()
+                    .map(|i| item(i.job, i.index, i.priority, &i.client, false)),
+            )
+            .collect();
+        Event::Queue { items }
+    }
+
+    /// Runs queued examples one at a time until the process ends.
+    fn work(&self) {
+        loop {
+            let item = {
+                let mut queue = self.queue.lock().unwrap();
+                loop {
+                    let next = queue
+                        .pending
+                        .iter()
+                        .enumerate()
+                        .reduce(|best, candidate| {
+                            if runs_before(candidate.1, best.1) {
+                                candidate
+                            } else {
+                                best
+                            }
+                        })
+                        .map(|(position, _)| position);
+                    if let Some(next) = next {
+                        let item = queue.pending.remove(next);
+                        queue.running = Some(Running {
+                            job: item.job,
+                            index: item.index,
+                            priority: item.priority,
+                            client: item.client.clone(),
+                            add_apps: self.add_apps.join(format!("job-{}", item.job)),
+                        });
+                        break item;
+                    }
+                    queue = self.wakeup.wait(queue).unwrap();
+                }
+            };
+
+            let example = self.configs[item.index].name.clone();
+            let _ = item.events.send(Event::Started {
+                job: item.job,
+                example: example.clone(),
+            });
+            let add_apps = self.add_apps.join(format!("job-{}", item.job));
+            // Jobs take turns with the sessions on the terminal, see `session`.
+            let ran = session::acquire_for_job().and_then(|()| self.run(item.index, &add_apps));
+            session::release();
+            let event = match ran {
+                Ok(report) => Event::Finished {
+                    job: item.job,
+                    example,
+                    status: report
+                        .examples
+                        .iter()
+                        .find(|e| e.name == self.configs[item.index].name)
+                        .map(|e| e.status),
+                    run_dir: Some(report.run_dir),
+                    error: None,
+                },
+                Err(e) => Event::Finished {
+                    job: item.job,
+                    example,
+                    status: None,
+                    run_dir: None,
+                    error: Some(format!("{:#}", e)),
+                },
+            };
+            let _ = item.events.send(event);
+
+            self.queue.lock().unwrap().running = None;
+            let _ = fs::remove_dir_all(&add_apps);
+        }
+    }
+
+    /// Runs the example `index`, taking added apps from `add_apps`.
+    fn run(&self, index: usize, add_apps: &Path) -> Result<RunReport> {
+        private_dir::ensure(add_apps)?;
+        let options = RunOptions {
+            add_apps_dir: Some(add_apps.to_path_buf()),
+            ..self.options.clone()
+        };
+        // Every job ends like a run of its own.
+        let ended = run_examples(self.configs, &[index], &options).map(|report| Ended::Run(Box::new(report)));
+        completion::notify(&options.on_complete, ended.as_ref());
+        match ended? {
+            Ended::Run(report) => Ok(*report),
+            Ended::Mode(_) => unreachable!("jobs run examples"),
+        }
+    }
+}
+
+/// Reads the request line of a client, at most `MAX_REQUEST` bytes.
+fn read_request(stream: &TcpStream) -> Result<Request> {
+    let mut line = String::new();
+    BufReader::new(stream.take(MAX_REQUEST)).read_line(&mut line)?;
+    if !line.ends_with('\n') {
+        bail!("no complete request line of at most {} bytes", MAX_REQUEST);
+    }
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// Whether `a` runs before `b`: higher priority first, then in order of arrival.
+fn runs_before(a: &Item, b: &Item) -> bool {
+    (a.priority, std::cmp::Reverse(a.job)) > (b.priority, std::cmp::Reverse(b.job))
+}
+
+fn send(out: &mut impl Write, event: &Event) -> Result<()> {
+    writeln!(out, "{}", serde_json::to_string(event)?)?;
+    out.flush()?;
+    Ok(())
+}
+
+fn default_token_file() -> Result<PathBuf> {
+    Ok(private_dir::runtime("serve")?.join("serve.token"))
+}
+
+/// The token given to the server by `--token-file` or the environment, if any.
+fn server_token(token_file: Option<&Path>) -> Result<Option<String>> {
+    if let Some(file) = token_file {
+        return read_token(file).map(Some);
+    }
+    Ok(std::env::var(TOKEN_VARIABLE).ok().filter(|token| !token.is_empty()))
+}
+
+/// The token of the server for a client: from `--token-file`, the environment or the file of a local server.
+fn client_token(token_file: Option<&Path>) -> Result<String> {
+    match server_token(token_file)? {
+        Some(token) => Ok(token),
+        None => read_token(&default_token_file()?).with_context(|| {
+            format!(
+                "No token for the run queue, pass --token-file or set {}",
+                TOKEN_VARIABLE
+            )
+        }),
+    }
+}
+
+fn read_token(file: &Path) -> Result<String> {
+    let mut token = String::new();
+    private_dir::open(file)?
+        .read_to_string(&mut token)
+        .with_context(|| format!("Failed reading {:?}", file))?;
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        bail!("The token file {:?} is empty", file);
+    }
+    Ok(token)
+}
+
+/// A random token of 128 bits, as hex.
+fn new_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    fs::File::open("/dev/urandom")
+        .and_then(|mut random| random.read_exact(&mut bytes))
+        .context("Failed to read /dev/urandom for the token")?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Connects to the server at `address` and sends `request` with the token.
+fn connect(address: &str, mut request: Value, token_file: Option<&Path>) -> Result<TcpStream> {
+    request["token"] = Value::String(client_token(token_file)?);
+    let mut stream = TcpStream::connect(address)
+        .with_context(|| format!("No run queue at {}, start one with `score-cli serve`", address))?;
+    writeln!(stream, "{}", request)?;
+    Ok(stream)
+}
+
+/// Prints the queue of the server at `address`.
+pub fn list(address: &str, token_file: Option<&Path>) -> Result<()> {
+    let stream = connect(address, serde_json::json!({"list": true}), token_file)?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let items = match serde_json::from_str(&line).context("Invalid reply of the run queue")? {
+        Event::Queue { items } => items,
+        Event::Error { message } => bail!("The run queue rejected the request: {}", message),
+        _ => bail!("Unexpected reply of the run queue: {}", line.trim()),
+    };
+    if items.is_empty() {
+        println!("The run queue is empty");
+    }
+    for item in items {
+        println!(
+            "{} {:>4}  {:>5}  {}  ({})",
+            if item.running { "▶" } else { " " },
+            item.priority,
+            format!("#{}", item.job),
+            item.example,
+            if item.client.is_empty() { "-" } else { &item.client }
+        );
+    }
+    Ok(())
+}
+
+/// Adds the app in `file` to the running job given by its id or example on the server at `address`.
+pub fn add_app(address: &str, job: &str, file: &Path, token_file: Option<&Path>) -> Result<()> {
+    let content = fs::read_to_string(file).with_context(|| format!("Failed reading {:?}", file))?;
+    let app: Value = serde_json::from_str(&content).with_context(|| format!("Invalid JSON in {:?}", file))?;
+    let request = match job.trim_start_matches('#').parse::<u64>() {
+        Ok(job) => serde_json::json!({"job": job, "add_app": app}),
+        Err(_) => serde_json::json!({"examples": [job], "add_app": app}),
+    };
+    let stream = connect(address, request, token_file)?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    match serde_json::from_str(&line).context("Invalid reply of the run queue")? {
+        Event::Added { job, example } => {
+            println!("➕ Added the app to job {} ('{}')", job, example);
+            Ok(())
+        }
+        Event::Error { message } => bail!("The run queue rejected the app: {}", message),
+        _ => bail!("Unexpected reply of the run queue: {}", line.trim()),
+    }
+}
+
+/// Queues `examples` on the server at `address` and prints its events, returning whether all examples passed.
+pub fn enqueue(
+    address: &str,
+    examples: &[String],
+    priority: i32,
+    client: &str,
+    token_file: Option<&Path>,
+) -> Result<bool> {
+    let request = serde_json::json!({"examples": examples, "priority": priority, "client": client});
+    let stream = connect(address, request, token_file)?;
+    for line in BufReader::new(&stream).lines() {
+        let event: Event = serde_json::from_str(&line?).context("Invalid reply of the run queue")?;
+        match event {
+            Event::Queued { job, example, position } => {
+                println!("⏳ Queued '{}' as job {}, {} examples ahead", example, job, position)
+            }
+            Event::Started { job, example } => println!("▶ Running '{}' (job {})", example, job),
+            Event::Finished {
+                example,
+                status,
+                run_dir,
+                error,
+                ..
+            } => match (status, error) {
+                (Some(status), _) => println!(
+                    "{} '{}' {} ({})",
+                    if status == Status::Failed { "❌" } else { "✅" },
+                    example,
+                    status.name(),
+                    run_dir.unwrap_or_default().display()
+                ),
+                (None, error) => println!("❌ '{}' could not run: {}", example, error.unwrap_or_default()),
+            },
+            Event::Done { failed } => return Ok(failed == 0),
+            Event::Error { message } => bail!("The run queue rejected the request: {}", message),
+            Event::Queue { .. } | Event::Added { .. } => {}
+        }
+    }
+    bail!("The run queue at {} closed the connection", address)
+}
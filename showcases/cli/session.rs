@@ -0,0 +1,262 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! One session at a time on a demo box shared by several people.
+//!
+//! A CLI started from a terminal to run examples holds the session lock
+//! `session.lock` in the private lock directory of the user (see `private_dir`)
+//! until it exits, so the sessions of one account, like the demo account of the
+//! box, run one at a time. The lock file tells
+//! who holds it and which example runs. A second session finding the lock
+//! taken offers to attach to the logs of the running example, to queue until
+//! the session ends, or to abort. `--force` ends the holding session instead,
+//! for a session left open or hanging, once it made sure that the holder is a
+//! CLI process of the same user. Its process group is only signalled if the
+//! holder leads it. Runs without a terminal, like CI jobs,
+//! do not take part, they coordinate through the locks of their resources.
+//! `serve` takes the lock for every job it runs, waiting for a session holding
+//! it, so jobs of the run queue and sessions on the terminal take turns.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{IsTerminal, Read};
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::sys::{self, Signal};
+use crate::{logs, private_dir, procfs};
+
+/// Time `--force` gives the holding session to exit on SIGTERM before it is killed.
+const FORCE_GRACE: Duration = Duration::from_secs(5);
+
+/// The session holding the lock, as written to the lock file.
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct Holder {
+    pid: u32,
+    pgid: u32, // process group, shared with the apps if the session leads it
+    user: String,
+    started: u64, // seconds since the epoch
+    command: String,
+    example: Option<String>,  // the example running, none while examples are chosen
+    run_dir: Option<PathBuf>, // where the running example logs
+}
+
+/// The lock of this session, with what it tells others.
+static SESSION: Mutex<Option<(File, Holder)>> = Mutex::new(None);
+
+/// What to do about a session holding the lock.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    Attach,
+    Queue,
+    Abort,
+}
+
+/// Takes the session lock if the CLI runs in a terminal, asking what to do if another session holds it.
+///
+/// Returns `false` if the user chose not to run, e.g. after attaching to the other session.
+pub fn acquire(force: bool) -> Result<bool> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Ok(true);
+    }
+    let path = private_dir::runtime("locks")?.join("session.lock");
+    let file = private_dir::open(&path)?;
+
+    if !sys::lock_exclusive(&file, false)? {
+        let mut content = String::new();
+        let _ = (&file).read_to_string(&mut content);
+        let holder: Holder = serde_json::from_str(&content).unwrap_or_default();
+        if force {
+            take_over(&file, &holder)?;
+        } else {
+            match ask(&holder)? {
+                Choice::Attach => {
+                    let run_dir = holder.run_dir.as_deref().expect("attach is only offered with a run");
+                    logs::show(run_dir, Vec::new(), true)?;
+                    return Ok(false);
+                }
+                Choice::Queue => {
+                    println!(
+                        "⏳ Waiting for the session of {} (pid {}) to end",
+                        holder.user, holder.pid
+                    );
+                    sys::lock_exclusive(&file, true).context("Failed to lock the session")?;
+                }
+                Choice::Abort => return Ok(false),
+            }
+        }
+    }
+
+    hold(file);
+    Ok(true)
+}
+
+/// Takes the session lock for a job of the run queue, waiting while another session holds it.
+///
+/// Taken whether or not the server runs in a terminal, until [`release`].
+pub fn acquire_for_job() -> Result<()> {
+    let path = private_dir::runtime("locks")?.join("session.lock");
+    let file = private_dir::open(&path)?;
+    if !sys::lock_exclusive(&file, false)? {
+        let mut content = String::new();
+        let _ = (&file).read_to_string(&mut content);
+        let holder: Holder = serde_json::from_str(&content).unwrap_or_default();
+        println!(
+            "⏳ Waiting for the session of {} (pid {}) to end",
+            holder.user, holder.pid
+        );
+        sys::lock_exclusive(&file, true).context("Failed to lock the session")?;
+    }
+    hold(file);
+    Ok(())
+}
+
+/// Gives back the session lock taken by [`acquire_for_job`].
+pub fn release() {
+    // Closing the file releases the lock.
+    SESSION.lock().unwrap().take();
+}
+
+/// Tells others that this process holds the session lock on `file`, until it exits or releases it.
+fn hold(file: File) {
+    let holder = Holder {
+        pid: std::process::id(),
+        pgid: sys::process_group(),
+        user: user(),
+        started: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs()),
+        command: std::env::args().collect::<Vec<_>>().join(" "),
+        example: None,
+        run_dir: None,
+    };
+    write(&file, &holder);
+    *SESSION.lock().unwrap() = Some((file, holder));
+}
+
+/// Tells other sessions that `example` runs now, logging to `run_dir`.
+pub(crate) fn running(example: &str, run_dir: &Path) {
+    if let Some((file, holder)) = SESSION.lock().unwrap().as_mut() {
+        holder.example = Some(example.to_string());
+        holder.run_dir = Some(run_dir.to_path_buf());
+        write(file, holder);
+    }
+}
+
+fn write(file: &File, holder: &Holder) {
+    let content = serde_json::to_string(holder).unwrap_or_default();
+    let _ = file.set_len(0);
+    let _ = file.write_all_at(content.as_bytes(), 0);
+}
+
+fn ask(holder: &Holder) -> Result<Choice> {
+    let since = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+        .saturating_sub(holder.started);
+    let activity = match holder.example {
+        Some(ref example) => format!("is running example '{}'", example),
+        None => "is choosing examples".to_string(),
+    };
+    let prompt = format!(
+        "Another session {} (pid {}, user {}, started {} min ago: {}). What now?",
+        activity,
+        holder.pid,
+        holder.user,
+        since / 60,
+        holder.command
+    );
+    let mut items = Vec::new();
+    if holder.run_dir.is_some() {
+        items.push((Choice::Attach, "Attach", "follow the logs of its examples"));
+    }
+    items.push((Choice::Queue, "Queue", "run once the other session ended"));
+    items.push((Choice::Abort, "Abort", "use --force to end the other session"));
+    Ok(cliclack::select(prompt).items(&items).interact()?)
+}
+
+/// Ends the session `holder`, with its apps if they share its process group, and takes the lock over.
+fn take_over(file: &File, holder: &Holder) -> Result<()> {
+    if holder.pid == 0 {
+        bail!("The session lock is held by a process that did not say which, retry in a moment");
+    }
+    check_holder(holder.pid)?;
+    println!(
+        "⚠️  Ending the session of {} (pid {}): {}",
+        holder.user, holder.pid, holder.command
+    );
+    let send = |signal| {
+        // Never the group of init or the kernel, and only a group the session leads.
+        let result = if holder.pgid > 1 && holder.pgid == holder.pid {
+            sys::send_group(holder.pgid, signal)
+        } else {
+            sys::send(holder.pid, signal)
+        };
+        result.with_context(|| format!("Failed to end the session (pid {})", holder.pid))
+    };
+    send(Signal::TERM)?;
+    let deadline = Instant::now() + FORCE_GRACE;
+    while !sys::lock_exclusive(file, false)? {
+        if Instant::now() >= deadline {
+            send(Signal::KILL)?;
+            sys::lock_exclusive(file, true).context("Failed to lock the session")?;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(())
+}
+
+/// Checks that `pid` is a process of this CLI run by the same user, before it is signalled.
+fn check_holder(pid: u32) -> Result<()> {
+    if !procfs::SUPPORTED {
+        bail!(
+            "Cannot tell which program holds the session lock (pid {}) on this system, end it yourself",
+            pid
+        );
+    }
+    let Some(owner) = procfs::owner(pid) else {
+        bail!(
+            "The session lock is held by pid {}, which is gone, retry in a moment",
+            pid
+        );
+    };
+    if owner != sys::effective_uid() {
+        bail!(
+            "The session lock is held by pid {} of another user (uid {}), not ending it",
+            pid,
+            owner
+        );
+    }
+    let ours = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.file_name()?.to_str()?.to_string()));
+    let theirs = procfs::executable_name(pid);
+    if theirs.is_none() || theirs != ours {
+        bail!(
+            "The session lock is held by pid {} running {}, which is no session of this CLI, not ending it",
+            pid,
+            theirs.as_deref().unwrap_or("an unknown program")
+        );
+    }
+    Ok(())
+}
+
+fn user() -> String {
+    ["SUDO_USER", "USER", "LOGNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|user| !user.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
@@ -0,0 +1,515 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Settings files of the CLI, the defaults of all invocations.
+//!
+//! Settings are read from three layers, each overriding the ones before it:
+//!
+//! 1. the system, `/etc/score-cli/config.toml`
+//! 2. the user, `~/.config/score-cli/config.toml`, written by `score-cli init`
+//! 3. the project, `.score-cli.toml` in the current directory or the nearest
+//!    directory above it, up to the root of its git repository or the home
+//!    directory. A project file of another user, or one other users can write,
+//!    is ignored with a warning.
+//!
+//! Environment variables and command line flags take precedence over all files.
+//! A file holds `key = value` lines of TOML: strings, booleans, integers and
+//! lists of strings. Some settings are applied as the environment variables the
+//! CLI reads anyway, with relative paths resolved against the file's directory:
+//!
+//! | Setting   | Applied as                                   |
+//! |-----------|----------------------------------------------|
+//! | `root`    | `SCORE_CLI_INIT_DIR`, a string or a list     |
+//! | `log_dir` | `SCORE_CLI_RUNS_DIR`, where runs are logged  |
+//! | `backend` | `PROFILE`, tested by app `when` conditions   |
+//! | `color`   | `NO_COLOR=1` if false                        |
+//!
+//! The `[exit_codes]` table maps result categories onto exit codes, see
+//! [`crate::exit_codes`].
+//!
+//! Every other key is the default of the flag of the same name, `_` standing
+//! for `-`, in all commands having the flag and accepting the value. Keys below
+//! a `[command]` table only apply to that command:
+//!
+//! ```toml
+//! parallel = "auto"
+//! junit = "results.xml"
+//!
+//! [soak]
+//! duration = "8h"
+//! ```
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use crate::{exit_codes, private_dir};
+
+/// The settings file of the system.
+pub const SYSTEM_FILE: &str = "/etc/score-cli/config.toml";
+/// Name of the settings file of a project, looked up from the current directory upwards.
+pub const PROJECT_FILE: &str = ".score-cli.toml";
+
+/// Settings applied as environment variables, unless these are set.
+const ENV_SETTINGS: &[(&str, &str)] = &[
+    ("root", "SCORE_CLI_INIT_DIR"),
+    ("log_dir", "SCORE_CLI_RUNS_DIR"),
+    ("backend", "PROFILE"),
+];
+/// Settings holding paths, relative to the file setting them.
+const PATH_SETTINGS: &[&str] = &["root", "log_dir"];
+
+/// A value of a setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Bool(bool),
+    Integer(i64),
+    List(Vec<String>),
+}
+
+/// Settings in the order of their files, keys of `[command]` tables as `command.key`.
+#[derive(Debug, Default)]
+pub struct Settings {
+    pub values: Vec<(String, Value)>,
+    files: HashMap<String, PathBuf>,            // the file each key was last set in
+    applied: Vec<(&'static str, &'static str)>, // environment variables set by `apply_env`, with their key
+}
+
+/// The settings file of the user: `$XDG_CONFIG_HOME/score-cli/config.toml` or `~/.config/score-cli/config.toml`.
+pub fn user_file() -> Option<PathBuf> {
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config.join("score-cli").join("config.toml"))
+}
+
+/// The settings file of the project the CLI is started in, if any and trusted.
+pub fn project_file() -> Option<PathBuf> {
+    let cwd = env::current_dir().ok()?;
+    let home = env::var_os("HOME").map(PathBuf::from);
+    for dir in cwd.ancestors() {
+        let file = dir.join(PROJECT_FILE);
+        if file.is_file() {
+            return match check_trusted(&file) {
+                Ok(()) => Some(file),
+                Err(e) => {
+                    eprintln!("⚠️  Ignoring project settings: {:#}", e);
+                    None
+                }
+            };
+        }
+        // The settings of a repository or home directory do not apply to what is around it.
+        if dir.join(".git").exists() || home.as_deref() == Some(dir) {
+            return None;
+        }
+    }
+    None
+}
+
+/// Checks that `file` belongs to the user and no other user can write it.
+fn check_trusted(file: &Path) -> Result<()> {
+    private_dir::check_owned(file)?;
+    let mode = fs::metadata(file)
+        .with_context(|| format!("Failed to inspect {:?}", file))?
+        .mode();
+    if mode & 0o022 != 0 {
+        bail!("{:?} is writable by other users, refusing to use it", file);
+    }
+    Ok(())
+}
+
+/// The settings files that exist, from the lowest to the highest precedence.
+pub fn files() -> Vec<PathBuf> {
+    let candidates = [Some(PathBuf::from(SYSTEM_FILE)), user_file(), project_file()];
+    candidates.into_iter().flatten().filter(|file| file.is_file()).collect()
+}
+
+impl Settings {
+    /// The settings of all layers.
+    pub fn load() -> Result<Settings> {
+        let mut settings = Settings::default();
+        for file in files() {
+            settings.merge(Settings::read(&file)?);
+        }
+        Ok(settings)
+    }
+
+    pub fn read(path: &Path) -> Result<Settings> {
+        let content = fs::read_to_string(path).with_context(|| format!("Failed reading {:?}", path))?;
+        let mut settings = parse(&content).with_context(|| format!("Invalid settings in {:?}", path))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        for (key, value) in &mut settings.values {
+            check(key, value).with_context(|| format!("Invalid settings in {:?}", path))?;
+            if PATH_SETTINGS.contains(&key.as_str()) {
+                resolve_paths(value, dir);
+            }
+            settings.files.insert(key.clone(), path.to_path_buf());
+        }
+        Ok(settings)
+    }
+
+    /// Overrides the settings by those of `other`.
+    fn merge(&mut self, other: Settings) {
+        for (key, value) in other.values {
+            match self.values.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, current)) => *current = value,
+                None => self.values.push((key, value)),
+            }
+        }
+        self.files.extend(other.files);
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.iter().find(|(k, _)| k == key).map(|(_, value)| value)
+    }
+
+    /// The file setting `key`, if any.
+    pub fn file(&self, key: &str) -> Option<&Path> {
+        self.files.get(key).map(PathBuf::as_path)
+    }
+
+    /// The key and file of the setting `apply_env` set the environment variable `variable` from, if any.
+    pub fn env_origin(&self, variable: &str) -> Option<(&str, &Path)> {
+        let &(key, _) = self.applied.iter().find(|(_, applied)| *applied == variable)?;
+        Some((key, self.file(key)?))
+    }
+
+    /// Sets the environment variables of the settings that are not set already.
+    pub fn apply_env(&mut self) -> Result<()> {
+        for &(key, variable) in ENV_SETTINGS {
+            if env::var_os(variable).is_some_and(|value| !value.is_empty()) {
+                continue;
+            }
+            match self.get(key) {
+                Some(Value::String(value)) => env::set_var(variable, value),
+                Some(Value::List(values)) => env::set_var(variable, env::join_paths(values)?),
+                _ => continue,
+            }
+            self.applied.push((key, variable));
+        }
+        if self.get("color") == Some(&Value::Bool(false)) && env::var_os("NO_COLOR").is_none() {
+            env::set_var("NO_COLOR", "1");
+            self.applied.push(("color", "NO_COLOR"));
+        }
+        Ok(())
+    }
+
+    /// Makes the settings the defaults of the flags of the same name in `command` and its subcommands.
+    pub fn apply_defaults(&self, command: clap::Command) -> Result<clap::Command> {
+        let mut command = command;
+        for (key, value) in &self.values {
+            if key == "color"
+                || ENV_SETTINGS.iter().any(|(k, _)| k == key)
+                || key.starts_with(&format!("{}.", exit_codes::TABLE))
+            {
+                continue;
+            }
+            let file = self.file(key).unwrap_or(Path::new("?"));
+            let (scope, flag) = match key.split_once('.') {
+                Some((scope, flag)) => (Some(scope), flag),
+                None => (None, key.as_str()),
+            };
+            let long = flag.replace('_', "-");
+            let values = match value {
+                Value::String(text) => vec![text.clone()],
+                Value::Bool(flag) => vec![flag.to_string()],
+                Value::Integer(number) => vec![number.to_string()],
+                Value::List(items) => items.clone(),
+            };
+            let mut applied = false;
+            command = match scope {
+                None => set_default(command, &long, &values, &mut applied),
+                Some(scope) => {
+                    if command.find_subcommand(scope).is_none() {
+                        bail!("Invalid settings in {:?}: there is no command \"{}\"", file, scope);
+                    }
+                    command.mut_subcommand(scope, |sub| set_default(sub, &long, &values, &mut applied))
+                }
+            };
+            if !applied {
+                bail!(
+                    "Invalid settings in {:?}: \"{}\" is no flag{} accepting {:?}",
+                    file,
+                    flag,
+                    scope.map(|scope| format!(" of {}", scope)).unwrap_or_default(),
+                    values.join(",")
+                );
+            }
+        }
+        Ok(command)
+    }
+
+    /// Writes the settings to `path` as TOML.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let mut content = String::from("# Settings of score-cli, written by `score-cli init`.\n");
+        let (top, tables): (Vec<_>, Vec<_>) = self.values.iter().partition(|(key, _)| !key.contains('.'));
+        for (key, value) in top {
+            let _ = writeln!(content, "{} = {}", key, format(value));
+        }
+        let mut table = "";
+        for (key, value) in tables {
+            let (scope, key) = key.split_once('.').expect("partitioned");
+            if scope != table {
+                let _ = writeln!(content, "\n[{}]", scope);
+                table = scope;
+            }
+            let _ = writeln!(content, "{} = {}", key, format(value));
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create directory {:?}", dir))?;
+        }
+        fs::write(path, content).with_context(|| format!("Failed to write settings {:?}", path))
+    }
+}
+
+/// Sets the default of the flag `--long` in `command` and its subcommands where it accepts `values`.
+fn set_default(command: clap::Command, long: &str, values: &[String], applied: &mut bool) -> clap::Command {
+    let mut command = command;
+    let arg = command.get_arguments().find(|arg| arg.get_long() == Some(long));
+    if let Some(arg) = arg {
+        let possible = arg.get_possible_values();
+        let accepted = possible.is_empty()
+            || values
+                .iter()
+                .all(|value| possible.iter().any(|p| p.matches(value, arg.is_ignore_case_set())));
+        if accepted {
+            let id = arg.get_id().to_string();
+            // Defaults are static without clap's `string` feature; the command lives as long as the process anyway.
+            let values: Vec<&'static str> = values
+                .iter()
+                .map(|value| &*Box::leak(value.clone().into_boxed_str()))
+                .collect();
+            // A flag with a default is no longer required, like `--duration` of soak.
+            command = command.mut_arg(id, |arg| arg.default_values(values).required(false));
+            *applied = true;
+        }
+    }
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |sub| set_default(sub, long, values, applied));
+    }
+    command
+}
+
+fn check(key: &str, value: &Value) -> Result<()> {
+    let expected = match key {
+        "root" if matches!(value, Value::String(_) | Value::List(_)) => return Ok(()),
+        "root" => "a string or a list of strings",
+        "color" if matches!(value, Value::Bool(_)) => return Ok(()),
+        "color" => "true or false",
+        "log_dir" | "backend" if matches!(value, Value::String(_)) => return Ok(()),
+        "log_dir" | "backend" => "a string",
+        _ => match key.strip_prefix(exit_codes::TABLE).and_then(|k| k.strip_prefix('.')) {
+            Some(category) if !exit_codes::CATEGORIES.contains(&category) => {
+                bail!(
+                    "\"{}\" is no result category, expected one of {}",
+                    category,
+                    exit_codes::CATEGORIES.join(", ")
+                )
+            }
+            Some(_) if matches!(value, Value::Integer(0..=255)) => return Ok(()),
+            Some(_) => "an exit code from 0 to 255",
+            None => return Ok(()),
+        },
+    };
+    bail!("setting \"{}\" has to be {}", key, expected)
+}
+
+fn resolve_paths(value: &mut Value, dir: &Path) {
+    let resolve = |path: &mut String| {
+        if Path::new(path).is_relative() {
+            *path = dir.join(&*path).display().to_string();
+        }
+    };
+    match value {
+        Value::String(path) => resolve(path),
+        Value::List(paths) => paths.iter_mut().for_each(resolve),
+        _ => {}
+    }
+}
+
+fn parse(content: &str) -> Result<Settings> {
+    let mut settings = Settings::default();
+    let mut table = None;
+    for (number, line) in content.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            let Some(name) = name.strip_suffix(']').map(str::trim).filter(|name| is_key(name)) else {
+                bail!("line {}: invalid table {}", number + 1, line);
+            };
+            table = Some(name.to_string());
+            continue;
+        }
+        let (key, value) = parse_line(line).with_context(|| format!("line {}", number + 1))?;
+        let key = match &table {
+            Some(table) => format!("{}.{}", table, key),
+            None => key,
+        };
+        if settings.get(&key).is_some() {
+            bail!("line {}: \"{}\" is set twice", number + 1, key);
+        }
+        settings.values.push((key, value));
+    }
+    Ok(settings)
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn parse_line(line: &str) -> Result<(String, Value)> {
+    let Some((key, value)) = line.split_once('=') else {
+        bail!("expected key = value, got \"{}\"", line);
+    };
+    let key = key.trim();
+    if !is_key(key) {
+        bail!("invalid key \"{}\"", key);
+    }
+    let value = value.trim();
+    let value = match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ if value.starts_with('"') => Value::String(parse_string(value)?),
+        _ if value.starts_with('[') => {
+            let Some(items) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) else {
+                bail!("unterminated list {}", value);
+            };
+            let items = split_list(items)?;
+            Value::List(items.iter().map(|item| parse_string(item)).collect::<Result<_>>()?)
+        }
+        _ => Value::Integer(
+            value
+                .replace('_', "")
+                .parse()
+                .map_err(|_| anyhow::anyhow!("invalid value {}, quote strings", value))?,
+        ),
+    };
+    Ok((key.to_string(), value))
+}
+
+/// A basic TOML string like `"a \"b\""`.
+fn parse_string(text: &str) -> Result<String> {
+    let text = text.trim();
+    let Some(inner) = text
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .filter(|_| text.len() >= 2)
+    else {
+        bail!("expected a quoted string, got {}", text);
+    };
+    let mut value = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => value.push('"'),
+            Some('\\') => value.push('\\'),
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            other => bail!("unsupported escape \\{} in {}", other.unwrap_or(' '), text),
+        }
+    }
+    Ok(value)
+}
+
+/// Items of a list, split at commas outside strings.
+fn split_list(items: &str) -> Result<Vec<&str>> {
+    let mut result = Vec::new();
+    let (mut start, mut quoted, mut escaped) = (0, false, false);
+    for (i, c) in items.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                result.push(&items[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if quoted {
+        bail!("unterminated string in [{}]", items);
+    }
+    result.push(&items[start..]);
+    Ok(result.into_iter().filter(|item| !item.trim().is_empty()).collect())
+}
+
+/// The line without a `#` comment outside strings.
+fn strip_comment(line: &str) -> &str {
+    let (mut quoted, mut escaped) = (false, false);
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn format(value: &Value) -> String {
+    let quote = |text: &str| {
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+            .replace('\t', "\\t");
+        format!("\"{}\"", escaped)
+    };
+    match value {
+        Value::String(text) => quote(text),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Integer(number) => number.to_string(),
+        Value::List(items) => format!(
+            "[{}]",
+            items.iter().map(|item| quote(item)).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format, parse_line, Value};
+
+    #[test]
+    fn values_survive_a_round_trip() {
+        let values = [
+            Value::String("C:\\demo \"quoted\"\nnext line\tand tab".to_string()),
+            Value::List(vec!["a\nb".to_string(), "c\t\"d\"".to_string()]),
+            Value::Bool(true),
+            Value::Integer(42),
+        ];
+        for value in values {
+            let line = format!("key = {}", format(&value));
+            assert!(!line.contains(['\n', '\t']), "{:?} is written raw", line);
+            assert_eq!(parse_line(&line).unwrap(), ("key".to_string(), value));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Detached signatures of run reports, making them tamper-evident as evidence.
+//!
+//! Signing and verification use `openssl dgst` with SHA-256, so any RSA or EC
+//! key in PEM format works and signatures can be checked without this CLI.
+
+use anyhow::{bail, Context, Result};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Path of the detached signature of `report`.
+pub fn signature_path(report: &Path) -> PathBuf {
+    let mut name = report.as_os_str().to_os_string();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+/// Signs `report` with the private key `key`, writing the signature next to it.
+pub fn sign(report: &Path, key: &Path) -> Result<PathBuf> {
+    let signature = signature_path(report);
+    let output = openssl(
+        Command::new("openssl")
+            .args(["dgst", "-sha256", "-sign"])
+            .arg(key)
+            .arg("-out")
+            .arg(&signature)
+            .arg(report),
+    )?;
+    if !output.status.success() {
+        bail!(
+            "Failed to sign {:?} with {:?}: {}",
+            report,
+            key,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(signature)
+}
+
+/// Checks the signature of `file`, a report or a release, against the public key `key`.
+pub fn verify(file: &Path, signature: &Path, key: &Path) -> Result<()> {
+    if !signature.is_file() {
+        bail!("No signature {:?} found for {:?}", signature, file);
+    }
+    let output = openssl(
+        Command::new("openssl")
+            .args(["dgst", "-sha256", "-verify"])
+            .arg(key)
+            .arg("-signature")
+            .arg(signature)
+            .arg(file),
+    )?;
+    if !output.status.success() {
+        bail!(
+            "Signature of {:?} does NOT match: it was modified or signed with another key ({})",
+            file,
+            String::from_utf8_lossy(&output.stdout).trim()
+        );
+    }
+    Ok(())
+}
+
+fn openssl(cmd: &mut Command) -> Result<Output> {
+    match cmd.output() {
+        Ok(output) => Ok(output),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            bail!("`openssl` is required to sign and verify reports and releases but was not found in PATH")
+        }
+        Err(e) => Err(e).context("Failed to run `openssl`"),
+    }
+}
@@ -0,0 +1,171 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Soak mode: keep an example running for a long time and watch its resource usage.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::monitor::Monitor;
+use crate::procfs::{self, ProcessSample};
+use crate::report::{AppReport, Status};
+use crate::{new_report, new_run_id, random_seed, run_score_with, slug, Run, RunOptions, RunningApp, ScoreConfig};
+
+/// Growth between first and last sample above which a monotonic trend is flagged.
+const LEAK_THRESHOLD_PERCENT: f64 = 10.0;
+
+struct SoakMonitor {
+    start: Instant,
+    duration: Duration,
+    interval: Duration,
+    next_sample: Instant,
+    samples: HashMap<usize, Vec<ProcessSample>>,
+}
+
+impl Monitor for SoakMonitor {
+    fn poll(&mut self, apps: &[&RunningApp]) -> bool {
+        let now = Instant::now();
+        if now >= self.next_sample {
+            self.next_sample = now + self.interval;
+            let elapsed = now - self.start;
+            for app in apps {
+                if let Some(sample) = procfs::sample(app.child.id(), elapsed) {
+                    println!(
+                        "Snapshot {:>6}s app {}: rss {} kB, cpu {:.1}s, {} fds",
+                        sample.elapsed_s, app.index, sample.rss_kb, sample.cpu_s, sample.fds
+                    );
+                    self.samples.entry(app.index).or_default().push(sample);
+                }
+            }
+        }
+        now - self.start >= self.duration
+    }
+}
+
+/// Runs `config` for `duration`, sampling every `interval`, and writes the soak report.
+///
+/// Returns whether the soak passed: all apps stayed alive and no growth trend was flagged.
+pub fn run(config: &ScoreConfig, duration: Duration, interval: Duration, options: &RunOptions) -> Result<bool> {
+    if !config.steps.is_empty() {
+        bail!(
+            "Soak mode runs an example with apps, '{}' is a composite example",
+            config.name
+        );
+    }
+    if let Some(reason) = config.skip_reason()? {
+        bail!("Example '{}' cannot run on this host: {}", config.name, reason);
+    }
+    let run = Run::new(new_run_id(), options.seed.unwrap_or_else(random_seed));
+    let run_dir = crate::run_dir(options, &run.id)?;
+    let example_dir = run_dir.join(slug(&config.name));
+    fs::create_dir_all(&example_dir).with_context(|| format!("Failed to create run directory {:?}", example_dir))?;
+    run.trace.write_to(run_dir.join("trace.json"));
+
+    println!(
+        "Soaking '{}' for {:?}, snapshots every {:?} (run {})",
+        config.name, duration, interval, run.id
+    );
+    if !procfs::SUPPORTED {
+        println!("Resource snapshots need Linux /proc, only checking that the apps stay alive");
+    }
+    let now = Instant::now();
+    let mut monitor = SoakMonitor {
+        start: now,
+        duration,
+        interval,
+        next_sample: now + interval,
+        samples: HashMap::new(),
+    };
+    let disk_guard = crate::disk_guard::start(&run_dir, options.min_free_mb, &run.log_pause);
+    let mut report = new_report(&run.id, run.seed, &run_dir, options);
+    run.checkpoint.start_example(&report, config, &example_dir);
+    let mut example = run_score_with(config, options, &run, &example_dir, &mut monitor)?;
+    let warnings = disk_guard.map(crate::disk_guard::Guard::finish).unwrap_or_default();
+    run.trace.finish()?;
+
+    let mut passed = true;
+    let ended_early = monitor.start.elapsed() < duration;
+    for app in example.apps.iter_mut() {
+        app.samples = monitor.samples.remove(&app.index).unwrap_or_default();
+        if ended_early {
+            app.warnings
+                .push("example ended before the soak duration elapsed".to_string());
+        }
+        app.warnings.extend(growth_warnings(&app.samples));
+        passed &= app.warnings.is_empty();
+        print_summary(app);
+    }
+    example.status = Status::of(passed, config.xfail.is_some());
+
+    report.warnings = warnings;
+    report.examples.push(example);
+    crate::sbom::enrich(&mut report, &crate::examples_roots());
+    let report_path = run_dir.join("report.json");
+    report.write(&report_path)?;
+    run.checkpoint.remove();
+    println!("Soak report written to {}", report_path.display());
+    if let Some(ref key) = options.sign_key {
+        let signature = crate::signing::sign(&report_path, key)?;
+        println!("Soak report signed: {}", signature.display());
+    }
+    Ok(passed)
+}
+
+/// Flags values that never decreased and grew noticeably over the soak: a leak heuristic.
+fn growth_warnings(samples: &[ProcessSample]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if samples.len() < 3 {
+        return warnings;
+    }
+    let series: [(&str, Vec<f64>); 2] = [
+        ("RSS", samples.iter().map(|s| s.rss_kb as f64).collect()),
+        ("file descriptors", samples.iter().map(|s| s.fds as f64).collect()),
+    ];
+    for (what, values) in series {
+        let monotonic = values.windows(2).all(|w| w[1] >= w[0]);
+        let (first, last) = (values[0], values[values.len() - 1]);
+        let growth = if first > 0.0 {
+            (last - first) * 100.0 / first
+        } else {
+            0.0
+        };
+        if monotonic && growth > LEAK_THRESHOLD_PERCENT {
+            warnings.push(format!(
+                "{} grew monotonically by {:.1}% ({} -> {}), possible leak",
+                what, growth, first, last
+            ));
+        }
+    }
+    warnings
+}
+
+fn print_summary(app: &AppReport) {
+    match (app.samples.first(), app.samples.last()) {
+        (Some(first), Some(last)) => {
+            let cpu_percent = if last.elapsed_s > first.elapsed_s {
+                (last.cpu_s - first.cpu_s) * 100.0 / (last.elapsed_s - first.elapsed_s) as f64
+            } else {
+                0.0
+            };
+            println!(
+                "App {} ({}): rss {} -> {} kB, fds {} -> {}, avg cpu {:.1}%",
+                app.index, app.path, first.rss_kb, last.rss_kb, first.fds, last.fds, cpu_percent
+            );
+        }
+        _ => println!("App {} ({}): no samples", app.index, app.path),
+    }
+    for warning in &app.warnings {
+        println!("  ⚠ {}", warning);
+    }
+}
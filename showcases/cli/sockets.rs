@@ -0,0 +1,135 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Sockets opened by the runner and passed to apps (socket activation).
+//!
+//! All sockets of an example are bound before its first app starts, so clients
+//! can connect right away and cooperating apps never race for a port. Apps get
+//! them as descriptors 3, 4, ... following the systemd `LISTEN_FDS` convention:
+//! `LISTEN_FDS` holds their number, `LISTEN_FDNAMES` their names separated by
+//! `:` and `LISTEN_PID` the pid of the app. As `LISTEN_PID` is only known after
+//! the fork, the app is started through `<cli> builtin listen-pid`, which sets
+//! it and then execs the app in the same process.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::net::{TcpListener, UdpSocket};
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::os::unix::net::UnixListener;
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::sys;
+
+/// Address of a socket to open.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Listen {
+    /// TCP listener on "host:port"
+    Tcp(String),
+    /// UDP socket bound to "host:port"
+    Udp(String),
+    /// Unix stream listener at this path
+    Unix(String),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SocketConfig {
+    #[serde(flatten)]
+    pub listen: Listen,
+    pub name: Option<String>, // entry in LISTEN_FDNAMES (default: "unknown", like systemd)
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+/// Sockets opened for an example. Apps declaring the same address share one socket.
+///
+/// The sockets are closed and unix socket files removed when this is dropped.
+#[derive(Default)]
+pub struct Sockets {
+    open: HashMap<Listen, OwnedFd>,
+    unix_paths: Vec<PathBuf>,
+}
+
+impl Sockets {
+    /// Opens the sockets of all `configs`.
+    pub fn open<'a>(configs: impl IntoIterator<Item = &'a SocketConfig>) -> Result<Sockets> {
+        let mut sockets = Sockets::default();
+        for config in configs {
+            if sockets.open.contains_key(&config.listen) {
+                continue;
+            }
+            let fd: OwnedFd = match &config.listen {
+                Listen::Tcp(address) => TcpListener::bind(address)
+                    .with_context(|| format!("Failed to listen on TCP {}", address))?
+                    .into(),
+                Listen::Udp(address) => UdpSocket::bind(address)
+                    .with_context(|| format!("Failed to bind UDP {}", address))?
+                    .into(),
+                Listen::Unix(path) => {
+                    // A socket file left behind by a crashed run would make bind fail.
+                    let _ = std::fs::remove_file(path);
+                    let listener = UnixListener::bind(path)
+                        .with_context(|| format!("Failed to listen on unix socket {}", path))?;
+                    sockets.unix_paths.push(PathBuf::from(path));
+                    listener.into()
+                }
+            };
+            sockets.open.insert(config.listen.clone(), fd);
+        }
+        Ok(sockets)
+    }
+
+    /// Makes `cmd` pass the sockets of `configs` to its app, in their order.
+    ///
+    /// `cmd` must run the app through `builtin listen-pid`.
+    pub fn pass(&self, cmd: &mut Command, configs: &[SocketConfig]) -> Result<()> {
+        if configs.len() > sys::MAX_PASSED_FDS {
+            bail!("at most {} sockets can be passed to an app", sys::MAX_PASSED_FDS);
+        }
+        let mut fds = [0; sys::MAX_PASSED_FDS];
+        for (fd, config) in fds.iter_mut().zip(configs) {
+            *fd = self.open[&config.listen].as_raw_fd();
+        }
+        let names: Vec<&str> = configs.iter().map(|c| c.name.as_deref().unwrap_or("unknown")).collect();
+        cmd.env("LISTEN_FDS", configs.len().to_string());
+        cmd.env("LISTEN_FDNAMES", names.join(":"));
+        let count = configs.len();
+        // SAFETY: the closure only issues fcntl, dup2 and close on descriptors, which are async-signal-safe.
+        unsafe {
+            cmd.pre_exec(move || sys::pass_fds(&fds[..count]));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Sockets {
+    fn drop(&mut self) {
+        for path in &self.unix_paths {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Sets `LISTEN_PID` to the pid of this process and replaces it with `command`.
+pub fn exec_with_listen_pid(command: &[String]) -> Result<()> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("no command given");
+    };
+    let err = Command::new(program)
+        .args(args)
+        .env("LISTEN_PID", std::process::id().to_string())
+        .exec();
+    Err(err).with_context(|| format!("Failed to execute {}", program))
+}
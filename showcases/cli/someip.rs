@@ -0,0 +1,163 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! SOME/IP service discovery smoke verification.
+//!
+//! Listens on the SD multicast group while an example starts and checks that
+//! every expected service gets offered before the timeout expires.
+
+use anyhow::{bail, Context, Result};
+use serde::{de, Deserialize, Deserializer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::Ipv4Addr;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::sys;
+
+const SD_SERVICE_ID: u16 = 0xFFFF;
+const SD_METHOD_ID: u16 = 0x8100;
+const SOMEIP_HEADER_LEN: usize = 16;
+const SD_ENTRY_LEN: usize = 16;
+const ENTRY_TYPE_OFFER_SERVICE: u8 = 0x01;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SomeIpSdConfig {
+    /// Service IDs that must be offered, as numbers or hex strings ("0x1234").
+    #[serde(deserialize_with = "deserialize_service_ids")]
+    pub services: Vec<u16>,
+    #[serde(default = "default_timeout")]
+    pub timeout: u64, // seconds
+    #[serde(default = "default_group")]
+    pub group: Ipv4Addr,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default = "default_interface")]
+    pub interface: Ipv4Addr, // local address used to join the group
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, serde_json::Value>, // fields the runner does not know, errors with --strict
+}
+
+fn default_timeout() -> u64 {
+    10
+}
+
+fn default_group() -> Ipv4Addr {
+    Ipv4Addr::new(224, 244, 224, 245)
+}
+
+fn default_port() -> u16 {
+    30490
+}
+
+fn default_interface() -> Ipv4Addr {
+    Ipv4Addr::UNSPECIFIED
+}
+
+fn deserialize_service_ids<'de, D>(deserializer: D) -> Result<Vec<u16>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ServiceId {
+        Number(u16),
+        Text(String),
+    }
+
+    Vec::<ServiceId>::deserialize(deserializer)?
+        .into_iter()
+        .map(|id| match id {
+            ServiceId::Number(n) => Ok(n),
+            ServiceId::Text(s) => {
+                let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                    Some(hex) => u16::from_str_radix(hex, 16),
+                    None => s.parse(),
+                };
+                parsed.map_err(|_| de::Error::custom(format!("Invalid service id \"{}\"", s)))
+            }
+        })
+        .collect()
+}
+
+/// Joins the SD multicast group and starts collecting offers in the background.
+///
+/// The socket is bound before returning, so offers sent by apps started afterwards are not missed.
+pub fn start_verification(config: &SomeIpSdConfig) -> Result<JoinHandle<Result<()>>> {
+    let socket = sys::reusable_udp_socket(Ipv4Addr::UNSPECIFIED, config.port)
+        .with_context(|| format!("Failed to bind SOME/IP SD port {}", config.port))?;
+    socket
+        .join_multicast_v4(&config.group, &config.interface)
+        .with_context(|| format!("Failed to join SOME/IP SD multicast group {}", config.group))?;
+
+    let config = config.clone();
+    Ok(std::thread::spawn(move || {
+        let deadline = Instant::now() + Duration::from_secs(config.timeout);
+        let mut missing: BTreeSet<u16> = config.services.iter().copied().collect();
+        let mut buf = [0u8; 65536];
+
+        while !missing.is_empty() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            socket.set_read_timeout(Some(remaining))?;
+            let len = match socket.recv(&mut buf) {
+                Ok(len) => len,
+                Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => break,
+                Err(e) => return Err(e).context("Failed to receive SOME/IP SD message"),
+            };
+            for service in offered_services(&buf[..len]) {
+                if missing.remove(&service) {
+                    println!("SOME/IP SD: service 0x{:04x} offered", service);
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            let ids: Vec<String> = missing.iter().map(|id| format!("0x{:04x}", id)).collect();
+            bail!(
+                "SOME/IP SD: services not offered within {}s: {}",
+                config.timeout,
+                ids.join(", ")
+            );
+        }
+        Ok(())
+    }))
+}
+
+/// Extracts the service IDs of all (non-stop) OfferService entries of an SD message.
+fn offered_services(msg: &[u8]) -> Vec<u16> {
+    let mut services = Vec::new();
+    if msg.len() < SOMEIP_HEADER_LEN + 8 {
+        return services;
+    }
+    let service_id = u16::from_be_bytes([msg[0], msg[1]]);
+    let method_id = u16::from_be_bytes([msg[2], msg[3]]);
+    if service_id != SD_SERVICE_ID || method_id != SD_METHOD_ID {
+        return services;
+    }
+
+    // SD payload: flags + reserved (4 bytes), entries array length (4 bytes), entries.
+    let payload = &msg[SOMEIP_HEADER_LEN..];
+    let entries_len = u32::from_be_bytes([payload[4], payload[5], payload[6], payload[7]]) as usize;
+    let entries = &payload[8..];
+    let entries = &entries[..entries_len.min(entries.len())];
+
+    for entry in entries.chunks_exact(SD_ENTRY_LEN) {
+        let ttl = u32::from_be_bytes([0, entry[9], entry[10], entry[11]]);
+        if entry[0] == ENTRY_TYPE_OFFER_SERVICE && ttl > 0 {
+            services.push(u16::from_be_bytes([entry[4], entry[5]]));
+        }
+    }
+    services
+}
@@ -0,0 +1,176 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Minimal HTTP/1.1 server for static files, the `static-server` built-in app.
+//!
+//! Serves GET and HEAD requests for files below a root directory, with
+//! `index.html` for directories. Every request is logged as one output line.
+//! It listens on the loopback interface unless another address is given, and
+//! a fixed number of workers handles the connections; when all of them are
+//! busy and the queue is full, new connections get `503 Service Unavailable`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Limit of the request line and headers together.
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+const WORKERS: usize = 8;
+/// Accepted connections waiting for a worker.
+const QUEUE: usize = 32;
+
+pub fn serve(root: &Path, bind: &str, port: u16) -> Result<()> {
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Cannot serve {:?}", root))?;
+    let listener = TcpListener::bind((bind, port)).with_context(|| format!("Failed to listen on {}:{}", bind, port))?;
+    println!("Serving {} on http://{}/", root.display(), listener.local_addr()?);
+
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(QUEUE);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..WORKERS {
+        let (root, receiver) = (root.clone(), receiver.clone());
+        std::thread::spawn(move || loop {
+            let Ok(stream) = receiver.lock().unwrap().recv() else {
+                return;
+            };
+            if let Err(e) = handle(&root, stream) {
+                eprintln!("Request failed: {:#}", e);
+            }
+        });
+    }
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        if let Err(TrySendError::Full(mut stream)) = sender.try_send(stream) {
+            println!("Too many connections, refusing one");
+            let _ = respond(&mut stream, "HEAD", "503 Service Unavailable", "text/plain", b"");
+        }
+    }
+    Ok(())
+}
+
+fn handle(root: &Path, mut stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_HEAD_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers, nothing in them changes the response.
+    let mut header = String::new();
+    loop {
+        header.clear();
+        match reader.read_line(&mut header)? {
+            0 if reader.get_ref().limit() == 0 => {
+                println!("Request with more than {} bytes of headers refused", MAX_HEAD_BYTES);
+                let body = b"Request header fields too large\n";
+                return respond(
+                    &mut stream,
+                    "GET",
+                    "431 Request Header Fields Too Large",
+                    "text/plain",
+                    body,
+                );
+            }
+            0..=2 => break,
+            _ => {}
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    let path = target.split(['?', '#']).next().unwrap_or("/");
+    let (status, content_type, body) = match method {
+        "GET" | "HEAD" => match resolve(root, path).and_then(|file| Some((fs::read(&file).ok()?, file))) {
+            Some((body, file)) => ("200 OK", content_type(&file), body),
+            None => ("404 Not Found", "text/plain", b"Not found\n".to_vec()),
+        },
+        _ => ("405 Method Not Allowed", "text/plain", b"Method not allowed\n".to_vec()),
+    };
+    println!("{} {} -> {}", method, target, status);
+    respond(&mut stream, method, status, content_type, &body)
+}
+
+fn respond(stream: &mut TcpStream, method: &str, status: &str, content_type: &str, body: &[u8]) -> Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    )?;
+    if method != "HEAD" {
+        stream.write_all(body)?;
+    }
+    Ok(())
+}
+
+/// Maps a request path to a file below `root`, refusing paths leaving it.
+fn resolve(root: &Path, path: &str) -> Option<PathBuf> {
+    let mut file = root.to_path_buf();
+    for component in Path::new(&percent_decode(path)).components() {
+        match component {
+            Component::Normal(part) => file.push(part),
+            Component::RootDir | Component::CurDir => {}
+            Component::ParentDir | Component::Prefix(_) => return None,
+        }
+    }
+    if file.is_dir() {
+        file.push("index.html");
+    }
+    // Symlinks may point outside the root.
+    let file = file.canonicalize().ok()?;
+    (file.starts_with(root) && file.is_file()).then_some(file)
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn content_type(file: &Path) -> &'static str {
+    match file.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "ico" => "image/x-icon",
+        "wasm" => "application/wasm",
+        "txt" | "log" | "csv" => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
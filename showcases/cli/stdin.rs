@@ -0,0 +1,54 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Data fed to the standard input of an app, so demos reading commands run unattended.
+//!
+//! The data is written on a thread and stdin is closed afterwards, so the app
+//! sees the end of its input like with `app < input.txt`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::io::Write;
+use std::process::Child;
+use std::sync::Arc;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Input {
+    /// The content of this file
+    File(String),
+    /// This text, e.g. "start\n"
+    Literal(String),
+}
+
+impl Input {
+    /// The bytes to feed, read before the app starts so a missing file fails its start.
+    pub fn read(&self) -> Result<Arc<[u8]>> {
+        match self {
+            Input::File(path) => Ok(std::fs::read(path)
+                .with_context(|| format!("Failed reading stdin file {}", path))?
+                .into()),
+            Input::Literal(text) => Ok(text.as_bytes().into()),
+        }
+    }
+}
+
+/// Writes `data` to the piped stdin of `child` and closes it.
+pub fn feed(child: &mut Child, data: Arc<[u8]>) {
+    let Some(mut stdin) = child.stdin.take() else {
+        return;
+    };
+    std::thread::spawn(move || {
+        // Apps may exit without reading everything, which is no error of the runner.
+        let _ = stdin.write_all(&data);
+    });
+}
@@ -0,0 +1,123 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Composite examples, running other examples one after another as steps.
+//!
+//! All steps are part of the same run: they share its run id, seed, trace and
+//! artifacts directory, where every step gets a sub directory.
+
+use anyhow::{bail, Result};
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::report::{ExampleReport, Status};
+use crate::{run_config, slug, Run, RunOptions, ScoreConfig};
+
+/// Checks that all steps exist and no composite example contains itself.
+pub fn validate(configs: &[ScoreConfig]) -> Result<()> {
+    for config in configs.iter().filter(|c| !c.steps.is_empty()) {
+        if !config.apps.is_empty() {
+            bail!("Example '{}' has both apps and steps, use one of them", config.name);
+        }
+        let mut path = vec![config.name.as_str()];
+        check_steps(configs, config, &mut path)?;
+    }
+    Ok(())
+}
+
+fn check_steps<'a>(configs: &'a [ScoreConfig], config: &'a ScoreConfig, path: &mut Vec<&'a str>) -> Result<()> {
+    for step in &config.steps {
+        let Some(target) = configs.iter().find(|c| c.name == *step) else {
+            bail!("Example '{}': step '{}' is no known example", config.name, step);
+        };
+        if path.contains(&target.name.as_str()) {
+            bail!(
+                "Example '{}': steps form a cycle: {} -> {}",
+                path[0],
+                path.join(" -> "),
+                step
+            );
+        }
+        path.push(&target.name);
+        check_steps(configs, target, path)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+/// Runs the steps of `config` in order, stopping at the first failing step.
+pub fn run(
+    configs: &[ScoreConfig],
+    config: &ScoreConfig,
+    options: &RunOptions,
+    run: &Run,
+    example_dir: &Path,
+) -> Result<ExampleReport> {
+    println!(
+        "▶ Running composite example: {} ({} steps)",
+        config.name,
+        config.steps.len()
+    );
+    let start = Instant::now();
+    let mut steps = Vec::new();
+    let mut failed = None;
+    for (i, name) in config.steps.iter().enumerate() {
+        let step = configs
+            .iter()
+            .find(|c| c.name == *name)
+            .expect("steps are validated when loading");
+        if let Some(ref failed) = failed {
+            let reason = format!("step '{}' failed", failed);
+            steps.push(ExampleReport::skipped(&step.name, &step.requirements, reason));
+            continue;
+        }
+        if options.stop_requested() {
+            steps.push(ExampleReport::skipped(
+                &step.name,
+                &step.requirements,
+                options.stop_reason(),
+            ));
+            continue;
+        }
+        println!("━━ Step {}/{}: {}", i + 1, config.steps.len(), step.name);
+        let step_dir = example_dir.join(format!("{:02}-{}", i + 1, slug(&step.name)));
+        fs::create_dir_all(&step_dir)?;
+        let report = run_config(configs, step, options, run, &step_dir)?;
+        if matches!(report.status, Status::Failed | Status::Xfail) {
+            failed = Some(step.name.clone());
+        }
+        steps.push(report);
+    }
+
+    let status = Status::of(failed.is_none(), config.xfail.is_some());
+    match (status, &failed) {
+        (Status::Passed, _) => println!("✅ Composite example '{}' finished successfully.", config.name),
+        (Status::Xpass, _) => println!("✅ Composite example '{}' passed although marked xfail.", config.name),
+        (_, Some(step)) => println!("❌ Composite example '{}' failed at step '{}'.", config.name, step),
+        (_, None) => {}
+    }
+    Ok(ExampleReport {
+        name: config.name.clone(),
+        requirements: config.requirements.clone(),
+        status,
+        reason: failed.map(|step| format!("step '{}' failed", step)),
+        infra_error: false,
+        attempts: 1,
+        duration_ms: start.elapsed().as_millis(),
+        apps: Vec::new(),
+        first_failure: None,
+        steps,
+        cgroup: None,
+        kpis: Vec::new(),
+    })
+}
@@ -0,0 +1,120 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Stress mode: repeat one example to surface flaky behavior.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::time::Duration;
+
+use crate::{new_report, new_run_id, random_seed, run_score, slug, Run, RunOptions, ScoreConfig};
+
+/// Runs `config` up to `iterations` times and prints pass/fail statistics.
+///
+/// All iterations share one run id, naming the run directory, and are told apart
+/// by their number. Artifacts of passing iterations are removed, those of failing
+/// ones are kept.
+/// Returns the number of failed iterations.
+pub fn run(config: &ScoreConfig, iterations: u32, until_failure: bool, options: &RunOptions) -> Result<u32> {
+    if !config.steps.is_empty() {
+        bail!(
+            "Stress mode repeats an example with apps, '{}' is a composite example",
+            config.name
+        );
+    }
+    if let Some(reason) = config.skip_reason()? {
+        bail!("Example '{}' cannot run on this host: {}", config.name, reason);
+    }
+    // One id for all iterations, so the run directory and SCORE_RUN_ID of the apps match.
+    let run_id = new_run_id();
+    let run_dir = crate::run_dir(options, &run_id)?;
+    let mut report = new_report(&run_id, 0, &run_dir, options);
+    let mut durations = Vec::new();
+    let mut failures = 0;
+
+    for iteration in 1..=iterations {
+        if options.stop_requested() {
+            println!("Stop file found, stopping after {} iterations", iteration - 1);
+            print_statistics(iteration - 1, failures, &durations);
+            return Ok(failures);
+        }
+        let run = Run::new(run_id.clone(), options.seed.unwrap_or_else(random_seed));
+        let iteration_dir = run_dir.join(format!("iteration-{}", iteration));
+        let example_dir = iteration_dir.join(slug(&config.name));
+        fs::create_dir_all(&example_dir)
+            .with_context(|| format!("Failed to create run directory {:?}", example_dir))?;
+        report.seed = run.seed;
+        run.checkpoint.start_example(&report, config, &example_dir);
+
+        println!(
+            "━━ Iteration {}/{} (seed {}, run {})",
+            iteration, iterations, run.seed, run.id
+        );
+        let failure = match run_score(config, options, &run, &example_dir) {
+            Ok(report) => {
+                durations.push(Duration::from_millis(report.duration_ms as u64));
+                let failed: Vec<String> = report
+                    .apps
+                    .iter()
+                    .filter(|app| app.exit_code != Some(0) && !app.stopped)
+                    .map(|app| match app.exit_code {
+                        Some(code) => format!("app {} ({}) exited with code {}", app.index, app.path, code),
+                        None => format!("app {} ({}) was killed by a signal", app.index, app.path),
+                    })
+                    .collect();
+                (!failed.is_empty()).then(|| failed.join(", "))
+            }
+            Err(e) => Some(format!("{:#}", e)),
+        };
+        run.checkpoint.remove();
+
+        run.trace.write(&iteration_dir.join("trace.json"))?;
+        match failure {
+            None => {
+                let _ = fs::remove_dir_all(&iteration_dir);
+            }
+            Some(reason) => {
+                failures += 1;
+                println!(
+                    "❌ Iteration {} failed (seed {}, run {}): {}. Artifacts kept in {}",
+                    iteration,
+                    run.seed,
+                    run.id,
+                    reason,
+                    iteration_dir.display()
+                );
+                if until_failure {
+                    print_statistics(iteration, failures, &durations);
+                    return Ok(failures);
+                }
+            }
+        }
+    }
+
+    print_statistics(iterations, failures, &durations);
+    Ok(failures)
+}
+
+fn print_statistics(runs: u32, failures: u32, durations: &[Duration]) {
+    println!("━━ Stress statistics");
+    println!("  iterations: {}", runs);
+    println!("  passed:     {}", runs - failures);
+    println!("  failed:     {}", failures);
+    println!(
+        "  flake rate: {:.2}%",
+        f64::from(failures) * 100.0 / f64::from(runs.max(1))
+    );
+    if let (Some(min), Some(max)) = (durations.iter().min(), durations.iter().max()) {
+        let avg = durations.iter().sum::<Duration>() / durations.len() as u32;
+        println!("  duration:   min {:?}, avg {:?}, max {:?}", min, avg, max);
+    }
+}
@@ -0,0 +1,234 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Checks of `--strict`, which turns soft problems into errors.
+//!
+//! Without `--strict`, unknown fields are ignored, deprecated fields are used
+//! with a warning, and features whose program is missing are left out with a
+//! warning. With it, each of these fails the example, or the run before it
+//! starts.
+
+use anyhow::{bail, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::completion::{self, Action};
+use crate::instrument::Instrument;
+use crate::{host, sbom, RunOptions, ScoreConfig};
+
+/// Fields of apps replaced by another field, as (deprecated, replacement).
+///
+/// The deprecated name is still read as the replacement, with a warning.
+pub(crate) const DEPRECATED_APP_FIELDS: &[(&str, &str)] = &[("root", "serve_dir")];
+
+/// Renames the deprecated fields of `app`, returning those it used.
+///
+/// Fails if the app gives both a deprecated field and its replacement.
+pub(crate) fn rename_deprecated(app: &mut serde_json::Map<String, Value>) -> Result<Vec<(&'static str, &'static str)>> {
+    let mut used = Vec::new();
+    for &(deprecated, replacement) in DEPRECATED_APP_FIELDS {
+        let Some(value) = app.remove(deprecated) else {
+            continue;
+        };
+        if app.contains_key(replacement) {
+            bail!(
+                "\"{}\" is the deprecated name of \"{}\", give only one of them",
+                deprecated,
+                replacement
+            );
+        }
+        app.insert(replacement.to_string(), value);
+        used.push((deprecated, replacement));
+    }
+    Ok(used)
+}
+
+/// Fails on unknown or deprecated fields of the example with `strict`, warns about deprecated fields otherwise.
+pub(crate) fn check_fields(config: &ScoreConfig, strict: bool) -> Result<()> {
+    for (i, app) in config.apps.iter().enumerate() {
+        for (deprecated, replacement) in &app.deprecated {
+            if strict {
+                bail!(
+                    "Example '{}', app {}: \"{}\" is deprecated, use \"{}\" (--strict)",
+                    config.name,
+                    i + 1,
+                    deprecated,
+                    replacement
+                );
+            }
+            println!(
+                "⚠️  Example '{}', app {}: \"{}\" is deprecated, use \"{}\"",
+                config.name,
+                i + 1,
+                deprecated,
+                replacement
+            );
+        }
+    }
+    if !strict {
+        return Ok(());
+    }
+    for (at, fields) in unknown_fields(config) {
+        if !fields.is_empty() {
+            let names: Vec<String> = fields.keys().map(|name| format!("\"{}\"", name)).collect();
+            bail!(
+                "Example '{}'{}: unknown fields {} (--strict)",
+                config.name,
+                at,
+                names.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The unknown fields of the example and of every object in it, with where they are, e.g. ", app 2, sockets[0]".
+fn unknown_fields(config: &ScoreConfig) -> Vec<(String, &BTreeMap<String, Value>)> {
+    let mut found = vec![(String::new(), &config.unknown)];
+    for (i, can) in config.can.iter().enumerate() {
+        found.push((format!(", can[{}]", i), &can.unknown));
+    }
+    if let Some(ref sd) = config.someip_sd {
+        found.push((", someip_sd".to_string(), &sd.unknown));
+    }
+    if let Some(ref network) = config.network {
+        found.push((", network".to_string(), &network.unknown));
+        for (i, node) in network.nodes.iter().enumerate() {
+            found.push((format!(", network.nodes[{}]", i), &node.unknown));
+        }
+    }
+    for (i, parameter) in config.parameters.iter().enumerate() {
+        found.push((format!(", parameters[{}]", i), &parameter.unknown));
+    }
+    for (i, channel) in config.channels.iter().enumerate() {
+        found.push((format!(", channels[{}]", i), &channel.unknown));
+    }
+    if let Some(ref health) = config.health_monitor {
+        found.push((", health_monitor".to_string(), &health.unknown));
+    }
+    for (i, app) in config.apps.iter().enumerate() {
+        found.push((format!(", app {}", i + 1), &app.unknown));
+        for (j, socket) in app.sockets.iter().enumerate() {
+            found.push((format!(", app {}, sockets[{}]", i + 1, j), &socket.unknown));
+        }
+    }
+    found
+}
+
+/// Fails if a program the selected examples or the options need is missing, before the run starts.
+///
+/// Without `--strict`, these features are left out with a warning, or fail only at the end of the run.
+pub(crate) fn check_programs(configs: &[ScoreConfig], selected: &[usize], options: &RunOptions) -> Result<()> {
+    let mut missing = Vec::new();
+    let mut need = |program: &str, purpose: &str| {
+        if host::find_in_path(program).is_none() {
+            missing.push(format!("{} ({})", program, purpose));
+        }
+    };
+    if options.instrument == Some(Instrument::Valgrind) {
+        need("valgrind", "--instrument valgrind");
+    }
+    if options.merge_coverage {
+        need("llvm-profdata", "--merge-coverage");
+    }
+    if options.sign_key.is_some() {
+        need("openssl", "--sign-key");
+    } else if sbom::has_checksums(&crate::examples_roots()) {
+        need("openssl", "checksums of the SBOM components");
+    }
+    if options
+        .on_complete
+        .iter()
+        .any(|action| matches!(action, Action::Sound(_)))
+        && !completion::SOUND_PLAYERS
+            .iter()
+            .any(|player| host::find_in_path(player).is_some())
+    {
+        missing.push(format!(
+            "{} (--on-complete sound)",
+            completion::SOUND_PLAYERS.join(" or ")
+        ));
+    }
+    if options.instrument == Some(Instrument::AsanEnv) {
+        let examples = selected.iter().map(|&i| &configs[i]);
+        let steps = examples.clone().flat_map(|config| &config.steps);
+        let steps = steps.filter_map(|name| configs.iter().find(|config| &config.name == name));
+        for config in examples.chain(steps) {
+            for (i, app) in config.apps.iter().enumerate() {
+                let variant = app.asan_path.clone().unwrap_or_else(|| format!("{}_asan", app.path));
+                if !Path::new(&variant).exists() {
+                    missing.push(format!(
+                        "{} (ASan variant of '{}', app {})",
+                        variant,
+                        config.name,
+                        i + 1
+                    ));
+                }
+            }
+        }
+    }
+    if !missing.is_empty() {
+        missing.dedup();
+        bail!("Missing programs (--strict): {}", missing.join(", "));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_fields, rename_deprecated};
+    use crate::ScoreConfig;
+    use serde_json::json;
+
+    fn config(value: serde_json::Value) -> ScoreConfig {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn finds_unknown_fields_in_nested_objects() {
+        let example = config(json!({
+            "name": "demo",
+            "description": "test",
+            "apps": [{"path": "app", "sockets": [{"tcp": "127.0.0.1:8080", "nmae": "http"}]}],
+        }));
+        let error = check_fields(&example, true).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "Example 'demo', app 1, sockets[0]: unknown fields \"nmae\" (--strict)"
+        );
+        assert!(check_fields(&example, false).is_ok());
+
+        let example = config(json!({
+            "name": "demo",
+            "description": "test",
+            "network": {"nodes": [{"name": "a", "address": "10.0.0.1/24", "adress": "x"}]},
+        }));
+        let error = check_fields(&example, true).unwrap_err().to_string();
+        assert!(
+            error.contains("network.nodes[0]: unknown fields \"adress\""),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn renames_deprecated_fields() {
+        let mut app = json!({"type": "static-server", "root": "web", "port": 8080});
+        let used = rename_deprecated(app.as_object_mut().unwrap()).unwrap();
+        assert_eq!(used, vec![("root", "serve_dir")]);
+        assert_eq!(app, json!({"type": "static-server", "serve_dir": "web", "port": 8080}));
+
+        let mut app = json!({"root": "web", "serve_dir": "web"});
+        assert!(rename_deprecated(app.as_object_mut().unwrap()).is_err());
+    }
+}
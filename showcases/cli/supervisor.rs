@@ -0,0 +1,184 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Event loop of the runner, on a tokio runtime.
+//!
+//! The output pipes of all apps are read by tasks of one runtime shared by the
+//! examples of the process. The supervisor of an example sleeps until something
+//! needs its attention: an app exited, which it learns from the pidfds of the
+//! apps or, where there are none, from SIGCHLD; an app printed its ready line;
+//! the runner was interrupted; or its next timer is due. It only reaps the apps
+//! the wait reported as exited, instead of checking every app each time.
+
+use std::future::poll_fn;
+use std::os::fd::AsFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::task::Poll;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::io::unix::AsyncFd;
+use tokio::io::Interest;
+use tokio::runtime::Runtime;
+use tokio::signal::unix::{signal, Signal, SignalKind};
+use tokio::sync::Notify;
+
+use crate::sys;
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+/// Set by [`stop_on_interrupt`].
+static STOPS_ON_INTERRUPT: AtomicBool = AtomicBool::new(false);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+/// Wakes the supervisors once the runner was interrupted.
+static INTERRUPT: Notify = Notify::const_new();
+
+/// The runtime reading app output, started on first use.
+pub(crate) fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("score-runner")
+            .enable_all()
+            .build()
+            .expect("Failed to start the async runtime")
+    })
+}
+
+/// Ends the run on SIGINT, SIGTERM or SIGHUP like the stop file does: the apps
+/// of the running example are stopped and the reports are written. A second
+/// signal ends the runner at once.
+///
+/// Without it the signals keep their default action, so programs embedding the
+/// runner decide themselves.
+pub fn stop_on_interrupt() -> Result<()> {
+    if STOPS_ON_INTERRUPT.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    let _context = runtime().enter();
+    let mut signals = Vec::new();
+    for (kind, number) in [
+        (SignalKind::interrupt(), sys::Signal::INT),
+        (SignalKind::terminate(), sys::Signal::TERM),
+        (SignalKind::hangup(), sys::Signal::HUP),
+    ] {
+        signals.push((
+            signal(kind).with_context(|| format!("Failed to handle {}", number))?,
+            number,
+        ));
+    }
+    runtime().spawn(async move {
+        loop {
+            let received = poll_fn(|cx| {
+                for (stream, number) in signals.iter_mut() {
+                    if stream.poll_recv(cx).is_ready() {
+                        return Poll::Ready(*number);
+                    }
+                }
+                Poll::Pending
+            })
+            .await;
+            if INTERRUPTED.swap(true, Ordering::SeqCst) {
+                sys::die_of(received);
+            }
+            println!(
+                "\n{} received, stopping the run (send it again to end the runner at once)",
+                received
+            );
+            INTERRUPT.notify_waiters();
+        }
+    });
+    Ok(())
+}
+
+/// Whether [`stop_on_interrupt`] handles the signals ending the runner.
+pub(crate) fn stops_on_interrupt() -> bool {
+    STOPS_ON_INTERRUPT.load(Ordering::SeqCst)
+}
+
+/// Whether the runner was interrupted, see [`stop_on_interrupt`].
+pub(crate) fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Sleeps the supervisor of an example until one of its events.
+pub(crate) struct Supervisor {
+    child_exits: Signal, // SIGCHLD, for apps without a pidfd
+}
+
+impl Supervisor {
+    /// Starts listening for app exits; created before the first app is spawned, so none is missed.
+    pub(crate) fn new() -> Result<Supervisor> {
+        let _context = runtime().enter();
+        Ok(Supervisor {
+            child_exits: signal(SignalKind::child()).context("Failed to handle SIGCHLD")?,
+        })
+    }
+
+    /// Waits until one of the running apps exited, `wake` was notified, the runner
+    /// was interrupted or `timeout` elapsed, and returns the positions of the apps
+    /// that may have exited, so only those are reaped.
+    ///
+    /// `pidfds` has an entry for each running app; apps that exited stay ready and
+    /// would end every wait at once. Apps without a pidfd are reported together on
+    /// any SIGCHLD.
+    pub(crate) fn wait(
+        &mut self,
+        pidfds: &[Option<&sys::Pidfd>],
+        wake: &Notify,
+        timeout: Duration,
+    ) -> Result<Vec<usize>> {
+        let without_pidfd: Vec<usize> = (0..pidfds.len())
+            .filter(|&position| pidfds[position].is_none())
+            .collect();
+        let child_exits = &mut self.child_exits;
+        runtime().block_on(async {
+            let interrupt = INTERRUPT.notified();
+            tokio::pin!(interrupt);
+            interrupt.as_mut().enable();
+            if interrupted() {
+                return Ok(Vec::new());
+            }
+            let exits = pidfds
+                .iter()
+                .map(|pidfd| {
+                    pidfd
+                        .map(|pidfd| AsyncFd::with_interest(pidfd.as_fd(), Interest::READABLE))
+                        .transpose()
+                })
+                .collect::<std::io::Result<Vec<_>>>()
+                .context("Failed to wait for the apps")?;
+            let exited = poll_fn(|cx| {
+                let ready: Vec<usize> = (0..exits.len())
+                    .filter(|&position| {
+                        exits[position]
+                            .as_ref()
+                            .is_some_and(|exit| exit.poll_read_ready(cx).is_ready())
+                    })
+                    .collect();
+                if !ready.is_empty() {
+                    return Poll::Ready(ready);
+                }
+                if !without_pidfd.is_empty() && child_exits.poll_recv(cx).is_ready() {
+                    return Poll::Ready(without_pidfd.clone());
+                }
+                Poll::Pending
+            });
+            Ok(tokio::select! {
+                exited = exited => exited,
+                _ = wake.notified() => Vec::new(),
+                _ = interrupt => Vec::new(),
+                _ = tokio::time::sleep(timeout) => Vec::new(),
+            })
+        })
+    }
+}
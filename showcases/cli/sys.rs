@@ -0,0 +1,1032 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Thin wrappers around OS calls not covered by `std`.
+//!
+//! Constants and structure layouts differ between Linux and QNX (`target_os = "nto"`)
+//! and are selected at compile time; everything else is plain POSIX.
+
+use std::ffi::{CStr, CString};
+use std::fs::File;
+use std::io;
+use std::net::{Ipv4Addr, UdpSocket};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::os::raw::{c_char, c_int, c_long, c_void};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt, PermissionsExt};
+use std::path::Path;
+use std::process::{Child, ExitStatus};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+const AF_INET: c_int = 2;
+const SOCK_DGRAM: c_int = 2;
+const SIGHUP: c_int = 1;
+const SIGINT: c_int = 2;
+const SIGKILL: c_int = 9;
+const SIGTERM: c_int = 15;
+const PRIO_PROCESS: c_int = 0;
+const LOCK_EX: c_int = 2;
+const LOCK_NB: c_int = 4;
+const F_DUPFD: c_int = 0;
+const F_SETFD: c_int = 2;
+const FD_CLOEXEC: c_int = 1;
+const O_WRONLY: c_int = 1;
+const O_RDWR: c_int = 2;
+const TCIFLUSH: c_int = 0;
+/// Default action of a signal, for `signal`.
+const SIG_DFL: usize = 0;
+const POLLIN: i16 = 1;
+const EINTR: i32 = 4;
+/// First descriptor of sockets passed to an app, following the `LISTEN_FDS` convention.
+const FIRST_PASSED_FD: c_int = 3;
+/// Upper bound of descriptors passed to an app, so passing them needs no allocation after fork.
+pub const MAX_PASSED_FDS: usize = 16;
+
+#[cfg(target_os = "linux")]
+mod consts {
+    use std::os::raw::{c_int, c_long};
+
+    pub const SOL_SOCKET: c_int = 1;
+    pub const SO_REUSEADDR: c_int = 2;
+    pub const SO_REUSEPORT: c_int = 15;
+    pub const SC_CLK_TCK: c_int = 2;
+    pub const CLOCK_MONOTONIC: c_int = 1;
+    pub const IOPRIO_WHO_PROCESS: c_long = 1;
+    pub const IOPRIO_CLASS_SHIFT: u32 = 13;
+    // None on architectures whose syscall number is not listed here, I/O priorities are unsupported there.
+    #[cfg(target_arch = "x86_64")]
+    pub const SYS_IOPRIO_SET: Option<c_long> = Some(251);
+    #[cfg(target_arch = "x86")]
+    pub const SYS_IOPRIO_SET: Option<c_long> = Some(289);
+    #[cfg(any(target_arch = "aarch64", target_arch = "riscv64", target_arch = "loongarch64"))]
+    pub const SYS_IOPRIO_SET: Option<c_long> = Some(30);
+    #[cfg(target_arch = "arm")]
+    pub const SYS_IOPRIO_SET: Option<c_long> = Some(314);
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        target_arch = "loongarch64",
+        target_arch = "arm"
+    )))]
+    pub const SYS_IOPRIO_SET: Option<c_long> = None;
+    #[cfg(target_arch = "x86_64")]
+    pub const SYS_BPF: Option<c_long> = Some(321);
+    #[cfg(target_arch = "x86")]
+    pub const SYS_BPF: Option<c_long> = Some(357);
+    #[cfg(any(target_arch = "aarch64", target_arch = "riscv64", target_arch = "loongarch64"))]
+    pub const SYS_BPF: Option<c_long> = Some(280);
+    #[cfg(target_arch = "arm")]
+    pub const SYS_BPF: Option<c_long> = Some(386);
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        target_arch = "loongarch64",
+        target_arch = "arm"
+    )))]
+    pub const SYS_BPF: Option<c_long> = None;
+    #[cfg(target_arch = "x86_64")]
+    pub const SYS_PERF_EVENT_OPEN: Option<c_long> = Some(298);
+    #[cfg(target_arch = "x86")]
+    pub const SYS_PERF_EVENT_OPEN: Option<c_long> = Some(336);
+    #[cfg(any(target_arch = "aarch64", target_arch = "riscv64", target_arch = "loongarch64"))]
+    pub const SYS_PERF_EVENT_OPEN: Option<c_long> = Some(241);
+    #[cfg(target_arch = "arm")]
+    pub const SYS_PERF_EVENT_OPEN: Option<c_long> = Some(364);
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        target_arch = "loongarch64",
+        target_arch = "arm"
+    )))]
+    pub const SYS_PERF_EVENT_OPEN: Option<c_long> = None;
+    pub const PERF_FLAG_FD_CLOEXEC: c_long = 8;
+    // Same number on all architectures, as added after the syscall tables were unified.
+    pub const SYS_PIDFD_SEND_SIGNAL: c_long = 424;
+    pub const SYS_PIDFD_OPEN: c_long = 434;
+    pub const SIGUSR1: c_int = 10;
+    pub const SIGUSR2: c_int = 12;
+    pub const SIGCONT: c_int = 18;
+    pub const SIGSTOP: c_int = 19;
+    pub const O_NOCTTY: c_int = 0o400;
+    pub const TCSANOW: c_int = 0;
+    #[cfg(any(
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64"
+    ))]
+    pub const O_NOFOLLOW: c_int = 0x8000;
+    #[cfg(not(any(
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "powerpc",
+        target_arch = "powerpc64"
+    )))]
+    pub const O_NOFOLLOW: c_int = 0x20000;
+    pub const O_NONBLOCK: c_int = 0o4000;
+    pub const O_PATH: c_int = 0o10000000;
+    pub const CLONE_NEWNET: c_int = 0x40000000;
+    pub const TIOCSCTTY: IoctlRequest = 0x540e;
+
+    pub type IoctlRequest = std::os::raw::c_ulong;
+}
+
+#[cfg(target_os = "nto")]
+mod consts {
+    use std::os::raw::c_int;
+
+    pub const SOL_SOCKET: c_int = 0xffff;
+    pub const SO_REUSEADDR: c_int = 0x0004;
+    pub const SO_REUSEPORT: c_int = 0x0200;
+    pub const SC_CLK_TCK: c_int = 3;
+    pub const CLOCK_MONOTONIC: c_int = 2;
+    pub const SIGUSR1: c_int = 16;
+    pub const SIGUSR2: c_int = 17;
+    pub const SIGSTOP: c_int = 23;
+    pub const SIGCONT: c_int = 25;
+    pub const O_NOCTTY: c_int = 0x800;
+    pub const TCSANOW: c_int = 0x0001;
+    pub const O_NOFOLLOW: c_int = 0o10000;
+    pub const O_NONBLOCK: c_int = 0o200;
+    pub const AT_FDCWD: c_int = -100;
+    pub const AT_SYMLINK_NOFOLLOW: c_int = 0x0002;
+    pub const TIOCSCTTY: IoctlRequest = 0x20007461; // _IO('t', 97)
+
+    pub type IoctlRequest = c_int;
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "nto")))]
+compile_error!("the SCORE CLI supports Linux and QNX only");
+
+pub use consts::O_NOFOLLOW;
+use consts::*;
+
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct SockAddrIn {
+    sin_family: u16,
+    sin_port: u16,
+    sin_addr: u32,
+    sin_zero: [u8; 8],
+}
+
+/// BSD layout with a leading length byte.
+#[cfg(target_os = "nto")]
+#[repr(C)]
+struct SockAddrIn {
+    sin_len: u8,
+    sin_family: u8,
+    sin_port: u16,
+    sin_addr: u32,
+    sin_zero: [u8; 8],
+}
+
+#[repr(C)]
+struct PollFd {
+    fd: c_int,
+    events: i16,
+    revents: i16,
+}
+
+/// The leading fields of `struct statvfs`, alike on Linux and QNX, the rest is only space.
+#[repr(C)]
+struct StatVfs {
+    f_bsize: std::os::raw::c_ulong,
+    f_frsize: std::os::raw::c_ulong,
+    f_blocks: u64,
+    f_bfree: u64,
+    f_bavail: u64,
+    rest: [u64; 16],
+}
+
+#[repr(C)]
+struct Timespec {
+    tv_sec: c_long,
+    tv_nsec: c_long,
+}
+
+extern "C" {
+    fn socket(domain: c_int, ty: c_int, protocol: c_int) -> c_int;
+    fn setsockopt(fd: c_int, level: c_int, name: c_int, value: *const c_void, len: u32) -> c_int;
+    fn bind(fd: c_int, addr: *const SockAddrIn, len: u32) -> c_int;
+    fn close(fd: c_int) -> c_int;
+    fn kill(pid: c_int, sig: c_int) -> c_int;
+    fn getpgrp() -> c_int;
+    fn geteuid() -> u32;
+    fn sysconf(name: c_int) -> c_long;
+    fn setpriority(which: c_int, who: u32, prio: c_int) -> c_int;
+    fn open(path: *const c_char, flags: c_int, ...) -> c_int;
+    fn write(fd: c_int, buf: *const c_void, count: usize) -> isize;
+    #[cfg(target_os = "linux")]
+    fn syscall(number: c_long, ...) -> c_long;
+    fn ioctl(fd: c_int, request: IoctlRequest, ...) -> c_int;
+    fn setsid() -> c_int;
+    #[cfg(target_os = "linux")]
+    fn setns(fd: c_int, nstype: c_int) -> c_int;
+    fn clock_gettime(clock: c_int, tp: *mut Timespec) -> c_int;
+    fn flock(fd: c_int, operation: c_int) -> c_int;
+    fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+    fn dup2(old: c_int, new: c_int) -> c_int;
+    fn dup(fd: c_int) -> c_int;
+    fn signal(signum: c_int, handler: usize) -> usize;
+    fn raise(signum: c_int) -> c_int;
+    fn tcgetattr(fd: c_int, termios: *mut c_void) -> c_int;
+    fn tcsetattr(fd: c_int, action: c_int, termios: *const c_void) -> c_int;
+    fn tcflush(fd: c_int, queue: c_int) -> c_int;
+    fn posix_openpt(flags: c_int) -> c_int;
+    fn grantpt(fd: c_int) -> c_int;
+    fn unlockpt(fd: c_int) -> c_int;
+    fn ptsname_r(fd: c_int, buf: *mut u8, len: usize) -> c_int;
+    fn mkfifo(path: *const c_char, mode: u32) -> c_int;
+    fn umask(mask: u32) -> u32;
+    #[cfg(target_os = "linux")]
+    fn getxattr(path: *const c_char, name: *const c_char, value: *mut c_void, size: usize) -> isize;
+    #[cfg(target_os = "nto")]
+    fn fchmodat(dir: c_int, path: *const c_char, mode: u32, flags: c_int) -> c_int;
+    fn poll(fds: *mut PollFd, count: std::os::raw::c_ulong, timeout: c_int) -> c_int;
+    // 64-bit block counts on 32-bit Linux as well.
+    #[cfg_attr(all(target_os = "linux", target_pointer_width = "32"), link_name = "statvfs64")]
+    fn statvfs(path: *const c_char, buf: *mut StatVfs) -> c_int;
+}
+
+/// Current CLOCK_MONOTONIC time, which `std::time::Instant` does not expose.
+pub fn monotonic_now() -> Duration {
+    let mut ts = Timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: clock_gettime only writes to the provided timespec.
+    if unsafe { clock_gettime(CLOCK_MONOTONIC, &mut ts) } != 0 {
+        return Duration::ZERO;
+    }
+    Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+}
+
+/// Takes an exclusive advisory lock on `file`, released when it is closed.
+///
+/// Returns `false` instead of waiting if `wait` is not set and another process holds the lock.
+pub fn lock_exclusive(file: &File, wait: bool) -> io::Result<bool> {
+    let operation = if wait { LOCK_EX } else { LOCK_EX | LOCK_NB };
+    // SAFETY: flock only operates on the open file descriptor.
+    if unsafe { flock(file.as_raw_fd(), operation) } == 0 {
+        return Ok(true);
+    }
+    let err = io::Error::last_os_error();
+    if err.kind() == io::ErrorKind::WouldBlock {
+        return Ok(false);
+    }
+    Err(err)
+}
+
+/// Makes the current process the leader of a new session, with the terminal open on `fd` as its
+/// controlling terminal, so the app gets job control and the signals of the terminal.
+///
+/// Meant for `pre_exec`: it only makes async-signal-safe calls and does not allocate.
+pub fn set_controlling_terminal(fd: c_int) -> io::Result<()> {
+    // SAFETY: setsid and TIOCSCTTY only change the session and terminal of the process.
+    unsafe {
+        if setsid() < 0 || ioctl(fd, TIOCSCTTY, 0 as c_int) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Moves `fds` to descriptors 3, 4, ... of the current process, inherited across exec.
+///
+/// Meant for `pre_exec`: it only makes async-signal-safe calls and does not allocate.
+pub fn pass_fds(fds: &[c_int]) -> io::Result<()> {
+    let target_end = FIRST_PASSED_FD + fds.len() as c_int;
+    let mut moved = [-1; MAX_PASSED_FDS];
+    // First move descriptors out of the target range, so none is overwritten before it was passed.
+    for (fd, moved) in fds.iter().zip(moved.iter_mut()) {
+        let mut fd = *fd;
+        if (FIRST_PASSED_FD..target_end).contains(&fd) {
+            // SAFETY: F_DUPFD only duplicates the open descriptor.
+            fd = unsafe { fcntl(fd, F_DUPFD, target_end) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        *moved = fd;
+    }
+    for (i, fd) in moved[..fds.len()].iter().enumerate() {
+        // SAFETY: dup2 only operates on descriptors; the copy does not have close-on-exec set.
+        if unsafe { dup2(*fd, FIRST_PASSED_FD + i as c_int) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    for (fd, original) in moved[..fds.len()].iter().zip(fds) {
+        if fd != original {
+            // SAFETY: closes the temporary copy made above.
+            unsafe { close(*fd) };
+        }
+    }
+    Ok(())
+}
+
+/// Stdout redirected to `/dev/null`, restored when this is dropped.
+pub struct SilencedStdout {
+    saved: c_int,
+}
+
+/// Discards everything written to stdout, by this process and the children it starts, until the guard is dropped.
+pub fn silence_stdout() -> io::Result<SilencedStdout> {
+    io::Write::flush(&mut io::stdout())?;
+    let null = File::options().write(true).open("/dev/null")?;
+    // SAFETY: dup and dup2 only operate on descriptors.
+    let saved = unsafe { dup(1) };
+    if saved < 0 || unsafe { dup2(null.as_raw_fd(), 1) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(SilencedStdout { saved })
+}
+
+impl Drop for SilencedStdout {
+    fn drop(&mut self) {
+        let _ = io::Write::flush(&mut io::stdout());
+        // SAFETY: restores the descriptor saved by silence_stdout and closes the copy.
+        unsafe {
+            dup2(self.saved, 1);
+            close(self.saved);
+        }
+    }
+}
+
+/// Kernel clock ticks per second, the unit of CPU times in `/proc/<pid>/stat`.
+pub fn clock_ticks_per_second() -> u64 {
+    // SAFETY: sysconf has no preconditions.
+    match unsafe { sysconf(SC_CLK_TCK) } {
+        ticks if ticks > 0 => ticks as u64,
+        _ => 100,
+    }
+}
+
+/// Sets the nice level of the calling process.
+pub fn set_nice(nice: i32) -> io::Result<()> {
+    // SAFETY: setpriority has no memory-safety preconditions.
+    if unsafe { setpriority(PRIO_PROCESS, 0, nice) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Whether [`set_io_priority`] is supported on this platform.
+#[cfg(target_os = "linux")]
+pub const IO_PRIORITIES: bool = SYS_IOPRIO_SET.is_some();
+#[cfg(not(target_os = "linux"))]
+pub const IO_PRIORITIES: bool = false;
+
+/// Sets the I/O scheduling class and level of the calling process (see ioprio_set(2)).
+#[cfg(target_os = "linux")]
+pub fn set_io_priority(class: u32, level: u32) -> io::Result<()> {
+    let Some(number) = SYS_IOPRIO_SET else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "I/O priorities are not supported on this architecture",
+        ));
+    };
+    let ioprio = (class << IOPRIO_CLASS_SHIFT | level) as c_long;
+    // SAFETY: ioprio_set takes plain integers; glibc has no wrapper for it.
+    if unsafe { syscall(number, IOPRIO_WHO_PROCESS, 0 as c_long, ioprio) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_io_priority(_class: u32, _level: u32) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "I/O priorities need Linux"))
+}
+
+/// Runs `f` on a thread inside the network namespace at `path`, e.g. `/run/netns/<name>`.
+///
+/// Only that thread enters the namespace, which needs CAP_SYS_ADMIN.
+#[cfg(target_os = "linux")]
+pub fn in_network_namespace<T: Send>(path: &Path, f: impl FnOnce() -> T + Send) -> io::Result<T> {
+    let namespace = File::open(path)?;
+    std::thread::scope(|scope| {
+        let entered = scope.spawn(|| {
+            // SAFETY: setns only changes the namespace of this thread, which ends after `f`.
+            if unsafe { setns(namespace.as_raw_fd(), CLONE_NEWNET) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(f())
+        });
+        entered.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn in_network_namespace<T: Send>(_path: &Path, _f: impl FnOnce() -> T + Send) -> io::Result<T> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Network namespaces need Linux",
+    ))
+}
+
+/// Sets the OOM-killer score adjustment of the calling process, -1000 (never killed) to 1000.
+///
+/// Formats the value on the stack, so it can run between fork and exec.
+#[cfg(target_os = "linux")]
+pub fn set_oom_score_adj(value: i32) -> io::Result<()> {
+    let mut text = [0u8; 8];
+    let mut len = 0;
+    if value < 0 {
+        text[0] = b'-';
+        len = 1;
+    }
+    let mut digits = [0u8; 4];
+    let mut count = 0;
+    let mut rest = value.unsigned_abs().min(1000);
+    loop {
+        digits[count] = b'0' + (rest % 10) as u8;
+        count += 1;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    for digit in digits[..count].iter().rev() {
+        text[len] = *digit;
+        len += 1;
+    }
+    // SAFETY: the path is NUL-terminated, the buffer holds `len` bytes and the descriptor is closed before returning.
+    unsafe {
+        let fd = open(c"/proc/self/oom_score_adj".as_ptr(), O_WRONLY);
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let written = write(fd, text.as_ptr().cast(), len);
+        let error = io::Error::last_os_error();
+        close(fd);
+        if written < 0 {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_oom_score_adj(_value: i32) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "OOM score adjustments need Linux",
+    ))
+}
+
+/// Moves the calling process into the cgroup whose `cgroup.procs` file is `procs`.
+///
+/// Meant for `pre_exec`: it only makes async-signal-safe calls and does not allocate.
+pub fn join_cgroup(procs: &CStr) -> io::Result<()> {
+    // SAFETY: the path is NUL-terminated and the descriptor is closed before returning.
+    unsafe {
+        let fd = open(procs.as_ptr(), O_WRONLY);
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // Writing 0 moves the writing process.
+        let written = write(fd, c"0".as_ptr().cast(), 1);
+        let error = io::Error::last_os_error();
+        close(fd);
+        if written < 0 {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// The value of the extended attribute `name` of `path`, at most 64 bytes, e.g. `user.delegate` of a cgroup.
+#[cfg(target_os = "linux")]
+pub fn xattr(path: &Path, name: &str) -> io::Result<Vec<u8>> {
+    let c_path =
+        CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let c_name = CString::new(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut value = [0u8; 64];
+    // SAFETY: both strings are NUL-terminated, getxattr writes at most `value.len()` bytes.
+    let len = unsafe { getxattr(c_path.as_ptr(), c_name.as_ptr(), value.as_mut_ptr().cast(), value.len()) };
+    if len < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(value[..len as usize].to_vec())
+}
+
+#[cfg(target_os = "nto")]
+pub fn xattr(_path: &Path, _name: &str) -> io::Result<Vec<u8>> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// Bytes available to unprivileged users on the file system of `path`.
+pub fn available_space(path: &Path) -> io::Result<u64> {
+    let path = CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: all-zero bytes are a valid StatVfs of plain integers.
+    let mut stat: StatVfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid NUL-terminated string and statvfs only writes to `stat`, which is large enough.
+    if unsafe { statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(stat.f_bavail.saturating_mul(stat.f_frsize as u64))
+}
+
+/// Creates the named pipe `path` with `mode`.
+///
+/// mkfifo applies the umask, so the pipe is never more open than `mode`. The
+/// mode is then set on the pipe as opened without following symlinks, so a
+/// file put at `path` in between is never changed.
+pub fn make_fifo(path: &Path, mode: u32) -> io::Result<()> {
+    let c_path =
+        CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: `c_path` is a valid NUL-terminated string.
+    if unsafe { mkfifo(c_path.as_ptr(), mode) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // Opening for reading without blocking succeeds without a writer.
+    let fifo = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(O_NONBLOCK | O_NOFOLLOW)
+        .open(path)?;
+    if !fifo.metadata()?.file_type().is_fifo() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "replaced by another file"));
+    }
+    fifo.set_permissions(std::fs::Permissions::from_mode(mode))
+}
+
+/// Sets the mode of `path` without following a symlink, failing if `path` is one.
+#[cfg(target_os = "linux")]
+pub fn chmod_nofollow(path: &Path, mode: u32) -> io::Result<()> {
+    // A descriptor of the file itself, which works for sockets too, changed through its /proc link.
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(O_PATH | O_NOFOLLOW)
+        .open(path)?;
+    if file.metadata()?.file_type().is_symlink() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "is a symbolic link"));
+    }
+    let link = format!("/proc/self/fd/{}", file.as_raw_fd());
+    std::fs::set_permissions(link, std::fs::Permissions::from_mode(mode))
+}
+
+/// Sets the mode of `path` without following a symlink, failing if `path` is one.
+#[cfg(target_os = "nto")]
+pub fn chmod_nofollow(path: &Path, mode: u32) -> io::Result<()> {
+    let path = CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: `path` is a valid NUL-terminated string.
+    if unsafe { fchmodat(AT_FDCWD, path.as_ptr(), mode, AT_SYMLINK_NOFOLLOW) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Adds `mask` to the umask of the current process, keeping the bits set already.
+///
+/// Meant for `pre_exec`: it only makes async-signal-safe calls and does not allocate.
+pub fn restrict_umask(mask: u32) {
+    // SAFETY: umask only changes the file mode creation mask of the process.
+    unsafe {
+        let current = umask(0);
+        umask(current | mask);
+    }
+}
+
+/// Issues the `bpf(2)` command `cmd` with `attr` as `union bpf_attr`, returning a new descriptor or 0.
+#[cfg(target_os = "linux")]
+pub fn bpf(cmd: c_int, attr: &mut [u8]) -> io::Result<c_int> {
+    let Some(number) = SYS_BPF else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "eBPF is not supported on this architecture",
+        ));
+    };
+    // SAFETY: `attr` is a valid buffer of the given size; pointers inside it are set up by the caller.
+    let result = unsafe { syscall(number, cmd as c_long, attr.as_mut_ptr(), attr.len() as c_long) };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(result as c_int)
+}
+
+/// Opens a perf event described by `attr` as `struct perf_event_attr` on all processes of `cpu`.
+#[cfg(target_os = "linux")]
+pub fn perf_event_open(attr: &mut [u8], cpu: u32) -> io::Result<OwnedFd> {
+    let Some(number) = SYS_PERF_EVENT_OPEN else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "perf events are not supported on this architecture",
+        ));
+    };
+    // SAFETY: `attr` is a valid buffer holding a perf_event_attr whose size field fits the buffer.
+    let fd = unsafe {
+        syscall(
+            number,
+            attr.as_mut_ptr(),
+            -1 as c_long,
+            cpu as c_long,
+            -1 as c_long,
+            PERF_FLAG_FD_CLOEXEC,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: perf_event_open returned a new descriptor owned by nobody else.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd as c_int) })
+}
+
+/// Attaches the eBPF program `prog` to the perf event `event` and enables the event.
+#[cfg(target_os = "linux")]
+pub fn attach_perf_event(event: &OwnedFd, prog: &OwnedFd) -> io::Result<()> {
+    const PERF_EVENT_IOC_ENABLE: std::os::raw::c_ulong = 0x2400;
+    const PERF_EVENT_IOC_SET_BPF: std::os::raw::c_ulong = 0x4004_2408;
+    // SAFETY: both requests take a plain integer argument.
+    unsafe {
+        if ioctl(event.as_raw_fd(), PERF_EVENT_IOC_SET_BPF, prog.as_raw_fd()) != 0
+            || ioctl(event.as_raw_fd(), PERF_EVENT_IOC_ENABLE, 0 as c_int) != 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// A signal sent to stop an app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signal(c_int);
+
+impl Signal {
+    pub const HUP: Signal = Signal(SIGHUP);
+    pub const INT: Signal = Signal(SIGINT);
+    pub const TERM: Signal = Signal(SIGTERM);
+    pub const STOP: Signal = Signal(SIGSTOP);
+    pub const CONT: Signal = Signal(SIGCONT);
+    pub const KILL: Signal = Signal(SIGKILL);
+
+    const NAMES: &[(&'static str, c_int)] = &[
+        ("SIGHUP", 1),
+        ("SIGINT", SIGINT),
+        ("SIGQUIT", 3),
+        ("SIGKILL", SIGKILL),
+        ("SIGUSR1", SIGUSR1),
+        ("SIGUSR2", SIGUSR2),
+        ("SIGTERM", SIGTERM),
+        ("SIGCONT", SIGCONT),
+        ("SIGSTOP", SIGSTOP),
+    ];
+
+    /// The signal that ended the process with `status`, if a signal did.
+    pub fn of(status: &ExitStatus) -> Option<Signal> {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal().map(Signal)
+    }
+
+    /// Parses a signal name like `SIGINT` or `INT`, or a signal number.
+    pub fn parse(spec: &str) -> Option<Signal> {
+        let spec = spec.trim();
+        if let Ok(number) = spec.parse::<c_int>() {
+            return (number > 0).then_some(Signal(number));
+        }
+        let name = spec.to_ascii_uppercase();
+        let name = name.strip_prefix("SIG").unwrap_or(&name);
+        Self::NAMES
+            .iter()
+            .find(|(known, _)| known[3..] == *name)
+            .map(|&(_, number)| Signal(number))
+    }
+}
+
+impl std::fmt::Display for Signal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match Self::NAMES.iter().find(|(_, number)| *number == self.0) {
+            Some((name, _)) => write!(f, "{}", name),
+            None => write!(f, "signal {}", self.0),
+        }
+    }
+}
+
+/// Sends `signal` to the process `pid`.
+pub fn send(pid: u32, signal: Signal) -> io::Result<()> {
+    // SAFETY: kill has no memory effects; callers pass pids of their own children.
+    if unsafe { kill(pid as c_int, signal.0) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Whether a process `pid` exists, which may be a later process reusing the pid of an earlier one.
+pub fn exists(pid: u32) -> bool {
+    // SAFETY: signal 0 only checks that the process exists and may be signalled.
+    let found = unsafe { kill(pid as c_int, 0) } == 0;
+    found || io::Error::last_os_error().kind() == io::ErrorKind::PermissionDenied
+}
+
+/// Sends `signal` to all processes of the process group `pgid`.
+pub fn send_group(pgid: u32, signal: Signal) -> io::Result<()> {
+    // SAFETY: as in `send`, a negative pid addresses the group.
+    if unsafe { kill(-(pgid as c_int), signal.0) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Process group of the runner, which its apps join unless they change it.
+pub fn process_group() -> u32 {
+    // SAFETY: getpgrp has no arguments and cannot fail.
+    unsafe { getpgrp() as u32 }
+}
+
+/// Settings of the terminal on stdin saved by [`save_terminal`], an opaque `struct termios`.
+static SAVED_TERMINAL: [AtomicU64; 32] = [const { AtomicU64::new(0) }; 32];
+static TERMINAL_SAVED: AtomicBool = AtomicBool::new(false);
+
+/// Saves the settings of the terminal on stdin, restored by [`restore_terminal`].
+///
+/// With `on_signals`, they are also restored if the runner is interrupted,
+/// terminated or hung up, before it ends as it would without a handler. Callers
+/// handling these signals themselves restore the terminal when they exit.
+pub fn save_terminal(on_signals: bool) -> io::Result<()> {
+    let mut termios = [0u64; 32];
+    // SAFETY: the buffer is larger than `struct termios` on all supported systems.
+    if unsafe { tcgetattr(0, termios.as_mut_ptr().cast()) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    for (saved, value) in SAVED_TERMINAL.iter().zip(termios) {
+        saved.store(value, Ordering::Relaxed);
+    }
+    TERMINAL_SAVED.store(true, Ordering::Release);
+    if !on_signals {
+        return Ok(());
+    }
+    static HANDLERS: Once = Once::new();
+    HANDLERS.call_once(|| {
+        for signum in [SIGINT, SIGTERM, SIGHUP] {
+            // SAFETY: the handler only makes async-signal-safe calls.
+            unsafe { signal(signum, on_fatal_signal as extern "C" fn(c_int) as usize) };
+        }
+    });
+    Ok(())
+}
+
+/// Restores the terminal settings saved by [`save_terminal`], once.
+pub fn restore_terminal() {
+    if TERMINAL_SAVED.swap(false, Ordering::Acquire) {
+        // SAFETY: `AtomicU64` has the layout of `u64`, so this is the buffer filled by tcgetattr.
+        unsafe { tcsetattr(0, TCSANOW, SAVED_TERMINAL.as_ptr().cast()) };
+    }
+}
+
+/// Discards input typed on the terminal but not read yet.
+pub fn discard_terminal_input() {
+    // SAFETY: no pointers involved; fails harmlessly if stdin is no terminal.
+    unsafe { tcflush(0, TCIFLUSH) };
+}
+
+extern "C" fn on_fatal_signal(signum: c_int) {
+    restore_terminal();
+    // SAFETY: signal and raise are async-signal-safe; the signal is delivered again once the handler returns.
+    unsafe {
+        signal(signum, SIG_DFL);
+        raise(signum);
+    }
+}
+
+/// Ends the runner as `signal` would without a handler, restoring the terminal first.
+pub fn die_of(signal: Signal) -> ! {
+    restore_terminal();
+    // SAFETY: as in `on_fatal_signal`; outside of a handler the signal is delivered before raise returns.
+    unsafe {
+        self::signal(signal.0, SIG_DFL);
+        raise(signal.0);
+    }
+    std::process::exit(128 + signal.0)
+}
+
+/// Effective user id of the runner.
+pub fn effective_uid() -> u32 {
+    // SAFETY: geteuid has no arguments and cannot fail.
+    unsafe { geteuid() }
+}
+
+/// A process referred to by a pidfd (Linux 5.3 and later), which signals and
+/// awaits exactly that process: after it was reaped, its pid may be given to
+/// another process, but the pidfd never refers to that one.
+pub struct Pidfd(OwnedFd);
+
+impl AsFd for Pidfd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.0.as_fd()
+    }
+}
+
+impl Pidfd {
+    /// Opens a pidfd for `pid`, failing on kernels before 5.3 and on QNX.
+    ///
+    /// Only race-free while `pid` cannot have been reaped yet, e.g. for a child
+    /// this process did not wait for, or when the process is checked afterwards.
+    #[cfg(target_os = "linux")]
+    pub fn open(pid: u32) -> io::Result<Pidfd> {
+        // SAFETY: pidfd_open has no memory effects; the returned descriptor is owned by the Pidfd.
+        let fd = unsafe { syscall(SYS_PIDFD_OPEN, pid as c_long, 0 as c_long) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: a new descriptor, opened with O_CLOEXEC by the kernel.
+        Ok(Pidfd(unsafe { OwnedFd::from_raw_fd(fd as c_int) }))
+    }
+
+    #[cfg(target_os = "nto")]
+    pub fn open(_pid: u32) -> io::Result<Pidfd> {
+        Err(io::Error::from(io::ErrorKind::Unsupported))
+    }
+
+    /// Sends `signal` to the process, failing with ESRCH once it exited.
+    pub fn send(&self, signal: Signal) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            let null = std::ptr::null::<c_void>();
+            // SAFETY: pidfd_send_signal reads no memory with a null siginfo.
+            let result = unsafe {
+                syscall(
+                    SYS_PIDFD_SEND_SIGNAL,
+                    self.0.as_raw_fd() as c_long,
+                    signal.0 as c_long,
+                    null,
+                    0 as c_long,
+                )
+            };
+            if result != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+        #[cfg(target_os = "nto")]
+        {
+            let _ = signal;
+            Err(io::Error::from(io::ErrorKind::Unsupported))
+        }
+    }
+
+    /// Waits until the process exited or `timeout` elapsed, returning whether it exited.
+    ///
+    /// Does not reap the process, the caller still waits for its status.
+    pub fn wait_exit(&self, timeout: Duration) -> io::Result<bool> {
+        Ok(!wait_any(&[self], timeout)?.is_empty())
+    }
+}
+
+/// Waits until at least one of the processes of `pidfds` exited or `timeout`
+/// elapsed, and returns the positions of those that exited.
+///
+/// Processes that exited stay ready, so callers leave them out of later waits.
+pub fn wait_any(pidfds: &[&Pidfd], timeout: Duration) -> io::Result<Vec<usize>> {
+    let fds: Vec<c_int> = pidfds.iter().map(|pidfd| pidfd.0.as_raw_fd()).collect();
+    wait_readable(&fds, timeout)
+}
+
+/// Waits until at least one of the descriptors `fds` is readable or hung up, or
+/// `timeout` elapsed, and returns the positions of the ready ones.
+pub fn wait_readable(fds: &[c_int], timeout: Duration) -> io::Result<Vec<usize>> {
+    let mut fds: Vec<PollFd> = fds
+        .iter()
+        .map(|&fd| PollFd {
+            fd,
+            events: POLLIN,
+            revents: 0,
+        })
+        .collect();
+    let deadline = Instant::now() + timeout;
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        // Rounded up, so a wait never ends before `timeout`.
+        let millis = left.as_micros().div_ceil(1000).min(c_int::MAX as u128) as c_int;
+        // SAFETY: poll writes only the revents of the `fds.len()` entries of `fds`.
+        let ready = unsafe { poll(fds.as_mut_ptr(), fds.len() as _, millis) };
+        if ready >= 0 {
+            return Ok(fds
+                .iter()
+                .enumerate()
+                .filter(|(_, fd)| fd.revents != 0)
+                .map(|(position, _)| position)
+                .collect());
+        }
+        let err = io::Error::last_os_error();
+        // Interrupted by a signal such as SIGCHLD: wait for the rest of the time.
+        if err.raw_os_error() != Some(EINTR) {
+            return Err(err);
+        }
+    }
+}
+
+/// Stops a child gracefully: `signal` (usually SIGTERM) first, SIGKILL if it is still alive after `grace`.
+///
+/// Giving apps the chance to exit normally lets them flush buffers such as coverage profiles.
+/// Where pidfds exist the exit is awaited through one instead of checked periodically.
+pub fn stop(child: &mut Child, signal: Signal, grace: Duration) -> io::Result<ExitStatus> {
+    if let Some(status) = child.try_wait()? {
+        return Ok(status);
+    }
+    // Not reaped until this returns, so the pid still belongs to the child.
+    let pidfd = Pidfd::open(child.id()).ok();
+    let sent = match pidfd {
+        Some(ref pidfd) => pidfd.send(signal),
+        None => send(child.id(), signal),
+    };
+    if sent.is_err() {
+        return child.wait();
+    }
+    if let Some(pidfd) = pidfd {
+        if pidfd.wait_exit(grace)? {
+            return child.wait();
+        }
+    } else {
+        let deadline = Instant::now() + grace;
+        while Instant::now() < deadline {
+            if let Some(status) = child.try_wait()? {
+                return Ok(status);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+    child.kill()?;
+    child.wait()
+}
+
+/// Binds a UDP socket with `SO_REUSEADDR`/`SO_REUSEPORT` set, so it can share
+/// a well-known port with the daemons running inside an example.
+pub fn reusable_udp_socket(addr: Ipv4Addr, port: u16) -> io::Result<UdpSocket> {
+    // SAFETY: plain socket syscalls on a descriptor owned by this function.
+    unsafe {
+        let fd = socket(AF_INET, SOCK_DGRAM, 0);
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let one: c_int = 1;
+        for option in [SO_REUSEADDR, SO_REUSEPORT] {
+            let value = &one as *const c_int as *const c_void;
+            if setsockopt(fd, SOL_SOCKET, option, value, std::mem::size_of::<c_int>() as u32) != 0 {
+                let err = io::Error::last_os_error();
+                close(fd);
+                return Err(err);
+            }
+        }
+        let sockaddr = SockAddrIn {
+            #[cfg(target_os = "nto")]
+            sin_len: std::mem::size_of::<SockAddrIn>() as u8,
+            sin_family: AF_INET as _,
+            sin_port: port.to_be(),
+            sin_addr: u32::from(addr).to_be(),
+            sin_zero: [0; 8],
+        };
+        if bind(fd, &sockaddr, std::mem::size_of::<SockAddrIn>() as u32) != 0 {
+            let err = io::Error::last_os_error();
+            close(fd);
+            return Err(err);
+        }
+        Ok(UdpSocket::from_raw_fd(fd))
+    }
+}
+
+/// Opens a pseudo terminal, returning its controlling side and the terminal side for an app.
+///
+/// Neither becomes the controlling terminal of the runner, and the controlling
+/// side is not inherited by apps.
+pub fn open_pty() -> io::Result<(File, File)> {
+    // SAFETY: posix_openpt only returns a new descriptor, owned by the File right away.
+    let fd = unsafe { posix_openpt(O_RDWR | O_NOCTTY) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: fd was just opened and is owned by nothing else.
+    let master = unsafe { File::from_raw_fd(fd) };
+    let mut name = [0u8; 128];
+    // SAFETY: the calls only operate on the open descriptor, ptsname_r writes at most `name.len()` bytes.
+    unsafe {
+        if fcntl(fd, F_SETFD, FD_CLOEXEC) < 0 || grantpt(fd) != 0 || unlockpt(fd) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let err = ptsname_r(fd, name.as_mut_ptr(), name.len());
+        if err != 0 {
+            return Err(io::Error::from_raw_os_error(err));
+        }
+    }
+    let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+    let path = String::from_utf8_lossy(&name[..len]).into_owned();
+    let slave = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(O_NOCTTY)
+        .open(path)?;
+    Ok((master, slave))
+}
@@ -0,0 +1,270 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Merged, chronologically sorted view of the logs of a recorded run.
+//!
+//! All lines of an example were timestamped by the runner against a single
+//! monotonic clock when they were read, so sorting by that timestamp gives the
+//! real order of events across apps, independent of the apps' own clocks.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::gc;
+use crate::log_index;
+use crate::pattern::Pattern;
+use crate::private_dir;
+
+/// 256-color palette codes cycled through for the sources of a timeline.
+const COLORS: &[u8] = &[39, 208, 70, 170, 220, 45, 203, 141, 118, 214];
+const RUNNER_COLOR: u8 = 244;
+
+pub(crate) struct Entry {
+    pub(crate) at: f64,
+    pub(crate) wall: String,
+    pub(crate) source: String,
+    pub(crate) stream: String,
+    pub(crate) text: String,
+}
+
+struct ExampleLog {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+pub struct Filter {
+    pub apps: Vec<String>,
+    pub grep: Option<Pattern>,
+    pub since: Option<Duration>, // time into the example
+    pub until: Option<Duration>,
+}
+
+impl Filter {
+    fn matches_source(&self, source: &str) -> bool {
+        self.apps.is_empty()
+            || self
+                .apps
+                .iter()
+                .any(|app| app == source || source.strip_prefix("app") == Some(app.as_str()))
+    }
+
+    fn matches(&self, entry: &Entry) -> bool {
+        self.matches_source(&entry.source)
+            && self.since.is_none_or(|since| entry.at >= since.as_secs_f64())
+            && self.until.is_none_or(|until| entry.at <= until.as_secs_f64())
+            && self.grep.as_ref().is_none_or(|p| p.is_match(&entry.text))
+    }
+}
+
+/// Parses a time into an example like `00:02:00`, `02:00` or a duration like `2m`.
+pub fn parse_offset(value: &str) -> Result<Duration, String> {
+    if !value.contains(':') {
+        return crate::parse_duration(value);
+    }
+    let mut seconds = 0.0;
+    for part in value.split(':') {
+        let part: f64 = part
+            .parse()
+            .map_err(|_| format!("invalid time \"{}\", use HH:MM:SS, MM:SS or e.g. 2m", value))?;
+        seconds = seconds * 60.0 + part;
+    }
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("invalid time \"{}\": {}", value, e))
+}
+
+/// Directory containing the runs recorded under the default artifacts location:
+/// `$SCORE_CLI_RUNS_DIR`, or `$TMPDIR/score-cli-<uid>/runs`.
+pub fn runs_root() -> PathBuf {
+    match std::env::var_os("SCORE_CLI_RUNS_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => private_dir::temp_root().join("runs"),
+    }
+}
+
+/// Creates [`runs_root`] if missing, with mode 0700, or checks that the existing one is private.
+///
+/// Other users must not plant runs in it, as `recover` acts on their checkpoints.
+pub fn create_runs_root() -> Result<PathBuf> {
+    let root = runs_root();
+    match std::env::var_os("SCORE_CLI_RUNS_DIR") {
+        Some(dir) if !dir.is_empty() => {
+            if let Some(parent) = root.parent() {
+                fs::create_dir_all(parent).with_context(|| format!("Failed to create directory {:?}", parent))?;
+            }
+            private_dir::ensure(&root)?;
+        }
+        _ => {
+            private_dir::temp("runs")?;
+        }
+    }
+    Ok(root)
+}
+
+/// Resolves a run given as a directory path, a run id under [`runs_root`], or `last`.
+pub fn resolve_run(run: &str) -> Result<PathBuf> {
+    let path = Path::new(run);
+    if path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    let root = runs_root();
+    if run == "last" {
+        let newest = fs::read_dir(&root)
+            .with_context(|| format!("No recorded runs in {:?}", root))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok());
+        return match newest {
+            Some(entry) => Ok(entry.path()),
+            None => bail!("No recorded runs in {:?}", root),
+        };
+    }
+    let path = root.join(run);
+    if !path.is_dir() {
+        bail!(
+            "Run {:?} not found (neither a directory nor a run id in {:?})",
+            run,
+            root
+        );
+    }
+    Ok(path)
+}
+
+/// Prints the merged timeline of the run in `run_dir`.
+pub fn show(run_dir: &Path, filter: &Filter, color: bool) -> Result<()> {
+    let mut examples = Vec::new();
+    collect(run_dir, run_dir, filter, &mut examples)?;
+    if examples.is_empty() {
+        bail!("No log files found in {:?}", run_dir);
+    }
+    // Examples ran one after another: order them by their first wall-clock timestamp.
+    examples.sort_by(|a, b| first_wall(a).cmp(first_wall(b)));
+
+    let color = color && std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    match print(&examples, filter, color) {
+        // The reader went away, e.g. `score-cli timeline | head`.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+fn print(examples: &[ExampleLog], filter: &Filter, color: bool) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let mut sources: Vec<String> = Vec::new();
+    for example in examples {
+        let lines: Vec<&Entry> = example.entries.iter().filter(|e| filter.matches(e)).collect();
+        if lines.is_empty() {
+            continue;
+        }
+        writeln!(out, "━━ {}", example.name)?;
+        for entry in lines {
+            let code = if entry.source == "runner" {
+                RUNNER_COLOR
+            } else {
+                let index = sources.iter().position(|s| *s == entry.source).unwrap_or_else(|| {
+                    sources.push(entry.source.clone());
+                    sources.len() - 1
+                });
+                COLORS[index % COLORS.len()]
+            };
+            let time = entry.wall.split_once('T').map(|(_, t)| t).unwrap_or(&entry.wall);
+            let marker = if entry.stream == "err" { "!" } else { " " };
+            let line = format!(
+                "{} {:>10.3}s [{:>6}]{} {}",
+                time, entry.at, entry.source, marker, entry.text
+            );
+            if color {
+                writeln!(out, "\x1b[38;5;{}m{}\x1b[0m", code, line)?;
+            } else {
+                writeln!(out, "{}", line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn first_wall(example: &ExampleLog) -> &str {
+    example.entries.first().map(|e| e.wall.as_str()).unwrap_or("")
+}
+
+/// Finds example directories (those containing app or runner logs) below `dir`.
+fn collect(root: &Path, dir: &Path, filter: &Filter, examples: &mut Vec<ExampleLog>) -> Result<()> {
+    let mut entries = Vec::new();
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {:?}", dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            collect(root, &path, filter, examples)?;
+            continue;
+        }
+        let Some(source) = log_source(&path).filter(|source| filter.matches_source(source)) else {
+            continue;
+        };
+        match log_index::Indexed::open(&path) {
+            // Only the lines in the time range are read.
+            Some(log) => {
+                let lines = log
+                    .read(filter.since, filter.until)
+                    .with_context(|| format!("Failed reading {:?}", path))?;
+                entries.extend(
+                    String::from_utf8_lossy(&lines)
+                        .lines()
+                        .filter_map(|line| parse_line(&source, line)),
+                );
+            }
+            None => {
+                let content = gc::read_log(&path)?;
+                entries.extend(content.lines().filter_map(|line| parse_line(&source, line)));
+            }
+        }
+    }
+    if !entries.is_empty() {
+        // Stable sort: lines of one app keep their order when timestamps tie.
+        entries.sort_by(|a, b| a.at.total_cmp(&b.at));
+        let name = dir.strip_prefix(root).unwrap_or(dir).display().to_string();
+        examples.push(ExampleLog {
+            name: if name.is_empty() { ".".to_string() } else { name },
+            entries,
+        });
+    }
+    Ok(())
+}
+
+/// Source name of a log file: `app<N>` for app logs, `runner` for the event log.
+pub(crate) fn log_source(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    // Logs compressed by `gc` as well.
+    let stem = name.strip_suffix(".log").or_else(|| name.strip_suffix(".log.zst"))?;
+    match stem.strip_prefix("app") {
+        Some(index) if !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()) => Some(stem.to_string()),
+        _ if stem == "runner" => Some(stem.to_string()),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_line(source: &str, line: &str) -> Option<Entry> {
+    let mut parts = line.splitn(4, ' ');
+    let at = parts.next()?.parse().ok()?;
+    let wall = parts.next()?.to_string();
+    let stream = parts.next()?.to_string();
+    Some(Entry {
+        at,
+        wall,
+        source: source.to_string(),
+        stream,
+        text: parts.next().unwrap_or("").to_string(),
+    })
+}
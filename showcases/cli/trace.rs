@@ -0,0 +1,149 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Runner lifecycle events in the Chrome trace event format, written as `trace.json`.
+//!
+//! The file opens in ui.perfetto.dev and chrome://tracing. Every example is a
+//! process, the runner and every app are threads of it. Timestamps are
+//! CLOCK_MONOTONIC in microseconds, the clock LTTng and most tracers of the
+//! SCORE components use as well, so traces of one machine line up.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::sys;
+
+/// Thread id of the runner inside an example's trace process.
+pub const RUNNER_TID: usize = 0;
+
+pub struct Trace {
+    run_id: String,
+    origin: Instant,
+    origin_us: u64,
+    examples: Mutex<usize>,
+    events: Mutex<Vec<Value>>,
+    file: Mutex<Option<PathBuf>>, // written on drop unless finished before
+}
+
+impl Trace {
+    pub fn new(run_id: &str) -> Self {
+        Trace {
+            run_id: run_id.to_string(),
+            origin: Instant::now(),
+            origin_us: sys::monotonic_now().as_micros() as u64,
+            examples: Mutex::new(0),
+            events: Mutex::new(Vec::new()),
+            file: Mutex::new(None),
+        }
+    }
+
+    /// Starts the trace process of an example and returns its pid.
+    pub fn example(&self, name: &str) -> usize {
+        let pid = {
+            let mut examples = self.examples.lock().unwrap_or_else(|e| e.into_inner());
+            *examples += 1;
+            *examples
+        };
+        self.metadata(pid, RUNNER_TID, "process_name", name);
+        self.metadata(pid, RUNNER_TID, "thread_name", "runner");
+        pid
+    }
+
+    /// Names the trace thread of an app.
+    pub fn app(&self, pid: usize, index: usize, label: &str) {
+        self.metadata(pid, index, "thread_name", label);
+    }
+
+    /// Records a point in time event.
+    pub fn instant(&self, pid: usize, tid: usize, name: &str, at: Instant, args: Value) {
+        self.push(json!({
+            "name": name,
+            "ph": "i",
+            "s": "t",
+            "ts": self.timestamp(at),
+            "pid": pid,
+            "tid": tid,
+            "args": args,
+        }));
+    }
+
+    /// Records an event lasting from `start` until `end`.
+    pub fn complete(&self, pid: usize, tid: usize, name: &str, start: Instant, end: Instant, args: Value) {
+        self.push(json!({
+            "name": name,
+            "ph": "X",
+            "ts": self.timestamp(start),
+            "dur": end.saturating_duration_since(start).as_micros() as u64,
+            "pid": pid,
+            "tid": tid,
+            "args": args,
+        }));
+    }
+
+    /// Writes the trace to `path` when the run ends, also if it ends with an error.
+    pub fn write_to(&self, path: PathBuf) {
+        *self.file.lock().unwrap_or_else(|e| e.into_inner()) = Some(path);
+    }
+
+    /// Writes the trace to the file given to [`Trace::write_to`] now.
+    pub fn finish(&self) -> Result<()> {
+        match self.file.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            Some(path) => self.write(&path),
+            None => Ok(()),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let events = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        let trace = json!({
+            "traceEvents": *events,
+            "displayTimeUnit": "ms",
+            "otherData": { "clock": "CLOCK_MONOTONIC", "run_id": self.run_id },
+        });
+        fs::write(path, serde_json::to_string(&trace)?).with_context(|| format!("Failed to write trace {:?}", path))
+    }
+
+    fn metadata(&self, pid: usize, tid: usize, kind: &str, name: &str) {
+        self.push(json!({
+            "name": kind,
+            "ph": "M",
+            "pid": pid,
+            "tid": tid,
+            "args": { "name": name },
+        }));
+    }
+
+    fn timestamp(&self, at: Instant) -> u64 {
+        match at.checked_duration_since(self.origin) {
+            Some(after) => self.origin_us + after.as_micros() as u64,
+            None => self
+                .origin_us
+                .saturating_sub(self.origin.duration_since(at).as_micros() as u64),
+        }
+    }
+
+    fn push(&self, event: Value) {
+        self.events.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+    }
+}
+
+impl Drop for Trace {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            eprintln!("⚠️  {:#}", e);
+        }
+    }
+}
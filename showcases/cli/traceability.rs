@@ -0,0 +1,158 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Mapping of requirements to the examples verifying them, as evidence from recorded runs.
+//!
+//! Examples list the requirements they verify in `requirements`. The matrix
+//! combines those declarations with the `report.json` files of recorded runs:
+//! a requirement is covered once an example verifying it passed in one of them.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::report::Status;
+use crate::ScoreConfig;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Markdown,
+    Json,
+}
+
+/// The parts of a `report.json` the matrix is built from.
+#[derive(Deserialize)]
+struct RecordedRun {
+    run_id: String,
+    examples: Vec<RecordedExample>,
+}
+
+#[derive(Deserialize)]
+struct RecordedExample {
+    name: String,
+    #[serde(default)]
+    requirements: Vec<String>,
+    status: Status,
+    #[serde(default)]
+    steps: Vec<RecordedExample>,
+}
+
+/// Adds `example` and, for composite examples, its steps to `all`.
+fn flatten(example: RecordedExample, all: &mut Vec<RecordedExample>) {
+    let mut example = example;
+    for step in std::mem::take(&mut example.steps) {
+        flatten(step, all);
+    }
+    all.push(example);
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Evidence {
+    pub examples: Vec<String>, // examples declaring the requirement
+    pub passed: Vec<Verdict>,  // passing runs of those examples
+    pub failed: Vec<Verdict>,  // failing runs of those examples
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Verdict {
+    pub example: String,
+    pub run_id: String,
+}
+
+impl Evidence {
+    fn status(&self) -> &'static str {
+        if !self.passed.is_empty() {
+            "covered"
+        } else if !self.failed.is_empty() {
+            "failing"
+        } else {
+            "not run"
+        }
+    }
+}
+
+/// Builds the matrix from the declarations in `configs` and the reports in `run_dirs`.
+pub fn matrix(configs: &[ScoreConfig], run_dirs: &[&Path]) -> Result<BTreeMap<String, Evidence>> {
+    let mut matrix: BTreeMap<String, Evidence> = BTreeMap::new();
+    for config in configs {
+        for requirement in &config.requirements {
+            matrix
+                .entry(requirement.clone())
+                .or_default()
+                .examples
+                .push(config.name.clone());
+        }
+    }
+    for dir in run_dirs {
+        let path = dir.join("report.json");
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed reading {:?}", path))?;
+        let run: RecordedRun =
+            serde_json::from_str(&content).with_context(|| format!("Invalid run report {:?}", path))?;
+        let mut examples = Vec::new();
+        for example in run.examples {
+            flatten(example, &mut examples);
+        }
+        // Skipped examples are no evidence either way.
+        for example in examples.into_iter().filter(|e| e.status != Status::Skipped) {
+            for requirement in example.requirements {
+                let evidence = matrix.entry(requirement).or_default();
+                if !evidence.examples.contains(&example.name) {
+                    // Declared when the run was recorded, but no longer by the current configs.
+                    evidence.examples.push(example.name.clone());
+                }
+                let verdict = Verdict {
+                    example: example.name.clone(),
+                    run_id: run.run_id.clone(),
+                };
+                let verdicts = if matches!(example.status, Status::Passed | Status::Flaky | Status::Xpass) {
+                    &mut evidence.passed
+                } else {
+                    &mut evidence.failed
+                };
+                // Examples used as steps of several composite examples run more than once.
+                if !verdicts.contains(&verdict) {
+                    verdicts.push(verdict);
+                }
+            }
+        }
+    }
+    Ok(matrix)
+}
+
+pub fn render(matrix: &BTreeMap<String, Evidence>, format: Format) -> Result<String> {
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(matrix)? + "\n",
+        Format::Markdown => {
+            let mut out = String::from("| Requirement | Status | Examples | Passing runs |\n|---|---|---|---|\n");
+            for (requirement, evidence) in matrix {
+                let runs: Vec<String> = evidence
+                    .passed
+                    .iter()
+                    .map(|v| format!("{} (`{}`)", v.example, v.run_id))
+                    .collect();
+                let _ = writeln!(
+                    out,
+                    "| `{}` | {} | {} | {} |",
+                    requirement,
+                    evidence.status(),
+                    evidence.examples.join(", "),
+                    runs.join(", ")
+                );
+            }
+            out
+        }
+    })
+}
@@ -0,0 +1,153 @@
+// *******************************************************************************
+// Copyright (c) 2026 Contributors to the Eclipse Foundation
+//
+// See the NOTICE file(s) distributed with this work for additional
+// information regarding copyright ownership.
+//
+// This program and the accompanying materials are made available under the
+// terms of the Apache License Version 2.0 which is available at
+// <https://www.apache.org/licenses/LICENSE-2.0>
+//
+// SPDX-License-Identifier: Apache-2.0
+// *******************************************************************************
+//! Pinning runs to a git revision of the examples tree (`--tree-rev`).
+//!
+//! Without `--fetch` the examples directory must be a clean checkout of the
+//! revision. With `--fetch` the revision is fetched from `origin` if needed and
+//! checked out into a detached worktree in the private directory
+//! `$XDG_STATE_HOME/score-cli/trees`. Later runs of the same revision reuse it
+//! once they checked that it belongs to the user, is a worktree of the same
+//! repository and is still a clean checkout of the revision.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{history, private_dir};
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn resolve(root: &Path, rev: &str) -> Result<String> {
+    git(
+        root,
+        &["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", rev)],
+    )
+}
+
+/// Checks that the examples in `root` are at `rev`, or with `fetch` checks them out at `rev`.
+///
+/// Returns the examples directory to use.
+pub fn pin(root: &Path, rev: &str, fetch: bool) -> Result<PathBuf> {
+    git(root, &["rev-parse", "--git-dir"])
+        .with_context(|| format!("--tree-rev needs the examples directory {:?} in a git repository", root))?;
+    if !fetch {
+        let Ok(commit) = resolve(root, rev) else {
+            bail!("Unknown revision {} in {:?}, pass --fetch to fetch it", rev, root);
+        };
+        let head = git(root, &["rev-parse", "HEAD"])?;
+        if head != commit {
+            bail!(
+                "Examples in {:?} are at {}, not at {} ({}); check it out or pass --fetch",
+                root,
+                head,
+                rev,
+                commit
+            );
+        }
+        if !git(root, &["status", "--porcelain", "--", "."])?.is_empty() {
+            bail!(
+                "Examples in {:?} have uncommitted changes, so they are not at {}",
+                root,
+                rev
+            );
+        }
+        println!("Examples tree at {} ({})", rev, commit);
+        return Ok(root.to_path_buf());
+    }
+
+    let commit = match resolve(root, rev) {
+        Ok(commit) => commit,
+        Err(_) => {
+            println!("Fetching {} from origin", rev);
+            git(root, &["fetch", "--quiet", "origin", rev]).with_context(|| format!("Failed to fetch {}", rev))?;
+            resolve(root, rev).or_else(|_| resolve(root, "FETCH_HEAD"))?
+        }
+    };
+    // The examples directory may be a sub directory of the repository.
+    let prefix = git(root, &["rev-parse", "--show-prefix"])?;
+    let trees = history::state_dir().join("trees");
+    if let Some(state) = trees.parent() {
+        fs::create_dir_all(state).with_context(|| format!("Failed to create directory {:?}", state))?;
+    }
+    private_dir::ensure(&trees)?;
+    let worktree = trees.join(&commit);
+    if fs::symlink_metadata(&worktree).is_ok() {
+        check_worktree(root, &worktree, &commit)?;
+    } else {
+        git(
+            root,
+            &[
+                "worktree",
+                "add",
+                "--detach",
+                "--quiet",
+                &worktree.to_string_lossy(),
+                &commit,
+            ],
+        )
+        .with_context(|| format!("Failed to check out {} into {:?}", commit, worktree))?;
+    }
+    println!(
+        "Examples tree at {} ({}), checked out in {}",
+        rev,
+        commit,
+        worktree.display()
+    );
+    Ok(worktree.join(prefix))
+}
+
+/// Checks that the existing `worktree` can be reused: it belongs to the user, is a worktree of the
+/// repository of `root` and a clean checkout of `commit`.
+fn check_worktree(root: &Path, worktree: &Path, commit: &str) -> Result<()> {
+    private_dir::check_owned(worktree)?;
+    let common_dir = |dir: &Path| -> Result<PathBuf> {
+        let common = PathBuf::from(git(dir, &["rev-parse", "--path-format=absolute", "--git-common-dir"])?);
+        common
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {:?}", common))
+    };
+    if common_dir(worktree)? != common_dir(root)? {
+        bail!(
+            "{:?} is not a worktree of the repository of {:?}, remove it to check out {} again",
+            worktree,
+            root,
+            commit
+        );
+    }
+    let head = git(worktree, &["rev-parse", "HEAD"])?;
+    if head != commit || !git(worktree, &["status", "--porcelain"])?.is_empty() {
+        bail!(
+            "{:?} is no longer a clean checkout of {}, remove it with `git worktree remove --force {}`",
+            worktree,
+            commit,
+            worktree.display()
+        );
+    }
+    Ok(())
+}